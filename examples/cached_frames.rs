@@ -0,0 +1,146 @@
+//! examples/cached_frames.rs – check that cached frames are only recorded when they change
+//!
+//! Renders the same triangle over and over with `CommandRecording::Cached`,
+//! then checks through `RenderStats::recordings` that once every swapchain
+//! image has its buffer, identical frames are resubmitted without recording,
+//! while a new clear color or `mark_dirty` records them again. Exits with an
+//! error if any check fails. Compile the mesh shaders first:
+//!
+//! ```text
+//! glslc shaders/mesh.vert -o shaders/mesh.vert.spv
+//! glslc shaders/mesh.frag -o shaders/mesh.frag.spv
+//! RUST_LOG=info cargo run --example cached_frames
+//! ```
+
+use log::{error, info, warn};
+use winit::{
+    application::ApplicationHandler,
+    event::WindowEvent,
+    event_loop::{ActiveEventLoop, EventLoop},
+    window::{Window, WindowId},
+};
+
+use wolf_engine::core::renderer::api::Renderer;
+use wolf_engine::core::renderer::backend::vulkan::frame::MAX_FRAMES_IN_FLIGHT;
+use wolf_engine::core::renderer::backend::vulkan::recording::RecordCache;
+use wolf_engine::core::renderer::backend::vulkan::{Mesh, Vertex, VulkanRenderer};
+use wolf_engine::core::renderer::config::{CommandRecording, RendererBuilder};
+use wolf_engine::core::renderer::stats::DrawCounts;
+use wolf_engine::error::{AppError, Result};
+
+// Enough to cycle through every swapchain image several times
+const FRAMES: usize = MAX_FRAMES_IN_FLIGHT * 8;
+const TRIANGLE: [Vertex; 3] = [
+    Vertex::new([0.0, -0.6, 0.5], [1.0, 0.2, 0.2]),
+    Vertex::new([0.6, 0.6, 0.5], [0.2, 1.0, 0.2]),
+    Vertex::new([-0.6, 0.6, 0.5], [0.2, 0.2, 1.0]),
+];
+const IDENTITY: [[f32; 4]; 4] = [
+    [1.0, 0.0, 0.0, 0.0],
+    [0.0, 1.0, 0.0, 0.0],
+    [0.0, 0.0, 1.0, 0.0],
+    [0.0, 0.0, 0.0, 1.0],
+];
+
+/// The cache bookkeeping on its own, without a GPU.
+fn check_record_cache() {
+    let counts = DrawCounts::default();
+    let mut cache = RecordCache::default();
+    cache.reset(3);
+    assert_eq!(cache.lookup(0, 7), None, "nothing recorded yet");
+    cache.recorded(0, 7, counts);
+    assert_eq!(cache.lookup(0, 7), Some(counts));
+    assert_eq!(cache.lookup(0, 8), None, "other signature");
+    assert_eq!(cache.lookup(1, 7), None, "other image");
+    cache.invalidate();
+    assert_eq!(cache.lookup(0, 7), None, "invalidated");
+    cache.recorded(4, 7, counts);
+    assert_eq!(cache.lookup(4, 7), Some(counts), "grows past reset");
+}
+
+#[derive(Default)]
+struct CachedFrames {
+    error: Option<AppError>,
+}
+
+impl CachedFrames {
+    fn run(&mut self, event_loop: &ActiveEventLoop) -> Result<()> {
+        let window = event_loop
+            .create_window(Window::default_attributes().with_title("wolf-engine: cached frames"))?;
+        let config = RendererBuilder::new()
+            .app_name("cached_frames")
+            .command_recording(CommandRecording::Cached)
+            .build()?;
+        let mut renderer = VulkanRenderer::new(config);
+        renderer.initialize(&window, event_loop)?;
+        let triangle = renderer.create_mesh(&TRIANGLE, &[0, 1, 2])?;
+
+        let warm = render(&mut renderer, &triangle, [0.1, 0.1, 0.15, 1.0])?;
+        if renderer.stats().frame_count == 0 {
+            warn!("No frame presented (window hidden?), nothing was checked");
+            return Ok(());
+        }
+        let same = render(&mut renderer, &triangle, [0.1, 0.1, 0.15, 1.0])?;
+        let recolored = render(&mut renderer, &triangle, [0.3, 0.1, 0.1, 1.0])?;
+        renderer.mark_dirty();
+        let dirty = render(&mut renderer, &triangle, [0.3, 0.1, 0.1, 1.0])?;
+        info!("Recorded {warm} warming up, then {same} / {recolored} / {dirty}");
+
+        let check = |ok: bool, what: &str| {
+            ok.then_some(()).ok_or_else(|| {
+                AppError::Config(format!("{what} (recorded {same}/{recolored}/{dirty})"))
+            })
+        };
+        check(
+            warm > 0 && warm < FRAMES as u64,
+            "warm-up should record once per image",
+        )?;
+        check(same == 0, "identical frames were recorded again")?;
+        check(recolored > 0, "a new clear color wasn't recorded")?;
+        check(dirty > 0, "mark_dirty didn't record again")?;
+        drop(triangle);
+        drop(renderer);
+        drop(window);
+        Ok(())
+    }
+}
+
+/// Render `FRAMES` identical frames, returning how many of them were recorded.
+fn render(renderer: &mut VulkanRenderer, triangle: &Mesh, clear: [f32; 4]) -> Result<u64> {
+    let before = renderer.stats().recordings;
+    for _ in 0..FRAMES {
+        renderer.render_with(|ctx| {
+            ctx.clear_color(clear);
+            ctx.push_constants(&IDENTITY)?;
+            ctx.draw_mesh(triangle);
+            Ok(())
+        })?;
+    }
+    Ok(renderer.stats().recordings - before)
+}
+
+impl ApplicationHandler for CachedFrames {
+    fn resumed(&mut self, event_loop: &ActiveEventLoop) {
+        if let Err(e) = self.run(event_loop) {
+            error!("{e}");
+            self.error.get_or_insert(e);
+        }
+        event_loop.exit();
+    }
+
+    fn window_event(&mut self, _event_loop: &ActiveEventLoop, _id: WindowId, _event: WindowEvent) {}
+}
+
+fn main() -> Result<()> {
+    env_logger::init();
+    check_record_cache();
+
+    let event_loop = EventLoop::new()?;
+    let mut app = CachedFrames::default();
+    event_loop.run_app(&mut app)?;
+    if let Some(e) = app.error {
+        return Err(e);
+    }
+    info!("✅ Unchanged frames were resubmitted without recording");
+    Ok(())
+}
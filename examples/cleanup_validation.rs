@@ -0,0 +1,125 @@
+//! examples/cleanup_validation.rs – check the teardown order of `VulkanRenderer` under validation
+//!
+//! Runs one full cycle: `initialize`, render a frame, then drop the renderer,
+//! which runs `cleanup` and destroys the device and instance. Validation
+//! messages are captured with `RendererBuilder::capture_validation`; any warning
+//! or error raised during teardown (a framebuffer outliving its render pass, a
+//! leaked object reported by `vkDestroyDevice`, ...) fails the run with a
+//! non-zero exit code. Messages from startup and the frame are only logged.
+//!
+//! Needs a debug build with the `debug-utils` feature (the defaults) and the
+//! Khronos validation layer installed; otherwise it reports the skip and exits
+//! successfully.
+//!
+//! ```text
+//! RUST_LOG=info cargo run --example cleanup_validation
+//! ```
+
+use std::ffi::CStr;
+
+use log::{error, info, warn};
+use vulkanalia::loader::{LIBRARY, LibloadingLoader};
+use vulkanalia::prelude::v1_0::*;
+use winit::{
+    application::ApplicationHandler,
+    event::WindowEvent,
+    event_loop::{ActiveEventLoop, EventLoop},
+    window::{Window, WindowId},
+};
+
+use wolf_engine::core::renderer::api::Renderer;
+use wolf_engine::core::renderer::backend::vulkan::VulkanRenderer;
+use wolf_engine::core::renderer::config::RendererBuilder;
+use wolf_engine::core::renderer::validation::ValidationLog;
+use wolf_engine::error::{AppError, Result};
+
+#[derive(Default)]
+struct CleanupValidation {
+    log: ValidationLog,
+    teardown_messages: usize,
+    error: Option<AppError>,
+}
+
+impl CleanupValidation {
+    fn run(&mut self, event_loop: &ActiveEventLoop) -> Result<()> {
+        let window = event_loop.create_window(
+            Window::default_attributes().with_title("wolf-engine: cleanup validation"),
+        )?;
+        let config = RendererBuilder::new()
+            .app_name("cleanup_validation")
+            .capture_validation(self.log.clone())
+            .build()?;
+        let mut renderer = VulkanRenderer::new(config);
+        renderer.initialize(&window, event_loop)?;
+        renderer.render()?;
+        if renderer.stats().frame_count == 0 {
+            warn!("No frame presented (window hidden?), tearing down anyway");
+        }
+        let startup = self.log.len();
+        if startup > 0 {
+            warn!("{startup} validation message(s) before teardown");
+        }
+
+        // Renderer first: its surface must go before the window
+        drop(renderer);
+        drop(window);
+        let teardown = self.log.since(startup);
+        for message in &teardown {
+            error!("Teardown {:?}: {}", message.severity, message.text);
+        }
+        self.teardown_messages = teardown.len();
+        Ok(())
+    }
+}
+
+impl ApplicationHandler for CleanupValidation {
+    fn resumed(&mut self, event_loop: &ActiveEventLoop) {
+        if let Err(e) = self.run(event_loop) {
+            error!("{e}");
+            self.error.get_or_insert(e);
+        }
+        event_loop.exit();
+    }
+
+    fn window_event(&mut self, _event_loop: &ActiveEventLoop, _id: WindowId, _event: WindowEvent) {}
+}
+
+/// Whether the Khronos validation layer is installed.
+fn validation_layer_available() -> Result<bool> {
+    let loader = unsafe { LibloadingLoader::new(LIBRARY) }?;
+    let entry = unsafe { Entry::new(loader) }?;
+    let layers = unsafe { entry.enumerate_instance_layer_properties() }
+        .map_err(|e| AppError::vk(e, "enumerate instance layers"))?;
+    Ok(layers.iter().any(|layer| {
+        let name = unsafe { CStr::from_ptr(layer.layer_name.as_ptr()) };
+        name.to_bytes() == b"VK_LAYER_KHRONOS_validation"
+    }))
+}
+
+fn main() -> Result<()> {
+    env_logger::init();
+    if !cfg!(all(debug_assertions, feature = "debug-utils")) {
+        info!("Skipped: needs a debug build with the debug-utils feature");
+        return Ok(());
+    }
+    if !validation_layer_available()? {
+        info!("Skipped: VK_LAYER_KHRONOS_validation isn't installed");
+        return Ok(());
+    }
+
+    let event_loop = EventLoop::new()?;
+    let mut app = CleanupValidation::default();
+    event_loop.run_app(&mut app)?;
+    if let Some(e) = app.error {
+        return Err(e);
+    }
+    match app.teardown_messages {
+        0 => {
+            info!("✅ Teardown passed validation");
+            Ok(())
+        }
+        n => Err(AppError::Config(format!(
+            "validation reported {n} message(s) during teardown"
+        ))),
+    }
+}
@@ -0,0 +1,156 @@
+//! examples/compute_mandelbrot.rs – compute shader → storage image → swapchain blit
+//!
+//! Every frame dispatches `mandelbrot.comp` over a window-sized `StorageImage`
+//! before the render pass, then blits the image onto the swapchain image after
+//! it; the barriers between the two (and against the previous frame's blit) are
+//! recorded by the engine. Compile the shader first:
+//!
+//! ```text
+//! glslc shaders/mandelbrot.comp -o shaders/mandelbrot.comp.spv
+//! ```
+//!
+//! Keys: arrows pan, `+` / `-` zoom, `R` resets the view.
+
+use log::info;
+use winit::{
+    event::{ElementState, KeyEvent, WindowEvent},
+    event_loop::ActiveEventLoop,
+    keyboard::{KeyCode, PhysicalKey},
+    window::{Window, WindowId},
+};
+
+use wolf_engine::app::App;
+use wolf_engine::core::renderer::api::Renderer;
+use wolf_engine::core::renderer::backend::vulkan::{
+    ComputePipeline, DrawContext, StorageImage, VulkanRenderer,
+};
+use wolf_engine::core::renderer::config::RendererConfig;
+use wolf_engine::error::{AppError, Result};
+
+const WORKGROUP_SIZE: u32 = 8; // local_size_x/y in mandelbrot.comp
+
+/// Push constants of `mandelbrot.comp`.
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+struct View {
+    center: [f32; 2],
+    scale: f32, // Complex-plane units per pixel
+    max_iter: u32,
+}
+
+impl View {
+    const HOME: Self = Self {
+        center: [-0.5, 0.0],
+        scale: 3.0 / 720.0,
+        max_iter: 256,
+    };
+}
+
+struct Mandelbrot {
+    renderer: VulkanRenderer,
+    view: View,
+    height: u32, // Of the last frame, so a pan step is a fraction of the screen
+    pipeline: Option<ComputePipeline>,
+    image: Option<StorageImage>, // Recreated when the window size changes
+}
+
+fn draw(
+    ctx: &mut DrawContext,
+    view: &View,
+    pipeline: &mut Option<ComputePipeline>,
+    image: &mut Option<StorageImage>,
+) -> Result<()> {
+    if pipeline.is_none() {
+        *pipeline = Some(ctx.create_compute_pipeline("mandelbrot.comp.spv")?);
+        info!("🖥️ Mandelbrot pipeline created");
+    }
+    let extent = ctx.extent();
+    if image.as_ref().is_none_or(|image| image.extent() != extent) {
+        // Frames still using the old image keep it alive until they finish
+        *image = Some(ctx.create_storage_image(extent)?);
+    }
+    let (Some(pipeline), Some(image)) = (pipeline.as_ref(), image.as_ref()) else {
+        return Ok(());
+    };
+
+    ctx.push_constants(view)?;
+    let groups = [
+        extent.width.div_ceil(WORKGROUP_SIZE),
+        extent.height.div_ceil(WORKGROUP_SIZE),
+        1,
+    ];
+    ctx.dispatch(pipeline, image, groups);
+    ctx.blit_to_swapchain(image);
+    Ok(())
+}
+
+impl Renderer for Mandelbrot {
+    fn new(config: RendererConfig) -> Self {
+        Self {
+            renderer: VulkanRenderer::new(config),
+            view: View::HOME,
+            height: 720,
+            pipeline: None,
+            image: None,
+        }
+    }
+
+    fn initialize(&mut self, window: &Window, event_loop: &ActiveEventLoop) -> Result<()> {
+        window.set_title("wolf-engine: compute mandelbrot");
+        self.renderer.initialize(window, event_loop)?;
+        info!("🖥️ Arrows: pan, +/-: zoom, R: reset");
+        Ok(())
+    }
+
+    fn is_initialized(&self) -> bool {
+        self.renderer.is_initialized()
+    }
+
+    fn window_event(&mut self, event_loop: &ActiveEventLoop, id: WindowId, event: &WindowEvent) {
+        self.renderer.window_event(event_loop, id, event);
+    }
+
+    fn render(&mut self) -> Result<()> {
+        let Self {
+            renderer,
+            view,
+            height,
+            pipeline,
+            image,
+        } = self;
+        renderer.render_with(|ctx| {
+            *height = ctx.extent().height.max(1);
+            draw(ctx, view, pipeline, image)
+        })
+    }
+
+    fn take_error(&mut self) -> Option<AppError> {
+        self.renderer.take_error()
+    }
+
+    fn on_key(&mut self, _window: &Window, event: &KeyEvent) {
+        if event.state != ElementState::Pressed {
+            return;
+        }
+        let step = self.view.scale * self.height as f32 * 0.1; // 10% of the screen
+        let view = &mut self.view;
+        match event.physical_key {
+            PhysicalKey::Code(KeyCode::ArrowLeft) => view.center[0] -= step,
+            PhysicalKey::Code(KeyCode::ArrowRight) => view.center[0] += step,
+            PhysicalKey::Code(KeyCode::ArrowUp) => view.center[1] += step,
+            PhysicalKey::Code(KeyCode::ArrowDown) => view.center[1] -= step,
+            PhysicalKey::Code(KeyCode::Equal | KeyCode::NumpadAdd) => view.scale *= 0.8,
+            PhysicalKey::Code(KeyCode::Minus | KeyCode::NumpadSubtract) => view.scale /= 0.8,
+            PhysicalKey::Code(KeyCode::KeyR) => *view = View::HOME,
+            _ => return,
+        }
+        // Deeper zooms need more iterations to resolve the boundary
+        let zoom = View::HOME.scale / view.scale;
+        view.max_iter = (256.0 * zoom.max(1.0).log2().mul_add(0.5, 1.0)) as u32;
+    }
+}
+
+fn main() -> Result<()> {
+    env_logger::init();
+    App::<Mandelbrot>::run()
+}
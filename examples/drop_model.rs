@@ -0,0 +1,254 @@
+//! examples/drop_model.rs – load `.obj` models dropped onto the window
+//!
+//! Overrides `Renderer::on_file_dropped` and `on_file_hovered`. Every `.obj`
+//! dropped onto the window is parsed, uploaded and shown spinning next to the
+//! ones dropped before, in a grid; dropping several files at once loads each of
+//! them. Files that fail to load are logged and skipped. Only positions
+//! (optionally followed by an RGB color) and faces are read, polygons are
+//! triangulated as fans. Compile the mesh shaders first:
+//!
+//! ```text
+//! glslc shaders/mesh.vert -o shaders/mesh.vert.spv
+//! glslc shaders/mesh.frag -o shaders/mesh.frag.spv
+//! RUST_LOG=info cargo run --example drop_model
+//! ```
+
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+use log::{info, warn};
+use winit::{
+    event::WindowEvent,
+    event_loop::ActiveEventLoop,
+    window::{Window, WindowId},
+};
+
+use wolf_engine::app::App;
+use wolf_engine::core::renderer::api::Renderer;
+use wolf_engine::core::renderer::backend::vulkan::{Mesh, Vertex, VulkanRenderer};
+use wolf_engine::core::renderer::config::{RendererBuilder, RendererConfig};
+use wolf_engine::core::window::WindowConfig;
+use wolf_engine::error::{AppError, Result};
+
+/// Vertices and triangle indices of the OBJ `text`, scaled into [-1, 1] around
+/// its center. Vertices without a color get one from their position.
+fn parse_obj(text: &str) -> Result<(Vec<Vertex>, Vec<u32>)> {
+    let mut positions: Vec<[f32; 3]> = Vec::new();
+    let mut colors: Vec<Option<[f32; 3]>> = Vec::new();
+    let mut indices = Vec::new();
+    for (number, line) in text.lines().enumerate() {
+        let error = |what: &str| AppError::Config(format!("line {}: {what}", number + 1));
+        let mut fields = line.split_whitespace();
+        match fields.next() {
+            Some("v") => {
+                let values = fields
+                    .map(str::parse)
+                    .collect::<std::result::Result<Vec<f32>, _>>()
+                    .map_err(|_| error("bad vertex"))?;
+                match values[..] {
+                    [x, y, z] | [x, y, z, _] => {
+                        positions.push([x, y, z]);
+                        colors.push(None);
+                    }
+                    [x, y, z, r, g, b] => {
+                        positions.push([x, y, z]);
+                        colors.push(Some([r, g, b]));
+                    }
+                    _ => return Err(error("vertex needs 3 coordinates")),
+                }
+            }
+            Some("f") => {
+                // `v`, `v/vt`, `v//vn` or `v/vt/vn`; negative indices count from the end
+                let corners = fields
+                    .map(|corner| {
+                        let index: i64 = corner
+                            .split('/')
+                            .next()
+                            .and_then(|v| v.parse().ok())
+                            .ok_or_else(|| error("bad face index"))?;
+                        let resolved = match index {
+                            i if i > 0 => i - 1,
+                            i if i < 0 => positions.len() as i64 + i,
+                            _ => -1,
+                        };
+                        u32::try_from(resolved)
+                            .ok()
+                            .filter(|&i| (i as usize) < positions.len())
+                            .ok_or_else(|| error("face index out of range"))
+                    })
+                    .collect::<Result<Vec<u32>>>()?;
+                if corners.len() < 3 {
+                    return Err(error("face needs 3 corners"));
+                }
+                for pair in corners[1..].windows(2) {
+                    indices.extend([corners[0], pair[0], pair[1]]);
+                }
+            }
+            _ => {} // Normals, texture coordinates, groups, materials, comments
+        }
+    }
+    if indices.is_empty() {
+        return Err(AppError::Config("no faces".into()));
+    }
+
+    let mut min = [f32::MAX; 3];
+    let mut max = [f32::MIN; 3];
+    for position in &positions {
+        for axis in 0..3 {
+            min[axis] = min[axis].min(position[axis]);
+            max[axis] = max[axis].max(position[axis]);
+        }
+    }
+    let center = [0, 1, 2].map(|axis| (min[axis] + max[axis]) * 0.5);
+    let half = (0..3)
+        .map(|axis| (max[axis] - min[axis]) * 0.5)
+        .fold(f32::EPSILON, f32::max);
+    let vertices = positions
+        .iter()
+        .zip(colors)
+        .map(|(position, color)| {
+            let p = [0, 1, 2].map(|axis| (position[axis] - center[axis]) / half);
+            let color = color.unwrap_or(p.map(|c| c.abs() * 0.7 + 0.3));
+            Vertex::new(p, color)
+        })
+        .collect();
+    Ok((vertices, indices))
+}
+
+/// The parser on a few small inputs, without a GPU.
+fn check_parse_obj() {
+    let quad = "v 0 0 0\nv 2 0 0\nv 2 2 0\nv 0 2 0\nf 1 2 3 4\n";
+    let (vertices, indices) = parse_obj(quad).unwrap();
+    assert_eq!(vertices.len(), 4);
+    assert_eq!(indices, [0, 1, 2, 0, 2, 3], "fan triangulation");
+
+    let relative = "v 0 0 0 1 0 0\nv 1 0 0\nv 0 1 0\nf -3/1/1 -2//2 -1\n";
+    let (vertices, indices) = parse_obj(relative).unwrap();
+    assert_eq!(indices, [0, 1, 2], "negative and slashed indices");
+    assert_eq!(vertices[0], Vertex::new([-1.0, -1.0, 0.0], [1.0, 0.0, 0.0]));
+
+    assert!(
+        parse_obj("v 0 0 0\nf 1 2 3\n").is_err(),
+        "index out of range"
+    );
+    assert!(parse_obj("v 0 0 0\n").is_err(), "no faces");
+}
+
+fn load_obj(renderer: &VulkanRenderer, path: &Path) -> Result<Mesh> {
+    let text = std::fs::read_to_string(path)?;
+    let (vertices, indices) = parse_obj(&text)?;
+    renderer.create_mesh(&vertices, &indices)
+}
+
+/// Column-major transform of model `slot` out of `count`: spun by `angle`,
+/// scaled into its grid cell, X squeezed by the aspect ratio and Z mapped from
+/// [-1, 1] into depth [0, 1].
+fn model_transform(slot: usize, count: usize, angle: f32, aspect: f32) -> [[f32; 4]; 4] {
+    let columns = (count as f32).sqrt().ceil().max(1.0) as usize;
+    let rows = count.div_ceil(columns).max(1);
+    let cell = 2.0 / columns.max(rows) as f32;
+    let scale = cell * 0.4;
+    let x = -1.0 + cell * ((slot % columns) as f32 + 0.5);
+    let y = -1.0 + cell * ((slot / columns) as f32 + 0.5);
+    let (s, c) = angle.sin_cos();
+    [
+        [c * scale / aspect, 0.0, -s * scale * 0.5, 0.0],
+        [0.0, scale, 0.0, 0.0],
+        [s * scale / aspect, 0.0, c * scale * 0.5, 0.0],
+        [x, y, 0.5, 1.0],
+    ]
+}
+
+struct DropModel {
+    renderer: VulkanRenderer,
+    dropped: Vec<PathBuf>, // Waiting for the next frame to be loaded
+    models: Vec<Mesh>,
+    start: Instant,
+}
+
+impl Renderer for DropModel {
+    fn new(config: RendererConfig) -> Self {
+        Self {
+            renderer: VulkanRenderer::new(config),
+            dropped: Vec::new(),
+            models: Vec::new(),
+            start: Instant::now(),
+        }
+    }
+
+    fn initialize(&mut self, window: &Window, event_loop: &ActiveEventLoop) -> Result<()> {
+        self.renderer.initialize(window, event_loop)?;
+        self.renderer.set_clear_color([0.06, 0.07, 0.09, 1.0]);
+        info!("🖥️ Drop .obj files onto the window to load them");
+        Ok(())
+    }
+
+    fn is_initialized(&self) -> bool {
+        self.renderer.is_initialized()
+    }
+
+    fn window_event(&mut self, event_loop: &ActiveEventLoop, id: WindowId, event: &WindowEvent) {
+        self.renderer.window_event(event_loop, id, event);
+        if let WindowEvent::HoveredFileCancelled = event {
+            info!("Drag left the window");
+        }
+    }
+
+    fn render(&mut self) -> Result<()> {
+        // Files dropped together arrive as separate events before this frame
+        for path in self.dropped.drain(..) {
+            match load_obj(&self.renderer, &path) {
+                Ok(mesh) => {
+                    info!(
+                        "✅ Loaded {} ({} indices)",
+                        path.display(),
+                        mesh.index_count()
+                    );
+                    self.models.push(mesh);
+                }
+                Err(e) => warn!("Skipped {}: {e}", path.display()),
+            }
+        }
+        let angle = self.start.elapsed().as_secs_f32() * 0.8;
+        let models = &self.models;
+        self.renderer.render_with(|ctx| {
+            let extent = ctx.extent();
+            let aspect = extent.width as f32 / extent.height.max(1) as f32;
+            for (slot, mesh) in models.iter().enumerate() {
+                ctx.push_constants(&model_transform(slot, models.len(), angle, aspect))?;
+                ctx.draw_mesh(mesh);
+            }
+            Ok(())
+        })
+    }
+
+    fn take_error(&mut self) -> Option<AppError> {
+        self.renderer.take_error()
+    }
+
+    fn on_file_dropped(&mut self, path: PathBuf) {
+        let is_obj = path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("obj"));
+        if is_obj {
+            self.dropped.push(path);
+        } else {
+            warn!("Ignored {}: not an .obj file", path.display());
+        }
+    }
+
+    fn on_file_hovered(&mut self, path: PathBuf) {
+        info!("Hovering {}", path.display());
+    }
+}
+
+fn main() -> Result<()> {
+    env_logger::init();
+    check_parse_obj();
+
+    let window = WindowConfig::new()
+        .title("wolf-engine: drop .obj files here")
+        .size(1280, 720);
+    let config = RendererBuilder::new().app_name("drop_model").build()?;
+    App::<DropModel>::run_with(window, config)
+}
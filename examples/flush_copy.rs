@@ -0,0 +1,111 @@
+//! examples/flush_copy.rs – GPU work without presenting through `VulkanRenderer::flush`
+//!
+//! Queues a buffer-to-buffer copy with `submit_with_frame`, runs it with `flush`
+//! instead of a frame, and reads the destination back, the way an offscreen
+//! tool (lightmap bake, thumbnails) uses the renderer. No frame is rendered or
+//! presented; the window only exists because `initialize` needs a surface. A
+//! wrong or missing byte fails the run with a non-zero exit code:
+//!
+//! ```text
+//! RUST_LOG=info cargo run --example flush_copy
+//! ```
+
+use log::{error, info};
+use vulkanalia::prelude::v1_0::*;
+use winit::{
+    application::ApplicationHandler,
+    event::WindowEvent,
+    event_loop::{ActiveEventLoop, EventLoop},
+    window::{Window, WindowId},
+};
+
+use wolf_engine::core::renderer::api::Renderer;
+use wolf_engine::core::renderer::backend::vulkan::barrier::buffer_barrier;
+use wolf_engine::core::renderer::backend::vulkan::{Buffer, VulkanRenderer};
+use wolf_engine::core::renderer::config::RendererBuilder;
+use wolf_engine::error::{AppError, Result};
+
+const SIZE: usize = 4096;
+
+/// Distinct bytes, so a partial or offset copy shows up.
+fn pattern() -> Vec<u8> {
+    (0..SIZE).map(|i| (i * 7 + i / 256) as u8).collect()
+}
+
+#[derive(Default)]
+struct FlushCopy {
+    error: Option<AppError>,
+}
+
+impl FlushCopy {
+    fn run(&mut self, event_loop: &ActiveEventLoop) -> Result<()> {
+        let window = event_loop.create_window(
+            Window::default_attributes()
+                .with_title("wolf-engine: flush copy")
+                .with_visible(false),
+        )?;
+        let config = RendererBuilder::new().app_name("flush_copy").build()?;
+        let mut renderer = VulkanRenderer::new(config);
+        renderer.initialize(&window, event_loop)?;
+        let context = renderer.context().ok_or(AppError::NotInitialized)?.clone();
+
+        let host = vk::MemoryPropertyFlags::HOST_VISIBLE | vk::MemoryPropertyFlags::HOST_COHERENT;
+        let size = SIZE as vk::DeviceSize;
+        let source = Buffer::new(&context, size, vk::BufferUsageFlags::TRANSFER_SRC, host)?;
+        let target = Buffer::new(&context, size, vk::BufferUsageFlags::TRANSFER_DST, host)?;
+        let expected = pattern();
+        source.write(&expected)?;
+        target.write(&[0; SIZE])?;
+
+        let (src, dst) = (source.handle(), target.handle());
+        let mut barrier = Ok(());
+        renderer.submit_with_frame(|device, cmd| {
+            let region = vk::BufferCopy::builder().size(size);
+            unsafe { device.cmd_copy_buffer(cmd, src, dst, &[region]) };
+            // Make the copy visible to the host read below
+            let (write, read) = (vk::AccessFlags::TRANSFER_WRITE, vk::AccessFlags::HOST_READ);
+            barrier = buffer_barrier(device, cmd, dst, write, read);
+        })?;
+        barrier?;
+        renderer.flush()?;
+        // Nothing left queued: a second flush returns at once
+        renderer.flush()?;
+
+        let mut copied = vec![0; SIZE];
+        target.read(&mut copied)?;
+        if let Some(first) = copied.iter().zip(&expected).position(|(a, b)| a != b) {
+            return Err(AppError::Config(format!(
+                "byte {first} reads {} after the flush, expected {}",
+                copied[first], expected[first]
+            )));
+        }
+        if renderer.stats().frame_count != 0 {
+            return Err(AppError::Config("flush rendered a frame".into()));
+        }
+        info!("✅ {SIZE}-byte copy flushed and read back, no frame presented");
+        drop((source, target, context));
+        drop(renderer);
+        drop(window);
+        Ok(())
+    }
+}
+
+impl ApplicationHandler for FlushCopy {
+    fn resumed(&mut self, event_loop: &ActiveEventLoop) {
+        if let Err(e) = self.run(event_loop) {
+            error!("{e}");
+            self.error.get_or_insert(e);
+        }
+        event_loop.exit();
+    }
+
+    fn window_event(&mut self, _event_loop: &ActiveEventLoop, _id: WindowId, _event: WindowEvent) {}
+}
+
+fn main() -> Result<()> {
+    env_logger::init();
+    let event_loop = EventLoop::new()?;
+    let mut app = FlushCopy::default();
+    event_loop.run_app(&mut app)?;
+    app.error.map_or(Ok(()), Err)
+}
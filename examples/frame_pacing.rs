@@ -0,0 +1,92 @@
+//! examples/frame_pacing.rs – check the frame cap and frame stats on a fake clock
+//!
+//! Drives `FrameLimiter` and `RenderStats` the way `VulkanRenderer` does each
+//! frame, but on a `FakeClock` that only moves when the simulated frame's work
+//! advances it, so the checks are exact and take no real time. Needs no window
+//! or GPU; any mismatch exits with an error:
+//!
+//! ```text
+//! RUST_LOG=info cargo run --example frame_pacing
+//! ```
+//!
+//! The same clock can be handed to a renderer with `RendererBuilder::clock`.
+
+use std::time::{Duration, Instant};
+
+use log::info;
+
+use wolf_engine::core::renderer::pacing::FrameLimiter;
+use wolf_engine::core::renderer::stats::RenderStats;
+use wolf_engine::core::renderer::time::{FakeClock, TimeSource};
+use wolf_engine::error::{AppError, Result};
+
+const MAX_FPS: u32 = 60;
+const FRAMES: usize = 120;
+const EPSILON: Duration = Duration::from_micros(1); // EMA rounding through f64
+
+/// Run `work.len()` frames: pace, time the frame from the previous start, then
+/// "render" by advancing the clock. Returns the stats and each frame's start.
+fn simulate(clock: &FakeClock, max_fps: u32, work: &[Duration]) -> (RenderStats, Vec<Instant>) {
+    let mut limiter = FrameLimiter::new(max_fps);
+    let mut stats = RenderStats::default();
+    let mut starts = Vec::with_capacity(work.len());
+    for &busy in work {
+        if let Some(limiter) = &mut limiter {
+            limiter.wait(clock);
+        }
+        let start = clock.now();
+        let frame_time = starts.last().map_or(Duration::ZERO, |&last| {
+            start.saturating_duration_since(last)
+        });
+        starts.push(start);
+        stats.record_frame(frame_time, Duration::ZERO);
+        clock.advance(busy);
+    }
+    (stats, starts)
+}
+
+fn check(what: &str, got: Duration, expected: Duration) -> Result<()> {
+    if got.abs_diff(expected) > EPSILON {
+        return Err(AppError::Config(format!(
+            "{what}: got {got:?}, expected {expected:?}"
+        )));
+    }
+    Ok(())
+}
+
+fn main() -> Result<()> {
+    env_logger::init();
+    let cap = Duration::from_secs(1) / MAX_FPS;
+
+    // Light frames are held to the cap: every start exactly one frame time apart
+    let clock = FakeClock::new();
+    let (stats, starts) = simulate(&clock, MAX_FPS, &[Duration::from_millis(4); FRAMES]);
+    for pair in starts.windows(2) {
+        check("capped frame interval", pair[1] - pair[0], cap)?;
+    }
+    check("capped average frame time", stats.frame_time, cap)?;
+    info!("✅ {MAX_FPS} fps cap: {FRAMES} frames {cap:?} apart");
+
+    // Frames slower than the cap run at their own pace, with no catch-up burst
+    let slow = Duration::from_millis(25);
+    let (stats, starts) = simulate(&clock, MAX_FPS, &[slow; FRAMES]);
+    for pair in starts.windows(2) {
+        check("late frame interval", pair[1] - pair[0], slow)?;
+    }
+    check("uncapped average frame time", stats.frame_time, slow)?;
+    info!("✅ Late frames aren't delayed further");
+
+    // The average smooths one spike: 10% weight on the newest sample
+    let mut work = vec![Duration::from_millis(10); FRAMES];
+    work.push(Duration::from_millis(50));
+    work.push(Duration::ZERO); // A frame's work shows in the next frame's interval
+    let (stats, _) = simulate(&clock, 0, &work);
+    let expected = Duration::from_secs_f64(0.010 * 0.9 + 0.050 * 0.1);
+    check("average after a spike", stats.frame_time, expected)?;
+    let fps = 1.0 / stats.frame_time.as_secs_f64();
+    info!(
+        "✅ Average after a 50 ms spike: {:?} ({fps:.1} fps)",
+        stats.frame_time
+    );
+    Ok(())
+}
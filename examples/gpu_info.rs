@@ -0,0 +1,182 @@
+//! examples/gpu_info.rs – capability report of every GPU, for bug reports
+//!
+//! Needs no window or display: creates a bare instance, prints the
+//! `Capabilities` (device, driver, API version, limits, sample counts, depth
+//! formats, notable features) of each physical device and exits. Present modes
+//! and surface formats need a surface; they are listed when the loader offers
+//! `VK_EXT_headless_surface` (Mesa drivers do), otherwise reported as unknown.
+//! The instance is created through `application_info`, checking on the way that
+//! the configured app / engine names and versions reach the driver. Before
+//! that, GPU selection is checked on a mocked discrete + integrated pair: the
+//! discrete one by default, the integrated one with `prefer_integrated`, and the
+//! best presenting one when the discrete GPU can't present. So is the depth
+//! format query on mocked format properties.
+//!
+//! ```text
+//! cargo run --example gpu_info > gpu_info.txt
+//! ```
+
+use std::ffi::CStr;
+
+use log::warn;
+use vulkanalia::loader::{LIBRARY, LibloadingLoader};
+use vulkanalia::prelude::v1_0::*;
+use vulkanalia::vk::{ExtHeadlessSurfaceExtension, KhrSurfaceExtension};
+
+use wolf_engine::core::renderer::backend::vulkan::Capabilities;
+use wolf_engine::core::renderer::backend::vulkan::capabilities::{
+    DEPTH_FORMATS, supported_depth_formats,
+};
+use wolf_engine::core::renderer::backend::vulkan::init::{application_info, application_names};
+use wolf_engine::core::renderer::backend::vulkan::selection::{
+    GpuCandidate, PresentPath, select_gpu,
+};
+use wolf_engine::core::renderer::config::{ENGINE_VERSION, RendererBuilder};
+use wolf_engine::error::{AppError, Result};
+
+/// Both orders of a discrete and an integrated GPU that can each render and
+/// present: the device type decides, not the position.
+fn check_selection() {
+    let gpu = |device_type| GpuCandidate {
+        device_type,
+        graphics_family: Some(0),
+        present_family: Some(0),
+    };
+    let discrete = gpu(vk::PhysicalDeviceType::DISCRETE_GPU);
+    let integrated = gpu(vk::PhysicalDeviceType::INTEGRATED_GPU);
+    for (candidates, discrete_index) in [([discrete, integrated], 0), ([integrated, discrete], 1)] {
+        let integrated_index = 1 - discrete_index;
+        assert_eq!(
+            select_gpu(&candidates, false),
+            Some(PresentPath::Direct(discrete_index))
+        );
+        assert_eq!(
+            select_gpu(&candidates, true),
+            Some(PresentPath::Direct(integrated_index))
+        );
+    }
+    // Without the preferred type the other one still beats the fallback
+    assert_eq!(select_gpu(&[discrete], true), Some(PresentPath::Direct(0)));
+
+    // Hybrid: the dGPU can't present, so the best-ranked presenting GPU is used
+    // (integrated over the earlier virtual one)
+    let headless_discrete = GpuCandidate {
+        present_family: None,
+        ..discrete
+    };
+    let virtual_gpu = gpu(vk::PhysicalDeviceType::VIRTUAL_GPU);
+    assert_eq!(
+        select_gpu(&[headless_discrete, virtual_gpu, integrated], false),
+        Some(PresentPath::CrossDevice {
+            render: 0,
+            present: 2
+        })
+    );
+}
+
+/// Only formats whose optimal tiling allows depth attachments are listed, in
+/// candidate order; linear-tiling support or other features don't count.
+fn check_depth_formats() {
+    let attachment = vk::FormatFeatureFlags::DEPTH_STENCIL_ATTACHMENT;
+    let properties = |format| match format {
+        vk::Format::D16_UNORM | vk::Format::D32_SFLOAT => vk::FormatProperties {
+            optimal_tiling_features: attachment | vk::FormatFeatureFlags::SAMPLED_IMAGE,
+            ..Default::default()
+        },
+        vk::Format::D24_UNORM_S8_UINT => vk::FormatProperties {
+            linear_tiling_features: attachment,
+            ..Default::default()
+        },
+        vk::Format::D32_SFLOAT_S8_UINT => vk::FormatProperties {
+            optimal_tiling_features: vk::FormatFeatureFlags::SAMPLED_IMAGE,
+            ..Default::default()
+        },
+        _ => vk::FormatProperties::default(),
+    };
+    assert_eq!(
+        supported_depth_formats(&DEPTH_FORMATS, properties),
+        [vk::Format::D32_SFLOAT, vk::Format::D16_UNORM]
+    );
+    assert!(supported_depth_formats(&DEPTH_FORMATS, |_| Default::default()).is_empty());
+}
+
+fn main() -> Result<()> {
+    env_logger::init();
+    check_selection();
+    check_depth_formats();
+    let loader = unsafe { LibloadingLoader::new(LIBRARY) }?;
+    let entry = unsafe { Entry::new(loader) }?;
+
+    let available = unsafe { entry.enumerate_instance_extension_properties(None) }
+        .map_err(|e| AppError::vk(e, "enumerate instance extensions"))?;
+    let has = |name: vk::ExtensionName| available.iter().any(|e| e.extension_name == name);
+    let props2 = has(vk::KHR_GET_PHYSICAL_DEVICE_PROPERTIES2_EXTENSION.name);
+    let headless =
+        has(vk::KHR_SURFACE_EXTENSION.name) && has(vk::EXT_HEADLESS_SURFACE_EXTENSION.name);
+
+    let mut exts: Vec<*const i8> = Vec::new();
+    if props2 {
+        exts.push(
+            vk::KHR_GET_PHYSICAL_DEVICE_PROPERTIES2_EXTENSION
+                .name
+                .as_ptr(),
+        );
+    }
+    if headless {
+        exts.push(vk::KHR_SURFACE_EXTENSION.name.as_ptr());
+        exts.push(vk::EXT_HEADLESS_SURFACE_EXTENSION.name.as_ptr());
+    }
+    #[allow(unused_mut)]
+    let mut flags = vk::InstanceCreateFlags::empty();
+    #[cfg(target_os = "macos")]
+    {
+        exts.push(vk::KHR_PORTABILITY_ENUMERATION_EXTENSION.name.as_ptr());
+        flags |= vk::InstanceCreateFlags::ENUMERATE_PORTABILITY_KHR;
+    }
+
+    let config = RendererBuilder::new()
+        .app_name("gpu_info")
+        .app_version(1, 2, 3)
+        .build()?;
+    let (app_name, engine_name) = application_names(&config)?;
+    let app_info = application_info(&config, &app_name, &engine_name, vk::make_version(1, 0, 0));
+    // What the driver sees: the configured names and versions
+    let name = |ptr| unsafe { CStr::from_ptr(ptr) };
+    assert_eq!(name(app_info.application_name), c"gpu_info");
+    assert_eq!(app_info.application_version, vk::make_version(1, 2, 3));
+    assert_eq!(name(app_info.engine_name), c"Wolf Engine");
+    assert_eq!(app_info.engine_version, ENGINE_VERSION);
+    let instance_info = vk::InstanceCreateInfo::builder()
+        .application_info(&app_info)
+        .enabled_extension_names(&exts)
+        .flags(flags);
+    let instance = unsafe { entry.create_instance(&instance_info, None) }
+        .map_err(|e| AppError::vk(e, "create instance"))?;
+
+    let surface = if headless {
+        let info = vk::HeadlessSurfaceCreateInfoEXT::builder();
+        unsafe { instance.create_headless_surface_ext(&info, None) }
+            .inspect_err(|e| warn!("Headless surface unavailable: {e}"))
+            .ok()
+    } else {
+        None
+    };
+
+    let result = unsafe { instance.enumerate_physical_devices() }
+        .map_err(|e| AppError::vk(e, "enumerate physical devices"));
+    if let Ok(devices) = &result {
+        println!("{} GPU(s) found\n", devices.len());
+        for (index, &device) in devices.iter().enumerate() {
+            let capabilities = Capabilities::query(&instance, device, props2, surface);
+            println!("GPU {index}: {capabilities}");
+        }
+    }
+
+    unsafe {
+        if let Some(surface) = surface {
+            instance.destroy_surface_khr(surface, None);
+        }
+        instance.destroy_instance(None);
+    }
+    result.map(|_| ())
+}
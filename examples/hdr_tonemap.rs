@@ -0,0 +1,140 @@
+//! examples/hdr_tonemap.rs – over-bright HDR scene with switchable tonemapping
+//!
+//! The scene is cleared to a color well above 1.0, which only survives in the
+//! R16G16B16A16_SFLOAT target. `T` cycles None (clamp) → Reinhard → ACES, `+`/`-`
+//! change the exposure, Alt+Enter toggles fullscreen. Compile the built-in
+//! shaders first:
+//!
+//! ```text
+//! glslc shaders/fullscreen.vert -o shaders/fullscreen.vert.spv
+//! glslc shaders/tonemap.frag -o shaders/tonemap.frag.spv
+//! ```
+
+use log::{error, info, warn};
+use winit::{
+    application::ApplicationHandler,
+    event::{ElementState, WindowEvent},
+    event_loop::{ActiveEventLoop, ControlFlow, EventLoop},
+    keyboard::{KeyCode, ModifiersState, PhysicalKey},
+    window::{Window, WindowId},
+};
+
+use wolf_engine::app::{is_fullscreen_toggle, set_fullscreen};
+use wolf_engine::core::renderer::api::Renderer;
+use wolf_engine::core::renderer::backend::vulkan::VulkanRenderer;
+use wolf_engine::core::renderer::config::{RendererBuilder, TonemapMode};
+use wolf_engine::error::Result;
+
+const OVER_BRIGHT: [f32; 4] = [6.0, 3.0, 1.2, 1.0];
+
+#[derive(Default)]
+struct App {
+    renderer: Option<VulkanRenderer>, // dropped before the window it presents to
+    window: Option<Window>,
+    modifiers: ModifiersState, // For Alt+Enter
+}
+
+impl App {
+    fn on_key(&mut self, key: KeyCode) {
+        let Some(renderer) = &mut self.renderer else {
+            return;
+        };
+        match key {
+            KeyCode::KeyT => {
+                let next = match renderer.tonemap() {
+                    TonemapMode::None => TonemapMode::Reinhard,
+                    TonemapMode::Reinhard => TonemapMode::Aces,
+                    TonemapMode::Aces => TonemapMode::None,
+                };
+                match renderer.set_tonemap(next) {
+                    Ok(()) => info!("🖥️ Tonemap: {next:?}"),
+                    Err(e) => warn!("{e}"),
+                }
+            }
+            KeyCode::Equal | KeyCode::NumpadAdd => {
+                renderer.set_exposure(renderer.exposure() * 1.25);
+                info!("🖥️ Exposure: {:.2}", renderer.exposure());
+            }
+            KeyCode::Minus | KeyCode::NumpadSubtract => {
+                renderer.set_exposure(renderer.exposure() / 1.25);
+                info!("🖥️ Exposure: {:.2}", renderer.exposure());
+            }
+            _ => {}
+        }
+    }
+}
+
+impl ApplicationHandler for App {
+    fn resumed(&mut self, event_loop: &ActiveEventLoop) {
+        let window = event_loop
+            .create_window(Window::default_attributes().with_title("wolf-engine: hdr tonemap"))
+            .expect("create window");
+
+        let config = RendererBuilder::new()
+            .app_name("hdr_tonemap")
+            .tonemap(TonemapMode::Reinhard)
+            .build()
+            .expect("valid config");
+        let mut renderer = VulkanRenderer::new(config);
+        renderer
+            .initialize(&window, event_loop)
+            .expect("Renderer initialization failed");
+        renderer.set_clear_color(OVER_BRIGHT);
+
+        if renderer.tonemap() == TonemapMode::None {
+            warn!("HDR path unavailable (see log above), showing the clamped scene");
+        } else {
+            info!(
+                "🖥️ Tonemap: {:?} – T to cycle, +/- exposure",
+                renderer.tonemap()
+            );
+        }
+
+        self.renderer = Some(renderer);
+        self.window = Some(window);
+    }
+
+    fn window_event(&mut self, event_loop: &ActiveEventLoop, id: WindowId, event: WindowEvent) {
+        if let WindowEvent::ModifiersChanged(modifiers) = &event {
+            self.modifiers = modifiers.state();
+        }
+        // The Resized event that follows recreates the swapchain
+        if is_fullscreen_toggle(&event, self.modifiers)
+            && let Some(window) = &self.window
+        {
+            set_fullscreen(window, window.fullscreen().is_none());
+        }
+        if let WindowEvent::KeyboardInput { event: key, .. } = &event
+            && key.state == ElementState::Pressed
+            && let PhysicalKey::Code(code) = key.physical_key
+        {
+            self.on_key(code);
+        }
+
+        let Some(renderer) = &mut self.renderer else {
+            return;
+        };
+        renderer.window_event(event_loop, id, &event);
+        if matches!(event, WindowEvent::RedrawRequested)
+            && let Err(e) = renderer.render()
+        {
+            error!("Render failed: {e}");
+            event_loop.exit();
+        }
+    }
+
+    fn about_to_wait(&mut self, _event_loop: &ActiveEventLoop) {
+        if let Some(window) = &self.window {
+            window.request_redraw();
+        }
+    }
+}
+
+fn main() -> Result<()> {
+    env_logger::init();
+    let mut app = App::default();
+    let event_loop = EventLoop::new()?;
+    event_loop.set_control_flow(ControlFlow::Poll);
+    event_loop.run_app(&mut app)?;
+    Ok(())
+}
@@ -0,0 +1,74 @@
+//! examples/headless_clear.rs – clear + readback through `HeadlessRenderer`
+//!
+//! Clears an offscreen image to a known color, reads it back and checks every
+//! pixel, exiting with an error on any mismatch. Needs no window, display or
+//! shaders, and builds without winit, so it checks the `headless` feature on
+//! its own:
+//!
+//! ```text
+//! cargo build --no-default-features --features headless
+//! cargo run --no-default-features --features headless --example headless_clear
+//! ```
+//!
+//! With only `headless` enabled the renderer is taken as `SelectedRenderer`,
+//! checking that it resolves to `HeadlessRenderer`. Without a GPU, point the
+//! loader at lavapipe as described in `headless_screenshot`.
+
+use log::info;
+use vulkanalia::vk;
+
+#[cfg(not(feature = "vulkan"))]
+use wolf_engine::core::renderer::backend::SelectedRenderer as Renderer;
+#[cfg(feature = "vulkan")]
+use wolf_engine::core::renderer::backend::vulkan::HeadlessRenderer as Renderer;
+use wolf_engine::core::renderer::config::RendererBuilder;
+use wolf_engine::error::{AppError, Result};
+
+const EXTENT: vk::Extent2D = vk::Extent2D {
+    width: 64,
+    height: 48,
+};
+const CLEAR: [f32; 4] = [0.25, 0.5, 0.75, 1.0];
+const TOLERANCE: u8 = 1; // Rounding of the UNORM conversion differs between drivers
+
+/// `color` as RGBA8, the way a UNORM attachment stores it.
+fn unorm8(color: [f32; 4]) -> [u8; 4] {
+    color.map(|c| (c.clamp(0.0, 1.0) * 255.0).round() as u8)
+}
+
+fn main() -> Result<()> {
+    env_logger::init();
+    let config = RendererBuilder::new().app_name("headless_clear").build()?;
+    let mut renderer = Renderer::new(config);
+    renderer.initialize()?;
+
+    let pixels = renderer.clear(EXTENT, CLEAR)?;
+    let expected = unorm8(CLEAR);
+    let pixel_count = (EXTENT.width * EXTENT.height) as usize;
+    if pixels.len() != pixel_count * 4 {
+        return Err(AppError::Config(format!(
+            "read back {} bytes, expected {}",
+            pixels.len(),
+            pixel_count * 4
+        )));
+    }
+    let mismatched = pixels
+        .chunks_exact(4)
+        .filter(|px| {
+            px.iter()
+                .zip(expected)
+                .any(|(&a, b)| a.abs_diff(b) > TOLERANCE)
+        })
+        .count();
+    if mismatched > 0 {
+        return Err(AppError::Config(format!(
+            "{mismatched} of {pixel_count} pixels differ from {expected:?} (first pixel: {:?})",
+            &pixels[..4]
+        )));
+    }
+    info!(
+        "✅ {}x{} cleared to {expected:?} and read back",
+        EXTENT.width, EXTENT.height
+    );
+    Ok(())
+}
@@ -0,0 +1,848 @@
+//! examples/headless_screenshot.rs – offscreen render → PNG, optional golden-image check
+//!
+//! Needs no window or display: creates an instance without surface extensions,
+//! renders a clear + RGB triangle into an offscreen R8G8B8A8_UNORM image, copies
+//! it into a host-visible buffer and writes it out as PNG. With `--golden` the
+//! result of any mode is compared per channel against a committed image and
+//! the process exits with 1 on mismatch, so it doubles as a visual regression
+//! test in CI. The PNGs are written and read by a small built-in codec that
+//! stores the pixels uncompressed, so make goldens with `--out`.
+//! `--fullscreen` draws the full-screen triangle with a solid-color fragment
+//! shader instead and checks that it covered every pixel. `--clear-rect` then
+//! clears a square in the middle mid-pass (`cmd_clear_attachments`) and checks
+//! for exactly two colors: the square and the full-screen color around it.
+//! `--msaa 4` draws the triangle in solid color into a multisampled image that
+//! the pass resolves into the captured one, and checks that the triangle's
+//! edges came out anti-aliased (colors between the triangle and the clear).
+//!
+//! ```text
+//! glslc shaders/triangle.vert -o shaders/triangle.vert.spv
+//! glslc shaders/triangle.frag -o shaders/triangle.frag.spv
+//! cargo run --example headless_screenshot -- --out shot.png \
+//!     [--golden golden/triangle.png] [--tolerance 2]
+//!
+//! glslc shaders/fullscreen.vert -o shaders/fullscreen.vert.spv
+//! glslc shaders/solid.frag -o shaders/solid.frag.spv
+//! cargo run --example headless_screenshot -- --fullscreen [--clear-rect] [--tolerance 1]
+//! cargo run --example headless_screenshot -- --msaa 4
+//! ```
+//!
+//! `golden/clear_rect.png` is the committed reference of `--clear-rect`. Unlike
+//! triangle edges it leaves the driver no rasterization choices, only the
+//! rounding of solid.frag's 0.5, hence a tolerance of 1:
+//!
+//! ```text
+//! cargo run --example headless_screenshot -- --clear-rect \
+//!     --golden golden/clear_rect.png --tolerance 1
+//! ```
+//!
+//! On CI machines without a GPU use Mesa's software driver lavapipe
+//! (Debian/Ubuntu: `apt install mesa-vulkan-drivers`) and point the loader at it:
+//!
+//! ```text
+//! export VK_DRIVER_FILES=/usr/share/vulkan/icd.d/lvp_icd.x86_64.json
+//! # loaders older than 1.3.234 read VK_ICD_FILENAMES instead
+//! ```
+//!
+//! Rasterization rules leave a little room between drivers, so goldens made on
+//! lavapipe should be compared on lavapipe; `--tolerance` absorbs edge pixels.
+
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::sync::Arc;
+
+use log::{error, info};
+use vulkanalia::loader::{LIBRARY, LibloadingLoader};
+use vulkanalia::prelude::v1_0::*;
+
+use wolf_engine::core::renderer::backend::vulkan::barrier::buffer_barrier;
+use wolf_engine::core::renderer::backend::vulkan::capabilities::sample_counts_from_limits;
+use wolf_engine::core::renderer::backend::vulkan::fullscreen::{
+    draw_fullscreen_triangle, load_fullscreen_vertex_shader,
+};
+use wolf_engine::core::renderer::backend::vulkan::image::ImageDesc;
+use wolf_engine::core::renderer::backend::vulkan::render_pass::clear_attachment_info;
+use wolf_engine::core::renderer::backend::vulkan::shader::load_shader_module;
+use wolf_engine::core::renderer::backend::vulkan::transfer::color_layers;
+use wolf_engine::core::renderer::backend::vulkan::{
+    Buffer, ClearTarget, ClearValue, ColorLoadOp, DeviceContext, RenderPassBuilder, Texture,
+};
+use wolf_engine::error::{AppError, Result};
+
+const WIDTH: u32 = 256;
+const HEIGHT: u32 = 256;
+const FORMAT: vk::Format = vk::Format::R8G8B8A8_UNORM; // Byte order matches PNG RGBA
+const CLEAR: [f32; 4] = [0.1, 0.1, 0.15, 1.0];
+const BACKGROUND: [u8; 4] = [26, 26, 38, 255]; // CLEAR as RGBA8, give or take rounding
+const SOLID: [u8; 4] = [255, 128, 0, 255]; // shaders/solid.frag as RGBA8
+const CLEARED: [u8; 4] = [0, 0, 255, 255]; // `--clear-rect` square
+const CLEAR_RECT: vk::Rect2D = vk::Rect2D {
+    offset: vk::Offset2D { x: 64, y: 64 },
+    extent: vk::Extent2D {
+        width: 128,
+        height: 128,
+    },
+};
+
+struct Args {
+    out: PathBuf,
+    golden: Option<PathBuf>,
+    tolerance: u8, // Max per-channel difference still counted as a match
+    shader_dir: PathBuf,
+    fullscreen: bool, // Full-screen triangle + solid color instead of the RGB triangle
+    clear_rect: bool, // Plus a mid-pass clear of CLEAR_RECT (implies fullscreen)
+    msaa: u32,        // Sample count of the drawn image; > 1 resolves into the target
+}
+
+fn parse_args() -> Result<Args> {
+    let mut args = Args {
+        out: PathBuf::from("headless_screenshot.png"),
+        golden: None,
+        tolerance: 0,
+        shader_dir: PathBuf::from(concat!(env!("CARGO_MANIFEST_DIR"), "/shaders")),
+        fullscreen: false,
+        clear_rect: false,
+        msaa: 1,
+    };
+    let mut it = std::env::args().skip(1);
+    while let Some(flag) = it.next() {
+        let mut value = || {
+            it.next()
+                .ok_or_else(|| AppError::Config(format!("{flag} needs a value")))
+        };
+        match flag.as_str() {
+            "--out" => args.out = value()?.into(),
+            "--golden" => args.golden = Some(value()?.into()),
+            "--shader-dir" => args.shader_dir = value()?.into(),
+            "--fullscreen" => args.fullscreen = true,
+            "--clear-rect" => {
+                args.fullscreen = true;
+                args.clear_rect = true;
+            }
+            "--msaa" => {
+                args.msaa = value()?
+                    .parse()
+                    .map_err(|e| AppError::Config(format!("--msaa: {e}")))?;
+            }
+            "--tolerance" => {
+                args.tolerance = value()?
+                    .parse()
+                    .map_err(|e| AppError::Config(format!("--tolerance: {e}")))?;
+            }
+            _ => return Err(AppError::Config(format!("unknown argument {flag}"))),
+        }
+    }
+    if args.msaa > 1 && args.fullscreen {
+        // The full-screen triangle has no edges inside the image to anti-alias
+        return Err(AppError::Config(
+            "--msaa draws the triangle, not --fullscreen".into(),
+        ));
+    }
+    Ok(args)
+}
+
+/// Instance + device without any surface or swapchain extension,
+/// plus the graphics queue family.
+fn create_context() -> Result<(Arc<DeviceContext>, u32)> {
+    let loader = unsafe { LibloadingLoader::new(LIBRARY) }?;
+    let entry = unsafe { Entry::new(loader) }?;
+
+    let app_info = vk::ApplicationInfo::builder()
+        .application_name(b"headless_screenshot\0")
+        .engine_name(b"Wolf Engine\0")
+        .api_version(vk::make_version(1, 0, 0));
+    #[allow(unused_mut)]
+    let mut exts: Vec<*const i8> = Vec::new();
+    #[allow(unused_mut)]
+    let mut flags = vk::InstanceCreateFlags::empty();
+    #[cfg(target_os = "macos")]
+    {
+        exts.push(vk::KHR_PORTABILITY_ENUMERATION_EXTENSION.name.as_ptr());
+        flags |= vk::InstanceCreateFlags::ENUMERATE_PORTABILITY_KHR;
+    }
+    let instance_info = vk::InstanceCreateInfo::builder()
+        .application_info(&app_info)
+        .enabled_extension_names(&exts)
+        .flags(flags);
+    let instance = unsafe { entry.create_instance(&instance_info, None) }
+        .map_err(|e| AppError::vk(e, "create instance"))?;
+
+    // First GPU with a graphics queue; lavapipe qualifies
+    let picked = unsafe { instance.enumerate_physical_devices() }
+        .map_err(|e| AppError::vk(e, "enumerate physical devices"))
+        .and_then(|devices| {
+            devices
+                .into_iter()
+                .find_map(|dev| {
+                    let families =
+                        unsafe { instance.get_physical_device_queue_family_properties(dev) };
+                    families
+                        .iter()
+                        .position(|f| f.queue_flags.contains(vk::QueueFlags::GRAPHICS))
+                        .map(|family| (dev, family as u32))
+                })
+                .ok_or_else(|| AppError::Config("no GPU with a graphics queue".into()))
+        });
+    let (physical_device, family) = match picked {
+        Ok(picked) => picked,
+        Err(e) => {
+            unsafe { instance.destroy_instance(None) };
+            return Err(e);
+        }
+    };
+    let name = unsafe { instance.get_physical_device_properties(physical_device) }
+        .device_name
+        .to_string_lossy()
+        .into_owned();
+    info!("✅ Rendering headless on {name}");
+
+    let priorities = [1.0];
+    let queue_infos = [vk::DeviceQueueCreateInfo::builder()
+        .queue_family_index(family)
+        .queue_priorities(&priorities)
+        .build()];
+    let device_info = vk::DeviceCreateInfo::builder().queue_create_infos(&queue_infos);
+    let device = match unsafe { instance.create_device(physical_device, &device_info, None) } {
+        Ok(device) => device,
+        Err(e) => {
+            unsafe { instance.destroy_instance(None) };
+            return Err(AppError::vk(e, "create device"));
+        }
+    };
+    // From here the context owns (and eventually destroys) both, and keeps the
+    // library loaded until then
+    let context = DeviceContext::new(entry, instance, device, physical_device);
+    Ok((Arc::new(context), family))
+}
+
+/// Raw handles of the one-off frame; null handles are ignored on destroy.
+#[derive(Default)]
+struct Frame {
+    render_pass: vk::RenderPass,
+    framebuffer: vk::Framebuffer,
+    vert: vk::ShaderModule,
+    frag: vk::ShaderModule,
+    layout: vk::PipelineLayout,
+    pipeline: vk::Pipeline,
+    pool: vk::CommandPool,
+    fence: vk::Fence,
+}
+
+impl Frame {
+    fn destroy(&self, device: &Device) {
+        unsafe {
+            device.destroy_fence(self.fence, None);
+            device.destroy_command_pool(self.pool, None);
+            device.destroy_pipeline(self.pipeline, None);
+            device.destroy_pipeline_layout(self.layout, None);
+            device.destroy_shader_module(self.frag, None);
+            device.destroy_shader_module(self.vert, None);
+            device.destroy_framebuffer(self.framebuffer, None);
+            device.destroy_render_pass(self.render_pass, None);
+        }
+    }
+}
+
+fn create_pipeline(
+    device: &Device,
+    frame: &mut Frame,
+    extent: vk::Extent2D,
+    samples: vk::SampleCountFlags,
+) -> Result<()> {
+    let stages = [
+        vk::PipelineShaderStageCreateInfo::builder()
+            .stage(vk::ShaderStageFlags::VERTEX)
+            .module(frame.vert)
+            .name(b"main\0")
+            .build(),
+        vk::PipelineShaderStageCreateInfo::builder()
+            .stage(vk::ShaderStageFlags::FRAGMENT)
+            .module(frame.frag)
+            .name(b"main\0")
+            .build(),
+    ];
+    let vertex_input = vk::PipelineVertexInputStateCreateInfo::builder();
+    let input_assembly = vk::PipelineInputAssemblyStateCreateInfo::builder()
+        .topology(vk::PrimitiveTopology::TRIANGLE_LIST);
+    // Fixed size, so the viewport can be baked in
+    let viewports = [vk::Viewport::builder()
+        .width(extent.width as f32)
+        .height(extent.height as f32)
+        .max_depth(1.0)
+        .build()];
+    let scissors = [vk::Rect2D::builder().extent(extent).build()];
+    let viewport_state = vk::PipelineViewportStateCreateInfo::builder()
+        .viewports(&viewports)
+        .scissors(&scissors);
+    let rasterization = vk::PipelineRasterizationStateCreateInfo::builder()
+        .polygon_mode(vk::PolygonMode::FILL)
+        .cull_mode(vk::CullModeFlags::NONE)
+        .front_face(vk::FrontFace::CLOCKWISE)
+        .line_width(1.0);
+    let multisample =
+        vk::PipelineMultisampleStateCreateInfo::builder().rasterization_samples(samples);
+    let blend_attachments = [vk::PipelineColorBlendAttachmentState::builder()
+        .color_write_mask(vk::ColorComponentFlags::all())
+        .blend_enable(false)
+        .build()];
+    let color_blend =
+        vk::PipelineColorBlendStateCreateInfo::builder().attachments(&blend_attachments);
+
+    let layout_info = vk::PipelineLayoutCreateInfo::builder();
+    frame.layout = unsafe { device.create_pipeline_layout(&layout_info, None) }
+        .map_err(|e| AppError::vk(e, "create pipeline layout"))?;
+
+    let info = vk::GraphicsPipelineCreateInfo::builder()
+        .stages(&stages)
+        .vertex_input_state(&vertex_input)
+        .input_assembly_state(&input_assembly)
+        .viewport_state(&viewport_state)
+        .rasterization_state(&rasterization)
+        .multisample_state(&multisample)
+        .color_blend_state(&color_blend)
+        .layout(frame.layout)
+        .render_pass(frame.render_pass)
+        .subpass(0);
+    frame.pipeline =
+        unsafe { device.create_graphics_pipelines(vk::PipelineCache::null(), &[info], None) }
+            .map_err(|e| AppError::vk(e, "create triangle pipeline"))?
+            .0[0];
+    Ok(())
+}
+
+/// Render one frame into `target` (drawn into `msaa` and resolved, if given)
+/// and copy it into `readback`.
+fn render(
+    context: &DeviceContext,
+    family: u32,
+    frame: &mut Frame,
+    target: &Texture,
+    msaa: Option<&Texture>,
+    readback: &Buffer,
+    args: &Args,
+) -> Result<()> {
+    let device = &context.device;
+    let shader_dir = &args.shader_dir;
+    let extent = target.extent();
+    let samples = vk::SampleCountFlags::from_bits_truncate(args.msaa.max(1));
+
+    // The pass itself moves the image into TRANSFER_SRC for the copy. With MSAA
+    // the samples stay in the multisampled image; only the resolve lands in the
+    // target, which is the one a buffer copy can read
+    let transfer = vk::ImageLayout::TRANSFER_SRC_OPTIMAL;
+    let builder = match msaa {
+        None => RenderPassBuilder::new()
+            .color_attachment(FORMAT, ColorLoadOp::Clear(CLEAR), transfer)
+            .subpass()
+            .color(0),
+        Some(_) => RenderPassBuilder::new()
+            .color_attachment(FORMAT, ColorLoadOp::DontCare, transfer)
+            .attachment(
+                vk::AttachmentDescription::builder()
+                    .format(FORMAT)
+                    .samples(samples)
+                    .load_op(vk::AttachmentLoadOp::CLEAR)
+                    .store_op(vk::AttachmentStoreOp::DONT_CARE)
+                    .stencil_load_op(vk::AttachmentLoadOp::DONT_CARE)
+                    .stencil_store_op(vk::AttachmentStoreOp::DONT_CARE)
+                    .initial_layout(vk::ImageLayout::UNDEFINED)
+                    .final_layout(vk::ImageLayout::COLOR_ATTACHMENT_OPTIMAL)
+                    .build(),
+            )
+            .clear_value(1, ClearValue::Float(CLEAR))
+            .subpass()
+            .color(1)
+            .resolve(0),
+    };
+    let builder = builder.dependency(
+        vk::SubpassDependency::builder()
+            .src_subpass(0)
+            .dst_subpass(vk::SUBPASS_EXTERNAL)
+            .src_stage_mask(vk::PipelineStageFlags::COLOR_ATTACHMENT_OUTPUT)
+            .src_access_mask(vk::AccessFlags::COLOR_ATTACHMENT_WRITE)
+            .dst_stage_mask(vk::PipelineStageFlags::TRANSFER)
+            .dst_access_mask(vk::AccessFlags::TRANSFER_READ)
+            .build(),
+    );
+    frame.render_pass = builder.build(device)?;
+
+    let mut attachments = vec![target.view()];
+    attachments.extend(msaa.map(Texture::view));
+    let fb_info = vk::FramebufferCreateInfo::builder()
+        .render_pass(frame.render_pass)
+        .attachments(&attachments)
+        .width(extent.width)
+        .height(extent.height)
+        .layers(1);
+    frame.framebuffer = unsafe { device.create_framebuffer(&fb_info, None) }
+        .map_err(|e| AppError::vk(e, "create framebuffer"))?;
+
+    if args.fullscreen {
+        frame.vert = load_fullscreen_vertex_shader(device, shader_dir)?;
+        frame.frag = load_shader_module(device, &shader_dir.join("solid.frag.spv"))?;
+    } else {
+        // One solid color for MSAA, so any other color at the edges comes from the resolve
+        let frag = if msaa.is_some() {
+            "solid.frag.spv"
+        } else {
+            "triangle.frag.spv"
+        };
+        frame.vert = load_shader_module(device, &shader_dir.join("triangle.vert.spv"))?;
+        frame.frag = load_shader_module(device, &shader_dir.join(frag))?;
+    }
+    create_pipeline(device, frame, extent, samples)?;
+
+    let queue = unsafe { device.get_device_queue(family, 0) };
+
+    let pool_info = vk::CommandPoolCreateInfo::builder()
+        .flags(vk::CommandPoolCreateFlags::TRANSIENT)
+        .queue_family_index(family);
+    frame.pool = unsafe { device.create_command_pool(&pool_info, None) }
+        .map_err(|e| AppError::vk(e, "create command pool"))?;
+    let alloc_info = vk::CommandBufferAllocateInfo::builder()
+        .command_pool(frame.pool)
+        .level(vk::CommandBufferLevel::PRIMARY)
+        .command_buffer_count(1);
+    let cmd = unsafe { device.allocate_command_buffers(&alloc_info) }
+        .map_err(|e| AppError::vk(e, "allocate command buffer"))?[0];
+    frame.fence = unsafe { device.create_fence(&vk::FenceCreateInfo::default(), None) }
+        .map_err(|e| AppError::vk(e, "create fence"))?;
+
+    unsafe {
+        let begin_info = vk::CommandBufferBeginInfo::builder()
+            .flags(vk::CommandBufferUsageFlags::ONE_TIME_SUBMIT);
+        device
+            .begin_command_buffer(cmd, &begin_info)
+            .map_err(|e| AppError::vk(e, "begin command buffer"))?;
+
+        let pass_info = vk::RenderPassBeginInfo::builder()
+            .render_pass(frame.render_pass)
+            .framebuffer(frame.framebuffer)
+            .render_area(vk::Rect2D::builder().extent(extent).build())
+            .clear_values(builder.clear_values());
+        device.cmd_begin_render_pass(cmd, &pass_info, vk::SubpassContents::INLINE);
+        device.cmd_bind_pipeline(cmd, vk::PipelineBindPoint::GRAPHICS, frame.pipeline);
+        if args.fullscreen {
+            draw_fullscreen_triangle(device, cmd);
+        } else {
+            device.cmd_draw(cmd, 3, 1, 0, 0);
+        }
+        if args.clear_rect {
+            let value = ClearValue::Float([0.0, 0.0, 1.0, 1.0]).into();
+            let (attachment, rect) =
+                clear_attachment_info(ClearTarget::Color(0), value, CLEAR_RECT);
+            device.cmd_clear_attachments(cmd, &[attachment], &[rect]);
+        }
+        device.cmd_end_render_pass(cmd);
+
+        // Tightly packed rows (buffer_row_length 0), so the buffer is plain RGBA8
+        let region = vk::BufferImageCopy::builder()
+            .image_subresource(color_layers())
+            .image_extent(vk::Extent3D {
+                width: extent.width,
+                height: extent.height,
+                depth: 1,
+            });
+        device.cmd_copy_image_to_buffer(
+            cmd,
+            target.image(),
+            vk::ImageLayout::TRANSFER_SRC_OPTIMAL,
+            readback.handle(),
+            &[region],
+        );
+        buffer_barrier(
+            device,
+            cmd,
+            readback.handle(),
+            vk::AccessFlags::TRANSFER_WRITE,
+            vk::AccessFlags::HOST_READ,
+        )?;
+        device
+            .end_command_buffer(cmd)
+            .map_err(|e| AppError::vk(e, "end command buffer"))?;
+
+        let cmds = [cmd];
+        let submit = vk::SubmitInfo::builder().command_buffers(&cmds);
+        device
+            .queue_submit(queue, &[submit], frame.fence)
+            .map_err(|e| AppError::vk(e, "submit headless frame"))?;
+        device
+            .wait_for_fences(&[frame.fence], true, u64::MAX)
+            .map_err(|e| AppError::vk(e, "wait for headless frame"))?;
+    }
+    Ok(())
+}
+
+fn capture(args: &Args) -> Result<Vec<u8>> {
+    let (context, family) = create_context()?;
+    let extent = vk::Extent2D {
+        width: WIDTH,
+        height: HEIGHT,
+    };
+    let target = Texture::new(
+        &context,
+        ImageDesc {
+            extent,
+            format: FORMAT,
+            samples: vk::SampleCountFlags::_1,
+            usage: vk::ImageUsageFlags::COLOR_ATTACHMENT | vk::ImageUsageFlags::TRANSFER_SRC,
+            aspect: vk::ImageAspectFlags::COLOR,
+        },
+    )?;
+    let msaa = if args.msaa > 1 {
+        let limits = unsafe {
+            context
+                .instance
+                .get_physical_device_properties(context.physical_device)
+        }
+        .limits;
+        let supported = sample_counts_from_limits(&limits);
+        if !supported.contains(&args.msaa) {
+            return Err(AppError::Config(format!(
+                "{}x MSAA unsupported (supported: {supported:?})",
+                args.msaa
+            )));
+        }
+        let desc = ImageDesc {
+            extent,
+            format: FORMAT,
+            samples: vk::SampleCountFlags::from_bits_truncate(args.msaa),
+            usage: vk::ImageUsageFlags::COLOR_ATTACHMENT
+                | vk::ImageUsageFlags::TRANSIENT_ATTACHMENT,
+            aspect: vk::ImageAspectFlags::COLOR,
+        };
+        Some(Texture::new(&context, desc)?)
+    } else {
+        None
+    };
+    let size = u64::from(WIDTH * HEIGHT * 4);
+    let readback = Buffer::new(
+        &context,
+        size,
+        vk::BufferUsageFlags::TRANSFER_DST,
+        vk::MemoryPropertyFlags::HOST_VISIBLE | vk::MemoryPropertyFlags::HOST_COHERENT,
+    )?;
+
+    let mut frame = Frame::default();
+    let msaa = msaa.as_ref();
+    let result = render(&context, family, &mut frame, &target, msaa, &readback, args);
+    unsafe { context.device.device_wait_idle() }.ok();
+    frame.destroy(&context.device);
+    result?;
+
+    let mut pixels = vec![0; size as usize];
+    readback.read(&mut pixels)?;
+    // Dropping readback and target here releases the last context clone, then the device
+    Ok(pixels)
+}
+
+const PNG_SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n'];
+const STORED_BLOCK: usize = 0xffff; // Max bytes of one uncompressed deflate block
+
+fn be32(bytes: &[u8]) -> u32 {
+    u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])
+}
+
+/// CRC-32 (ISO 3309) of a PNG chunk's type and data.
+fn crc32(bytes: &[u8]) -> u32 {
+    !bytes.iter().fold(!0u32, |crc, &byte| {
+        (0..8).fold(crc ^ u32::from(byte), |crc, _| {
+            if crc & 1 == 1 {
+                (crc >> 1) ^ 0xedb8_8320
+            } else {
+                crc >> 1
+            }
+        })
+    })
+}
+
+/// Adler-32 checksum closing a zlib stream.
+fn adler32(bytes: &[u8]) -> u32 {
+    let (a, b) = bytes.iter().fold((1u32, 0u32), |(a, b), &byte| {
+        let a = (a + u32::from(byte)) % 65521;
+        (a, (b + a) % 65521)
+    });
+    (b << 16) | a
+}
+
+/// Encode 8-bit RGBA as a PNG of stored (uncompressed) deflate blocks: larger
+/// than a compressed one, but any PNG viewer opens it and it needs no codec.
+fn write_png(path: &Path, width: u32, height: u32, rgba: &[u8]) -> Result<()> {
+    // Filter type 0 (none) in front of every row
+    let row = width as usize * 4;
+    let raw: Vec<u8> = rgba
+        .chunks_exact(row)
+        .flat_map(|line| std::iter::once(0).chain(line.iter().copied()))
+        .collect();
+    let mut zlib = vec![0x78, 0x01]; // Deflate, 32 KiB window, no preset dictionary
+    let blocks = raw.len().div_ceil(STORED_BLOCK);
+    for (i, block) in raw.chunks(STORED_BLOCK).enumerate() {
+        let len = block.len() as u16;
+        zlib.push(u8::from(i + 1 == blocks)); // BFINAL on the last, BTYPE 00 (stored)
+        zlib.extend(len.to_le_bytes());
+        zlib.extend((!len).to_le_bytes());
+        zlib.extend(block);
+    }
+    zlib.extend(adler32(&raw).to_be_bytes());
+
+    let mut header = Vec::with_capacity(13);
+    header.extend(width.to_be_bytes());
+    header.extend(height.to_be_bytes());
+    header.extend([8, 6, 0, 0, 0]); // 8-bit RGBA, deflate, adaptive filters, no interlace
+    let mut png = PNG_SIGNATURE.to_vec();
+    for (kind, data) in [
+        (b"IHDR", &header[..]),
+        (b"IDAT", &zlib[..]),
+        (b"IEND", &[][..]),
+    ] {
+        png.extend((data.len() as u32).to_be_bytes());
+        let start = png.len();
+        png.extend(kind);
+        png.extend(data);
+        let crc = crc32(&png[start..]);
+        png.extend(crc.to_be_bytes());
+    }
+    std::fs::write(path, png)?;
+    Ok(())
+}
+
+/// Decode an 8-bit RGBA PNG into (width, height, pixels). Only what `write_png`
+/// produces is read, stored deflate blocks and unfiltered rows, so goldens are
+/// made with this example's `--out`.
+fn read_png(path: &Path) -> Result<(u32, u32, Vec<u8>)> {
+    let bad = |what: &str| AppError::Config(format!("{}: {what}", path.display()));
+    let bytes = std::fs::read(path)?;
+    let mut rest = bytes
+        .strip_prefix(&PNG_SIGNATURE[..])
+        .ok_or_else(|| bad("not a PNG"))?;
+
+    // Chunks: length, type, data, CRC of type + data
+    let mut size = None;
+    let mut zlib = Vec::new();
+    loop {
+        let len = rest.get(..4).map(be32).ok_or_else(|| bad("truncated"))? as usize;
+        let chunk = rest.get(4..8 + len).ok_or_else(|| bad("truncated"))?;
+        let crc = rest.get(8 + len..12 + len).map(be32);
+        if crc != Some(crc32(chunk)) {
+            return Err(bad("chunk CRC mismatch"));
+        }
+        let (kind, data) = chunk.split_at(4);
+        match kind {
+            b"IHDR" => {
+                if data.len() != 13 || data[8..] != [8, 6, 0, 0, 0] {
+                    return Err(bad("golden must be 8-bit RGBA and not interlaced"));
+                }
+                size = Some((be32(&data[..4]), be32(&data[4..8])));
+            }
+            b"IDAT" => zlib.extend_from_slice(data),
+            b"IEND" => break,
+            _ if kind[0].is_ascii_uppercase() => return Err(bad("unsupported critical chunk")),
+            _ => {} // Ancillary chunks (gamma, text, ...)
+        }
+        rest = &rest[12 + len..];
+    }
+    let (width, height) = size.ok_or_else(|| bad("no IHDR chunk"))?;
+
+    // zlib header, stored blocks (flags, LEN, NLEN, data), Adler-32
+    let unsupported = || bad("only uncompressed PNGs (as written by --out) are read");
+    let mut stream = match &zlib[..] {
+        [method, _, stream @ ..] if method & 0x0f == 8 => stream,
+        _ => return Err(bad("bad zlib header")),
+    };
+    let mut raw = Vec::new();
+    loop {
+        let [flags, l0, l1, n0, n1, tail @ ..] = stream else {
+            return Err(bad("truncated"));
+        };
+        let len = u16::from_le_bytes([*l0, *l1]);
+        if *flags >> 1 != 0 || !len != u16::from_le_bytes([*n0, *n1]) {
+            return Err(unsupported());
+        }
+        let block = tail.get(..len as usize).ok_or_else(|| bad("truncated"))?;
+        raw.extend_from_slice(block);
+        stream = &tail[len as usize..];
+        if *flags & 1 == 1 {
+            break;
+        }
+    }
+    if stream.get(..4).map(be32) != Some(adler32(&raw)) {
+        return Err(bad("Adler-32 mismatch"));
+    }
+
+    let row = width as usize * 4;
+    if raw.len() != (row + 1) * height as usize {
+        return Err(bad("image data doesn't match its size"));
+    }
+    let mut pixels = Vec::with_capacity(row * height as usize);
+    for line in raw.chunks_exact(row + 1) {
+        if line[0] != 0 {
+            return Err(unsupported());
+        }
+        pixels.extend_from_slice(&line[1..]);
+    }
+    Ok((width, height, pixels))
+}
+
+/// Expected `--clear-rect` output: CLEARED inside CLEAR_RECT, SOLID elsewhere.
+fn clear_rect_image() -> Vec<u8> {
+    let vk::Rect2D { offset, extent } = CLEAR_RECT;
+    let inside =
+        |pos: u32, start: i32, size: u32| (start as u32..start as u32 + size).contains(&pos);
+    (0..HEIGHT)
+        .flat_map(|y| (0..WIDTH).map(move |x| (x, y)))
+        .flat_map(|(x, y)| {
+            if inside(x, offset.x, extent.width) && inside(y, offset.y, extent.height) {
+                CLEARED
+            } else {
+                SOLID
+            }
+        })
+        .collect()
+}
+
+/// Number of pixels that are neither `a` nor `b` (off by more than `tolerance`
+/// from both): the blended edge pixels of an anti-aliased shape.
+fn count_between(pixels: &[u8], a: [u8; 4], b: [u8; 4], tolerance: u8) -> usize {
+    let near = |pixel: &[u8], color: [u8; 4]| {
+        pixel
+            .iter()
+            .zip(color)
+            .all(|(p, c)| p.abs_diff(c) <= tolerance)
+    };
+    pixels
+        .chunks_exact(4)
+        .filter(|pixel| !near(pixel, a) && !near(pixel, b))
+        .count()
+}
+
+/// Number of pixels with any channel off by more than `tolerance`.
+fn count_mismatches(actual: &[u8], golden: &[u8], tolerance: u8) -> usize {
+    actual
+        .chunks_exact(4)
+        .zip(golden.chunks_exact(4))
+        .filter(|(a, g)| {
+            a.iter()
+                .zip(g.iter())
+                .any(|(a, g)| a.abs_diff(*g) > tolerance)
+        })
+        .count()
+}
+
+/// The PNG codec round trip, and the committed golden against the image it
+/// should hold, without a GPU.
+fn check_png() -> Result<()> {
+    let expected = clear_rect_image();
+    let path = std::env::temp_dir().join("wolf_engine_png_check.png");
+    write_png(&path, WIDTH, HEIGHT, &expected)?;
+    let decoded = read_png(&path);
+    std::fs::remove_file(&path)?;
+    assert_eq!(decoded?, (WIDTH, HEIGHT, expected.clone()), "round trip");
+
+    let golden = Path::new(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/golden/clear_rect.png"
+    ));
+    let (width, height, pixels) = read_png(golden)?;
+    assert_eq!((width, height), (WIDTH, HEIGHT), "golden size");
+    assert_eq!(count_mismatches(&pixels, &expected, 0), 0, "golden pixels");
+    Ok(())
+}
+
+fn main() -> Result<ExitCode> {
+    env_logger::init();
+    check_png()?;
+    let args = parse_args()?;
+
+    let pixels = capture(&args)?;
+    write_png(&args.out, WIDTH, HEIGHT, &pixels)?;
+    info!("✅ Wrote {}", args.out.display());
+
+    // Any mode can be compared against a golden, on top of its own check
+    if let Some(golden_path) = &args.golden {
+        let (width, height, golden) = read_png(golden_path)?;
+        if (width, height) != (WIDTH, HEIGHT) {
+            error!("Golden is {width}x{height}, expected {WIDTH}x{HEIGHT}");
+            return Ok(ExitCode::FAILURE);
+        }
+        match count_mismatches(&pixels, &golden, args.tolerance) {
+            0 => info!(
+                "✅ Matches {} (tolerance {})",
+                golden_path.display(),
+                args.tolerance
+            ),
+            bad => {
+                error!(
+                    "{bad} of {} pixels differ from {} by more than {}",
+                    WIDTH * HEIGHT,
+                    golden_path.display(),
+                    args.tolerance
+                );
+                return Ok(ExitCode::FAILURE);
+            }
+        }
+    }
+
+    // The cleared square, and the fragment shader's color everywhere else
+    if args.clear_rect {
+        let expected = clear_rect_image();
+        return match count_mismatches(&pixels, &expected, args.tolerance) {
+            0 => {
+                info!("✅ Mid-pass clear left exactly the cleared square");
+                Ok(ExitCode::SUCCESS)
+            }
+            bad => {
+                error!(
+                    "{bad} of {} pixels differ after the mid-pass clear",
+                    WIDTH * HEIGHT
+                );
+                Ok(ExitCode::FAILURE)
+            }
+        };
+    }
+
+    // Edge pixels blend the triangle into the clear color only if the samples
+    // were resolved; a single-sample image has just the two colors
+    if args.msaa > 1 {
+        // At least 1: the clear color rounds either way
+        let tolerance = args.tolerance.max(1);
+        return match count_between(&pixels, SOLID, BACKGROUND, tolerance) {
+            0 => {
+                error!(
+                    "{}x MSAA capture has no anti-aliased edge pixels",
+                    args.msaa
+                );
+                Ok(ExitCode::FAILURE)
+            }
+            edge => {
+                info!(
+                    "✅ {}x MSAA capture has {edge} anti-aliased edge pixels",
+                    args.msaa
+                );
+                Ok(ExitCode::SUCCESS)
+            }
+        };
+    }
+
+    // Every pixel must come from the fragment shader, none left at the clear color
+    if args.fullscreen {
+        let solid = SOLID.repeat((WIDTH * HEIGHT) as usize);
+        return match count_mismatches(&pixels, &solid, args.tolerance) {
+            0 => {
+                info!(
+                    "✅ Full-screen triangle covered all {} pixels",
+                    WIDTH * HEIGHT
+                );
+                Ok(ExitCode::SUCCESS)
+            }
+            bad => {
+                error!(
+                    "Full-screen triangle missed {bad} of {} pixels",
+                    WIDTH * HEIGHT
+                );
+                Ok(ExitCode::FAILURE)
+            }
+        };
+    }
+
+    Ok(ExitCode::SUCCESS)
+}
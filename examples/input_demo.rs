@@ -0,0 +1,181 @@
+//! examples/input_demo.rs – log every input event through the `Renderer` hooks
+//!
+//! Wraps `VulkanRenderer` and overrides `on_key`, `on_cursor_moved`,
+//! `on_mouse_button`, `on_scroll` and `on_resize`; `App` calls them after its own
+//! `window_event` handling. The frame is a plain clear. `power_saving` is on, so
+//! unfocusing the window switches a MAILBOX swapchain to vsync (logged, and
+//! checked) until focus returns; `present_mode_fallback` explains the switch.
+//! At startup the MAILBOX → FIFO fallback record and the image count requested
+//! for MAILBOX are checked. Keys:
+//!
+//! - `G`: toggle cursor grab (confined, or locked where confining isn't supported)
+//! - `F11` / Alt+Enter: toggle fullscreen (Alt+Enter is handled by `App` itself)
+//! - `Esc`: release the cursor
+
+use log::{error, info, warn};
+use vulkanalia::vk;
+use winit::{
+    dpi::{PhysicalPosition, PhysicalSize},
+    event::{ElementState, KeyEvent, MouseButton, MouseScrollDelta, WindowEvent},
+    event_loop::ActiveEventLoop,
+    keyboard::{KeyCode, PhysicalKey},
+    window::{CursorGrabMode, Window, WindowId},
+};
+
+use wolf_engine::app::{App, set_fullscreen};
+use wolf_engine::core::renderer::api::Renderer;
+use wolf_engine::core::renderer::backend::vulkan::present::{
+    desired_image_count, select_present_mode,
+};
+use wolf_engine::core::renderer::backend::vulkan::{
+    FallbackReason, PresentModeFallback, VulkanRenderer,
+};
+use wolf_engine::core::renderer::config::{PresentMode, RendererBuilder, RendererConfig};
+use wolf_engine::core::window::WindowConfig;
+use wolf_engine::error::{AppError, Result};
+
+struct InputDemo {
+    renderer: VulkanRenderer,
+    grabbed: bool, // Cursor confined/locked to the window
+}
+
+impl InputDemo {
+    fn set_grab(&mut self, window: &Window, grab: bool) {
+        let result = if grab {
+            // Confined keeps the cursor visible; some platforms only offer Locked
+            window
+                .set_cursor_grab(CursorGrabMode::Confined)
+                .or_else(|_| window.set_cursor_grab(CursorGrabMode::Locked))
+        } else {
+            window.set_cursor_grab(CursorGrabMode::None)
+        };
+        match result {
+            Ok(()) => {
+                self.grabbed = grab;
+                window.set_cursor_visible(!grab);
+                info!("🖥️ Cursor grab: {grab}");
+            }
+            Err(e) => warn!("Cursor grab unavailable: {e}"),
+        }
+    }
+}
+
+impl Renderer for InputDemo {
+    fn new(config: RendererConfig) -> Self {
+        Self {
+            renderer: VulkanRenderer::new(config),
+            grabbed: false,
+        }
+    }
+
+    fn initialize(&mut self, window: &Window, event_loop: &ActiveEventLoop) -> Result<()> {
+        // A surface without MAILBOX falls back to FIFO and says so
+        let (mode, fallback) =
+            select_present_mode(&[vk::PresentModeKHR::FIFO], &[PresentMode::Mailbox]);
+        let expected = PresentModeFallback {
+            requested: vk::PresentModeKHR::MAILBOX,
+            chosen: vk::PresentModeKHR::FIFO,
+            reason: FallbackReason::Unsupported,
+        };
+        assert_eq!((mode, fallback), (vk::PresentModeKHR::FIFO, Some(expected)));
+        // MAILBOX asks for a third image where `min + 1` would only give two
+        let mailbox = vk::PresentModeKHR::MAILBOX;
+        assert_eq!(desired_image_count(1, 0, mailbox), 3);
+        assert_eq!(desired_image_count(1, 8, mailbox), 3);
+        assert_eq!(
+            desired_image_count(1, 2, mailbox),
+            2,
+            "clamped to the maximum"
+        );
+        assert_eq!(desired_image_count(3, 0, mailbox), 4);
+        assert_eq!(desired_image_count(1, 0, vk::PresentModeKHR::FIFO), 2);
+
+        self.renderer.initialize(window, event_loop)?;
+        self.renderer.set_clear_color([0.08, 0.1, 0.12, 1.0]);
+        if let Some(fallback) = self.renderer.present_mode_fallback() {
+            info!("🖥️ Present mode: {fallback}");
+        }
+        info!("🖥️ G: grab cursor, F11 / Alt+Enter: fullscreen, Esc: release");
+        Ok(())
+    }
+
+    fn is_initialized(&self) -> bool {
+        self.renderer.is_initialized()
+    }
+
+    fn window_event(&mut self, event_loop: &ActiveEventLoop, id: WindowId, event: &WindowEvent) {
+        self.renderer.window_event(event_loop, id, event);
+        if let WindowEvent::Focused(focused) = event {
+            let mode = self.renderer.present_mode();
+            info!("🖥️ Focused: {focused}, present mode {mode:?}");
+            if let Some(fallback) = self.renderer.present_mode_fallback() {
+                info!("🖥️ {fallback}");
+            }
+            // A minimized window keeps its swapchain until the next resize
+            let uncapped = [vk::PresentModeKHR::MAILBOX, vk::PresentModeKHR::IMMEDIATE];
+            if !focused && mode.is_some_and(|mode| uncapped.contains(&mode)) {
+                error!("power_saving left {mode:?} active on focus loss");
+            }
+        }
+    }
+
+    fn render(&mut self) -> Result<()> {
+        self.renderer.render()
+    }
+
+    fn take_error(&mut self) -> Option<AppError> {
+        self.renderer.take_error()
+    }
+
+    fn on_key(&mut self, window: &Window, event: &KeyEvent) {
+        info!(
+            "Key {:?} ({:?}) {:?}{}",
+            event.physical_key,
+            event.logical_key,
+            event.state,
+            if event.repeat { " (repeat)" } else { "" }
+        );
+        if event.state != ElementState::Pressed || event.repeat {
+            return;
+        }
+        match event.physical_key {
+            PhysicalKey::Code(KeyCode::KeyG) => self.set_grab(window, !self.grabbed),
+            PhysicalKey::Code(KeyCode::Escape) if self.grabbed => self.set_grab(window, false),
+            PhysicalKey::Code(KeyCode::F11) => {
+                set_fullscreen(window, window.fullscreen().is_none());
+            }
+            _ => {}
+        }
+    }
+
+    fn on_cursor_moved(&mut self, _window: &Window, position: PhysicalPosition<f64>) {
+        info!("Cursor at ({:.0}, {:.0})", position.x, position.y);
+    }
+
+    fn on_mouse_button(&mut self, _window: &Window, button: MouseButton, state: ElementState) {
+        info!("Mouse {button:?} {state:?}");
+    }
+
+    fn on_scroll(&mut self, _window: &Window, delta: MouseScrollDelta) {
+        match delta {
+            MouseScrollDelta::LineDelta(x, y) => info!("Scroll {x:+.1}, {y:+.1} lines"),
+            MouseScrollDelta::PixelDelta(p) => info!("Scroll {:+.0}, {:+.0} px", p.x, p.y),
+        }
+    }
+
+    fn on_resize(&mut self, _window: &Window, size: PhysicalSize<u32>) {
+        info!("🖥️ Resized to {}x{}", size.width, size.height);
+    }
+}
+
+fn main() -> Result<()> {
+    env_logger::init();
+    let window = WindowConfig::new()
+        .title("wolf-engine: input demo")
+        .size(1280, 720);
+    let config = RendererBuilder::new()
+        .app_name("input_demo")
+        .power_saving(true)
+        .build()?;
+    App::<InputDemo>::run_with(window, config)
+}
@@ -0,0 +1,151 @@
+//! examples/manual_frames.rs – drive frames with `begin_frame` / `end_frame`
+//!
+//! Drives winit directly (like `resize_stress`) and renders without a callback:
+//! each redraw takes a `Frame` from `VulkanRenderer::begin_frame`, sets a
+//! pulsing clear color through `Frame::draw` and finishes it with `end_frame`.
+//! Frame `DROPPED_FRAME` is deliberately dropped without `end_frame`; the
+//! renderer logs an error and skips it. The run then checks that the frames
+//! after it are presented again, i.e. the swapchain wasn't left wedged, and
+//! exits.
+//!
+//! ```text
+//! RUST_LOG=info cargo run --example manual_frames
+//! ```
+
+use std::time::Instant;
+
+use log::{error, info};
+use winit::{
+    application::ApplicationHandler,
+    event::WindowEvent,
+    event_loop::{ActiveEventLoop, ControlFlow, EventLoop},
+    window::{Window, WindowId},
+};
+
+use wolf_engine::core::renderer::api::Renderer;
+use wolf_engine::core::renderer::backend::vulkan::VulkanRenderer;
+use wolf_engine::core::renderer::config::RendererConfig;
+use wolf_engine::error::{AppError, Result};
+
+const DROPPED_FRAME: u32 = 30; // Redraw whose `Frame` is dropped unfinished
+const FRAMES: u32 = 90; // Redraws before exiting
+
+/// The renderer is declared first so it (and its surface) is dropped before
+/// the window.
+struct Frames {
+    renderer: VulkanRenderer,
+    window: Window,
+}
+
+#[derive(Default)]
+struct ManualFrames {
+    frames: Option<Frames>,
+    start: Option<Instant>,
+    redraws: u32,
+    presented_after_drop: u64, // `frame_count` gained since the dropped frame
+    error: Option<AppError>,
+}
+
+impl ManualFrames {
+    fn open(&mut self, event_loop: &ActiveEventLoop) -> Result<()> {
+        let attributes = Window::default_attributes().with_title("wolf-engine: manual frames");
+        let window = event_loop.create_window(attributes)?;
+        let mut renderer = VulkanRenderer::new(RendererConfig::default());
+        renderer.initialize(&window, event_loop)?;
+        self.frames = Some(Frames { renderer, window });
+        self.start = Some(Instant::now());
+        Ok(())
+    }
+
+    fn redraw(&mut self) -> Result<()> {
+        let Some(Frames { renderer, .. }) = &mut self.frames else {
+            return Ok(());
+        };
+        self.redraws += 1;
+        let before = renderer.stats().frame_count;
+        let Some(mut frame) = renderer.begin_frame()? else {
+            return Ok(()); // Hidden or minimized
+        };
+        let pulse = self
+            .start
+            .map_or(0.0, |start| start.elapsed().as_secs_f32().sin());
+        frame.draw(|ctx| {
+            ctx.clear_color([0.1, 0.2 + 0.1 * pulse, 0.3, 1.0]);
+            Ok(())
+        })?;
+        if self.redraws == DROPPED_FRAME {
+            info!("🖥️ Dropping frame {DROPPED_FRAME} without end_frame");
+            drop(frame);
+            if renderer.stats().frame_count != before {
+                return Err(AppError::Config("dropped frame was presented".into()));
+            }
+            return Ok(());
+        }
+        frame.end_frame()?;
+        if self.redraws > DROPPED_FRAME {
+            self.presented_after_drop += renderer.stats().frame_count - before;
+        }
+        Ok(())
+    }
+
+    fn fail(&mut self, event_loop: &ActiveEventLoop, e: AppError) {
+        error!("{e}");
+        self.error.get_or_insert(e);
+        event_loop.exit();
+    }
+}
+
+impl ApplicationHandler for ManualFrames {
+    fn resumed(&mut self, event_loop: &ActiveEventLoop) {
+        if self.frames.is_none()
+            && let Err(e) = self.open(event_loop)
+        {
+            self.fail(event_loop, e);
+        }
+    }
+
+    fn window_event(&mut self, event_loop: &ActiveEventLoop, id: WindowId, event: WindowEvent) {
+        let Some(Frames { renderer, .. }) = &mut self.frames else {
+            return;
+        };
+        renderer.window_event(event_loop, id, &event);
+        let result = match renderer.take_error() {
+            Some(e) => Err(e),
+            None if matches!(event, WindowEvent::RedrawRequested) => self.redraw(),
+            None => Ok(()),
+        };
+        if let Err(e) = result {
+            return self.fail(event_loop, e);
+        }
+        if self.redraws >= FRAMES {
+            let after = FRAMES - DROPPED_FRAME;
+            if self.presented_after_drop == 0 {
+                let e = AppError::Config(format!(
+                    "no frame presented in {after} redraws after the dropped one"
+                ));
+                return self.fail(event_loop, e);
+            }
+            info!(
+                "✅ {} of {after} frames presented after the dropped one",
+                self.presented_after_drop
+            );
+            self.frames = None;
+            event_loop.exit();
+        }
+    }
+
+    fn about_to_wait(&mut self, _event_loop: &ActiveEventLoop) {
+        if let Some(frames) = &self.frames {
+            frames.window.request_redraw();
+        }
+    }
+}
+
+fn main() -> Result<()> {
+    env_logger::init();
+    let event_loop = EventLoop::new()?;
+    event_loop.set_control_flow(ControlFlow::Poll);
+    let mut app = ManualFrames::default();
+    event_loop.run_app(&mut app)?;
+    app.error.map_or(Ok(()), Err)
+}
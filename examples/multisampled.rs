@@ -0,0 +1,119 @@
+//! examples/multisampled.rs – cycle MSAA 1x → 2x → 4x → 8x at runtime
+//!
+//! Press `M` to step to the next sample count. Each switch rebuilds the render
+//! pass, the multisampled color target and the framebuffers through
+//! `VulkanRenderer::set_msaa`; levels the GPU doesn't support are reported and the
+//! current level is kept. The renderer has no mesh pipeline yet, so the frame is
+//! a clear only: pipelines added later must be rebuilt with
+//! `VulkanRenderer::msaa_samples` after every switch. Alt+Enter toggles fullscreen.
+
+use log::{error, info, warn};
+use winit::{
+    application::ApplicationHandler,
+    event::{ElementState, WindowEvent},
+    event_loop::{ActiveEventLoop, ControlFlow, EventLoop},
+    keyboard::{KeyCode, ModifiersState, PhysicalKey},
+    window::{Window, WindowId},
+};
+
+use wolf_engine::app::{is_fullscreen_toggle, set_fullscreen};
+use wolf_engine::core::renderer::api::Renderer;
+use wolf_engine::core::renderer::backend::vulkan::VulkanRenderer;
+use wolf_engine::core::renderer::config::RendererConfig;
+use wolf_engine::error::Result;
+
+const LEVELS: [u32; 4] = [1, 2, 4, 8];
+
+#[derive(Default)]
+struct App {
+    renderer: Option<VulkanRenderer>, // dropped before the window it presents to
+    window: Option<Window>,
+    modifiers: ModifiersState, // For Alt+Enter
+    level: usize,              // Index into LEVELS of the last requested level
+}
+
+impl App {
+    fn cycle_msaa(&mut self) {
+        let Some(renderer) = &mut self.renderer else {
+            return;
+        };
+        // Keep stepping even past unsupported levels so the cycle wraps back to 1x
+        self.level = (self.level + 1) % LEVELS.len();
+        let requested = LEVELS[self.level];
+        match renderer.set_msaa(requested) {
+            Ok(()) => info!("🖥️ MSAA: {}x", renderer.msaa_samples()),
+            Err(e) => warn!(
+                "{requested}x MSAA unavailable ({e}), staying at {}x",
+                renderer.msaa_samples()
+            ),
+        }
+    }
+}
+
+impl ApplicationHandler for App {
+    fn resumed(&mut self, event_loop: &ActiveEventLoop) {
+        let window = event_loop
+            .create_window(Window::default_attributes().with_title("wolf-engine: multisampled"))
+            .expect("create window");
+
+        let mut renderer = VulkanRenderer::new(RendererConfig::default());
+        renderer
+            .initialize(&window, event_loop)
+            .expect("Renderer initialization failed");
+        renderer.set_clear_color([0.05, 0.05, 0.08, 1.0]);
+
+        info!(
+            "Supported sample counts: {:?}; press M to cycle",
+            renderer.supported_sample_counts()
+        );
+        info!("🖥️ MSAA: {}x", renderer.msaa_samples());
+
+        self.renderer = Some(renderer);
+        self.window = Some(window);
+    }
+
+    fn window_event(&mut self, event_loop: &ActiveEventLoop, id: WindowId, event: WindowEvent) {
+        if let WindowEvent::ModifiersChanged(modifiers) = &event {
+            self.modifiers = modifiers.state();
+        }
+        // The Resized event that follows recreates the swapchain
+        if is_fullscreen_toggle(&event, self.modifiers)
+            && let Some(window) = &self.window
+        {
+            set_fullscreen(window, window.fullscreen().is_none());
+        }
+        if let WindowEvent::KeyboardInput { event: key, .. } = &event
+            && key.state == ElementState::Pressed
+            && !key.repeat
+            && key.physical_key == PhysicalKey::Code(KeyCode::KeyM)
+        {
+            self.cycle_msaa();
+        }
+
+        let Some(renderer) = &mut self.renderer else {
+            return;
+        };
+        renderer.window_event(event_loop, id, &event);
+        if matches!(event, WindowEvent::RedrawRequested)
+            && let Err(e) = renderer.render()
+        {
+            error!("Render failed: {e}");
+            event_loop.exit();
+        }
+    }
+
+    fn about_to_wait(&mut self, _event_loop: &ActiveEventLoop) {
+        if let Some(window) = &self.window {
+            window.request_redraw();
+        }
+    }
+}
+
+fn main() -> Result<()> {
+    env_logger::init();
+    let mut app = App::default();
+    let event_loop = EventLoop::new()?;
+    event_loop.set_control_flow(ControlFlow::Poll);
+    event_loop.run_app(&mut app)?;
+    Ok(())
+}
@@ -0,0 +1,102 @@
+//! examples/normals.rs – a spinning cube with its face normals drawn by a geometry shader
+//!
+//! The cube is drawn twice each frame: filled with the built-in mesh pipeline,
+//! then through a `PipelineDesc` that adds `normals.geom` as
+//! `ShaderStage::Geometry`, which turns every triangle into a short line along
+//! its normal. All normals should point outwards; one pointing in marks a
+//! triangle wound the wrong way. Needs the `geometryShader` device feature,
+//! otherwise the first frame fails with a config error. Compile the shaders first:
+//!
+//! ```text
+//! glslc shaders/mesh.vert -o shaders/mesh.vert.spv
+//! glslc shaders/mesh.frag -o shaders/mesh.frag.spv
+//! glslc shaders/normals.geom -o shaders/normals.geom.spv
+//! ```
+
+use std::time::Instant;
+
+use log::info;
+
+use wolf_engine::app::App;
+use wolf_engine::core::renderer::backend::vulkan::{
+    DrawContext, Mesh, PipelineDesc, ShaderStage, Vertex,
+};
+use wolf_engine::core::renderer::config::{RendererBuilder, Topology};
+use wolf_engine::core::window::WindowConfig;
+use wolf_engine::error::Result;
+
+const CUBE_VERTICES: [Vertex; 8] = [
+    Vertex::new([-1.0, -1.0, -1.0], [0.9, 0.2, 0.2]),
+    Vertex::new([1.0, -1.0, -1.0], [0.2, 0.9, 0.2]),
+    Vertex::new([1.0, 1.0, -1.0], [0.2, 0.2, 0.9]),
+    Vertex::new([-1.0, 1.0, -1.0], [0.9, 0.9, 0.2]),
+    Vertex::new([-1.0, -1.0, 1.0], [0.9, 0.2, 0.9]),
+    Vertex::new([1.0, -1.0, 1.0], [0.2, 0.9, 0.9]),
+    Vertex::new([1.0, 1.0, 1.0], [0.9, 0.9, 0.9]),
+    Vertex::new([-1.0, 1.0, 1.0], [0.4, 0.4, 0.4]),
+];
+// Counter-clockwise seen from outside, so every normal points away from the center
+#[rustfmt::skip]
+const CUBE_INDICES: [u32; 36] = [
+    0, 3, 2, 2, 1, 0, // Back
+    4, 5, 6, 6, 7, 4, // Front
+    0, 4, 7, 7, 3, 0, // Left
+    1, 2, 6, 6, 5, 1, // Right
+    3, 7, 6, 6, 2, 3, // Top
+    0, 1, 5, 5, 4, 0, // Bottom
+];
+const CUBE_SCALE: f32 = 0.35;
+
+/// Column-major transform: tumble by `angle`, scale, squeeze X by the aspect
+/// ratio and map Z from [-1, 1] into depth [0, 1].
+fn cube_transform(angle: f32, aspect: f32) -> [[f32; 4]; 4] {
+    let (sy, cy) = angle.sin_cos();
+    let (sx, cx) = (angle * 0.7).sin_cos();
+    // Rows of rotate_y(angle) * rotate_x(0.7 * angle)
+    let r = [
+        [cy, sy * sx, sy * cx],
+        [0.0, cx, -sx],
+        [-sy, cy * sx, cy * cx],
+    ];
+    let column = |j: usize| {
+        [
+            r[0][j] * CUBE_SCALE / aspect,
+            r[1][j] * CUBE_SCALE,
+            r[2][j] * CUBE_SCALE * 0.5,
+            0.0,
+        ]
+    };
+    [column(0), column(1), column(2), [0.0, 0.0, 0.5, 1.0]]
+}
+
+fn main() -> Result<()> {
+    env_logger::init();
+    let config = RendererBuilder::new().app_name("normals").build()?;
+    let normals = PipelineDesc::new(Topology::TriangleList, false)
+        .with_stage(ShaderStage::Geometry, "normals.geom.spv");
+
+    let start = Instant::now();
+    let mut cube: Option<Mesh> = None;
+    let window = WindowConfig::new().title("wolf-engine: normals");
+    App::run_with_draw(window, config, move |ctx: &mut DrawContext| {
+        if cube.is_none() {
+            cube = Some(ctx.create_mesh(&CUBE_VERTICES, &CUBE_INDICES)?);
+            info!("🖥️ Cube uploaded");
+        }
+        let Some(cube) = &cube else {
+            return Ok(());
+        };
+
+        let extent = ctx.extent();
+        let aspect = extent.width as f32 / extent.height.max(1) as f32;
+        let angle = start.elapsed().as_secs_f32(); // One radian per second
+
+        ctx.clear_color([0.05, 0.05, 0.08, 1.0]);
+        ctx.push_constants(&cube_transform(angle, aspect))?;
+        ctx.draw_mesh(cube);
+        ctx.set_pipeline(normals.clone());
+        ctx.draw_mesh(cube);
+        ctx.reset_pipeline();
+        Ok(())
+    })
+}
@@ -0,0 +1,131 @@
+//! examples/outline.rs – a stencil outline around a spinning cube
+//!
+//! The renderer is built with `RendererBuilder::stencil(true)`, so the depth
+//! buffer has a stencil aspect. Each frame the cube is drawn with
+//! `StencilState::write()` and reference 1, marking its pixels; then a slightly
+//! larger copy is drawn with `outline.frag` and `StencilState::not_equal()`,
+//! which only passes outside the marked pixels and leaves a solid outline.
+//! At startup the stencil state of both pipelines is checked as it reaches
+//! `vk::PipelineDepthStencilStateCreateInfo`. Compile the shaders first:
+//!
+//! ```text
+//! glslc shaders/mesh.vert -o shaders/mesh.vert.spv
+//! glslc shaders/mesh.frag -o shaders/mesh.frag.spv
+//! glslc shaders/outline.frag -o shaders/outline.frag.spv
+//! ```
+
+use std::time::Instant;
+
+use log::info;
+use vulkanalia::vk;
+
+use wolf_engine::app::App;
+use wolf_engine::core::renderer::backend::vulkan::mesh::depth_stencil_state;
+use wolf_engine::core::renderer::backend::vulkan::{DrawContext, Mesh, PipelineDesc, Vertex};
+use wolf_engine::core::renderer::config::{RendererBuilder, StencilState, Topology};
+use wolf_engine::core::window::WindowConfig;
+use wolf_engine::error::Result;
+
+const CUBE_VERTICES: [Vertex; 8] = [
+    Vertex::new([-1.0, -1.0, -1.0], [0.9, 0.2, 0.2]),
+    Vertex::new([1.0, -1.0, -1.0], [0.2, 0.9, 0.2]),
+    Vertex::new([1.0, 1.0, -1.0], [0.2, 0.2, 0.9]),
+    Vertex::new([-1.0, 1.0, -1.0], [0.9, 0.9, 0.2]),
+    Vertex::new([-1.0, -1.0, 1.0], [0.9, 0.2, 0.9]),
+    Vertex::new([1.0, -1.0, 1.0], [0.2, 0.9, 0.9]),
+    Vertex::new([1.0, 1.0, 1.0], [0.9, 0.9, 0.9]),
+    Vertex::new([-1.0, 1.0, 1.0], [0.4, 0.4, 0.4]),
+];
+#[rustfmt::skip]
+const CUBE_INDICES: [u32; 36] = [
+    0, 1, 2, 2, 3, 0, // Back
+    4, 5, 6, 6, 7, 4, // Front
+    0, 4, 7, 7, 3, 0, // Left
+    1, 5, 6, 6, 2, 1, // Right
+    3, 2, 6, 6, 7, 3, // Top
+    0, 1, 5, 5, 4, 0, // Bottom
+];
+const CUBE_SCALE: f32 = 0.3;
+const OUTLINE_SCALE: f32 = 1.08; // Outline copy relative to the cube
+
+/// Column-major transform: tumble by `angle`, scale, squeeze X by the aspect
+/// ratio and map Z from [-1, 1] into depth [0, 1].
+fn cube_transform(angle: f32, scale: f32, aspect: f32) -> [[f32; 4]; 4] {
+    let (sy, cy) = angle.sin_cos();
+    let (sx, cx) = (angle * 0.7).sin_cos();
+    // Rows of rotate_y(angle) * rotate_x(0.7 * angle)
+    let r = [
+        [cy, sy * sx, sy * cx],
+        [0.0, cx, -sx],
+        [-sy, cy * sx, cy * cx],
+    ];
+    let column = |j: usize| {
+        [
+            r[0][j] * scale / aspect,
+            r[1][j] * scale,
+            r[2][j] * scale * 0.5,
+            0.0,
+        ]
+    };
+    [column(0), column(1), column(2), [0.0, 0.0, 0.5, 1.0]]
+}
+
+/// The stencil half of both pipelines' depth-stencil state.
+fn check_stencil_states() {
+    let mark = depth_stencil_state(Some(StencilState::write()));
+    assert_eq!(mark.stencil_test_enable, vk::TRUE);
+    assert_eq!(mark.front, mark.back);
+    assert_eq!(mark.front.compare_op, vk::CompareOp::ALWAYS);
+    assert_eq!(mark.front.pass_op, vk::StencilOp::REPLACE);
+    assert_eq!(mark.front.write_mask, 0xFF);
+
+    let outline = depth_stencil_state(Some(StencilState::not_equal()));
+    assert_eq!(outline.front.compare_op, vk::CompareOp::NOT_EQUAL);
+    assert_eq!(outline.front.pass_op, vk::StencilOp::KEEP);
+    assert_eq!(outline.front.write_mask, 0);
+
+    let plain = depth_stencil_state(None);
+    assert_eq!(plain.stencil_test_enable, vk::FALSE);
+    assert_eq!(plain.depth_test_enable, vk::TRUE);
+}
+
+fn main() -> Result<()> {
+    env_logger::init();
+    check_stencil_states();
+    let config = RendererBuilder::new()
+        .app_name("outline")
+        .stencil(true)
+        .build()?;
+    let mark = PipelineDesc::new(Topology::TriangleList, false).with_stencil(StencilState::write());
+    let outline = PipelineDesc::new(Topology::TriangleList, false)
+        .with_fragment("outline.frag.spv")
+        .with_stencil(StencilState::not_equal());
+
+    let start = Instant::now();
+    let mut cube: Option<Mesh> = None;
+    let window = WindowConfig::new().title("wolf-engine: outline");
+    App::run_with_draw(window, config, move |ctx: &mut DrawContext| {
+        if cube.is_none() {
+            cube = Some(ctx.create_mesh(&CUBE_VERTICES, &CUBE_INDICES)?);
+            info!("🖥️ Cube uploaded");
+        }
+        let Some(cube) = &cube else {
+            return Ok(());
+        };
+
+        let area = ctx.scene_area().extent;
+        let aspect = area.width as f32 / area.height.max(1) as f32;
+        let angle = start.elapsed().as_secs_f32(); // One radian per second
+
+        ctx.clear_color([0.05, 0.05, 0.08, 1.0]);
+        ctx.set_stencil_reference(1);
+        ctx.set_pipeline(mark.clone());
+        ctx.push_constants(&cube_transform(angle, CUBE_SCALE, aspect))?;
+        ctx.draw_mesh(cube);
+        ctx.set_pipeline(outline.clone());
+        ctx.push_constants(&cube_transform(angle, CUBE_SCALE * OUTLINE_SCALE, aspect))?;
+        ctx.draw_mesh(cube);
+        ctx.reset_pipeline();
+        Ok(())
+    })
+}
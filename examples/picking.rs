@@ -0,0 +1,211 @@
+//! examples/picking.rs – click a cube to get its index back from the GPU
+//!
+//! Three spinning cubes are drawn with `DrawContext::set_pick_id(index + 1)`.
+//! A left click calls `VulkanRenderer::pick` at the cursor, which redraws the
+//! last frame's meshes into a 1x1 object-id target and reads the pixel back;
+//! 0 means the background was hit. `W` toggles wireframe, which rebuilds the
+//! mesh pipeline through `VulkanRenderer::set_polygon_mode` (needs
+//! `fillModeNonSolid`); picking keeps working on the rebuilt pipeline. Compile
+//! the shaders first:
+//!
+//! ```text
+//! glslc shaders/mesh.vert -o shaders/mesh.vert.spv
+//! glslc shaders/mesh.frag -o shaders/mesh.frag.spv
+//! glslc shaders/pick.frag -o shaders/pick.frag.spv
+//! ```
+
+use std::time::Instant;
+
+use log::{info, warn};
+use winit::{
+    dpi::PhysicalPosition,
+    event::{ElementState, KeyEvent, MouseButton, WindowEvent},
+    event_loop::ActiveEventLoop,
+    keyboard::{KeyCode, PhysicalKey},
+    window::{Window, WindowId},
+};
+
+use wolf_engine::app::App;
+use wolf_engine::core::renderer::api::Renderer;
+use wolf_engine::core::renderer::backend::vulkan::{DrawContext, Mesh, Vertex, VulkanRenderer};
+use wolf_engine::core::renderer::config::{PolygonMode, RendererConfig};
+use wolf_engine::core::renderer::stats::{BindCounts, DrawCounts};
+use wolf_engine::core::window::WindowConfig;
+use wolf_engine::error::{AppError, Result};
+
+const CUBE_VERTICES: [Vertex; 8] = [
+    Vertex::new([-1.0, -1.0, -1.0], [0.9, 0.2, 0.2]),
+    Vertex::new([1.0, -1.0, -1.0], [0.2, 0.9, 0.2]),
+    Vertex::new([1.0, 1.0, -1.0], [0.2, 0.2, 0.9]),
+    Vertex::new([-1.0, 1.0, -1.0], [0.9, 0.9, 0.2]),
+    Vertex::new([-1.0, -1.0, 1.0], [0.9, 0.2, 0.9]),
+    Vertex::new([1.0, -1.0, 1.0], [0.2, 0.9, 0.9]),
+    Vertex::new([1.0, 1.0, 1.0], [0.9, 0.9, 0.9]),
+    Vertex::new([-1.0, 1.0, 1.0], [0.4, 0.4, 0.4]),
+];
+#[rustfmt::skip]
+const CUBE_INDICES: [u32; 36] = [
+    0, 1, 2, 2, 3, 0, // Back
+    4, 5, 6, 6, 7, 4, // Front
+    0, 4, 7, 7, 3, 0, // Left
+    1, 5, 6, 6, 2, 1, // Right
+    3, 2, 6, 6, 7, 3, // Top
+    0, 1, 5, 5, 4, 0, // Bottom
+];
+const CUBE_OFFSETS: [f32; 3] = [-0.6, 0.0, 0.6]; // Horizontal NDC positions
+const CUBE_SCALE: f32 = 0.18;
+
+/// Column-major transform of one cube: tumble by `angle`, scale, squeeze X by the
+/// aspect ratio, move to `offset` and map Z from [-1, 1] into depth [0, 1].
+fn cube_transform(angle: f32, offset: f32, aspect: f32) -> [[f32; 4]; 4] {
+    let (sy, cy) = angle.sin_cos();
+    let (sx, cx) = (angle * 0.7).sin_cos();
+    // Rows of rotate_y(angle) * rotate_x(0.7 * angle)
+    let r = [
+        [cy, sy * sx, sy * cx],
+        [0.0, cx, -sx],
+        [-sy, cy * sx, cy * cx],
+    ];
+    let column = |j: usize| {
+        [
+            r[0][j] * CUBE_SCALE / aspect,
+            r[1][j] * CUBE_SCALE,
+            r[2][j] * CUBE_SCALE * 0.5,
+            0.0,
+        ]
+    };
+    [column(0), column(1), column(2), [offset, 0.0, 0.5, 1.0]]
+}
+
+struct Picking {
+    renderer: VulkanRenderer,
+    start: Instant,
+    cube: Option<Mesh>,
+    cursor: PhysicalPosition<f64>, // Last CursorMoved position, physical pixels
+}
+
+fn draw(ctx: &mut DrawContext, cube: &mut Option<Mesh>, start: Instant) -> Result<()> {
+    if cube.is_none() {
+        *cube = Some(ctx.create_mesh(&CUBE_VERTICES, &CUBE_INDICES)?);
+        info!("🖥️ Cube uploaded");
+    }
+    let Some(cube) = cube.as_ref() else {
+        return Ok(());
+    };
+    let extent = ctx.extent();
+    let aspect = extent.width as f32 / extent.height.max(1) as f32;
+    let time = start.elapsed().as_secs_f32();
+
+    ctx.clear_color([0.05, 0.05, 0.08, 1.0]);
+    for (index, offset) in CUBE_OFFSETS.into_iter().enumerate() {
+        let angle = time * (0.5 + 0.3 * index as f32); // Each cube at its own speed
+        ctx.push_constants(&cube_transform(angle, offset, aspect))?;
+        ctx.set_pick_id(index as u32 + 1); // 0 is the background
+        ctx.draw_mesh(cube);
+    }
+    Ok(())
+}
+
+impl Renderer for Picking {
+    fn new(config: RendererConfig) -> Self {
+        Self {
+            renderer: VulkanRenderer::new(config),
+            start: Instant::now(),
+            cube: None,
+            cursor: PhysicalPosition::new(0.0, 0.0),
+        }
+    }
+
+    fn initialize(&mut self, window: &Window, event_loop: &ActiveEventLoop) -> Result<()> {
+        self.renderer.initialize(window, event_loop)?;
+        info!("🖥️ Left click a cube to pick it, W toggles wireframe");
+        Ok(())
+    }
+
+    fn is_initialized(&self) -> bool {
+        self.renderer.is_initialized()
+    }
+
+    fn window_event(&mut self, event_loop: &ActiveEventLoop, id: WindowId, event: &WindowEvent) {
+        self.renderer.window_event(event_loop, id, event);
+    }
+
+    fn render(&mut self) -> Result<()> {
+        let Self {
+            renderer,
+            start,
+            cube,
+            ..
+        } = self;
+        renderer.render_with(|ctx| draw(ctx, cube, *start))?;
+        // Counted while the frame was recorded; 0 when no frame was (minimized)
+        let draws = renderer.stats().draws;
+        let cubes = CUBE_OFFSETS.len() as u32;
+        let expected = DrawCounts {
+            calls: cubes,
+            indices: u64::from(cubes) * CUBE_INDICES.len() as u64,
+            // All cubes share the built-in pipeline and no material
+            binds: BindCounts {
+                pipelines: 1,
+                materials: 0,
+            },
+            binds_saved: 0,
+        };
+        assert!(
+            draws.calls == 0 || draws == expected,
+            "draw stats {draws:?}, expected {expected:?}"
+        );
+        Ok(())
+    }
+
+    fn take_error(&mut self) -> Option<AppError> {
+        self.renderer.take_error()
+    }
+
+    fn on_key(&mut self, _window: &Window, event: &KeyEvent) {
+        if event.state != ElementState::Pressed
+            || event.repeat
+            || event.physical_key != PhysicalKey::Code(KeyCode::KeyW)
+        {
+            return;
+        }
+        let mode = match self.renderer.polygon_mode() {
+            PolygonMode::Fill => PolygonMode::Line,
+            _ => PolygonMode::Fill,
+        };
+        let old = self.renderer.mesh_pipeline();
+        let count = self.renderer.pipeline_count();
+        if let Err(e) = self.renderer.set_polygon_mode(mode) {
+            return warn!("Wireframe unavailable: {e}");
+        }
+        // The rebuilt pipeline replaces the old one instead of joining it
+        let new = self.renderer.mesh_pipeline();
+        assert!(new.is_some() && new != old, "mesh pipeline not recreated");
+        assert_eq!(self.renderer.pipeline_count(), count.max(1));
+        info!("🖥️ Polygon mode: {mode:?}");
+    }
+
+    fn on_cursor_moved(&mut self, _window: &Window, position: PhysicalPosition<f64>) {
+        self.cursor = position;
+    }
+
+    fn on_mouse_button(&mut self, _window: &Window, button: MouseButton, state: ElementState) {
+        if button != MouseButton::Left || state != ElementState::Pressed {
+            return;
+        }
+        let (x, y) = (self.cursor.x.max(0.0) as u32, self.cursor.y.max(0.0) as u32);
+        match self.renderer.pick(x, y) {
+            Ok(0) => info!("🖥️ Picked the background at ({x}, {y})"),
+            Ok(id) => info!("🖥️ Picked cube {} at ({x}, {y})", id - 1),
+            Err(e) => warn!("Pick failed: {e}"),
+        }
+    }
+}
+
+fn main() -> Result<()> {
+    env_logger::init();
+    let window = WindowConfig::new()
+        .title("wolf-engine: picking")
+        .size(1280, 720);
+    App::<Picking>::run_with(window, RendererConfig::default())
+}
@@ -0,0 +1,156 @@
+//! examples/present_sync.rs – check the present transition under synchronization validation
+//!
+//! Renders a few frames that write the swapchain image (the pass's clear plus a
+//! triangle) with the validation layer's synchronization validation on
+//! (`RendererBuilder::sync_validation`), so a missing dependency between those
+//! writes and the render pass's transition to PRESENT_SRC_KHR shows up as a
+//! SYNC-HAZARD message. Any validation warning or error while rendering fails
+//! the run with a non-zero exit code.
+//!
+//! Needs a debug build with the `debug-utils` feature (the defaults) and the
+//! Khronos validation layer installed; otherwise it reports the skip and exits
+//! successfully. Compile the mesh shaders first:
+//!
+//! ```text
+//! glslc shaders/mesh.vert -o shaders/mesh.vert.spv
+//! glslc shaders/mesh.frag -o shaders/mesh.frag.spv
+//! RUST_LOG=info cargo run --example present_sync
+//! ```
+
+use std::ffi::CStr;
+
+use log::{error, info, warn};
+use vulkanalia::loader::{LIBRARY, LibloadingLoader};
+use vulkanalia::prelude::v1_0::*;
+use winit::{
+    application::ApplicationHandler,
+    event::WindowEvent,
+    event_loop::{ActiveEventLoop, EventLoop},
+    window::{Window, WindowId},
+};
+
+use wolf_engine::core::renderer::api::Renderer;
+use wolf_engine::core::renderer::backend::vulkan::frame::MAX_FRAMES_IN_FLIGHT;
+use wolf_engine::core::renderer::backend::vulkan::{Vertex, VulkanRenderer};
+use wolf_engine::core::renderer::config::RendererBuilder;
+use wolf_engine::core::renderer::validation::ValidationLog;
+use wolf_engine::error::{AppError, Result};
+
+// Enough to reuse every frame slot and swapchain image a few times
+const FRAMES: usize = MAX_FRAMES_IN_FLIGHT * 4;
+const TRIANGLE: [Vertex; 3] = [
+    Vertex::new([0.0, -0.6, 0.5], [1.0, 0.2, 0.2]),
+    Vertex::new([0.6, 0.6, 0.5], [0.2, 1.0, 0.2]),
+    Vertex::new([-0.6, 0.6, 0.5], [0.2, 0.2, 1.0]),
+];
+const IDENTITY: [[f32; 4]; 4] = [
+    [1.0, 0.0, 0.0, 0.0],
+    [0.0, 1.0, 0.0, 0.0],
+    [0.0, 0.0, 1.0, 0.0],
+    [0.0, 0.0, 0.0, 1.0],
+];
+
+#[derive(Default)]
+struct PresentSync {
+    log: ValidationLog,
+    frame_messages: usize,
+    error: Option<AppError>,
+}
+
+impl PresentSync {
+    fn run(&mut self, event_loop: &ActiveEventLoop) -> Result<()> {
+        let window = event_loop
+            .create_window(Window::default_attributes().with_title("wolf-engine: present sync"))?;
+        let config = RendererBuilder::new()
+            .app_name("present_sync")
+            .capture_validation(self.log.clone())
+            .sync_validation(true)
+            .build()?;
+        let mut renderer = VulkanRenderer::new(config);
+        renderer.initialize(&window, event_loop)?;
+        let triangle = renderer.create_mesh(&TRIANGLE, &[0, 1, 2])?;
+        let startup = self.log.len();
+        if startup > 0 {
+            warn!("{startup} validation message(s) before the first frame");
+        }
+
+        for _ in 0..FRAMES {
+            renderer.render_with(|ctx| {
+                ctx.clear_color([0.1, 0.1, 0.15, 1.0]);
+                ctx.push_constants(&IDENTITY)?;
+                ctx.draw_mesh(&triangle);
+                Ok(())
+            })?;
+        }
+        let presented = renderer.stats().frame_count;
+        if presented == 0 {
+            warn!("No frame presented (window hidden?), nothing was checked");
+        }
+        // Wait for the GPU so messages from the last submits are in
+        if let Some(device) = renderer.device() {
+            unsafe { device.device_wait_idle() }.ok();
+        }
+        let frames = self.log.since(startup);
+        for message in &frames {
+            error!("Frame {:?}: {}", message.severity, message.text);
+        }
+        self.frame_messages = frames.len();
+        info!("Rendered {presented} frame(s)");
+        drop(triangle);
+        drop(renderer);
+        drop(window);
+        Ok(())
+    }
+}
+
+impl ApplicationHandler for PresentSync {
+    fn resumed(&mut self, event_loop: &ActiveEventLoop) {
+        if let Err(e) = self.run(event_loop) {
+            error!("{e}");
+            self.error.get_or_insert(e);
+        }
+        event_loop.exit();
+    }
+
+    fn window_event(&mut self, _event_loop: &ActiveEventLoop, _id: WindowId, _event: WindowEvent) {}
+}
+
+/// Whether the Khronos validation layer is installed.
+fn validation_layer_available() -> Result<bool> {
+    let loader = unsafe { LibloadingLoader::new(LIBRARY) }?;
+    let entry = unsafe { Entry::new(loader) }?;
+    let layers = unsafe { entry.enumerate_instance_layer_properties() }
+        .map_err(|e| AppError::vk(e, "enumerate instance layers"))?;
+    Ok(layers.iter().any(|layer| {
+        let name = unsafe { CStr::from_ptr(layer.layer_name.as_ptr()) };
+        name.to_bytes() == b"VK_LAYER_KHRONOS_validation"
+    }))
+}
+
+fn main() -> Result<()> {
+    env_logger::init();
+    if !cfg!(all(debug_assertions, feature = "debug-utils")) {
+        info!("Skipped: needs a debug build with the debug-utils feature");
+        return Ok(());
+    }
+    if !validation_layer_available()? {
+        info!("Skipped: VK_LAYER_KHRONOS_validation isn't installed");
+        return Ok(());
+    }
+
+    let event_loop = EventLoop::new()?;
+    let mut app = PresentSync::default();
+    event_loop.run_app(&mut app)?;
+    if let Some(e) = app.error {
+        return Err(e);
+    }
+    match app.frame_messages {
+        0 => {
+            info!("✅ Frames passed synchronization validation");
+            Ok(())
+        }
+        n => Err(AppError::Config(format!(
+            "validation reported {n} message(s) while rendering"
+        ))),
+    }
+}
@@ -0,0 +1,182 @@
+//! examples/resize_stress.rs – resize the window rapidly to shake out swapchain recreation
+//!
+//! Drives winit directly (like `two_windows`) so it can resize its own window:
+//! every few frames the next size in `SIZES` is requested, down to 1x1 and back
+//! up, for `CYCLES` passes. Each resize recreates the swapchain, and the frames
+//! in between run into out-of-date / suboptimal swapchains and sizes the surface
+//! may refuse. Frames the renderer skipped instead of presenting are logged.
+//! It exits with a summary once done; in debug builds validation errors raised
+//! during recreation show up in the log as well.
+//!
+//! ```text
+//! RUST_LOG=info cargo run --example resize_stress
+//! ```
+
+use log::{error, info, warn};
+use winit::{
+    application::ApplicationHandler,
+    dpi::PhysicalSize,
+    event::WindowEvent,
+    event_loop::{ActiveEventLoop, ControlFlow, EventLoop},
+    window::{Window, WindowId},
+};
+
+use wolf_engine::core::renderer::api::Renderer;
+use wolf_engine::core::renderer::backend::vulkan::VulkanRenderer;
+use wolf_engine::core::renderer::config::RendererConfig;
+use wolf_engine::error::{AppError, Result};
+
+/// Sizes visited in order each cycle; 1x1 is the smallest surface most
+/// platforms allow (0x0 only happens when minimized).
+const SIZES: [(u32, u32); 8] = [
+    (1280, 720),
+    (1, 1),
+    (640, 480),
+    (1920, 1080),
+    (1, 1),
+    (320, 200),
+    (1024, 1024),
+    (800, 600),
+];
+const CYCLES: u32 = 5;
+const FRAMES_PER_SIZE: u32 = 4; // Render attempts before moving to the next size
+
+/// The renderer is declared first so it (and its surface) is dropped before
+/// the window.
+struct Stress {
+    renderer: VulkanRenderer,
+    window: Window,
+}
+
+#[derive(Default)]
+struct ResizeStress {
+    stress: Option<Stress>,
+    step: usize,         // Index into SIZES
+    cycle: u32,          // Completed passes over SIZES
+    frames_at_step: u32, // Render attempts since the last resize
+    skipped: u32,        // Render calls that returned without presenting
+    rendered: u32,
+    error: Option<AppError>,
+}
+
+impl ResizeStress {
+    fn open(&mut self, event_loop: &ActiveEventLoop) -> Result<()> {
+        let (width, height) = SIZES[0];
+        let attributes = Window::default_attributes()
+            .with_title("wolf-engine: resize stress")
+            .with_inner_size(PhysicalSize::new(width, height));
+        let window = event_loop.create_window(attributes)?;
+        let mut renderer = VulkanRenderer::new(RendererConfig::default());
+        renderer.initialize(&window, event_loop)?;
+        renderer.set_clear_color([0.1, 0.3, 0.2, 1.0]);
+        self.stress = Some(Stress { renderer, window });
+        Ok(())
+    }
+
+    /// Render one frame and count it as skipped if no frame was presented.
+    fn frame(&mut self) -> Result<()> {
+        let Some(Stress { renderer, window }) = &mut self.stress else {
+            return Ok(());
+        };
+        let before = renderer.stats().frame_count;
+        renderer.render()?;
+        if renderer.stats().frame_count == before {
+            self.skipped += 1;
+            let size = window.inner_size();
+            info!(
+                "Skipped frame at {}x{} (cycle {}, step {})",
+                size.width, size.height, self.cycle, self.step
+            );
+        } else {
+            self.rendered += 1;
+        }
+        self.frames_at_step += 1;
+        Ok(())
+    }
+
+    /// Request the next size once the current one had its frames. Returns
+    /// false when every cycle is done.
+    fn advance(&mut self, event_loop: &ActiveEventLoop) -> bool {
+        if self.frames_at_step < FRAMES_PER_SIZE {
+            return true;
+        }
+        self.frames_at_step = 0;
+        self.step = (self.step + 1) % SIZES.len();
+        if self.step == 0 {
+            self.cycle += 1;
+            info!("🖥️ Resize cycle {}/{CYCLES} done", self.cycle);
+            if self.cycle == CYCLES {
+                return false;
+            }
+        }
+        let Some(Stress { renderer, window }) = &mut self.stress else {
+            return true;
+        };
+        let (width, height) = SIZES[self.step];
+        // Some platforms apply the size right away and may not send Resized for it
+        if let Some(size) = window.request_inner_size(PhysicalSize::new(width, height)) {
+            renderer.window_event(event_loop, window.id(), &WindowEvent::Resized(size));
+            if size != PhysicalSize::new(width, height) {
+                warn!(
+                    "Asked for {width}x{height}, got {}x{}",
+                    size.width, size.height
+                );
+            }
+        }
+        true
+    }
+
+    fn fail(&mut self, event_loop: &ActiveEventLoop, e: AppError) {
+        error!("{e}");
+        self.error.get_or_insert(e);
+        event_loop.exit();
+    }
+}
+
+impl ApplicationHandler for ResizeStress {
+    fn resumed(&mut self, event_loop: &ActiveEventLoop) {
+        if self.stress.is_none()
+            && let Err(e) = self.open(event_loop)
+        {
+            self.fail(event_loop, e);
+        }
+    }
+
+    fn window_event(&mut self, event_loop: &ActiveEventLoop, id: WindowId, event: WindowEvent) {
+        let Some(Stress { renderer, .. }) = &mut self.stress else {
+            return;
+        };
+        renderer.window_event(event_loop, id, &event);
+        let result = match renderer.take_error() {
+            Some(e) => Err(e),
+            None if matches!(event, WindowEvent::RedrawRequested) => self.frame(),
+            None => Ok(()),
+        };
+        if let Err(e) = result {
+            return self.fail(event_loop, e);
+        }
+        if matches!(event, WindowEvent::RedrawRequested) && !self.advance(event_loop) {
+            info!(
+                "✅ Resize stress passed: {CYCLES} cycles, {} frames presented, {} skipped",
+                self.rendered, self.skipped
+            );
+            self.stress = None;
+            event_loop.exit();
+        }
+    }
+
+    fn about_to_wait(&mut self, _event_loop: &ActiveEventLoop) {
+        if let Some(stress) = &self.stress {
+            stress.window.request_redraw();
+        }
+    }
+}
+
+fn main() -> Result<()> {
+    env_logger::init();
+    let event_loop = EventLoop::new()?;
+    event_loop.set_control_flow(ControlFlow::Poll);
+    let mut app = ResizeStress::default();
+    event_loop.run_app(&mut app)?;
+    app.error.map_or(Ok(()), Err)
+}
@@ -0,0 +1,136 @@
+//! examples/rotating_quad.rs – a spinning quad drawn purely through `App::run_with_draw`
+//!
+//! No `Renderer` impl: the callback gets a `DrawContext` every frame, uploads the
+//! quad on its first call and pushes a rotation matrix before drawing it. Compile
+//! the built-in mesh shaders first:
+//!
+//! ```text
+//! glslc shaders/mesh.vert -o shaders/mesh.vert.spv
+//! glslc shaders/mesh.frag -o shaders/mesh.frag.spv
+//! cargo run --example rotating_quad [90|180|270]
+//! ```
+//!
+//! A rotation argument forces that surface pre-transform, as for a rotated
+//! kiosk panel; the quad is pre-rotated with `DrawContext::pre_rotation` so it
+//! still spins upright on screen. On startup the pre-transform selection is
+//! checked on mocked surfaces: forced when supported, ignored otherwise.
+
+use std::time::Instant;
+
+use log::info;
+use vulkanalia::vk;
+
+use wolf_engine::app::App;
+use wolf_engine::core::renderer::backend::vulkan::capabilities::{
+    content_turns, select_pre_transform,
+};
+use wolf_engine::core::renderer::backend::vulkan::viewport::{pre_rotation, rotate_extent};
+use wolf_engine::core::renderer::backend::vulkan::{DrawContext, Mesh, Vertex};
+use wolf_engine::core::renderer::config::{RendererBuilder, SurfaceTransform};
+use wolf_engine::core::window::WindowConfig;
+use wolf_engine::error::{AppError, Result};
+
+const QUAD_VERTICES: [Vertex; 4] = [
+    Vertex::new([-0.5, -0.5, 0.0], [1.0, 0.2, 0.2]),
+    Vertex::new([0.5, -0.5, 0.0], [0.2, 1.0, 0.2]),
+    Vertex::new([0.5, 0.5, 0.0], [0.2, 0.2, 1.0]),
+    Vertex::new([-0.5, 0.5, 0.0], [1.0, 1.0, 0.2]),
+];
+const QUAD_INDICES: [u32; 6] = [0, 1, 2, 2, 3, 0];
+
+/// Column-major rotation about Z, with X squeezed by the aspect ratio so the
+/// quad stays square in a non-square window.
+fn rotation(angle: f32, aspect: f32) -> [[f32; 4]; 4] {
+    let (sin, cos) = angle.sin_cos();
+    [
+        [cos / aspect, sin, 0.0, 0.0],
+        [-sin / aspect, cos, 0.0, 0.0],
+        [0.0, 0.0, 1.0, 0.0],
+        [0.0, 0.0, 0.0, 1.0],
+    ]
+}
+
+/// Column-major `a * b`.
+fn mul(a: [[f32; 4]; 4], b: [[f32; 4]; 4]) -> [[f32; 4]; 4] {
+    let mut out = [[0.0; 4]; 4];
+    for (col, out_col) in out.iter_mut().enumerate() {
+        for (row, value) in out_col.iter_mut().enumerate() {
+            *value = (0..4).map(|k| a[k][row] * b[col][k]).sum();
+        }
+    }
+    out
+}
+
+/// A forced transform is used when the surface supports it and ignored (the
+/// current one kept, nothing to pre-rotate) when it doesn't.
+fn check_pre_transform() {
+    use vk::SurfaceTransformFlagsKHR as T;
+    let forced = Some(SurfaceTransform::Rotate90);
+    let supported = T::IDENTITY | T::ROTATE_90;
+    assert_eq!(
+        select_pre_transform(forced, supported, T::IDENTITY),
+        T::ROTATE_90
+    );
+    assert_eq!(
+        select_pre_transform(forced, T::IDENTITY, T::IDENTITY),
+        T::IDENTITY
+    );
+    assert_eq!(
+        select_pre_transform(None, supported, T::ROTATE_90),
+        T::ROTATE_90
+    );
+
+    assert_eq!(content_turns(T::ROTATE_90, T::IDENTITY), 1);
+    assert_eq!(content_turns(T::ROTATE_90, T::ROTATE_90), 0);
+    assert_eq!(content_turns(T::IDENTITY, T::ROTATE_90), 3);
+    let extent = vk::Extent2D {
+        width: 1920,
+        height: 1080,
+    };
+    assert_eq!(rotate_extent(extent, 1).width, 1080);
+    assert_eq!(rotate_extent(extent, 2), extent);
+    // A quarter turn moves +X (right) onto +Y (down): clockwise on screen
+    assert_eq!(pre_rotation(1)[0], [0.0, 1.0, 0.0, 0.0]);
+    assert_eq!(mul(pre_rotation(1), pre_rotation(3)), pre_rotation(0));
+}
+
+fn main() -> Result<()> {
+    env_logger::init();
+    check_pre_transform();
+    let mut builder = RendererBuilder::new().app_name("rotating_quad");
+    if let Some(arg) = std::env::args().nth(1) {
+        let transform = match arg.as_str() {
+            "90" => SurfaceTransform::Rotate90,
+            "180" => SurfaceTransform::Rotate180,
+            "270" => SurfaceTransform::Rotate270,
+            _ => {
+                return Err(AppError::Config(format!(
+                    "rotation must be 90, 180 or 270: {arg}"
+                )));
+            }
+        };
+        builder = builder.force_pre_transform(transform);
+    }
+    let config = builder.build()?;
+
+    let start = Instant::now();
+    let mut quad: Option<Mesh> = None;
+    let window = WindowConfig::new().title("wolf-engine: rotating quad");
+    App::run_with_draw(window, config, move |ctx: &mut DrawContext| {
+        if quad.is_none() {
+            quad = Some(ctx.create_mesh(&QUAD_VERTICES, &QUAD_INDICES)?);
+            info!("🖥️ Quad uploaded");
+        }
+
+        let extent = ctx.display_extent();
+        let aspect = extent.width as f32 / extent.height.max(1) as f32;
+        let angle = start.elapsed().as_secs_f32(); // One radian per second
+
+        ctx.clear_color([0.05, 0.05, 0.08, 1.0]);
+        ctx.push_constants(&mul(ctx.pre_rotation(), rotation(angle, aspect)))?;
+        if let Some(quad) = &quad {
+            ctx.draw_mesh(quad);
+        }
+        Ok(())
+    })
+}
@@ -0,0 +1,447 @@
+//! examples/subpasses.rs – two-subpass render pass (geometry → lighting via input attachment)
+//!
+//! Subpass 0 draws the RGB triangle into a transient G-buffer color attachment,
+//! subpass 1 reads it back as an input attachment (`subpassLoad`) in a
+//! full-screen pass and writes the inverted color into the target. On tiled GPUs
+//! the G-buffer never leaves on-chip memory. The target is copied back and
+//! checked: the G-buffer's clear color must come out inverted around the
+//! triangle, and the triangle's centroid as the inverse of its blended vertex
+//! colors. Exits with an error on any mismatch. Needs no window:
+//!
+//! ```text
+//! glslc shaders/triangle.vert -o shaders/triangle.vert.spv
+//! glslc shaders/triangle.frag -o shaders/triangle.frag.spv
+//! glslc shaders/fullscreen.vert -o shaders/fullscreen.vert.spv
+//! glslc shaders/lighting.frag -o shaders/lighting.frag.spv
+//! RUST_LOG=info cargo run --example subpasses
+//! ```
+//!
+//! Without a GPU, point the loader at lavapipe as described in `headless_screenshot`.
+
+use log::info;
+use vulkanalia::prelude::v1_0::*;
+
+use wolf_engine::core::renderer::backend::vulkan::barrier::buffer_barrier;
+use wolf_engine::core::renderer::backend::vulkan::fullscreen::{
+    draw_fullscreen_triangle, load_fullscreen_vertex_shader,
+};
+use wolf_engine::core::renderer::backend::vulkan::image::ImageDesc;
+use wolf_engine::core::renderer::backend::vulkan::shader::load_shader_module;
+use wolf_engine::core::renderer::backend::vulkan::transfer::color_layers;
+use wolf_engine::core::renderer::backend::vulkan::{
+    Buffer, ClearValue, ColorLoadOp, DescriptorLayoutBuilder, HEADLESS_FORMAT, HeadlessRenderer,
+    RenderPassBuilder, Texture,
+};
+use wolf_engine::core::renderer::config::{RendererBuilder, RendererConfig};
+use wolf_engine::error::{AppError, Result};
+
+const EXTENT: vk::Extent2D = vk::Extent2D {
+    width: 256,
+    height: 256,
+};
+const GBUFFER_FORMAT: vk::Format = vk::Format::R16G16B16A16_SFLOAT;
+const GBUFFER_CLEAR: [f32; 4] = [0.2, 0.4, 0.6, 1.0];
+// What the lighting subpass makes of GBUFFER_CLEAR, and of the triangle's
+// centroid, where the three vertex colors blend to a third each
+const BACKGROUND: [u8; 4] = [204, 153, 102, 255];
+const CENTROID: [u8; 4] = [170, 170, 170, 255];
+const CENTROID_PIXEL: (u32, u32) = (128, 149); // shaders/triangle.vert, at 256x256
+const TOLERANCE: u8 = 3; // UNORM rounding and barycentrics at the pixel center
+
+/// Raw handles of the pass; null handles are ignored on destroy.
+#[derive(Default)]
+struct Deferred {
+    render_pass: vk::RenderPass,
+    framebuffer: vk::Framebuffer,
+    set_layout: vk::DescriptorSetLayout,
+    descriptor_pool: vk::DescriptorPool,
+    geometry_layout: vk::PipelineLayout,
+    lighting_layout: vk::PipelineLayout,
+    geometry: vk::Pipeline, // Subpass 0
+    lighting: vk::Pipeline, // Subpass 1
+    shaders: Vec<vk::ShaderModule>,
+    pool: vk::CommandPool,
+    fence: vk::Fence,
+}
+
+impl Deferred {
+    fn destroy(&self, device: &Device) {
+        unsafe {
+            device.destroy_fence(self.fence, None);
+            device.destroy_command_pool(self.pool, None);
+            device.destroy_pipeline(self.lighting, None);
+            device.destroy_pipeline(self.geometry, None);
+            device.destroy_pipeline_layout(self.lighting_layout, None);
+            device.destroy_pipeline_layout(self.geometry_layout, None);
+            device.destroy_descriptor_pool(self.descriptor_pool, None);
+            device.destroy_descriptor_set_layout(self.set_layout, None);
+            for &module in &self.shaders {
+                device.destroy_shader_module(module, None);
+            }
+            device.destroy_framebuffer(self.framebuffer, None);
+            device.destroy_render_pass(self.render_pass, None);
+        }
+    }
+}
+
+/// G-buffer (attachment 0) + target (attachment 1), two chained subpasses. The
+/// pass leaves the target in TRANSFER_SRC for the readback.
+fn deferred_pass() -> RenderPassBuilder {
+    let gbuffer = vk::AttachmentDescription::builder()
+        .format(GBUFFER_FORMAT)
+        .samples(vk::SampleCountFlags::_1)
+        .load_op(vk::AttachmentLoadOp::CLEAR)
+        .store_op(vk::AttachmentStoreOp::DONT_CARE) // Only lives inside the pass
+        .stencil_load_op(vk::AttachmentLoadOp::DONT_CARE)
+        .stencil_store_op(vk::AttachmentStoreOp::DONT_CARE)
+        .initial_layout(vk::ImageLayout::UNDEFINED)
+        .final_layout(vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL)
+        .build();
+
+    RenderPassBuilder::new()
+        .attachment(gbuffer)
+        .clear_value(0, ClearValue::Float(GBUFFER_CLEAR))
+        // Lighting covers every pixel, so the old target contents don't matter
+        .color_attachment(
+            HEADLESS_FORMAT,
+            ColorLoadOp::DontCare,
+            vk::ImageLayout::TRANSFER_SRC_OPTIMAL,
+        )
+        .subpass() // 0: geometry
+        .color(0)
+        .subpass() // 1: lighting
+        .input(0)
+        .color(1)
+        .chain_dependencies()
+        .dependency(
+            vk::SubpassDependency::builder()
+                .src_subpass(1)
+                .dst_subpass(vk::SUBPASS_EXTERNAL)
+                .src_stage_mask(vk::PipelineStageFlags::COLOR_ATTACHMENT_OUTPUT)
+                .src_access_mask(vk::AccessFlags::COLOR_ATTACHMENT_WRITE)
+                .dst_stage_mask(vk::PipelineStageFlags::TRANSFER)
+                .dst_access_mask(vk::AccessFlags::TRANSFER_READ)
+                .build(),
+        )
+}
+
+/// Pipeline for `subpass` of `render_pass` without vertex input, covering `EXTENT`.
+fn create_pipeline(
+    device: &Device,
+    render_pass: vk::RenderPass,
+    subpass: u32,
+    layout: vk::PipelineLayout,
+    (vert, frag): (vk::ShaderModule, vk::ShaderModule),
+) -> Result<vk::Pipeline> {
+    let stages = [
+        vk::PipelineShaderStageCreateInfo::builder()
+            .stage(vk::ShaderStageFlags::VERTEX)
+            .module(vert)
+            .name(b"main\0")
+            .build(),
+        vk::PipelineShaderStageCreateInfo::builder()
+            .stage(vk::ShaderStageFlags::FRAGMENT)
+            .module(frag)
+            .name(b"main\0")
+            .build(),
+    ];
+    let vertex_input = vk::PipelineVertexInputStateCreateInfo::builder();
+    let input_assembly = vk::PipelineInputAssemblyStateCreateInfo::builder()
+        .topology(vk::PrimitiveTopology::TRIANGLE_LIST);
+    // Fixed size, so the viewport can be baked in
+    let viewports = [vk::Viewport::builder()
+        .width(EXTENT.width as f32)
+        .height(EXTENT.height as f32)
+        .max_depth(1.0)
+        .build()];
+    let scissors = [vk::Rect2D::builder().extent(EXTENT).build()];
+    let viewport_state = vk::PipelineViewportStateCreateInfo::builder()
+        .viewports(&viewports)
+        .scissors(&scissors);
+    let rasterization = vk::PipelineRasterizationStateCreateInfo::builder()
+        .polygon_mode(vk::PolygonMode::FILL)
+        .cull_mode(vk::CullModeFlags::NONE)
+        .front_face(vk::FrontFace::CLOCKWISE)
+        .line_width(1.0);
+    let multisample = vk::PipelineMultisampleStateCreateInfo::builder()
+        .rasterization_samples(vk::SampleCountFlags::_1);
+    let blend_attachments = [vk::PipelineColorBlendAttachmentState::builder()
+        .color_write_mask(vk::ColorComponentFlags::all())
+        .blend_enable(false)
+        .build()];
+    let color_blend =
+        vk::PipelineColorBlendStateCreateInfo::builder().attachments(&blend_attachments);
+
+    let info = vk::GraphicsPipelineCreateInfo::builder()
+        .stages(&stages)
+        .vertex_input_state(&vertex_input)
+        .input_assembly_state(&input_assembly)
+        .viewport_state(&viewport_state)
+        .rasterization_state(&rasterization)
+        .multisample_state(&multisample)
+        .color_blend_state(&color_blend)
+        .layout(layout)
+        .render_pass(render_pass)
+        .subpass(subpass);
+    let pipelines =
+        unsafe { device.create_graphics_pipelines(vk::PipelineCache::null(), &[info], None) }
+            .map_err(|e| AppError::vk(e, "create subpass pipeline"))?;
+    Ok(pipelines.0[0])
+}
+
+/// Create the pass, its framebuffer, both pipelines and the input attachment
+/// descriptor, then render once into `target` and copy it into `readback`.
+fn render(
+    renderer: &HeadlessRenderer,
+    config: &RendererConfig,
+    deferred: &mut Deferred,
+    gbuffer: &Texture,
+    target: &Texture,
+    readback: &Buffer,
+) -> Result<()> {
+    let context = renderer.context().ok_or(AppError::NotInitialized)?;
+    let device = &context.device;
+    let (queue, family) = renderer.queue();
+
+    let builder = deferred_pass();
+    deferred.render_pass = builder.build(device)?;
+    let attachments = [gbuffer.view(), target.view()];
+    let fb_info = vk::FramebufferCreateInfo::builder()
+        .render_pass(deferred.render_pass)
+        .attachments(&attachments)
+        .width(EXTENT.width)
+        .height(EXTENT.height)
+        .layers(1);
+    deferred.framebuffer = unsafe { device.create_framebuffer(&fb_info, None) }
+        .map_err(|e| AppError::vk(e, "create framebuffer"))?;
+
+    // The lighting subpass reads the G-buffer through set 0, binding 0
+    deferred.set_layout = DescriptorLayoutBuilder::new()
+        .binding(
+            0,
+            vk::DescriptorType::INPUT_ATTACHMENT,
+            vk::ShaderStageFlags::FRAGMENT,
+        )
+        .build(device)?;
+    let pool_sizes = [vk::DescriptorPoolSize::builder()
+        .type_(vk::DescriptorType::INPUT_ATTACHMENT)
+        .descriptor_count(1)
+        .build()];
+    let pool_info = vk::DescriptorPoolCreateInfo::builder()
+        .max_sets(1)
+        .pool_sizes(&pool_sizes);
+    deferred.descriptor_pool = unsafe { device.create_descriptor_pool(&pool_info, None) }
+        .map_err(|e| AppError::vk(e, "create descriptor pool"))?;
+    let set_layouts = [deferred.set_layout];
+    let alloc_info = vk::DescriptorSetAllocateInfo::builder()
+        .descriptor_pool(deferred.descriptor_pool)
+        .set_layouts(&set_layouts);
+    let set = unsafe { device.allocate_descriptor_sets(&alloc_info) }
+        .map_err(|e| AppError::vk(e, "allocate descriptor set"))?[0];
+    // Layout the lighting subpass reads it in (`RenderPassBuilder::input`)
+    let image_info = [vk::DescriptorImageInfo::builder()
+        .image_view(gbuffer.view())
+        .image_layout(vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL)
+        .build()];
+    let write = vk::WriteDescriptorSet::builder()
+        .dst_set(set)
+        .dst_binding(0)
+        .descriptor_type(vk::DescriptorType::INPUT_ATTACHMENT)
+        .image_info(&image_info);
+    unsafe { device.update_descriptor_sets(&[write], &[] as &[vk::CopyDescriptorSet]) };
+
+    let shader_dir = &config.shader_dir;
+    let shader = |name: &str| load_shader_module(device, &shader_dir.join(name));
+    let triangle = (shader("triangle.vert.spv")?, shader("triangle.frag.spv")?);
+    deferred.shaders.extend([triangle.0, triangle.1]);
+    let fullscreen = load_fullscreen_vertex_shader(device, shader_dir)?;
+    deferred.shaders.push(fullscreen);
+    let lighting = shader("lighting.frag.spv")?;
+    deferred.shaders.push(lighting);
+
+    let geometry_info = vk::PipelineLayoutCreateInfo::builder();
+    deferred.geometry_layout = unsafe { device.create_pipeline_layout(&geometry_info, None) }
+        .map_err(|e| AppError::vk(e, "create geometry pipeline layout"))?;
+    let lighting_info = vk::PipelineLayoutCreateInfo::builder().set_layouts(&set_layouts);
+    deferred.lighting_layout = unsafe { device.create_pipeline_layout(&lighting_info, None) }
+        .map_err(|e| AppError::vk(e, "create lighting pipeline layout"))?;
+    let render_pass = deferred.render_pass;
+    deferred.geometry =
+        create_pipeline(device, render_pass, 0, deferred.geometry_layout, triangle)?;
+    deferred.lighting = create_pipeline(
+        device,
+        render_pass,
+        1,
+        deferred.lighting_layout,
+        (fullscreen, lighting),
+    )?;
+    info!("✅ Two-subpass render pass and its pipelines created (geometry → lighting)");
+
+    let pool_info = vk::CommandPoolCreateInfo::builder()
+        .flags(vk::CommandPoolCreateFlags::TRANSIENT)
+        .queue_family_index(family);
+    deferred.pool = unsafe { device.create_command_pool(&pool_info, None) }
+        .map_err(|e| AppError::vk(e, "create command pool"))?;
+    let alloc_info = vk::CommandBufferAllocateInfo::builder()
+        .command_pool(deferred.pool)
+        .level(vk::CommandBufferLevel::PRIMARY)
+        .command_buffer_count(1);
+    let cmd = unsafe { device.allocate_command_buffers(&alloc_info) }
+        .map_err(|e| AppError::vk(e, "allocate command buffer"))?[0];
+    deferred.fence = unsafe { device.create_fence(&vk::FenceCreateInfo::default(), None) }
+        .map_err(|e| AppError::vk(e, "create fence"))?;
+
+    unsafe {
+        let begin_info = vk::CommandBufferBeginInfo::builder()
+            .flags(vk::CommandBufferUsageFlags::ONE_TIME_SUBMIT);
+        device
+            .begin_command_buffer(cmd, &begin_info)
+            .map_err(|e| AppError::vk(e, "begin command buffer"))?;
+
+        let pass_info = vk::RenderPassBeginInfo::builder()
+            .render_pass(deferred.render_pass)
+            .framebuffer(deferred.framebuffer)
+            .render_area(vk::Rect2D::builder().extent(EXTENT).build())
+            .clear_values(builder.clear_values());
+        device.cmd_begin_render_pass(cmd, &pass_info, vk::SubpassContents::INLINE);
+
+        // Subpass 0: the triangle into the G-buffer
+        device.cmd_bind_pipeline(cmd, vk::PipelineBindPoint::GRAPHICS, deferred.geometry);
+        device.cmd_draw(cmd, 3, 1, 0, 0);
+
+        // Subpass 1: every pixel of the G-buffer, read in place, into the target
+        device.cmd_next_subpass(cmd, vk::SubpassContents::INLINE);
+        device.cmd_bind_pipeline(cmd, vk::PipelineBindPoint::GRAPHICS, deferred.lighting);
+        device.cmd_bind_descriptor_sets(
+            cmd,
+            vk::PipelineBindPoint::GRAPHICS,
+            deferred.lighting_layout,
+            0,
+            &[set],
+            &[],
+        );
+        draw_fullscreen_triangle(device, cmd);
+        device.cmd_end_render_pass(cmd);
+
+        // Tightly packed rows (buffer_row_length 0), so the buffer is plain RGBA8
+        let region = vk::BufferImageCopy::builder()
+            .image_subresource(color_layers())
+            .image_extent(vk::Extent3D {
+                width: EXTENT.width,
+                height: EXTENT.height,
+                depth: 1,
+            });
+        device.cmd_copy_image_to_buffer(
+            cmd,
+            target.image(),
+            vk::ImageLayout::TRANSFER_SRC_OPTIMAL,
+            readback.handle(),
+            &[region],
+        );
+        buffer_barrier(
+            device,
+            cmd,
+            readback.handle(),
+            vk::AccessFlags::TRANSFER_WRITE,
+            vk::AccessFlags::HOST_READ,
+        )?;
+        device
+            .end_command_buffer(cmd)
+            .map_err(|e| AppError::vk(e, "end command buffer"))?;
+
+        let cmds = [cmd];
+        let submit = vk::SubmitInfo::builder().command_buffers(&cmds);
+        device
+            .queue_submit(queue, &[submit], deferred.fence)
+            .map_err(|e| AppError::vk(e, "submit deferred frame"))?;
+        device
+            .wait_for_fences(&[deferred.fence], true, u64::MAX)
+            .map_err(|e| AppError::vk(e, "wait for deferred frame"))?;
+    }
+    Ok(())
+}
+
+fn capture(renderer: &HeadlessRenderer, config: &RendererConfig) -> Result<Vec<u8>> {
+    let context = renderer.context().ok_or(AppError::NotInitialized)?;
+    let gbuffer = Texture::new(
+        context,
+        ImageDesc {
+            extent: EXTENT,
+            format: GBUFFER_FORMAT,
+            samples: vk::SampleCountFlags::_1,
+            usage: vk::ImageUsageFlags::COLOR_ATTACHMENT
+                | vk::ImageUsageFlags::INPUT_ATTACHMENT
+                | vk::ImageUsageFlags::TRANSIENT_ATTACHMENT,
+            aspect: vk::ImageAspectFlags::COLOR,
+        },
+    )?;
+    let target = Texture::new(
+        context,
+        ImageDesc {
+            extent: EXTENT,
+            format: HEADLESS_FORMAT,
+            samples: vk::SampleCountFlags::_1,
+            usage: vk::ImageUsageFlags::COLOR_ATTACHMENT | vk::ImageUsageFlags::TRANSFER_SRC,
+            aspect: vk::ImageAspectFlags::COLOR,
+        },
+    )?;
+    let size = u64::from(EXTENT.width * EXTENT.height * 4);
+    let readback = Buffer::new(
+        context,
+        size,
+        vk::BufferUsageFlags::TRANSFER_DST,
+        vk::MemoryPropertyFlags::HOST_VISIBLE | vk::MemoryPropertyFlags::HOST_COHERENT,
+    )?;
+
+    let mut deferred = Deferred::default();
+    let result = render(
+        renderer,
+        config,
+        &mut deferred,
+        &gbuffer,
+        &target,
+        &readback,
+    );
+    unsafe { context.device.device_wait_idle() }.ok();
+    deferred.destroy(&context.device);
+    result?;
+
+    let mut pixels = vec![0; size as usize];
+    readback.read(&mut pixels)?;
+    Ok(pixels)
+}
+
+/// Check the pixel at (`x`, `y`) against `expected`, naming it `what` on failure.
+fn check_pixel(pixels: &[u8], (x, y): (u32, u32), expected: [u8; 4], what: &str) -> Result<()> {
+    let start = ((y * EXTENT.width + x) * 4) as usize;
+    let actual = &pixels[start..start + 4];
+    if actual
+        .iter()
+        .zip(expected)
+        .any(|(&a, e)| a.abs_diff(e) > TOLERANCE)
+    {
+        return Err(AppError::Config(format!(
+            "{what} at ({x}, {y}) is {actual:?}, expected {expected:?}"
+        )));
+    }
+    Ok(())
+}
+
+fn main() -> Result<()> {
+    env_logger::init();
+    let config = RendererBuilder::new().app_name("subpasses").build()?;
+    let mut renderer = HeadlessRenderer::new(config.clone());
+    renderer.initialize()?;
+
+    let captured = capture(&renderer, &config);
+    renderer.cleanup();
+    let pixels = captured?;
+
+    let (right, bottom) = (EXTENT.width - 1, EXTENT.height - 1);
+    for corner in [(0, 0), (right, 0), (0, bottom), (right, bottom)] {
+        check_pixel(&pixels, corner, BACKGROUND, "background")?;
+    }
+    check_pixel(&pixels, CENTROID_PIXEL, CENTROID, "triangle centroid")?;
+    info!("✅ Lighting subpass read the geometry subpass's output");
+    Ok(())
+}
@@ -0,0 +1,103 @@
+//! examples/text_input.rs – type text through the platform input method (IME)
+//!
+//! Opens the window with `WindowConfig::ime(true)` and overrides
+//! `Renderer::on_ime`: the composition in progress (preedit) and every piece of
+//! committed text are logged, and committed text is appended to a line buffer.
+//! `Enter` logs the line and starts a new one, `Backspace` removes the last
+//! character. With a CJK input method active, composed characters arrive as
+//! commits rather than key presses. The frame is a plain clear.
+//!
+//! ```text
+//! RUST_LOG=info cargo run --example text_input
+//! ```
+
+use log::info;
+use winit::{
+    event::{ElementState, KeyEvent, WindowEvent},
+    event_loop::ActiveEventLoop,
+    keyboard::{Key, NamedKey},
+    window::{Window, WindowId},
+};
+
+use wolf_engine::app::App;
+use wolf_engine::core::renderer::api::{ImeEvent, Renderer};
+use wolf_engine::core::renderer::backend::vulkan::VulkanRenderer;
+use wolf_engine::core::renderer::config::{RendererBuilder, RendererConfig};
+use wolf_engine::core::window::WindowConfig;
+use wolf_engine::error::{AppError, Result};
+
+struct TextInput {
+    renderer: VulkanRenderer,
+    line: String, // Committed text since the last Enter
+}
+
+impl Renderer for TextInput {
+    fn new(config: RendererConfig) -> Self {
+        Self {
+            renderer: VulkanRenderer::new(config),
+            line: String::new(),
+        }
+    }
+
+    fn initialize(&mut self, window: &Window, event_loop: &ActiveEventLoop) -> Result<()> {
+        self.renderer.initialize(window, event_loop)?;
+        self.renderer.set_clear_color([0.1, 0.1, 0.14, 1.0]);
+        info!("🖥️ Type (IME on), Enter: finish the line, Backspace: delete");
+        Ok(())
+    }
+
+    fn is_initialized(&self) -> bool {
+        self.renderer.is_initialized()
+    }
+
+    fn window_event(&mut self, event_loop: &ActiveEventLoop, id: WindowId, event: &WindowEvent) {
+        self.renderer.window_event(event_loop, id, event);
+    }
+
+    fn render(&mut self) -> Result<()> {
+        self.renderer.render()
+    }
+
+    fn take_error(&mut self) -> Option<AppError> {
+        self.renderer.take_error()
+    }
+
+    fn on_key(&mut self, _window: &Window, event: &KeyEvent) {
+        if event.state != ElementState::Pressed {
+            return;
+        }
+        match &event.logical_key {
+            Key::Named(NamedKey::Enter) => {
+                info!("🖥️ Line: {:?}", self.line);
+                self.line.clear();
+            }
+            Key::Named(NamedKey::Backspace) => {
+                self.line.pop();
+            }
+            _ => {}
+        }
+    }
+
+    fn on_ime(&mut self, event: ImeEvent) {
+        match event {
+            ImeEvent::Enabled => info!("IME enabled"),
+            ImeEvent::Preedit(text, _) if !text.is_empty() => info!("Composing {text:?}"),
+            ImeEvent::Preedit(..) => {}
+            ImeEvent::Commit(text) => {
+                self.line.push_str(&text);
+                info!("Committed {text:?}, line now {:?}", self.line);
+            }
+            ImeEvent::Disabled => info!("IME disabled"),
+        }
+    }
+}
+
+fn main() -> Result<()> {
+    env_logger::init();
+    let window = WindowConfig::new()
+        .title("wolf-engine: text input")
+        .size(960, 540)
+        .ime(true);
+    let config = RendererBuilder::new().app_name("text_input").build()?;
+    App::<TextInput>::run_with(window, config)
+}
@@ -0,0 +1,520 @@
+//! examples/two_windows.rs – two windows sharing one device, each with its own swapchain
+//!
+//! Drives winit directly instead of `App` (which owns a single window). The
+//! instance and device are created once, as one `Arc<DeviceContext>`; every
+//! window gets its own surface and swapchain on it and is cleared to its own
+//! color each frame. Events are routed by `WindowId`: a resize rebuilds only
+//! that window's swapchain, and closing a window destroys only its swapchain
+//! and surface while the other keeps rendering. Closing the last one exits,
+//! and dropping the last context clone then destroys the device.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use log::{error, info};
+use vulkanalia::loader::{LIBRARY, LibloadingLoader};
+use vulkanalia::prelude::v1_0::*;
+use vulkanalia::vk::{KhrSurfaceExtension, KhrSwapchainExtension};
+use vulkanalia::window as vk_window;
+use winit::{
+    application::ApplicationHandler,
+    dpi::PhysicalSize,
+    event::WindowEvent,
+    event_loop::{ActiveEventLoop, ControlFlow, EventLoop},
+    window::{Window, WindowId},
+};
+
+use wolf_engine::core::renderer::backend::vulkan::DeviceContext;
+use wolf_engine::core::renderer::backend::vulkan::barrier::{color_subresource, image_barrier};
+use wolf_engine::core::renderer::backend::vulkan::present::desired_image_count;
+use wolf_engine::error::{AppError, Result};
+
+/// Title and clear color of each window.
+const WINDOWS: [(&str, [f32; 4]); 2] = [
+    ("wolf-engine: window A", [0.6, 0.1, 0.1, 1.0]),
+    ("wolf-engine: window B", [0.1, 0.2, 0.6, 1.0]),
+];
+
+/// The device every window renders with, and its graphics + present queue.
+struct Gpu {
+    context: Arc<DeviceContext>,
+    queue: vk::Queue,
+    family: u32,
+}
+
+impl Gpu {
+    /// Instance with the surface extensions `window` needs, plus `window`'s
+    /// surface. The surface picks the device, so the first window is known to
+    /// be able to present; later ones are checked in `Pane::new`.
+    fn create(window: &Window) -> Result<(Self, vk::SurfaceKHR)> {
+        let loader = unsafe { LibloadingLoader::new(LIBRARY) }?;
+        let entry = unsafe { Entry::new(loader) }?;
+
+        let app_info = vk::ApplicationInfo::builder()
+            .application_name(b"two_windows\0")
+            .engine_name(b"Wolf Engine\0")
+            .api_version(vk::make_version(1, 0, 0));
+        #[allow(unused_mut)]
+        let mut exts: Vec<*const i8> = vk_window::get_required_instance_extensions(window)
+            .iter()
+            .map(|e| e.as_ptr())
+            .collect();
+        #[allow(unused_mut)]
+        let mut flags = vk::InstanceCreateFlags::empty();
+        #[cfg(target_os = "macos")]
+        {
+            exts.push(vk::KHR_PORTABILITY_ENUMERATION_EXTENSION.name.as_ptr());
+            flags |= vk::InstanceCreateFlags::ENUMERATE_PORTABILITY_KHR;
+        }
+        let instance_info = vk::InstanceCreateInfo::builder()
+            .application_info(&app_info)
+            .enabled_extension_names(&exts)
+            .flags(flags);
+        let instance = unsafe { entry.create_instance(&instance_info, None) }
+            .map_err(|e| AppError::vk(e, "create instance"))?;
+
+        // Nothing owns the instance and surface until the context does
+        let fail = |e: AppError, surface: vk::SurfaceKHR| {
+            unsafe {
+                instance.destroy_surface_khr(surface, None);
+                instance.destroy_instance(None);
+            }
+            e
+        };
+        let surface =
+            unsafe { vk_window::create_surface(&instance, window, window) }.map_err(|e| {
+                fail(
+                    AppError::vk(e, "create window surface"),
+                    vk::SurfaceKHR::null(),
+                )
+            })?;
+
+        // First GPU with a queue family that renders and presents to the surface
+        let devices = unsafe { instance.enumerate_physical_devices() }
+            .map_err(|e| fail(AppError::vk(e, "enumerate physical devices"), surface))?;
+        let picked = devices.into_iter().find_map(|dev| {
+            let families = unsafe { instance.get_physical_device_queue_family_properties(dev) };
+            (0..families.len() as u32)
+                .find(|&i| {
+                    let graphics = families[i as usize]
+                        .queue_flags
+                        .contains(vk::QueueFlags::GRAPHICS);
+                    let present = unsafe {
+                        instance.get_physical_device_surface_support_khr(dev, i, surface)
+                    };
+                    graphics && present.unwrap_or(false)
+                })
+                .map(|family| (dev, family))
+        });
+        let Some((physical_device, family)) = picked else {
+            let e = AppError::Surface("no GPU can both render and present to this window".into());
+            return Err(fail(e, surface));
+        };
+
+        let priorities = [1.0];
+        let queue_infos = [vk::DeviceQueueCreateInfo::builder()
+            .queue_family_index(family)
+            .queue_priorities(&priorities)
+            .build()];
+        let device_exts = [vk::KHR_SWAPCHAIN_EXTENSION.name.as_ptr()];
+        let device_info = vk::DeviceCreateInfo::builder()
+            .queue_create_infos(&queue_infos)
+            .enabled_extension_names(&device_exts);
+        let device = unsafe { instance.create_device(physical_device, &device_info, None) }
+            .map_err(|e| fail(AppError::vk(e, "create device"), surface))?;
+        let queue = unsafe { device.get_device_queue(family, 0) };
+        let name = unsafe { instance.get_physical_device_properties(physical_device) }
+            .device_name
+            .to_string_lossy()
+            .into_owned();
+        info!("✅ One device for every window: {name}");
+
+        let context = DeviceContext::new(entry, instance, device, physical_device);
+        let gpu = Self {
+            context: Arc::new(context),
+            queue,
+            family,
+        };
+        Ok((gpu, surface))
+    }
+}
+
+/// One window and everything presenting to it. The surface is destroyed in
+/// `drop`, before the window field goes.
+struct Pane {
+    context: Arc<DeviceContext>,
+    queue: vk::Queue,
+    surface: vk::SurfaceKHR,
+    swapchain: vk::SwapchainKHR,
+    images: Vec<vk::Image>,
+    extent: vk::Extent2D, // Zero while minimized: nothing to draw into
+    stale: bool,          // Resized since the swapchain was built
+    pool: vk::CommandPool,
+    cmd: vk::CommandBuffer,
+    image_available: vk::Semaphore,
+    render_finished: Vec<vk::Semaphore>, // Per image: a present may still wait on it
+    in_flight: vk::Fence,
+    clear: [f32; 4],
+    window: Window,
+}
+
+impl Pane {
+    /// Sync objects and a first swapchain for `surface` of `window` on `gpu`.
+    /// Takes over `surface`, destroying it on failure too.
+    fn new(gpu: &Gpu, window: Window, surface: vk::SurfaceKHR, clear: [f32; 4]) -> Result<Self> {
+        let mut pane = Self {
+            context: Arc::clone(&gpu.context),
+            queue: gpu.queue,
+            surface,
+            swapchain: vk::SwapchainKHR::null(),
+            images: Vec::new(),
+            extent: vk::Extent2D::default(),
+            stale: false,
+            pool: vk::CommandPool::null(),
+            cmd: vk::CommandBuffer::null(),
+            image_available: vk::Semaphore::null(),
+            render_finished: Vec::new(),
+            in_flight: vk::Fence::null(),
+            clear,
+            window,
+        };
+        let context = &gpu.context;
+        let present = unsafe {
+            context.instance.get_physical_device_surface_support_khr(
+                context.physical_device,
+                gpu.family,
+                surface,
+            )
+        }
+        .map_err(|e| AppError::vk(e, "query present support"))?;
+        if !present {
+            return Err(AppError::Surface(
+                "the shared device can't present to this window".into(),
+            ));
+        }
+
+        let device = &context.device;
+        let pool_info = vk::CommandPoolCreateInfo::builder()
+            .flags(vk::CommandPoolCreateFlags::RESET_COMMAND_BUFFER)
+            .queue_family_index(gpu.family);
+        pane.pool = unsafe { device.create_command_pool(&pool_info, None) }
+            .map_err(|e| AppError::vk(e, "create command pool"))?;
+        let alloc_info = vk::CommandBufferAllocateInfo::builder()
+            .command_pool(pane.pool)
+            .level(vk::CommandBufferLevel::PRIMARY)
+            .command_buffer_count(1);
+        pane.cmd = unsafe { device.allocate_command_buffers(&alloc_info) }
+            .map_err(|e| AppError::vk(e, "allocate command buffer"))?[0];
+        pane.image_available =
+            unsafe { device.create_semaphore(&vk::SemaphoreCreateInfo::default(), None) }
+                .map_err(|e| AppError::vk(e, "create semaphore"))?;
+        let fence_info = vk::FenceCreateInfo::builder().flags(vk::FenceCreateFlags::SIGNALED);
+        pane.in_flight = unsafe { device.create_fence(&fence_info, None) }
+            .map_err(|e| AppError::vk(e, "create fence"))?;
+        pane.create_swapchain()?;
+        Ok(pane)
+    }
+
+    /// Build (or rebuild) the swapchain at the window's current size. Waits
+    /// for the shared queue first, so no frame of this window still uses the
+    /// old images.
+    fn create_swapchain(&mut self) -> Result<()> {
+        let context = Arc::clone(&self.context);
+        let (instance, device) = (&context.instance, &context.device);
+        unsafe { device.queue_wait_idle(self.queue) }
+            .map_err(|e| AppError::vk(e, "wait for queue"))?;
+        self.stale = false;
+
+        let caps = unsafe {
+            instance
+                .get_physical_device_surface_capabilities_khr(context.physical_device, self.surface)
+        }
+        .map_err(|e| AppError::vk(e, "query surface capabilities"))?;
+        let extent = if caps.current_extent.width != u32::MAX {
+            caps.current_extent
+        } else {
+            let size = self.window.inner_size();
+            vk::Extent2D {
+                width: size
+                    .width
+                    .clamp(caps.min_image_extent.width, caps.max_image_extent.width),
+                height: size
+                    .height
+                    .clamp(caps.min_image_extent.height, caps.max_image_extent.height),
+            }
+        };
+        if extent.width == 0 || extent.height == 0 {
+            self.extent = vk::Extent2D::default(); // Minimized; rebuilt on the next resize
+            return Ok(());
+        }
+        // Frames are cleared with a transfer command, straight into the image
+        if !caps
+            .supported_usage_flags
+            .contains(vk::ImageUsageFlags::TRANSFER_DST)
+        {
+            return Err(AppError::Surface(
+                "swapchain images can't be transfer destinations".into(),
+            ));
+        }
+        let formats = unsafe {
+            instance.get_physical_device_surface_formats_khr(context.physical_device, self.surface)
+        }
+        .map_err(|e| AppError::vk(e, "query surface formats"))?;
+        let format = formats
+            .first()
+            .ok_or_else(|| AppError::Surface("surface offers no formats".into()))?;
+
+        // FIFO is the one present mode every surface supports
+        let present_mode = vk::PresentModeKHR::FIFO;
+        let image_count =
+            desired_image_count(caps.min_image_count, caps.max_image_count, present_mode);
+        let old_swapchain = self.swapchain;
+        let info = vk::SwapchainCreateInfoKHR::builder()
+            .surface(self.surface)
+            .min_image_count(image_count)
+            .image_format(format.format)
+            .image_color_space(format.color_space)
+            .image_extent(extent)
+            .image_array_layers(1)
+            .image_usage(vk::ImageUsageFlags::TRANSFER_DST)
+            .image_sharing_mode(vk::SharingMode::EXCLUSIVE)
+            .pre_transform(caps.current_transform)
+            .composite_alpha(vk::CompositeAlphaFlagsKHR::OPAQUE)
+            .present_mode(present_mode)
+            .clipped(true)
+            .old_swapchain(old_swapchain);
+        let created = unsafe { device.create_swapchain_khr(&info, None) };
+        // Retired either way; nothing of it is in use after the wait above
+        unsafe { device.destroy_swapchain_khr(old_swapchain, None) };
+        self.swapchain = match created {
+            Ok(swapchain) => swapchain,
+            Err(e) => {
+                self.swapchain = vk::SwapchainKHR::null();
+                return Err(AppError::vk(e, "create swapchain"));
+            }
+        };
+        self.images = unsafe { device.get_swapchain_images_khr(self.swapchain) }
+            .map_err(|e| AppError::vk(e, "get swapchain images"))?;
+        self.extent = extent;
+
+        for semaphore in self.render_finished.drain(..) {
+            unsafe { device.destroy_semaphore(semaphore, None) };
+        }
+        for _ in 0..self.images.len() {
+            let semaphore =
+                unsafe { device.create_semaphore(&vk::SemaphoreCreateInfo::default(), None) }
+                    .map_err(|e| AppError::vk(e, "create semaphore"))?;
+            self.render_finished.push(semaphore);
+        }
+        info!(
+            "🖥️ {}: {}x{} swapchain with {} images",
+            self.window.title(),
+            extent.width,
+            extent.height,
+            self.images.len()
+        );
+        Ok(())
+    }
+
+    /// Clear the next swapchain image to this window's color and present it.
+    fn draw(&mut self) -> Result<()> {
+        if self.stale {
+            self.create_swapchain()?;
+        }
+        if self.extent.width == 0 {
+            return Ok(());
+        }
+        let context = Arc::clone(&self.context);
+        let device = &context.device;
+        unsafe {
+            device
+                .wait_for_fences(&[self.in_flight], true, u64::MAX)
+                .map_err(|e| AppError::vk(e, "wait for frame"))?;
+            let acquired = device.acquire_next_image_khr(
+                self.swapchain,
+                u64::MAX,
+                self.image_available,
+                vk::Fence::null(),
+            );
+            let index = match acquired {
+                Ok((index, _)) => index as usize,
+                Err(vk::ErrorCode::OUT_OF_DATE_KHR) => return self.create_swapchain(),
+                Err(e) => return Err(AppError::vk(e, "acquire swapchain image")),
+            };
+            device
+                .reset_fences(&[self.in_flight])
+                .map_err(|e| AppError::vk(e, "reset fence"))?;
+
+            let cmd = self.cmd;
+            let image = self.images[index];
+            device
+                .reset_command_buffer(cmd, vk::CommandBufferResetFlags::empty())
+                .map_err(|e| AppError::vk(e, "reset command buffer"))?;
+            let begin_info = vk::CommandBufferBeginInfo::builder()
+                .flags(vk::CommandBufferUsageFlags::ONE_TIME_SUBMIT);
+            device
+                .begin_command_buffer(cmd, &begin_info)
+                .map_err(|e| AppError::vk(e, "begin command buffer"))?;
+            let range = color_subresource();
+            let transfer = vk::ImageLayout::TRANSFER_DST_OPTIMAL;
+            image_barrier(
+                device,
+                cmd,
+                image,
+                vk::ImageLayout::UNDEFINED,
+                transfer,
+                range,
+            )?;
+            let clear = vk::ClearColorValue {
+                float32: self.clear,
+            };
+            device.cmd_clear_color_image(cmd, image, transfer, &clear, &[range]);
+            let present = vk::ImageLayout::PRESENT_SRC_KHR;
+            image_barrier(device, cmd, image, transfer, present, range)?;
+            device
+                .end_command_buffer(cmd)
+                .map_err(|e| AppError::vk(e, "end command buffer"))?;
+
+            let wait_semaphores = [self.image_available];
+            let wait_stages = [vk::PipelineStageFlags::TRANSFER];
+            let cmds = [cmd];
+            let signal_semaphores = [self.render_finished[index]];
+            let submit = vk::SubmitInfo::builder()
+                .wait_semaphores(&wait_semaphores)
+                .wait_dst_stage_mask(&wait_stages)
+                .command_buffers(&cmds)
+                .signal_semaphores(&signal_semaphores);
+            device
+                .queue_submit(self.queue, &[submit], self.in_flight)
+                .map_err(|e| AppError::vk(e, "submit frame"))?;
+
+            let swapchains = [self.swapchain];
+            let indices = [index as u32];
+            let present_info = vk::PresentInfoKHR::builder()
+                .wait_semaphores(&signal_semaphores)
+                .swapchains(&swapchains)
+                .image_indices(&indices);
+            match device.queue_present_khr(self.queue, &present_info) {
+                Ok(vk::SuccessCode::SUBOPTIMAL_KHR) | Err(vk::ErrorCode::OUT_OF_DATE_KHR) => {
+                    self.create_swapchain()
+                }
+                Ok(_) => Ok(()),
+                Err(e) => Err(AppError::vk(e, "present")),
+            }
+        }
+    }
+}
+
+impl Drop for Pane {
+    // Only this window's objects; the device lives on in the other panes' clones
+    fn drop(&mut self) {
+        let (instance, device) = (&self.context.instance, &self.context.device);
+        unsafe {
+            device.queue_wait_idle(self.queue).ok();
+            for &semaphore in &self.render_finished {
+                device.destroy_semaphore(semaphore, None);
+            }
+            device.destroy_semaphore(self.image_available, None);
+            device.destroy_fence(self.in_flight, None);
+            device.destroy_command_pool(self.pool, None);
+            device.destroy_swapchain_khr(self.swapchain, None);
+            instance.destroy_surface_khr(self.surface, None);
+        }
+    }
+}
+
+#[derive(Default)]
+struct TwoWindows {
+    panes: HashMap<WindowId, Pane>,
+    gpu: Option<Gpu>, // Created with the first window
+    error: Option<AppError>,
+}
+
+impl TwoWindows {
+    fn open(&mut self, event_loop: &ActiveEventLoop, title: &str, clear: [f32; 4]) -> Result<()> {
+        let attributes = Window::default_attributes()
+            .with_title(title)
+            .with_inner_size(PhysicalSize::new(640, 480));
+        let window = event_loop.create_window(attributes)?;
+        let surface = match &self.gpu {
+            Some(gpu) => {
+                let instance = &gpu.context.instance;
+                unsafe { vk_window::create_surface(instance, &window, &window) }
+                    .map_err(|e| AppError::vk(e, "create window surface"))?
+            }
+            None => {
+                let (gpu, surface) = Gpu::create(&window)?;
+                self.gpu = Some(gpu);
+                surface
+            }
+        };
+        let Some(gpu) = &self.gpu else {
+            return Err(AppError::NotInitialized);
+        };
+        let pane = Pane::new(gpu, window, surface, clear)?;
+        info!("🖥️ Opened {title}");
+        self.panes.insert(pane.window.id(), pane);
+        Ok(())
+    }
+
+    fn fail(&mut self, event_loop: &ActiveEventLoop, e: AppError) {
+        error!("{e}");
+        self.error.get_or_insert(e);
+        event_loop.exit();
+    }
+}
+
+impl ApplicationHandler for TwoWindows {
+    fn resumed(&mut self, event_loop: &ActiveEventLoop) {
+        if !self.panes.is_empty() {
+            return;
+        }
+        for (title, clear) in WINDOWS {
+            if let Err(e) = self.open(event_loop, title, clear) {
+                return self.fail(event_loop, e);
+            }
+        }
+    }
+
+    fn window_event(&mut self, event_loop: &ActiveEventLoop, id: WindowId, event: WindowEvent) {
+        if matches!(event, WindowEvent::CloseRequested) {
+            // Only this window's swapchain and surface go away
+            if let Some(pane) = self.panes.remove(&id) {
+                info!("🖥️ Closed {}", pane.window.title());
+            }
+            if self.panes.is_empty() {
+                event_loop.exit();
+            }
+            return;
+        }
+        let Some(pane) = self.panes.get_mut(&id) else {
+            return;
+        };
+        let result = match event {
+            WindowEvent::Resized(_) => {
+                pane.stale = true;
+                Ok(())
+            }
+            WindowEvent::RedrawRequested => pane.draw(),
+            _ => Ok(()),
+        };
+        if let Err(e) = result {
+            self.fail(event_loop, e);
+        }
+    }
+
+    fn about_to_wait(&mut self, _event_loop: &ActiveEventLoop) {
+        for pane in self.panes.values() {
+            pane.window.request_redraw();
+        }
+    }
+}
+
+fn main() -> Result<()> {
+    env_logger::init();
+    let event_loop = EventLoop::new()?;
+    event_loop.set_control_flow(ControlFlow::Poll);
+    let mut app = TwoWindows::default();
+    event_loop.run_app(&mut app)?;
+    app.error.map_or(Ok(()), Err)
+}
@@ -0,0 +1,11 @@
+// shaders/fullscreen.vert – one triangle covering the screen, no vertex buffer
+#version 450
+
+// (0,0) at the top-left of the target, (1,1) at the bottom-right
+layout(location = 0) out vec2 out_uv;
+
+void main() {
+    vec2 uv = vec2((gl_VertexIndex << 1) & 2, gl_VertexIndex & 2);
+    out_uv = uv;
+    gl_Position = vec4(uv * 2.0 - 1.0, 0.0, 1.0);
+}
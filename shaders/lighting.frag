@@ -0,0 +1,13 @@
+// shaders/lighting.frag – second subpass of examples/subpasses.rs: reads the
+// G-buffer the first subpass wrote as an input attachment
+#version 450
+
+layout(input_attachment_index = 0, set = 0, binding = 0) uniform subpassInput gbuffer;
+
+layout(location = 0) in vec2 uv;
+layout(location = 0) out vec4 out_color;
+
+void main() {
+    // Stand-in for lighting: invert the geometry color, easy to check on readback
+    out_color = vec4(1.0 - subpassLoad(gbuffer).rgb, 1.0);
+}
@@ -0,0 +1,18 @@
+// shaders/mesh.vert – built-in mesh pipeline: per-vertex color, one transform
+#version 450
+
+layout(location = 0) in vec3 position;
+layout(location = 1) in vec3 in_color;
+
+layout(location = 0) out vec3 color;
+
+// First 64 of the 128 push constant bytes (`DrawContext::push_constants`)
+layout(push_constant) uniform Push {
+    mat4 transform;
+} push;
+
+void main() {
+    gl_Position = push.transform * vec4(position, 1.0);
+    gl_PointSize = 1.0; // Only read with Topology::PointList
+    color = in_color;
+}
@@ -0,0 +1,9 @@
+// shaders/outline.frag – one constant color, for stencil-masked outlines
+#version 450
+
+layout(location = 0) in vec3 color; // Unused, matches mesh.vert's output
+layout(location = 0) out vec4 out_color;
+
+void main() {
+    out_color = vec4(1.0, 0.6, 0.1, 1.0);
+}
@@ -0,0 +1,13 @@
+// shaders/pick.frag – object id for VulkanRenderer::pick (R32_UINT target)
+#version 450
+
+layout(location = 0) out uint out_id;
+
+// Bytes 64..68 of the push constants, after mesh.vert's transform
+layout(push_constant) uniform Push {
+    layout(offset = 64) uint id;
+} push;
+
+void main() {
+    out_id = push.id;
+}
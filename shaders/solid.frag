@@ -0,0 +1,9 @@
+// shaders/solid.frag – one constant color, for coverage checks of full-screen passes
+#version 450
+
+layout(location = 0) in vec2 uv;
+layout(location = 0) out vec4 out_color;
+
+void main() {
+    out_color = vec4(1.0, 0.5, 0.0, 1.0);
+}
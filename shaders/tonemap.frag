@@ -0,0 +1,31 @@
+// shaders/tonemap.frag – HDR input attachment → display range
+#version 450
+
+layout(input_attachment_index = 0, set = 0, binding = 0) uniform subpassInput hdr;
+
+// Matches TonemapPush in src/core/renderer/backend/vulkan/tonemap.rs
+layout(push_constant) uniform Push {
+    float exposure;
+    float gamma; // 1.0 for sRGB swapchains (hardware encodes), 2.2 otherwise
+    uint mode;   // TonemapMode: 0 = clamp, 1 = Reinhard, 2 = ACES
+} pc;
+
+layout(location = 0) out vec4 out_color;
+
+// Narkowicz 2015 fit of the ACES filmic curve
+vec3 aces(vec3 x) {
+    const float a = 2.51, b = 0.03, c = 2.43, d = 0.59, e = 0.14;
+    return clamp((x * (a * x + b)) / (x * (c * x + d) + e), 0.0, 1.0);
+}
+
+void main() {
+    vec3 color = subpassLoad(hdr).rgb * pc.exposure;
+    if (pc.mode == 1u) {
+        color = color / (1.0 + color);
+    } else if (pc.mode == 2u) {
+        color = aces(color);
+    } else {
+        color = clamp(color, 0.0, 1.0);
+    }
+    out_color = vec4(pow(color, vec3(1.0 / pc.gamma)), 1.0);
+}
@@ -1,24 +1,100 @@
 // src/app.rs
 
 use crate::core::renderer::api::Renderer;
-use crate::error::Result;
+use crate::core::renderer::backend::vulkan::{DrawCallback, DrawContext};
+use crate::core::renderer::config::RendererConfig;
+use crate::core::renderer::log_targets::APP;
+use crate::core::renderer::pacing::{Visibility, control_flow};
+use crate::core::window::{WindowConfig, nth_monitor};
+use crate::error::{AppError, Result};
+use log::{error, warn};
+use std::time::Instant;
 use winit::{
     application::ApplicationHandler,
-    event::WindowEvent,
+    event::{ElementState, StartCause, WindowEvent},
     event_loop::{ActiveEventLoop, ControlFlow, EventLoop},
-    window::{WindowAttributes, WindowId},
+    keyboard::{Key, ModifiersState, NamedKey},
+    monitor::MonitorHandle,
+    window::{Fullscreen, Window, WindowId},
 };
 
-pub struct App<R: Renderer + Default> {
+pub struct App<R: Renderer> {
     renderer: R,
-    window: Option<winit::window::Window>,
+    window_config: WindowConfig, // Attributes of the window created on `resumed`
+    window: Option<Window>,
+    modifiers: ModifiersState,  // Held modifier keys, for Alt+Enter
+    visibility: Visibility,     // Minimized / occluded: rendering is paused
+    redraw_at: Option<Instant>, // Earliest scheduled redraw (RedrawMode::OnDemand)
+    error: Option<AppError>,    // First failure in a callback, returned by `run`
 }
 
-impl<R: Renderer + Default> ApplicationHandler for App<R> {
+/// Switch `window` between borderless fullscreen on its current monitor and windowed.
+/// The `Resized` event that follows makes the renderer recreate its swapchain.
+pub fn set_fullscreen(window: &Window, enabled: bool) {
+    window.set_fullscreen(enabled.then_some(Fullscreen::Borderless(None)));
+}
+
+/// Monitors `window` can go fullscreen on, in the order `set_fullscreen_on` and
+/// `WindowConfig::fullscreen_monitor` index them.
+pub fn monitors(window: &Window) -> Vec<MonitorHandle> {
+    window.available_monitors().collect()
+}
+
+/// Fullscreen on monitor `monitor` (index into `monitors`): exclusive in video
+/// mode `video_mode` (index into that monitor's `video_modes()`) or borderless
+/// without one. Returns false and changes nothing if an index is out of range.
+/// The `Resized` event that follows makes the renderer recreate its swapchain.
+pub fn set_fullscreen_on(window: &Window, monitor: usize, video_mode: Option<usize>) -> bool {
+    let Some(handle) = nth_monitor(window.available_monitors(), Some(monitor)) else {
+        return false;
+    };
+    let fullscreen = match video_mode {
+        None => Fullscreen::Borderless(Some(handle)),
+        Some(index) => match handle.video_modes().nth(index) {
+            Some(mode) => Fullscreen::Exclusive(mode),
+            None => return false,
+        },
+    };
+    window.set_fullscreen(Some(fullscreen));
+    true
+}
+
+/// Whether `event` is an Alt+Enter press, the usual fullscreen toggle.
+pub fn is_fullscreen_toggle(event: &WindowEvent, modifiers: ModifiersState) -> bool {
+    matches!(
+        event,
+        WindowEvent::KeyboardInput { event: key, .. }
+            if key.state == ElementState::Pressed
+                && !key.repeat
+                && key.logical_key == Key::Named(NamedKey::Enter)
+                && modifiers.alt_key()
+    )
+}
+
+impl<R: Renderer> ApplicationHandler for App<R> {
     fn resumed(&mut self, event_loop: &ActiveEventLoop) {
-        let window = event_loop
-            .create_window(WindowAttributes::default())
-            .expect("Failed to create window");
+        let config = &self.window_config;
+        let monitors: Vec<MonitorHandle> = event_loop.available_monitors().collect();
+        if let Some(index) = config.fullscreen_monitor
+            && index >= monitors.len()
+        {
+            warn!(
+                target: APP,
+                "No monitor {index} ({} found), using the current one",
+                monitors.len()
+            );
+        }
+        let attributes = config
+            .attributes()
+            .with_fullscreen(config.fullscreen_on(monitors));
+        let window = match event_loop.create_window(attributes) {
+            Ok(window) => window,
+            Err(e) => return self.fail(event_loop, e.into()),
+        };
+        // Off unless asked for, so key handling isn't routed through the IME
+        if self.window_config.ime {
+            window.set_ime_allowed(true);
+        }
 
         // keep window alive in App
         self.window = Some(window);
@@ -26,26 +102,302 @@ impl<R: Renderer + Default> ApplicationHandler for App<R> {
         // Safe to unwrap because we just set it
         let window_ref = self.window.as_ref().unwrap();
 
-        self.renderer
-            .initialize(window_ref, event_loop)
-            .expect("Renderer initialization failed");
+        if let Err(e) = self.renderer.initialize(window_ref, event_loop) {
+            self.fail(event_loop, e);
+        }
     }
 
     fn window_event(&mut self, event_loop: &ActiveEventLoop, id: WindowId, event: WindowEvent) {
+        if let WindowEvent::ModifiersChanged(modifiers) = &event {
+            self.modifiers = modifiers.state();
+        }
+        if is_fullscreen_toggle(&event, self.modifiers) {
+            self.set_fullscreen(!self.is_fullscreen());
+        }
+
         self.renderer.window_event(event_loop, id, &event);
+        self.dispatch_input(&event);
+        // window_event can't return errors (e.g. a failed resize), so the renderer parks them
+        if let Some(e) = self.renderer.take_error() {
+            return self.fail(event_loop, e);
+        }
+
+        // Hidden windows don't render (saves power); the frame loop stands still
+        if let Some(hidden) = self.visibility.update(&event)
+            && self.renderer.is_initialized()
+        {
+            if hidden {
+                self.renderer.pause();
+            } else if let Err(e) = self.renderer.resume() {
+                return self.fail(event_loop, e);
+            }
+        }
+
+        if matches!(event, WindowEvent::RedrawRequested)
+            && let Err(e) = self.redraw()
+        {
+            self.fail(event_loop, e);
+        }
+    }
+
+    fn new_events(&mut self, _event_loop: &ActiveEventLoop, cause: StartCause) {
+        // A `request_redraw_at` deadline passed: draw the frame it was for
+        if let StartCause::ResumeTimeReached { .. } = cause {
+            self.redraw_at = None;
+            if let Some(window) = &self.window {
+                window.request_redraw();
+            }
+        }
+    }
+
+    fn about_to_wait(&mut self, event_loop: &ActiveEventLoop) {
+        if let Some(at) = self.renderer.next_redraw() {
+            self.request_redraw_at(at);
+        }
+        // Hidden: sleep until the next event; on demand: until the next deadline
+        let hidden = self.visibility.hidden();
+        let flow = control_flow(hidden, self.window_config.redraw, self.redraw_at);
+        event_loop.set_control_flow(flow);
+        // Continuous rendering: ask for the next frame as soon as events are drained
+        if flow == ControlFlow::Poll
+            && let Some(window) = &self.window
+        {
+            window.request_redraw();
+        }
     }
 }
 
-impl<R: Renderer + Default> App<R> {
-    pub fn run() -> Result<()> {
-        let mut app = App {
-            renderer: R::default(),
+impl<R: Renderer> App<R> {
+    fn new(renderer: R, window_config: WindowConfig) -> Self {
+        Self {
+            renderer,
+            window_config,
             window: None,
+            modifiers: ModifiersState::empty(),
+            visibility: Visibility::default(),
+            redraw_at: None,
+            error: None,
+        }
+    }
+
+    /// Draw a frame, if there is anything to draw into: not before `initialize`
+    /// succeeded (it may have failed) and not while the window is hidden.
+    fn redraw(&mut self) -> Result<()> {
+        if !self.renderer.is_initialized() || self.visibility.hidden() {
+            return Ok(());
+        }
+        self.renderer.render()
+    }
+
+    /// Forward input and resize events to the renderer's `on_*` hooks.
+    fn dispatch_input(&mut self, event: &WindowEvent) {
+        let Some(window) = &self.window else {
+            return;
         };
+        let renderer = &mut self.renderer;
+        match event {
+            WindowEvent::KeyboardInput { event, .. } => renderer.on_key(window, event),
+            WindowEvent::CursorMoved { position, .. } => {
+                renderer.on_cursor_moved(window, *position)
+            }
+            WindowEvent::MouseInput { state, button, .. } => {
+                renderer.on_mouse_button(window, *button, *state)
+            }
+            WindowEvent::MouseWheel { delta, .. } => renderer.on_scroll(window, *delta),
+            WindowEvent::Ime(ime) => renderer.on_ime(ime.clone()),
+            WindowEvent::DroppedFile(path) => renderer.on_file_dropped(path.clone()),
+            WindowEvent::HoveredFile(path) => renderer.on_file_hovered(path.clone()),
+            WindowEvent::Resized(size) if renderer.is_initialized() => {
+                renderer.on_resize(window, *size)
+            }
+            _ => {}
+        }
+    }
 
+    /// Record `e` (only the first one is kept, later ones are usually fallout)
+    /// and stop the event loop.
+    fn fail(&mut self, event_loop: &ActiveEventLoop, e: AppError) {
+        self.record(e);
+        event_loop.exit();
+    }
+
+    fn record(&mut self, e: AppError) {
+        error!(target: APP, "{e}");
+        self.error.get_or_insert(e);
+    }
+
+    /// Wake up and draw a frame at `at` (the earliest request wins), from
+    /// `Renderer::next_redraw`. Only matters with `RedrawMode::OnDemand`, where
+    /// the event loop otherwise sleeps until the next event.
+    fn request_redraw_at(&mut self, at: Instant) {
+        self.redraw_at = Some(self.redraw_at.map_or(at, |current| current.min(at)));
+    }
+
+    /// Switch between borderless fullscreen and windowed, on Alt+Enter.
+    fn set_fullscreen(&mut self, enabled: bool) {
+        if let Some(window) = &self.window {
+            set_fullscreen(window, enabled);
+        }
+    }
+
+    fn is_fullscreen(&self) -> bool {
+        self.window
+            .as_ref()
+            .is_some_and(|window| window.fullscreen().is_some())
+    }
+
+    /// First error raised by the app or the renderer, if any.
+    fn take_error(&mut self) -> Option<AppError> {
+        self.error.take().or_else(|| self.renderer.take_error())
+    }
+
+    /// Run with the default window and renderer configuration.
+    pub fn run() -> Result<()> {
+        Self::run_with(WindowConfig::default(), RendererConfig::default())
+    }
+
+    /// Run with a config, usually produced by `RendererBuilder::build`, in a
+    /// default window.
+    pub fn run_with_config(config: RendererConfig) -> Result<()> {
+        Self::run_with(WindowConfig::default(), config)
+    }
+
+    /// Run in a window described by `window`, rendering with `renderer`.
+    /// Returns the first error that stopped the event loop, if any.
+    pub fn run_with(window: WindowConfig, renderer: RendererConfig) -> Result<()> {
+        window.validate()?;
+        renderer.validate()?;
+        Self::run_renderer(R::new(renderer), window)
+    }
+
+    fn run_renderer(renderer: R, window_config: WindowConfig) -> Result<()> {
+        let mut app = App::new(renderer, window_config);
         let event_loop = EventLoop::new()?;
         event_loop.set_control_flow(ControlFlow::Poll);
         event_loop.run_app(&mut app)?;
-        Ok(())
+        app.into_result()
+    }
+
+    /// What `run` returns once the event loop is done.
+    fn into_result(mut self) -> Result<()> {
+        self.take_error().map_or(Ok(()), Err)
+    }
+}
+
+impl App<DrawCallback> {
+    /// Run in a window described by `window`, with `draw` filling every frame
+    /// instead of a custom `Renderer`:
+    ///
+    /// ```ignore
+    /// App::run_with_draw(WindowConfig::default(), config, move |ctx: &mut DrawContext| {
+    ///     ctx.clear_color([0.1, 0.1, 0.1, 1.0]);
+    ///     ctx.push_constants(&transform)?;
+    ///     ctx.draw_mesh(&mesh);
+    ///     Ok(())
+    /// })
+    /// ```
+    ///
+    /// The first error returned by `draw` stops the event loop and is returned here.
+    pub fn run_with_draw(
+        window: WindowConfig,
+        config: RendererConfig,
+        draw: impl FnMut(&mut DrawContext<'_>) -> Result<()> + 'static,
+    ) -> Result<()> {
+        window.validate()?;
+        config.validate()?;
+        let renderer = DrawCallback::with_callback(config, draw);
+        Self::run_renderer(renderer, window)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Initialized from the start; `render` fails with `Config(message)`.
+    struct Failing {
+        message: &'static str,
+        parked: Option<AppError>, // Error `window_event` would have parked
+        frames: usize,
+    }
+
+    impl Renderer for Failing {
+        fn new(_config: RendererConfig) -> Self {
+            Self {
+                message: "render failed",
+                parked: None,
+                frames: 0,
+            }
+        }
+
+        fn initialize(&mut self, _window: &Window, _event_loop: &ActiveEventLoop) -> Result<()> {
+            Ok(())
+        }
+
+        fn is_initialized(&self) -> bool {
+            true
+        }
+
+        fn window_event(&mut self, _: &ActiveEventLoop, _: WindowId, _: &WindowEvent) {}
+
+        fn render(&mut self) -> Result<()> {
+            self.frames += 1;
+            Err(AppError::Config(self.message.into()))
+        }
+
+        fn take_error(&mut self) -> Option<AppError> {
+            self.parked.take()
+        }
+    }
+
+    fn app() -> App<Failing> {
+        App::new(
+            Failing::new(RendererConfig::default()),
+            WindowConfig::default(),
+        )
+    }
+
+    /// One frame the way `window_event` draws it on `RedrawRequested`.
+    fn redraw(app: &mut App<Failing>) {
+        if let Err(e) = app.redraw() {
+            app.record(e);
+        }
+    }
+
+    #[test]
+    fn render_error_is_returned_by_run() {
+        let mut app = app();
+        redraw(&mut app);
+        let result = app.into_result();
+        assert!(
+            matches!(&result, Err(AppError::Config(m)) if m == "render failed"),
+            "{result:?}"
+        );
+    }
+
+    #[test]
+    fn first_error_wins() {
+        let mut app = app();
+        redraw(&mut app);
+        app.renderer.message = "fallout";
+        redraw(&mut app);
+        assert_eq!(app.renderer.frames, 2);
+        assert!(matches!(app.into_result(), Err(AppError::Config(m)) if m == "render failed"));
+    }
+
+    #[test]
+    fn parked_renderer_error_is_returned() {
+        let mut app = app();
+        app.renderer.parked = Some(AppError::NotInitialized);
+        assert!(matches!(app.into_result(), Err(AppError::NotInitialized)));
+    }
+
+    #[test]
+    fn hidden_windows_are_not_drawn() {
+        let mut app = app();
+        app.visibility.update(&WindowEvent::Occluded(true));
+        redraw(&mut app);
+        assert_eq!(app.renderer.frames, 0);
+        assert!(app.into_result().is_ok());
     }
 }
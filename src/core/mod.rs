@@ -1 +1,3 @@
 pub mod renderer;
+#[cfg(feature = "vulkan")]
+pub mod window;
@@ -1,13 +1,108 @@
-use crate::error::Result;
-use winit::{event::WindowEvent, event_loop::ActiveEventLoop, window::Window, window::WindowId};
+// The `Renderer` trait drives a backend from winit and only exists with the
+// windowed `vulkan` feature; `HeadlessRenderer` has its own inherent API.
+#[cfg(feature = "vulkan")]
+use crate::core::renderer::config::RendererConfig;
+#[cfg(feature = "vulkan")]
+use crate::error::{AppError, Result};
+#[cfg(feature = "vulkan")]
+use std::path::PathBuf;
+#[cfg(feature = "vulkan")]
+use std::time::Instant;
+#[cfg(feature = "vulkan")]
+use winit::dpi::{PhysicalPosition, PhysicalSize};
+#[cfg(feature = "vulkan")]
+use winit::event::{ElementState, KeyEvent, MouseButton, MouseScrollDelta, WindowEvent};
 
+/// Input method event: composition (preedit) updates and committed text.
+#[cfg(feature = "vulkan")]
+pub use winit::event::Ime as ImeEvent;
+#[cfg(feature = "vulkan")]
+use winit::{event_loop::ActiveEventLoop, window::Window, window::WindowId};
+
+/// Called when a device allocation fails with ERROR_OUT_OF_DEVICE_MEMORY. Return
+/// true after freeing something (dropping textures, buffers) to have the
+/// allocation retried once; false surfaces the error. Runs inside the failing
+/// allocation, so it must not create GPU resources itself.
+pub type MemoryPressure = Box<dyn FnMut() -> bool + Send>;
+
+#[cfg(feature = "vulkan")]
 pub trait Renderer {
+    /// Construct an uninitialized renderer from a validated config.
+    fn new(config: RendererConfig) -> Self
+    where
+        Self: Sized;
+
     /// Initialize the renderer with window and event loop.
     fn initialize(&mut self, window: &Window, event_loop: &ActiveEventLoop) -> Result<()>;
 
+    /// Whether `initialize` has completed successfully (and no teardown happened since).
+    /// `App` only renders and forwards resizes once this is true.
+    fn is_initialized(&self) -> bool;
+
     /// Handle window events (resize, close, etc).
     fn window_event(&mut self, event_loop: &ActiveEventLoop, id: WindowId, event: &WindowEvent);
 
-    /// Draw a frame (stub for now, you can expand later).
+    /// Record, submit and present one frame. `App` calls it whenever the frame
+    /// loop (`RedrawMode`, frame cap) wants a frame and the window isn't paused.
     fn render(&mut self) -> Result<()>;
+
+    /// The window went out of sight (minimized or occluded). `App` stops calling
+    /// `render` until `resume`; frame state shouldn't advance meanwhile.
+    fn pause(&mut self) {}
+
+    /// The window is visible again. The surface may have changed while hidden,
+    /// so this is the place to check the swapchain.
+    fn resume(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    /// Whether `pause` is in effect.
+    fn is_paused(&self) -> bool {
+        false
+    }
+
+    /// Install the out-of-memory hook, replacing any previous one. Backends
+    /// without eviction support drop it.
+    fn set_memory_pressure(&mut self, _callback: MemoryPressure) {}
+
+    /// When the next frame is wanted, e.g. the next step of an animation. With
+    /// `RedrawMode::OnDemand`, `App` sleeps until then (or the next event)
+    /// instead of spinning; continuous rendering ignores it.
+    fn next_redraw(&self) -> Option<Instant> {
+        None
+    }
+
+    /// Error raised in a callback that can't return one (`window_event`), if any.
+    /// `App` polls this after every event and stops on the first error.
+    fn take_error(&mut self) -> Option<AppError> {
+        None
+    }
+
+    // Input hooks, called by `App` after `window_event`. All default to no-ops.
+
+    /// Key press/release (including repeats, see `event.repeat`).
+    fn on_key(&mut self, _window: &Window, _event: &KeyEvent) {}
+
+    /// Cursor moved, in physical pixels from the top-left of the window.
+    fn on_cursor_moved(&mut self, _window: &Window, _position: PhysicalPosition<f64>) {}
+
+    fn on_mouse_button(&mut self, _window: &Window, _button: MouseButton, _state: ElementState) {}
+
+    /// Wheel or touchpad scroll.
+    fn on_scroll(&mut self, _window: &Window, _delta: MouseScrollDelta) {}
+
+    /// New window size; the swapchain has already been recreated for it.
+    fn on_resize(&mut self, _window: &Window, _size: PhysicalSize<u32>) {}
+
+    /// Input method event, only sent with `WindowConfig::ime` on. Text typed
+    /// through an IME (CJK input, dead keys on some platforms) arrives as
+    /// `ImeEvent::Commit`, not as key events.
+    fn on_ime(&mut self, _event: ImeEvent) {}
+
+    /// A file was dropped onto the window. Dropping several files at once sends
+    /// one call per file, in the platform's order.
+    fn on_file_dropped(&mut self, _path: PathBuf) {}
+
+    /// A file is being dragged over the window; one call per file, like drops.
+    fn on_file_hovered(&mut self, _path: PathBuf) {}
 }
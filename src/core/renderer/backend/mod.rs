@@ -1,7 +1,153 @@
 // src/core/renderer/backend/mod.rs
-#[cfg(feature = "vulkan")]
+//
+// `vulkan` builds the windowed renderer; `headless` alone builds only the
+// offscreen parts of the Vulkan backend, without winit or any surface and
+// swapchain code.
+#[cfg(any(feature = "vulkan", feature = "headless"))]
 pub mod vulkan;
 
+#[cfg(feature = "vulkan")]
+use std::path::PathBuf;
+#[cfg(feature = "vulkan")]
+use std::time::Instant;
+
+#[cfg(feature = "vulkan")]
+use log::{info, warn};
+#[cfg(feature = "vulkan")]
+use winit::dpi::{PhysicalPosition, PhysicalSize};
+#[cfg(feature = "vulkan")]
+use winit::event::{ElementState, KeyEvent, MouseButton, MouseScrollDelta, WindowEvent};
+#[cfg(feature = "vulkan")]
+use winit::{event_loop::ActiveEventLoop, window::Window, window::WindowId};
+
+#[cfg(feature = "vulkan")]
+use crate::core::renderer::api::{ImeEvent, MemoryPressure, Renderer};
+#[cfg(feature = "vulkan")]
+use crate::core::renderer::config::RendererConfig;
+#[cfg(feature = "vulkan")]
+use crate::core::renderer::log_targets::RENDERER;
+#[cfg(feature = "vulkan")]
+use crate::error::{AppError, Result};
+
 // Re-export the selected backend under a common name:
+#[cfg(all(feature = "headless", not(feature = "vulkan")))]
+pub use vulkan::HeadlessRenderer as SelectedRenderer;
 #[cfg(feature = "vulkan")]
 pub use vulkan::VulkanRenderer as SelectedRenderer;
+
+/// Env var picking the backend at runtime, e.g. `WOLF_BACKEND=vulkan`.
+pub const BACKEND_ENV: &str = "WOLF_BACKEND";
+
+/// Backends compiled into this build; the first one is the default.
+pub const AVAILABLE_BACKENDS: &[&str] = &[
+    #[cfg(feature = "vulkan")]
+    "vulkan",
+];
+
+/// Backend named by `WOLF_BACKEND`, or the compile-time default when it's unset.
+/// Unknown or not-compiled-in names fall back to the default with a warning.
+#[cfg(feature = "vulkan")]
+pub fn create_selected(config: RendererConfig) -> Box<dyn Renderer> {
+    let requested = std::env::var(BACKEND_ENV).ok();
+    create_named(requested.as_deref(), config)
+}
+
+/// Backend called `name` (case-insensitive), `None` meaning the default.
+#[cfg(feature = "vulkan")]
+pub fn create_named(name: Option<&str>, config: RendererConfig) -> Box<dyn Renderer> {
+    let name = name.map(|n| n.trim().to_ascii_lowercase());
+    match name.as_deref() {
+        None | Some("") => {}
+        #[cfg(feature = "vulkan")]
+        Some("vulkan") => {
+            info!(target: RENDERER, "✅ Backend: vulkan ({BACKEND_ENV})");
+            return Box::new(vulkan::VulkanRenderer::new(config));
+        }
+        Some(other) => warn!(
+            target: RENDERER,
+            "{BACKEND_ENV}={other} is not available (compiled in: {}), using the default",
+            AVAILABLE_BACKENDS.join(", ")
+        ),
+    }
+    Box::new(SelectedRenderer::new(config))
+}
+
+/// Lets `App<Box<dyn Renderer>>` pick its backend at runtime through `create_selected`.
+#[cfg(feature = "vulkan")]
+impl Renderer for Box<dyn Renderer> {
+    fn new(config: RendererConfig) -> Self {
+        create_selected(config)
+    }
+
+    fn initialize(&mut self, window: &Window, event_loop: &ActiveEventLoop) -> Result<()> {
+        (**self).initialize(window, event_loop)
+    }
+
+    fn is_initialized(&self) -> bool {
+        (**self).is_initialized()
+    }
+
+    fn window_event(&mut self, event_loop: &ActiveEventLoop, id: WindowId, event: &WindowEvent) {
+        (**self).window_event(event_loop, id, event)
+    }
+
+    fn render(&mut self) -> Result<()> {
+        (**self).render()
+    }
+
+    fn pause(&mut self) {
+        (**self).pause()
+    }
+
+    fn resume(&mut self) -> Result<()> {
+        (**self).resume()
+    }
+
+    fn is_paused(&self) -> bool {
+        (**self).is_paused()
+    }
+
+    fn set_memory_pressure(&mut self, callback: MemoryPressure) {
+        (**self).set_memory_pressure(callback)
+    }
+
+    fn next_redraw(&self) -> Option<Instant> {
+        (**self).next_redraw()
+    }
+
+    fn take_error(&mut self) -> Option<AppError> {
+        (**self).take_error()
+    }
+
+    fn on_key(&mut self, window: &Window, event: &KeyEvent) {
+        (**self).on_key(window, event)
+    }
+
+    fn on_cursor_moved(&mut self, window: &Window, position: PhysicalPosition<f64>) {
+        (**self).on_cursor_moved(window, position)
+    }
+
+    fn on_mouse_button(&mut self, window: &Window, button: MouseButton, state: ElementState) {
+        (**self).on_mouse_button(window, button, state)
+    }
+
+    fn on_scroll(&mut self, window: &Window, delta: MouseScrollDelta) {
+        (**self).on_scroll(window, delta)
+    }
+
+    fn on_resize(&mut self, window: &Window, size: PhysicalSize<u32>) {
+        (**self).on_resize(window, size)
+    }
+
+    fn on_ime(&mut self, event: ImeEvent) {
+        (**self).on_ime(event)
+    }
+
+    fn on_file_dropped(&mut self, path: PathBuf) {
+        (**self).on_file_dropped(path)
+    }
+
+    fn on_file_hovered(&mut self, path: PathBuf) {
+        (**self).on_file_hovered(path)
+    }
+}
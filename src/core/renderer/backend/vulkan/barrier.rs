@@ -0,0 +1,355 @@
+// src/core/renderer/backend/vulkan/barrier.rs
+use vulkanalia::prelude::v1_0::*;
+
+use crate::error::{AppError, Result};
+
+/// Source and destination scopes of one pipeline barrier.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BarrierMasks {
+    pub src_stage: vk::PipelineStageFlags,
+    pub src_access: vk::AccessFlags,
+    pub dst_stage: vk::PipelineStageFlags,
+    pub dst_access: vk::AccessFlags,
+}
+
+/// Stage/access masks for the image layout transitions the engine performs.
+/// Anything else is an error: a guessed mask either races or stalls the GPU.
+pub fn transition_masks(old: vk::ImageLayout, new: vk::ImageLayout) -> Result<BarrierMasks> {
+    use vulkanalia::vk::AccessFlags as A;
+    use vulkanalia::vk::ImageLayout as L;
+    use vulkanalia::vk::PipelineStageFlags as S;
+
+    let (src_stage, src_access, dst_stage, dst_access) = match (old, new) {
+        // Fresh images: nothing to wait for, contents discarded
+        (L::UNDEFINED, L::TRANSFER_DST_OPTIMAL) => {
+            (S::TOP_OF_PIPE, A::empty(), S::TRANSFER, A::TRANSFER_WRITE)
+        }
+        (L::UNDEFINED, L::COLOR_ATTACHMENT_OPTIMAL) => (
+            S::TOP_OF_PIPE,
+            A::empty(),
+            S::COLOR_ATTACHMENT_OUTPUT,
+            A::COLOR_ATTACHMENT_READ | A::COLOR_ATTACHMENT_WRITE,
+        ),
+        (L::UNDEFINED, L::DEPTH_STENCIL_ATTACHMENT_OPTIMAL) => (
+            S::TOP_OF_PIPE,
+            A::empty(),
+            S::EARLY_FRAGMENT_TESTS,
+            A::DEPTH_STENCIL_ATTACHMENT_READ | A::DEPTH_STENCIL_ATTACHMENT_WRITE,
+        ),
+        (L::UNDEFINED, L::GENERAL) => (
+            S::TOP_OF_PIPE,
+            A::empty(),
+            S::COMPUTE_SHADER,
+            A::SHADER_READ | A::SHADER_WRITE,
+        ),
+
+        // Uploads and mip generation
+        (L::TRANSFER_DST_OPTIMAL, L::SHADER_READ_ONLY_OPTIMAL) => (
+            S::TRANSFER,
+            A::TRANSFER_WRITE,
+            S::FRAGMENT_SHADER,
+            A::SHADER_READ,
+        ),
+        (L::TRANSFER_DST_OPTIMAL, L::TRANSFER_SRC_OPTIMAL) => (
+            S::TRANSFER,
+            A::TRANSFER_WRITE,
+            S::TRANSFER,
+            A::TRANSFER_READ,
+        ),
+        (L::TRANSFER_SRC_OPTIMAL, L::SHADER_READ_ONLY_OPTIMAL) => (
+            S::TRANSFER,
+            A::TRANSFER_READ,
+            S::FRAGMENT_SHADER,
+            A::SHADER_READ,
+        ),
+
+        // Render-to-texture and compute output
+        (L::COLOR_ATTACHMENT_OPTIMAL, L::SHADER_READ_ONLY_OPTIMAL) => (
+            S::COLOR_ATTACHMENT_OUTPUT,
+            A::COLOR_ATTACHMENT_WRITE,
+            S::FRAGMENT_SHADER,
+            A::SHADER_READ,
+        ),
+        (L::SHADER_READ_ONLY_OPTIMAL, L::COLOR_ATTACHMENT_OPTIMAL) => (
+            S::FRAGMENT_SHADER,
+            A::SHADER_READ,
+            S::COLOR_ATTACHMENT_OUTPUT,
+            A::COLOR_ATTACHMENT_WRITE,
+        ),
+        (L::GENERAL, L::SHADER_READ_ONLY_OPTIMAL) => (
+            S::COMPUTE_SHADER,
+            A::SHADER_WRITE,
+            S::FRAGMENT_SHADER,
+            A::SHADER_READ,
+        ),
+        // Storage images: back-to-back dispatches, blit to the swapchain, reuse
+        (L::GENERAL, L::GENERAL) => (
+            S::COMPUTE_SHADER,
+            A::SHADER_WRITE,
+            S::COMPUTE_SHADER,
+            A::SHADER_READ | A::SHADER_WRITE,
+        ),
+        (L::GENERAL, L::TRANSFER_SRC_OPTIMAL) => (
+            S::COMPUTE_SHADER,
+            A::SHADER_WRITE,
+            S::TRANSFER,
+            A::TRANSFER_READ,
+        ),
+        // Write-after-read: only the blit has to finish
+        (L::TRANSFER_SRC_OPTIMAL, L::GENERAL) => (
+            S::TRANSFER,
+            A::empty(),
+            S::COMPUTE_SHADER,
+            A::SHADER_READ | A::SHADER_WRITE,
+        ),
+
+        // Readback (screenshots) of rendered or presentable images
+        (L::COLOR_ATTACHMENT_OPTIMAL | L::PRESENT_SRC_KHR, L::TRANSFER_SRC_OPTIMAL) => (
+            S::COLOR_ATTACHMENT_OUTPUT,
+            A::COLOR_ATTACHMENT_WRITE,
+            S::TRANSFER,
+            A::TRANSFER_READ,
+        ),
+        (L::TRANSFER_SRC_OPTIMAL, L::PRESENT_SRC_KHR) => {
+            // Presentation is ordered by semaphores; only the layout change matters
+            (S::TRANSFER, A::TRANSFER_READ, S::BOTTOM_OF_PIPE, A::empty())
+        }
+
+        // Blits onto the presentable image (compute output)
+        (L::PRESENT_SRC_KHR, L::TRANSFER_DST_OPTIMAL) => (
+            S::COLOR_ATTACHMENT_OUTPUT,
+            A::COLOR_ATTACHMENT_WRITE,
+            S::TRANSFER,
+            A::TRANSFER_WRITE,
+        ),
+        (L::TRANSFER_DST_OPTIMAL, L::PRESENT_SRC_KHR) => (
+            S::TRANSFER,
+            A::TRANSFER_WRITE,
+            S::BOTTOM_OF_PIPE,
+            A::empty(),
+        ),
+
+        _ => {
+            return Err(AppError::Config(format!(
+                "unsupported image layout transition {old:?} -> {new:?}"
+            )));
+        }
+    };
+    Ok(BarrierMasks {
+        src_stage,
+        src_access,
+        dst_stage,
+        dst_access,
+    })
+}
+
+/// Pipeline stages that perform the accesses in `access`.
+/// Empty access maps to `empty_stage` (TOP_OF_PIPE as source, BOTTOM_OF_PIPE as destination).
+fn stages_for_access(
+    access: vk::AccessFlags,
+    empty_stage: vk::PipelineStageFlags,
+) -> Result<vk::PipelineStageFlags> {
+    use vulkanalia::vk::AccessFlags as A;
+    use vulkanalia::vk::PipelineStageFlags as S;
+
+    if access.is_empty() {
+        return Ok(empty_stage);
+    }
+    let shaders = S::VERTEX_SHADER | S::FRAGMENT_SHADER | S::COMPUTE_SHADER;
+    let table = [
+        (A::TRANSFER_READ | A::TRANSFER_WRITE, S::TRANSFER),
+        (A::HOST_READ | A::HOST_WRITE, S::HOST),
+        (A::VERTEX_ATTRIBUTE_READ | A::INDEX_READ, S::VERTEX_INPUT),
+        (A::INDIRECT_COMMAND_READ, S::DRAW_INDIRECT),
+        (A::UNIFORM_READ | A::SHADER_READ | A::SHADER_WRITE, shaders),
+    ];
+
+    let mut stages = S::empty();
+    let mut handled = A::empty();
+    for (flags, stage) in table {
+        if access.intersects(flags) {
+            stages |= stage;
+            handled |= flags;
+        }
+    }
+    if !handled.contains(access) {
+        return Err(AppError::Config(format!(
+            "no stage mapping for buffer access {:?}",
+            access & !handled
+        )));
+    }
+    Ok(stages)
+}
+
+/// Stage/access masks for a buffer barrier between `src_access` and `dst_access`.
+pub fn buffer_masks(
+    src_access: vk::AccessFlags,
+    dst_access: vk::AccessFlags,
+) -> Result<BarrierMasks> {
+    Ok(BarrierMasks {
+        src_stage: stages_for_access(src_access, vk::PipelineStageFlags::TOP_OF_PIPE)?,
+        src_access,
+        dst_stage: stages_for_access(dst_access, vk::PipelineStageFlags::BOTTOM_OF_PIPE)?,
+        dst_access,
+    })
+}
+
+/// Record a layout transition of `subresource` of `image` into `cmd`.
+pub fn image_barrier(
+    device: &Device,
+    cmd: vk::CommandBuffer,
+    image: vk::Image,
+    old_layout: vk::ImageLayout,
+    new_layout: vk::ImageLayout,
+    subresource: vk::ImageSubresourceRange,
+) -> Result<()> {
+    let masks = transition_masks(old_layout, new_layout)?;
+    let barrier = vk::ImageMemoryBarrier::builder()
+        .old_layout(old_layout)
+        .new_layout(new_layout)
+        .src_queue_family_index(vk::QUEUE_FAMILY_IGNORED)
+        .dst_queue_family_index(vk::QUEUE_FAMILY_IGNORED)
+        .image(image)
+        .subresource_range(subresource)
+        .src_access_mask(masks.src_access)
+        .dst_access_mask(masks.dst_access);
+    unsafe {
+        device.cmd_pipeline_barrier(
+            cmd,
+            masks.src_stage,
+            masks.dst_stage,
+            vk::DependencyFlags::empty(),
+            &[] as &[vk::MemoryBarrier],
+            &[] as &[vk::BufferMemoryBarrier],
+            &[barrier],
+        );
+    }
+    Ok(())
+}
+
+/// Record a barrier making `src_access` writes to all of `buffer` visible to `dst_access`.
+pub fn buffer_barrier(
+    device: &Device,
+    cmd: vk::CommandBuffer,
+    buffer: vk::Buffer,
+    src_access: vk::AccessFlags,
+    dst_access: vk::AccessFlags,
+) -> Result<()> {
+    let masks = buffer_masks(src_access, dst_access)?;
+    let barrier = vk::BufferMemoryBarrier::builder()
+        .src_access_mask(src_access)
+        .dst_access_mask(dst_access)
+        .src_queue_family_index(vk::QUEUE_FAMILY_IGNORED)
+        .dst_queue_family_index(vk::QUEUE_FAMILY_IGNORED)
+        .buffer(buffer)
+        .offset(0)
+        .size(vk::WHOLE_SIZE);
+    unsafe {
+        device.cmd_pipeline_barrier(
+            cmd,
+            masks.src_stage,
+            masks.dst_stage,
+            vk::DependencyFlags::empty(),
+            &[] as &[vk::MemoryBarrier],
+            &[barrier],
+            &[] as &[vk::ImageMemoryBarrier],
+        );
+    }
+    Ok(())
+}
+
+/// Whole-image color subresource (mip 0, layer 0), the common case.
+pub fn color_subresource() -> vk::ImageSubresourceRange {
+    vk::ImageSubresourceRange::builder()
+        .aspect_mask(vk::ImageAspectFlags::COLOR)
+        .base_mip_level(0)
+        .level_count(1)
+        .base_array_layer(0)
+        .layer_count(1)
+        .build()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use vulkanalia::vk::AccessFlags as A;
+    use vulkanalia::vk::ImageLayout as L;
+    use vulkanalia::vk::PipelineStageFlags as S;
+
+    fn masks(src_stage: S, src_access: A, dst_stage: S, dst_access: A) -> BarrierMasks {
+        BarrierMasks {
+            src_stage,
+            src_access,
+            dst_stage,
+            dst_access,
+        }
+    }
+
+    #[test]
+    fn upload_transitions() {
+        assert_eq!(
+            transition_masks(L::UNDEFINED, L::TRANSFER_DST_OPTIMAL).unwrap(),
+            masks(S::TOP_OF_PIPE, A::empty(), S::TRANSFER, A::TRANSFER_WRITE)
+        );
+        assert_eq!(
+            transition_masks(L::TRANSFER_DST_OPTIMAL, L::SHADER_READ_ONLY_OPTIMAL).unwrap(),
+            masks(
+                S::TRANSFER,
+                A::TRANSFER_WRITE,
+                S::FRAGMENT_SHADER,
+                A::SHADER_READ
+            )
+        );
+    }
+
+    #[test]
+    fn attachment_transitions() {
+        assert_eq!(
+            transition_masks(L::UNDEFINED, L::DEPTH_STENCIL_ATTACHMENT_OPTIMAL).unwrap(),
+            masks(
+                S::TOP_OF_PIPE,
+                A::empty(),
+                S::EARLY_FRAGMENT_TESTS,
+                A::DEPTH_STENCIL_ATTACHMENT_READ | A::DEPTH_STENCIL_ATTACHMENT_WRITE
+            )
+        );
+        assert_eq!(
+            transition_masks(L::COLOR_ATTACHMENT_OPTIMAL, L::SHADER_READ_ONLY_OPTIMAL).unwrap(),
+            masks(
+                S::COLOR_ATTACHMENT_OUTPUT,
+                A::COLOR_ATTACHMENT_WRITE,
+                S::FRAGMENT_SHADER,
+                A::SHADER_READ
+            )
+        );
+    }
+
+    #[test]
+    fn presentable_images_end_at_bottom_of_pipe() {
+        for old in [L::TRANSFER_SRC_OPTIMAL, L::TRANSFER_DST_OPTIMAL] {
+            let present = transition_masks(old, L::PRESENT_SRC_KHR).unwrap();
+            assert_eq!(present.dst_stage, S::BOTTOM_OF_PIPE);
+            assert!(present.dst_access.is_empty());
+        }
+        // Readback of a presented image waits for its color writes
+        assert_eq!(
+            transition_masks(L::PRESENT_SRC_KHR, L::TRANSFER_SRC_OPTIMAL).unwrap(),
+            masks(
+                S::COLOR_ATTACHMENT_OUTPUT,
+                A::COLOR_ATTACHMENT_WRITE,
+                S::TRANSFER,
+                A::TRANSFER_READ
+            )
+        );
+    }
+
+    #[test]
+    fn unsupported_transition_is_an_error() {
+        let result = transition_masks(
+            L::SHADER_READ_ONLY_OPTIMAL,
+            L::DEPTH_STENCIL_ATTACHMENT_OPTIMAL,
+        );
+        assert!(matches!(result, Err(AppError::Config(_))));
+        assert!(transition_masks(L::PRESENT_SRC_KHR, L::UNDEFINED).is_err());
+    }
+}
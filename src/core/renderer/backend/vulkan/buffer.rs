@@ -0,0 +1,188 @@
+// src/core/renderer/backend/vulkan/buffer.rs
+use std::sync::Arc;
+
+use vulkanalia::prelude::v1_0::*;
+
+use super::context::DeviceContext;
+use super::image::find_memory_type;
+use super::tracker::ResourceKind;
+use crate::error::{AppError, Result};
+
+/// Buffer + its own memory allocation, destroyed on Drop.
+pub struct Buffer {
+    buffer: vk::Buffer,
+    memory: vk::DeviceMemory,
+    size: vk::DeviceSize,            // Requested size
+    allocation_size: vk::DeviceSize, // Bytes of `memory` (>= size)
+    properties: vk::MemoryPropertyFlags,
+    context: Arc<DeviceContext>,
+}
+
+impl Buffer {
+    /// On ERROR_OUT_OF_DEVICE_MEMORY the context's memory-pressure hook gets one
+    /// chance to free memory before the error is returned.
+    pub fn new(
+        context: &Arc<DeviceContext>,
+        size: vk::DeviceSize,
+        usage: vk::BufferUsageFlags,
+        properties: vk::MemoryPropertyFlags,
+    ) -> Result<Self> {
+        context.try_alloc(|| Self::allocate(context, size, usage, properties))
+    }
+
+    fn allocate(
+        context: &Arc<DeviceContext>,
+        size: vk::DeviceSize,
+        usage: vk::BufferUsageFlags,
+        properties: vk::MemoryPropertyFlags,
+    ) -> Result<Self> {
+        let device = &context.device;
+        let buffer_info = vk::BufferCreateInfo::builder()
+            .size(size)
+            .usage(usage)
+            .sharing_mode(vk::SharingMode::EXCLUSIVE);
+        let buffer = unsafe { device.create_buffer(&buffer_info, None) }
+            .map_err(|e| AppError::vk(e, "create buffer"))?;
+
+        let requirements = unsafe { device.get_buffer_memory_requirements(buffer) };
+        let memory = find_memory_type(
+            &context.instance,
+            context.physical_device,
+            requirements.memory_type_bits,
+            properties,
+        )
+        .and_then(|memory_type| {
+            let alloc_info = vk::MemoryAllocateInfo::builder()
+                .allocation_size(requirements.size)
+                .memory_type_index(memory_type);
+            unsafe { device.allocate_memory(&alloc_info, None) }
+                .map_err(|e| AppError::vk(e, "allocate buffer memory"))
+        });
+        let memory = match memory {
+            Ok(memory) => memory,
+            Err(e) => {
+                unsafe { device.destroy_buffer(buffer, None) };
+                return Err(e);
+            }
+        };
+        if let Err(e) = unsafe { device.bind_buffer_memory(buffer, memory, 0) } {
+            unsafe {
+                device.destroy_buffer(buffer, None);
+                device.free_memory(memory, None);
+            }
+            return Err(AppError::vk(e, "bind buffer memory"));
+        }
+
+        context.memory().record_alloc(requirements.size);
+        context.resources().created(ResourceKind::BUFFER);
+        Ok(Self {
+            buffer,
+            memory,
+            size,
+            allocation_size: requirements.size,
+            properties,
+            context: Arc::clone(context),
+        })
+    }
+
+    pub fn handle(&self) -> vk::Buffer {
+        self.buffer
+    }
+
+    pub fn size(&self) -> vk::DeviceSize {
+        self.size
+    }
+
+    /// Copy `bytes` to the start of a HOST_VISIBLE buffer.
+    pub fn write(&self, bytes: &[u8]) -> Result<()> {
+        if !self
+            .properties
+            .contains(vk::MemoryPropertyFlags::HOST_VISIBLE)
+        {
+            return Err(AppError::Config("buffer is not HOST_VISIBLE".into()));
+        }
+        if bytes.len() as vk::DeviceSize > self.size {
+            return Err(AppError::Config(format!(
+                "write of {} bytes into a {}-byte buffer",
+                bytes.len(),
+                self.size
+            )));
+        }
+        let device = &self.context.device;
+        unsafe {
+            let ptr = device
+                .map_memory(self.memory, 0, vk::WHOLE_SIZE, vk::MemoryMapFlags::empty())
+                .map_err(|e| AppError::vk(e, "map buffer memory"))?;
+            std::ptr::copy_nonoverlapping(bytes.as_ptr(), ptr.cast::<u8>(), bytes.len());
+            // Non-coherent memory needs an explicit flush to become visible to the GPU
+            if !self
+                .properties
+                .contains(vk::MemoryPropertyFlags::HOST_COHERENT)
+            {
+                let range = vk::MappedMemoryRange::builder()
+                    .memory(self.memory)
+                    .offset(0)
+                    .size(vk::WHOLE_SIZE);
+                if let Err(e) = device.flush_mapped_memory_ranges(&[range]) {
+                    device.unmap_memory(self.memory);
+                    return Err(AppError::vk(e, "flush buffer memory"));
+                }
+            }
+            device.unmap_memory(self.memory);
+        }
+        Ok(())
+    }
+
+    /// Copy the start of a HOST_VISIBLE buffer into `bytes` (GPU readback).
+    /// The GPU writes must be complete, e.g. after waiting on the submit's fence.
+    pub fn read(&self, bytes: &mut [u8]) -> Result<()> {
+        if !self
+            .properties
+            .contains(vk::MemoryPropertyFlags::HOST_VISIBLE)
+        {
+            return Err(AppError::Config("buffer is not HOST_VISIBLE".into()));
+        }
+        if bytes.len() as vk::DeviceSize > self.size {
+            return Err(AppError::Config(format!(
+                "read of {} bytes from a {}-byte buffer",
+                bytes.len(),
+                self.size
+            )));
+        }
+        let device = &self.context.device;
+        unsafe {
+            let ptr = device
+                .map_memory(self.memory, 0, vk::WHOLE_SIZE, vk::MemoryMapFlags::empty())
+                .map_err(|e| AppError::vk(e, "map buffer memory"))?;
+            // Non-coherent memory may hold stale cache lines until invalidated
+            if !self
+                .properties
+                .contains(vk::MemoryPropertyFlags::HOST_COHERENT)
+            {
+                let range = vk::MappedMemoryRange::builder()
+                    .memory(self.memory)
+                    .offset(0)
+                    .size(vk::WHOLE_SIZE);
+                if let Err(e) = device.invalidate_mapped_memory_ranges(&[range]) {
+                    device.unmap_memory(self.memory);
+                    return Err(AppError::vk(e, "invalidate buffer memory"));
+                }
+            }
+            std::ptr::copy_nonoverlapping(ptr.cast::<u8>(), bytes.as_mut_ptr(), bytes.len());
+            device.unmap_memory(self.memory);
+        }
+        Ok(())
+    }
+}
+
+impl Drop for Buffer {
+    // The caller must make sure no in-flight frame still reads the buffer
+    fn drop(&mut self) {
+        unsafe {
+            self.context.device.destroy_buffer(self.buffer, None);
+            self.context.device.free_memory(self.memory, None);
+        }
+        self.context.memory().record_free(self.allocation_size);
+        self.context.resources().destroyed(ResourceKind::BUFFER);
+    }
+}
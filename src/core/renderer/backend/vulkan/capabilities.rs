@@ -0,0 +1,431 @@
+// src/core/renderer/backend/vulkan/capabilities.rs
+use std::fmt;
+
+use vulkanalia::prelude::v1_0::*;
+
+use super::device_info::GpuReport;
+use crate::core::renderer::config::{CompositeAlpha, SurfaceTransform};
+use crate::error::{AppError, Result};
+
+/// Sample counts usable for a color + depth framebuffer, ascending (e.g. `[1, 2, 4]`).
+/// A count is only valid if both the color and the depth masks allow it.
+pub fn sample_counts_from_limits(limits: &vk::PhysicalDeviceLimits) -> Vec<u32> {
+    let supported = limits.framebuffer_color_sample_counts & limits.framebuffer_depth_sample_counts;
+
+    [
+        (vk::SampleCountFlags::_1, 1),
+        (vk::SampleCountFlags::_2, 2),
+        (vk::SampleCountFlags::_4, 4),
+        (vk::SampleCountFlags::_8, 8),
+        (vk::SampleCountFlags::_16, 16),
+        (vk::SampleCountFlags::_32, 32),
+        (vk::SampleCountFlags::_64, 64),
+    ]
+    .into_iter()
+    .filter(|(flag, _)| supported.contains(*flag))
+    .map(|(_, count)| count)
+    .collect()
+}
+
+/// Width actually usable for line rasterization: 1.0 without `wideLines`,
+/// otherwise clamped into the device's `lineWidthRange`.
+pub fn clamp_line_width(width: f32, range: [f32; 2], wide_lines: bool) -> f32 {
+    if !wide_lines || !width.is_finite() {
+        return 1.0;
+    }
+    width.clamp(range[0], range[1])
+}
+
+/// Depth formats in order of preference. D24_UNORM_S8_UINT goes last: MoltenVK
+/// (Apple GPUs) doesn't support it, while D32_SFLOAT is available nearly everywhere.
+pub const DEPTH_FORMAT_CANDIDATES: [vk::Format; 3] = [
+    vk::Format::D32_SFLOAT,
+    vk::Format::D32_SFLOAT_S8_UINT,
+    vk::Format::D24_UNORM_S8_UINT,
+];
+
+/// Every depth and depth-stencil format a device may offer: the
+/// `DEPTH_FORMAT_CANDIDATES` first, then the narrower ones.
+pub const DEPTH_FORMATS: [vk::Format; 6] = [
+    vk::Format::D32_SFLOAT,
+    vk::Format::D32_SFLOAT_S8_UINT,
+    vk::Format::D24_UNORM_S8_UINT,
+    vk::Format::X8_D24_UNORM_PACK32,
+    vk::Format::D16_UNORM,
+    vk::Format::D16_UNORM_S8_UINT,
+];
+
+/// The `candidates` usable as optimal-tiling depth attachments, in their order.
+/// `properties` as for `find_supported_format`.
+pub fn supported_depth_formats(
+    candidates: &[vk::Format],
+    properties: impl Fn(vk::Format) -> vk::FormatProperties,
+) -> Vec<vk::Format> {
+    let features = vk::FormatFeatureFlags::DEPTH_STENCIL_ATTACHMENT;
+    candidates
+        .iter()
+        .copied()
+        .filter(|&format| {
+            properties(format)
+                .optimal_tiling_features
+                .contains(features)
+        })
+        .collect()
+}
+
+/// First of `candidates` whose `tiling` features include all of `features`.
+/// `properties` looks up a format's properties, normally
+/// `get_physical_device_format_properties` for the chosen GPU.
+pub fn find_supported_format(
+    candidates: &[vk::Format],
+    tiling: vk::ImageTiling,
+    features: vk::FormatFeatureFlags,
+    properties: impl Fn(vk::Format) -> vk::FormatProperties,
+) -> Option<vk::Format> {
+    candidates.iter().copied().find(|&format| {
+        let props = properties(format);
+        match tiling {
+            vk::ImageTiling::LINEAR => props.linear_tiling_features.contains(features),
+            _ => props.optimal_tiling_features.contains(features),
+        }
+    })
+}
+
+/// What an offscreen target holds. Passes ask for the intent and `resolve`
+/// picks the first candidate format the GPU can render to, so format choice and
+/// fallbacks live in one place.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RenderTargetFormat {
+    Hdr,          // Scene color before tonemapping
+    Sdr,          // 8-bit color, e.g. UI layers or thumbnails
+    Depth,        // Depth buffers and shadow maps
+    DepthStencil, // Depth with a stencil aspect (`RendererConfig::stencil`)
+    Uint,         // One u32 per pixel, e.g. the picking id target
+}
+
+impl RenderTargetFormat {
+    /// Formats in order of preference.
+    pub fn candidates(self) -> &'static [vk::Format] {
+        match self {
+            // B10G11R11 has no alpha, which the scene color doesn't need
+            Self::Hdr => &[
+                vk::Format::R16G16B16A16_SFLOAT,
+                vk::Format::B10G11R11_UFLOAT_PACK32,
+                vk::Format::R32G32B32A32_SFLOAT,
+            ],
+            Self::Sdr => &[vk::Format::R8G8B8A8_UNORM, vk::Format::B8G8R8A8_UNORM],
+            Self::Depth => &DEPTH_FORMAT_CANDIDATES,
+            Self::DepthStencil => &DEPTH_FORMAT_CANDIDATES[1..],
+            Self::Uint => &[vk::Format::R32_UINT],
+        }
+    }
+
+    /// Optimal-tiling features a candidate needs to be used as the target.
+    pub fn features(self) -> vk::FormatFeatureFlags {
+        match self {
+            Self::Depth | Self::DepthStencil => vk::FormatFeatureFlags::DEPTH_STENCIL_ATTACHMENT,
+            Self::Hdr | Self::Sdr | Self::Uint => vk::FormatFeatureFlags::COLOR_ATTACHMENT,
+        }
+    }
+
+    /// First supported candidate; `properties` as for `find_supported_format`.
+    pub fn resolve(
+        self,
+        properties: impl Fn(vk::Format) -> vk::FormatProperties,
+    ) -> Result<vk::Format> {
+        let candidates = self.candidates();
+        find_supported_format(
+            candidates,
+            vk::ImageTiling::OPTIMAL,
+            self.features(),
+            properties,
+        )
+        .ok_or_else(|| {
+            AppError::Config(format!(
+                "none of the {self:?} target formats {candidates:?} is supported"
+            ))
+        })
+    }
+}
+
+/// Whether a depth format also carries a stencil aspect.
+pub fn has_stencil(format: vk::Format) -> bool {
+    matches!(
+        format,
+        vk::Format::D32_SFLOAT_S8_UINT
+            | vk::Format::D24_UNORM_S8_UINT
+            | vk::Format::D16_UNORM_S8_UINT
+            | vk::Format::S8_UINT
+    )
+}
+
+pub fn composite_alpha_flag(mode: CompositeAlpha) -> vk::CompositeAlphaFlagsKHR {
+    match mode {
+        CompositeAlpha::Opaque => vk::CompositeAlphaFlagsKHR::OPAQUE,
+        CompositeAlpha::PreMultiplied => vk::CompositeAlphaFlagsKHR::PRE_MULTIPLIED,
+        CompositeAlpha::PostMultiplied => vk::CompositeAlphaFlagsKHR::POST_MULTIPLIED,
+        CompositeAlpha::Inherit => vk::CompositeAlphaFlagsKHR::INHERIT,
+    }
+}
+
+/// Composite alpha flag for `requested`, or the first of `supported` when the
+/// surface can't do it (OPAQUE if it reports nothing at all).
+pub fn select_composite_alpha(
+    requested: CompositeAlpha,
+    supported: vk::CompositeAlphaFlagsKHR,
+) -> vk::CompositeAlphaFlagsKHR {
+    let wanted = composite_alpha_flag(requested);
+    if supported.contains(wanted) {
+        return wanted;
+    }
+    [
+        vk::CompositeAlphaFlagsKHR::OPAQUE,
+        vk::CompositeAlphaFlagsKHR::PRE_MULTIPLIED,
+        vk::CompositeAlphaFlagsKHR::POST_MULTIPLIED,
+        vk::CompositeAlphaFlagsKHR::INHERIT,
+    ]
+    .into_iter()
+    .find(|&flag| supported.contains(flag))
+    .unwrap_or(vk::CompositeAlphaFlagsKHR::OPAQUE)
+}
+
+pub fn surface_transform_flag(transform: SurfaceTransform) -> vk::SurfaceTransformFlagsKHR {
+    match transform {
+        SurfaceTransform::Identity => vk::SurfaceTransformFlagsKHR::IDENTITY,
+        SurfaceTransform::Rotate90 => vk::SurfaceTransformFlagsKHR::ROTATE_90,
+        SurfaceTransform::Rotate180 => vk::SurfaceTransformFlagsKHR::ROTATE_180,
+        SurfaceTransform::Rotate270 => vk::SurfaceTransformFlagsKHR::ROTATE_270,
+    }
+}
+
+/// Swapchain pre-transform: `forced` if the surface supports it, else the
+/// surface's `current` one.
+pub fn select_pre_transform(
+    forced: Option<SurfaceTransform>,
+    supported: vk::SurfaceTransformFlagsKHR,
+    current: vk::SurfaceTransformFlagsKHR,
+) -> vk::SurfaceTransformFlagsKHR {
+    forced
+        .map(surface_transform_flag)
+        .filter(|&flag| supported.contains(flag))
+        .unwrap_or(current)
+}
+
+/// Clockwise quarter turns of a rotation transform (mirrored ones count as 0).
+pub fn quarter_turns(transform: vk::SurfaceTransformFlagsKHR) -> u32 {
+    match transform {
+        vk::SurfaceTransformFlagsKHR::ROTATE_90 => 1,
+        vk::SurfaceTransformFlagsKHR::ROTATE_180 => 2,
+        vk::SurfaceTransformFlagsKHR::ROTATE_270 => 3,
+        _ => 0,
+    }
+}
+
+/// Quarter turns the content must be rotated by itself when presenting with
+/// `pre_transform` on a surface in the `current` orientation: zero unless the
+/// transform was forced to something else.
+pub fn content_turns(
+    pre_transform: vk::SurfaceTransformFlagsKHR,
+    current: vk::SurfaceTransformFlagsKHR,
+) -> u32 {
+    (quarter_turns(pre_transform) + 4 - quarter_turns(current)) % 4
+}
+
+/// Everything the renderer can do on its GPU and surface, queried once after
+/// `initialize` (`VulkanRenderer::capabilities`) so user code can pick settings
+/// without further Vulkan calls.
+#[derive(Debug, Clone)]
+pub struct Capabilities {
+    pub gpu: GpuReport,                 // Limits, features, present modes, formats
+    pub depth_formats: Vec<vk::Format>, // Usable DEPTH_FORMATS, preferred first
+}
+
+impl Capabilities {
+    pub fn query(
+        instance: &Instance,
+        physical_device: vk::PhysicalDevice,
+        props2: bool,
+        surface: Option<vk::SurfaceKHR>,
+    ) -> Self {
+        let depth_formats = supported_depth_formats(&DEPTH_FORMATS, |format| unsafe {
+            instance.get_physical_device_format_properties(physical_device, format)
+        });
+        Self {
+            gpu: GpuReport::query(instance, physical_device, props2, surface),
+            depth_formats,
+        }
+    }
+
+    /// Largest 2D texture (and render target) side, in pixels.
+    pub fn max_texture_size(&self) -> u32 {
+        self.gpu.max_image_dimension_2d
+    }
+
+    pub fn supports_sample_count(&self, samples: u32) -> bool {
+        self.gpu.sample_counts.contains(&samples)
+    }
+
+    pub fn supports_present_mode(&self, mode: vk::PresentModeKHR) -> bool {
+        self.gpu.present_modes.contains(&mode)
+    }
+
+    pub fn supports_surface_format(&self, format: vk::Format) -> bool {
+        self.gpu.surface_formats.iter().any(|f| f.format == format)
+    }
+
+    pub fn supports_depth_format(&self, format: vk::Format) -> bool {
+        self.depth_formats.contains(&format)
+    }
+
+    /// Check that the settings the renderer runs with appear in the snapshot;
+    /// a mismatch means the snapshot and the setup code disagree.
+    pub fn check_active(
+        &self,
+        swapchain_format: vk::Format,
+        depth_format: vk::Format,
+        msaa_samples: u32,
+    ) -> Result<()> {
+        if !self.supports_surface_format(swapchain_format) {
+            return Err(AppError::Config(format!(
+                "swapchain format {swapchain_format:?} missing from the surface formats"
+            )));
+        }
+        if !self.supports_depth_format(depth_format) {
+            return Err(AppError::Config(format!(
+                "depth format {depth_format:?} missing from {:?}",
+                self.depth_formats
+            )));
+        }
+        if !self.supports_sample_count(msaa_samples) {
+            return Err(AppError::Config(format!(
+                "{msaa_samples}x MSAA missing from {:?}",
+                self.gpu.sample_counts
+            )));
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Capabilities {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.gpu)?;
+        writeln!(f, "  Depth formats: {:?}", self.depth_formats)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Format properties with `depth` formats usable as optimal-tiling depth
+    /// attachments, and every other format only sampled.
+    fn properties(depth: &[vk::Format]) -> impl Fn(vk::Format) -> vk::FormatProperties + '_ {
+        move |format| {
+            let mut features = vk::FormatFeatureFlags::SAMPLED_IMAGE;
+            if depth.contains(&format) {
+                features |= vk::FormatFeatureFlags::DEPTH_STENCIL_ATTACHMENT;
+            }
+            vk::FormatProperties {
+                optimal_tiling_features: features,
+                // Linear tiling doesn't count
+                linear_tiling_features: vk::FormatFeatureFlags::DEPTH_STENCIL_ATTACHMENT,
+                ..Default::default()
+            }
+        }
+    }
+
+    #[test]
+    fn only_depth_attachment_formats_are_supported() {
+        let device = [vk::Format::D16_UNORM, vk::Format::D32_SFLOAT];
+        assert_eq!(
+            supported_depth_formats(&DEPTH_FORMATS, properties(&device)),
+            [vk::Format::D32_SFLOAT, vk::Format::D16_UNORM]
+        );
+        assert!(supported_depth_formats(&DEPTH_FORMATS, properties(&[])).is_empty());
+    }
+
+    #[test]
+    fn depth_formats_keep_the_candidate_order() {
+        let candidates = [vk::Format::D24_UNORM_S8_UINT, vk::Format::D32_SFLOAT];
+        assert_eq!(
+            supported_depth_formats(&candidates, properties(&DEPTH_FORMATS)),
+            candidates
+        );
+    }
+
+    fn limits(
+        color: vk::SampleCountFlags,
+        depth: vk::SampleCountFlags,
+    ) -> vk::PhysicalDeviceLimits {
+        vk::PhysicalDeviceLimits {
+            framebuffer_color_sample_counts: color,
+            framebuffer_depth_sample_counts: depth,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn sample_counts_need_both_color_and_depth() {
+        let color = vk::SampleCountFlags::_1
+            | vk::SampleCountFlags::_2
+            | vk::SampleCountFlags::_4
+            | vk::SampleCountFlags::_8;
+        let depth = vk::SampleCountFlags::_1 | vk::SampleCountFlags::_2 | vk::SampleCountFlags::_4;
+        assert_eq!(sample_counts_from_limits(&limits(color, depth)), [1, 2, 4]);
+        assert_eq!(sample_counts_from_limits(&limits(depth, color)), [1, 2, 4]);
+    }
+
+    #[test]
+    fn no_common_sample_count_gives_none() {
+        let counts = limits(vk::SampleCountFlags::_8, vk::SampleCountFlags::_4);
+        assert!(sample_counts_from_limits(&counts).is_empty());
+    }
+
+    const OPAQUE: vk::CompositeAlphaFlagsKHR = vk::CompositeAlphaFlagsKHR::OPAQUE;
+    const PRE: vk::CompositeAlphaFlagsKHR = vk::CompositeAlphaFlagsKHR::PRE_MULTIPLIED;
+    const POST: vk::CompositeAlphaFlagsKHR = vk::CompositeAlphaFlagsKHR::POST_MULTIPLIED;
+    const INHERIT: vk::CompositeAlphaFlagsKHR = vk::CompositeAlphaFlagsKHR::INHERIT;
+
+    #[test]
+    fn supported_composite_alpha_is_used_as_requested() {
+        let all = OPAQUE | PRE | POST | INHERIT;
+        assert_eq!(select_composite_alpha(CompositeAlpha::Opaque, all), OPAQUE);
+        assert_eq!(
+            select_composite_alpha(CompositeAlpha::PreMultiplied, all),
+            PRE
+        );
+        assert_eq!(
+            select_composite_alpha(CompositeAlpha::PostMultiplied, all),
+            POST
+        );
+        assert_eq!(
+            select_composite_alpha(CompositeAlpha::Inherit, all),
+            INHERIT
+        );
+    }
+
+    #[test]
+    fn unsupported_composite_alpha_falls_back_in_order() {
+        let requested = CompositeAlpha::PostMultiplied;
+        assert_eq!(select_composite_alpha(requested, PRE | OPAQUE), OPAQUE);
+        assert_eq!(select_composite_alpha(requested, INHERIT | PRE), PRE);
+        assert_eq!(
+            select_composite_alpha(CompositeAlpha::Opaque, INHERIT | POST),
+            POST
+        );
+    }
+
+    #[test]
+    fn inherit_only_surfaces_get_inherit() {
+        assert_eq!(
+            select_composite_alpha(CompositeAlpha::Opaque, INHERIT),
+            INHERIT
+        );
+        // Nothing reported at all: OPAQUE, which every surface must support
+        let none = vk::CompositeAlphaFlagsKHR::empty();
+        assert_eq!(
+            select_composite_alpha(CompositeAlpha::PreMultiplied, none),
+            OPAQUE
+        );
+    }
+}
@@ -0,0 +1,314 @@
+// src/core/renderer/backend/vulkan/compute.rs
+//
+// Compute work recorded ahead of the frame's render pass. A compute shader
+// writes one storage image (set 0, binding 0, `rgba8`), which can then be
+// blitted onto the swapchain image after the render pass:
+//   dispatch (GENERAL) -> TRANSFER_SRC -> blit -> swapchain PRESENT_SRC
+// Shaders are compiled ahead of time like the graphics ones:
+//   glslc shaders/mandelbrot.comp -o shaders/mandelbrot.comp.spv
+use std::path::Path;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicI32, Ordering};
+
+use vulkanalia::prelude::v1_0::*;
+use vulkanalia::vk::Handle;
+
+use super::barrier::{color_subresource, image_barrier};
+use super::context::DeviceContext;
+use super::descriptor::{DescriptorLayoutBuilder, write_storage_image};
+use super::image::ImageDesc;
+use super::mesh::MESH_PUSH_SIZE;
+use super::shader::{SpecializationMap, load_shader_module};
+use super::texture::Texture;
+use super::transfer::color_layers;
+use crate::error::{AppError, Result};
+
+/// Format of `StorageImage`: storage and blit-source support are both mandatory.
+pub const STORAGE_IMAGE_FORMAT: vk::Format = vk::Format::R8G8B8A8_UNORM;
+
+/// Set 0 of every compute pipeline: one storage image at binding 0. Identically
+/// defined layouts are compatible, so any `StorageImage` binds to any pipeline.
+fn storage_set_layout(device: &Device) -> Result<vk::DescriptorSetLayout> {
+    DescriptorLayoutBuilder::new()
+        .binding(
+            0,
+            vk::DescriptorType::STORAGE_IMAGE,
+            vk::ShaderStageFlags::COMPUTE,
+        )
+        .build(device)
+}
+
+struct PipelineInner {
+    context: Arc<DeviceContext>,
+    module: vk::ShaderModule,
+    set_layout: vk::DescriptorSetLayout,
+    layout: vk::PipelineLayout,
+    pipeline: vk::Pipeline,
+}
+
+impl Drop for PipelineInner {
+    // Null handles (from a failed `new`) are ignored by Vulkan
+    fn drop(&mut self) {
+        let device = &self.context.device;
+        unsafe {
+            device.destroy_pipeline(self.pipeline, None);
+            device.destroy_pipeline_layout(self.layout, None);
+            device.destroy_descriptor_set_layout(self.set_layout, None);
+            device.destroy_shader_module(self.module, None);
+        }
+    }
+}
+
+/// Compute shader with one storage image and `MESH_PUSH_SIZE` bytes of push
+/// constants. Cheap to clone; frames that dispatch it hold a clone until their
+/// fence signals.
+#[derive(Clone)]
+pub struct ComputePipeline {
+    inner: Arc<PipelineInner>,
+}
+
+impl ComputePipeline {
+    /// Load the SPIR-V compute shader at `path` (entry point `main`).
+    pub fn new(context: &Arc<DeviceContext>, path: &Path) -> Result<Self> {
+        Self::with_specialization(context, path, &SpecializationMap::new())
+    }
+
+    /// Like `new`, with the shader's specialization constants set from
+    /// `specialization` (e.g. a workgroup size or iteration count).
+    pub fn with_specialization(
+        context: &Arc<DeviceContext>,
+        path: &Path,
+        specialization: &SpecializationMap,
+    ) -> Result<Self> {
+        let device = &context.device;
+        let mut inner = PipelineInner {
+            context: Arc::clone(context),
+            module: load_shader_module(device, path)?,
+            set_layout: vk::DescriptorSetLayout::null(),
+            layout: vk::PipelineLayout::null(),
+            pipeline: vk::Pipeline::null(),
+        };
+        inner.set_layout = storage_set_layout(device)?;
+
+        let set_layouts = [inner.set_layout];
+        let push_ranges = [vk::PushConstantRange::builder()
+            .stage_flags(vk::ShaderStageFlags::COMPUTE)
+            .offset(0)
+            .size(MESH_PUSH_SIZE as u32)
+            .build()];
+        let layout_info = vk::PipelineLayoutCreateInfo::builder()
+            .set_layouts(&set_layouts)
+            .push_constant_ranges(&push_ranges);
+        inner.layout = unsafe { device.create_pipeline_layout(&layout_info, None) }
+            .map_err(|e| AppError::vk(e, "create compute pipeline layout"))?;
+
+        let specialization = specialization.build();
+        let specialization_info = specialization.info();
+        let stage = vk::PipelineShaderStageCreateInfo::builder()
+            .stage(vk::ShaderStageFlags::COMPUTE)
+            .module(inner.module)
+            .name(b"main\0")
+            .specialization_info(&specialization_info);
+        let info = vk::ComputePipelineCreateInfo::builder()
+            .stage(stage)
+            .layout(inner.layout);
+        inner.pipeline =
+            unsafe { device.create_compute_pipelines(vk::PipelineCache::null(), &[info], None) }
+                .map_err(|e| AppError::vk(e, "create compute pipeline"))?
+                .0[0];
+        Ok(Self {
+            inner: Arc::new(inner),
+        })
+    }
+
+    pub fn pipeline(&self) -> vk::Pipeline {
+        self.inner.pipeline
+    }
+
+    pub fn layout(&self) -> vk::PipelineLayout {
+        self.inner.layout
+    }
+}
+
+struct StorageInner {
+    context: Arc<DeviceContext>,
+    texture: Texture,
+    set_layout: vk::DescriptorSetLayout,
+    pool: vk::DescriptorPool,
+    set: vk::DescriptorSet,
+    // Layout after the last recorded command; frames are submitted in recording order
+    layout: AtomicI32,
+}
+
+impl Drop for StorageInner {
+    fn drop(&mut self) {
+        let device = &self.context.device;
+        unsafe {
+            device.destroy_descriptor_pool(self.pool, None); // Frees `set`
+            device.destroy_descriptor_set_layout(self.set_layout, None);
+        }
+    }
+}
+
+/// Device-local `STORAGE_IMAGE_FORMAT` image a compute shader writes, with its
+/// own descriptor set. Cheap to clone; frames hold a clone like `ComputePipeline`.
+#[derive(Clone)]
+pub struct StorageImage {
+    inner: Arc<StorageInner>,
+}
+
+impl StorageImage {
+    pub fn new(context: &Arc<DeviceContext>, extent: vk::Extent2D) -> Result<Self> {
+        if extent.width == 0 || extent.height == 0 {
+            return Err(AppError::Config(
+                "storage image needs a non-zero extent".into(),
+            ));
+        }
+        let texture = Texture::new(
+            context,
+            ImageDesc {
+                extent,
+                format: STORAGE_IMAGE_FORMAT,
+                samples: vk::SampleCountFlags::_1,
+                usage: vk::ImageUsageFlags::STORAGE | vk::ImageUsageFlags::TRANSFER_SRC,
+                aspect: vk::ImageAspectFlags::COLOR,
+            },
+        )?;
+        let device = &context.device;
+        let mut inner = StorageInner {
+            context: Arc::clone(context),
+            texture,
+            set_layout: vk::DescriptorSetLayout::null(),
+            pool: vk::DescriptorPool::null(),
+            set: vk::DescriptorSet::null(),
+            layout: AtomicI32::new(vk::ImageLayout::UNDEFINED.as_raw()),
+        };
+        inner.set_layout = storage_set_layout(device)?;
+
+        let pool_sizes = [vk::DescriptorPoolSize::builder()
+            .type_(vk::DescriptorType::STORAGE_IMAGE)
+            .descriptor_count(1)
+            .build()];
+        let pool_info = vk::DescriptorPoolCreateInfo::builder()
+            .max_sets(1)
+            .pool_sizes(&pool_sizes);
+        inner.pool = unsafe { device.create_descriptor_pool(&pool_info, None) }
+            .map_err(|e| AppError::vk(e, "create storage image descriptor pool"))?;
+
+        let set_layouts = [inner.set_layout];
+        let alloc_info = vk::DescriptorSetAllocateInfo::builder()
+            .descriptor_pool(inner.pool)
+            .set_layouts(&set_layouts);
+        inner.set = unsafe { device.allocate_descriptor_sets(&alloc_info) }
+            .map_err(|e| AppError::vk(e, "allocate storage image descriptor set"))?[0];
+        write_storage_image(device, inner.set, 0, inner.texture.view());
+
+        Ok(Self {
+            inner: Arc::new(inner),
+        })
+    }
+
+    pub fn image(&self) -> vk::Image {
+        self.inner.texture.image()
+    }
+
+    pub fn extent(&self) -> vk::Extent2D {
+        self.inner.texture.extent()
+    }
+
+    /// Record a transition to `new` from whatever the last recorded command left.
+    fn transition(
+        &self,
+        device: &Device,
+        cmd: vk::CommandBuffer,
+        new: vk::ImageLayout,
+    ) -> Result<()> {
+        let old = vk::ImageLayout::from_raw(self.inner.layout.load(Ordering::Relaxed));
+        image_barrier(device, cmd, self.image(), old, new, color_subresource())?;
+        self.inner.layout.store(new.as_raw(), Ordering::Relaxed);
+        Ok(())
+    }
+}
+
+/// One `DrawContext::dispatch` call, kept alive by its frame like `DrawCall`.
+pub(crate) struct Dispatch {
+    pipeline: ComputePipeline,
+    target: StorageImage,
+    push: [u8; MESH_PUSH_SIZE],
+    groups: [u32; 3],
+}
+
+impl Dispatch {
+    pub(crate) fn new(
+        pipeline: &ComputePipeline,
+        target: &StorageImage,
+        push: [u8; MESH_PUSH_SIZE],
+        groups: [u32; 3],
+    ) -> Self {
+        Self {
+            pipeline: pipeline.clone(),
+            target: target.clone(),
+            push,
+            groups,
+        }
+    }
+
+    /// Record outside any render pass; waits for earlier writes and blits of the target.
+    pub(crate) fn record(&self, device: &Device, cmd: vk::CommandBuffer) -> Result<()> {
+        let general = vk::ImageLayout::GENERAL;
+        self.target.transition(device, cmd, general)?;
+        let bind_point = vk::PipelineBindPoint::COMPUTE;
+        unsafe {
+            device.cmd_bind_pipeline(cmd, bind_point, self.pipeline.pipeline());
+            device.cmd_bind_descriptor_sets(
+                cmd,
+                bind_point,
+                self.pipeline.layout(),
+                0,
+                &[self.target.inner.set],
+                &[],
+            );
+            device.cmd_push_constants(
+                cmd,
+                self.pipeline.layout(),
+                vk::ShaderStageFlags::COMPUTE,
+                0,
+                &self.push,
+            );
+            let [x, y, z] = self.groups;
+            device.cmd_dispatch(cmd, x, y, z);
+        }
+        Ok(())
+    }
+}
+
+/// Scale `source` over the whole presentable `image` (in PRESENT_SRC_KHR), leaving
+/// `image` in TRANSFER_DST_OPTIMAL; `record_capture` moves it back to PRESENT_SRC.
+pub(crate) fn record_blit(
+    device: &Device,
+    cmd: vk::CommandBuffer,
+    source: &StorageImage,
+    image: vk::Image,
+    extent: vk::Extent2D,
+) -> Result<()> {
+    let src = vk::ImageLayout::TRANSFER_SRC_OPTIMAL;
+    let dst = vk::ImageLayout::TRANSFER_DST_OPTIMAL;
+    source.transition(device, cmd, src)?;
+    let present = vk::ImageLayout::PRESENT_SRC_KHR;
+    image_barrier(device, cmd, image, present, dst, color_subresource())?;
+
+    let layers = color_layers();
+    let corner = |extent: vk::Extent2D| vk::Offset3D {
+        x: extent.width as i32,
+        y: extent.height as i32,
+        z: 1,
+    };
+    let region = vk::ImageBlit::builder()
+        .src_subresource(layers)
+        .src_offsets([vk::Offset3D::default(), corner(source.extent())])
+        .dst_subresource(layers)
+        .dst_offsets([vk::Offset3D::default(), corner(extent)]);
+    let filter = vk::Filter::LINEAR;
+    unsafe { device.cmd_blit_image(cmd, source.image(), src, image, dst, &[region], filter) };
+    Ok(())
+}
@@ -0,0 +1,183 @@
+// src/core/renderer/backend/vulkan/context.rs
+use std::ops::Deref;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, PoisonError};
+
+use log::warn;
+use vulkanalia::prelude::v1_0::*;
+
+#[cfg(all(debug_assertions, feature = "debug-utils"))]
+use super::debug::{DebugSink, destroy_debug_messenger};
+use super::tracker::ResourceTracker;
+use crate::core::renderer::api::MemoryPressure;
+use crate::core::renderer::log_targets::RENDERER;
+use crate::error::{AppError, Result};
+
+/// Bytes and allocation count of device memory owned by RAII resources.
+#[derive(Debug, Default)]
+pub struct MemoryStats {
+    bytes: AtomicU64,
+    allocations: AtomicU64,
+}
+
+impl MemoryStats {
+    pub fn bytes(&self) -> u64 {
+        self.bytes.load(Ordering::Relaxed)
+    }
+
+    pub fn allocations(&self) -> u64 {
+        self.allocations.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn record_alloc(&self, size: vk::DeviceSize) {
+        self.bytes.fetch_add(size, Ordering::Relaxed);
+        self.allocations.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_free(&self, size: vk::DeviceSize) {
+        self.bytes.fetch_sub(size, Ordering::Relaxed);
+        self.allocations.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+/// Run `alloc`; if it fails with ERROR_OUT_OF_DEVICE_MEMORY, ask `on_pressure`
+/// to free memory and retry once if it did. Other errors pass straight through.
+pub fn try_alloc<T>(
+    mut alloc: impl FnMut() -> Result<T>,
+    on_pressure: impl FnOnce() -> bool,
+) -> Result<T> {
+    match alloc() {
+        Err(AppError::Vk(code, ctx)) if code == vk::Result::ERROR_OUT_OF_DEVICE_MEMORY => {
+            if on_pressure() {
+                alloc()
+            } else {
+                Err(AppError::Vk(code, ctx))
+            }
+        }
+        result => result,
+    }
+}
+
+/// Owner of the logical device, the instance it came from and the loaded
+/// Vulkan library.
+///
+/// Shared as `Arc<DeviceContext>` by the renderer and every RAII resource
+/// (`Texture`, `Buffer`), so a resource's `Drop` can always reach a live device.
+/// The device and instance are destroyed when the last clone goes away, and
+/// the library is unloaded only after that.
+pub struct DeviceContext {
+    pub instance: Instance,
+    pub device: Device,
+    pub physical_device: vk::PhysicalDevice,
+    memory: MemoryStats,
+    resources: ResourceTracker, // Live object counts, reported on drop (debug builds)
+    memory_pressure: Mutex<Option<MemoryPressure>>, // Eviction hook for `try_alloc`
+    // Messenger and its transcript / capture sink; they outlive the device so
+    // teardown problems (e.g. leaked objects) still get reported
+    #[cfg(all(debug_assertions, feature = "debug-utils"))]
+    debug_messenger: Option<vk::DebugUtilsMessengerEXT>,
+    #[cfg(all(debug_assertions, feature = "debug-utils"))]
+    debug_log: Option<Box<DebugSink>>,
+    // Last, so libvulkan stays loaded until `drop` destroyed the device and instance
+    entry: Entry,
+}
+
+impl DeviceContext {
+    pub fn new(
+        entry: Entry,
+        instance: Instance,
+        device: Device,
+        physical_device: vk::PhysicalDevice,
+    ) -> Self {
+        Self {
+            entry,
+            instance,
+            device,
+            physical_device,
+            memory: MemoryStats::default(),
+            resources: ResourceTracker::new(),
+            memory_pressure: Mutex::new(None),
+            #[cfg(all(debug_assertions, feature = "debug-utils"))]
+            debug_messenger: None,
+            #[cfg(all(debug_assertions, feature = "debug-utils"))]
+            debug_log: None,
+        }
+    }
+
+    /// Take over the debug messenger and its sink: the messenger is destroyed
+    /// after the device, the sink after the instance.
+    #[cfg(all(debug_assertions, feature = "debug-utils"))]
+    pub(crate) fn with_debug_messenger(
+        mut self,
+        messenger: Option<vk::DebugUtilsMessengerEXT>,
+        debug_log: Option<Box<DebugSink>>,
+    ) -> Self {
+        self.debug_messenger = messenger;
+        self.debug_log = debug_log;
+        self
+    }
+
+    /// Entry point of the Vulkan library the instance was created from.
+    pub fn entry(&self) -> &Entry {
+        &self.entry
+    }
+
+    /// Device memory currently held by RAII resources.
+    pub fn memory(&self) -> &MemoryStats {
+        &self.memory
+    }
+
+    /// Live Vulkan objects per type (debug builds; all zero in release).
+    pub fn resources(&self) -> &ResourceTracker {
+        &self.resources
+    }
+
+    pub fn set_memory_pressure(&self, callback: Option<MemoryPressure>) {
+        *self
+            .memory_pressure
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner) = callback;
+    }
+
+    /// `try_alloc` with the installed memory-pressure hook, for resources that
+    /// own their memory (`Buffer`, `Texture`).
+    pub fn try_alloc<T>(&self, alloc: impl FnMut() -> Result<T>) -> Result<T> {
+        try_alloc(alloc, || {
+            let mut hook = self
+                .memory_pressure
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner);
+            let Some(callback) = hook.as_mut() else {
+                return false;
+            };
+            warn!(target: RENDERER, "Out of device memory, asking the app to free some");
+            callback()
+        })
+    }
+}
+
+impl Deref for DeviceContext {
+    type Target = Device;
+
+    fn deref(&self) -> &Device {
+        &self.device
+    }
+}
+
+impl Drop for DeviceContext {
+    fn drop(&mut self) {
+        // Everything created on this device should be gone by now
+        self.resources.report();
+        unsafe {
+            self.device.device_wait_idle().ok();
+            self.device.destroy_device(None);
+            #[cfg(all(debug_assertions, feature = "debug-utils"))]
+            if let Some(messenger) = &self.debug_messenger {
+                destroy_debug_messenger(&self.instance, messenger);
+            }
+            self.instance.destroy_instance(None);
+        }
+        // debug_log drops after this, once the instance can't report anymore,
+        // then the entry unloads the library
+    }
+}
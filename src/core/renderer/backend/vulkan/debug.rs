@@ -0,0 +1,117 @@
+// src/core/renderer/backend/vulkan/debug.rs
+//
+// ===== Debug Utils helpers (debug builds with the `debug-utils` feature) =====
+//
+use log::{error, info, warn};
+use std::ffi::CStr;
+use std::fs::File;
+use std::io::Write;
+use std::sync::Mutex;
+
+use vulkanalia::prelude::v1_0::*;
+use vulkanalia::vk::ExtDebugUtilsExtension;
+
+use crate::core::renderer::config::DebugConfig;
+use crate::core::renderer::log_targets::VALIDATION;
+use crate::core::renderer::validation::{ValidationLog, ValidationSeverity};
+use crate::error::{AppError, Result};
+
+/// Where the callback copies messages besides the log, handed over through
+/// `user_data`. Must outlive both the messenger and the instance (create/destroy
+/// messages).
+#[derive(Debug)]
+pub struct DebugSink {
+    file: Option<Mutex<File>>,      // `DebugConfig::log_file` transcript
+    capture: Option<ValidationLog>, // `DebugConfig::capture`
+}
+
+/// Open (truncating) the transcript file of `DebugConfig::log_file` and pick up
+/// `DebugConfig::capture`. None when neither is configured.
+pub fn open_debug_sink(config: &DebugConfig) -> Result<Option<Box<DebugSink>>> {
+    let file = match &config.log_file {
+        Some(path) => {
+            let file = File::create(path)?;
+            info!(target: VALIDATION, "📝 Writing validation messages to {}", path.display());
+            Some(Mutex::new(file))
+        }
+        None => None,
+    };
+    let capture = config.capture.clone();
+    Ok((file.is_some() || capture.is_some()).then(|| Box::new(DebugSink { file, capture })))
+}
+
+unsafe extern "system" fn debug_callback(
+    sev: vk::DebugUtilsMessageSeverityFlagsEXT,
+    ty: vk::DebugUtilsMessageTypeFlagsEXT,
+    data: *const vk::DebugUtilsMessengerCallbackDataEXT,
+    user_data: *mut std::ffi::c_void,
+) -> vk::Bool32 {
+    // Convert C string to Rust string
+    let message = unsafe { std::ffi::CStr::from_ptr((*data).message).to_string_lossy() };
+
+    // Log with appropriate severity
+    let (level, severity) = if sev.contains(vk::DebugUtilsMessageSeverityFlagsEXT::ERROR) {
+        error!(target: VALIDATION, "[{ty:?}] {message}");
+        ("ERROR", Some(ValidationSeverity::Error))
+    } else if sev.contains(vk::DebugUtilsMessageSeverityFlagsEXT::WARNING) {
+        warn!(target: VALIDATION, "[{ty:?}] {message}");
+        ("WARN", Some(ValidationSeverity::Warning))
+    } else {
+        info!(target: VALIDATION, "[{ty:?}] {message}");
+        ("INFO", None)
+    };
+
+    // Tee into the transcript file and the capture buffer, if configured
+    if !user_data.is_null() {
+        let sink = unsafe { &*(user_data as *const DebugSink) };
+        if let Some(Ok(mut file)) = sink.file.as_ref().map(Mutex::lock) {
+            writeln!(file, "[{level}] [{ty:?}] {message}").ok();
+        }
+        if let (Some(capture), Some(severity)) = (&sink.capture, severity) {
+            capture.push(severity, format!("[{ty:?}] {message}"));
+        }
+    }
+    vk::FALSE
+}
+
+pub fn build_debug_messenger_ci(
+    sink: Option<&DebugSink>,
+) -> vk::DebugUtilsMessengerCreateInfoEXTBuilder<'static> {
+    let user_data = sink.map_or(std::ptr::null_mut(), |sink| {
+        sink as *const DebugSink as *mut std::ffi::c_void
+    });
+
+    vk::DebugUtilsMessengerCreateInfoEXT::builder()
+        .message_severity(
+            vk::DebugUtilsMessageSeverityFlagsEXT::WARNING
+                | vk::DebugUtilsMessageSeverityFlagsEXT::ERROR,
+        )
+        .message_type(
+            vk::DebugUtilsMessageTypeFlagsEXT::GENERAL
+                | vk::DebugUtilsMessageTypeFlagsEXT::VALIDATION
+                | vk::DebugUtilsMessageTypeFlagsEXT::PERFORMANCE,
+        )
+        .user_callback(Some(debug_callback))
+        .user_data(user_data)
+}
+
+pub fn create_debug_messenger(
+    instance: &Instance,
+    ci: &vk::DebugUtilsMessengerCreateInfoEXT,
+) -> Result<vk::DebugUtilsMessengerEXT> {
+    unsafe { instance.create_debug_utils_messenger_ext(ci, None) }
+        .map_err(|e| AppError::vk(e, "create debug utils messenger"))
+}
+
+pub fn destroy_debug_messenger(instance: &Instance, messenger: &vk::DebugUtilsMessengerEXT) {
+    unsafe { instance.destroy_debug_utils_messenger_ext(*messenger, None) };
+}
+
+/// Label for `cmd_begin_debug_utils_label_ext`, tinted `color` in capture tools
+/// (all zeros = no color). Points into `name`, which must outlive it.
+pub fn debug_label(name: &CStr, color: [f32; 4]) -> vk::DebugUtilsLabelEXT {
+    vk::DebugUtilsLabelEXT::builder()
+        .label_name(name.to_bytes_with_nul())
+        .color(color)
+        .build()
+}
@@ -0,0 +1,267 @@
+// src/core/renderer/backend/vulkan/descriptor.rs
+use log::info;
+use smallvec::SmallVec;
+
+use vulkanalia::prelude::v1_0::*;
+
+use crate::core::renderer::config::DescriptorPoolSizes;
+use crate::core::renderer::log_targets::RENDERER;
+use crate::error::{AppError, Result};
+
+/// Growable descriptor set allocator.
+/// Allocates from the current pool and chains a fresh pool when it runs out,
+/// so scenes with many materials never see `ERROR_OUT_OF_POOL_MEMORY`.
+#[derive(Debug)]
+pub struct DescriptorAllocator {
+    sets_per_pool: u32,
+    sizes: DescriptorPoolSizes,
+    current: Option<vk::DescriptorPool>, // Pool new sets come from
+    full: Vec<vk::DescriptorPool>,       // Exhausted pools, kept until reset/destroy
+    free: Vec<vk::DescriptorPool>,       // Reset pools, reused before creating new ones
+}
+
+impl DescriptorAllocator {
+    pub fn new(sets_per_pool: u32, sizes: DescriptorPoolSizes) -> Self {
+        Self {
+            sets_per_pool,
+            sizes,
+            current: None,
+            full: Vec::new(),
+            free: Vec::new(),
+        }
+    }
+
+    /// Number of pools created so far (including the current one).
+    pub fn pool_count(&self) -> usize {
+        self.full.len() + self.free.len() + self.current.is_some() as usize
+    }
+
+    /// Allocate one set, growing the pool chain when the current pool is exhausted.
+    pub fn allocate(
+        &mut self,
+        device: &Device,
+        layout: vk::DescriptorSetLayout,
+    ) -> Result<vk::DescriptorSet> {
+        let pool = match self.current {
+            Some(pool) => pool,
+            None => self.grow(device)?,
+        };
+
+        match allocate_from(device, pool, layout) {
+            Ok(set) => Ok(set),
+            Err(vk::ErrorCode::OUT_OF_POOL_MEMORY | vk::ErrorCode::FRAGMENTED_POOL) => {
+                // Retire the exhausted pool and retry once on a fresh one
+                self.full.push(pool);
+                self.current = None;
+                let pool = self.grow(device)?;
+                allocate_from(device, pool, layout)
+                    .map_err(|e| AppError::vk(e, "allocate descriptor set from fresh pool"))
+            }
+            Err(e) => Err(AppError::vk(e, "allocate descriptor set")),
+        }
+    }
+
+    /// Return every set to its pool; keeps the pools for reuse.
+    pub fn reset(&mut self, device: &Device) {
+        for pool in self.full.drain(..).chain(self.current.take()) {
+            unsafe { device.reset_descriptor_pool(pool, vk::DescriptorPoolResetFlags::empty()) }
+                .ok();
+            self.free.push(pool);
+        }
+    }
+
+    /// Destroy all pools (and implicitly every set allocated from them).
+    pub fn destroy(&mut self, device: &Device) {
+        let pools = self.full.drain(..).chain(self.free.drain(..));
+        for pool in pools.chain(self.current.take()) {
+            unsafe { device.destroy_descriptor_pool(pool, None) };
+        }
+    }
+
+    fn grow(&mut self, device: &Device) -> Result<vk::DescriptorPool> {
+        if let Some(pool) = self.free.pop() {
+            self.current = Some(pool);
+            return Ok(pool);
+        }
+
+        let sets = self.sets_per_pool;
+        let mut pool_sizes: SmallVec<[vk::DescriptorPoolSize; 4]> = SmallVec::new();
+        for (ty, per_set) in [
+            (
+                vk::DescriptorType::UNIFORM_BUFFER,
+                self.sizes.uniform_buffers,
+            ),
+            (
+                vk::DescriptorType::COMBINED_IMAGE_SAMPLER,
+                self.sizes.combined_image_samplers,
+            ),
+            (
+                vk::DescriptorType::STORAGE_BUFFER,
+                self.sizes.storage_buffers,
+            ),
+            (vk::DescriptorType::STORAGE_IMAGE, self.sizes.storage_images),
+        ] {
+            // Zero-sized entries are invalid, skip unused types
+            if per_set > 0 {
+                pool_sizes.push(
+                    vk::DescriptorPoolSize::builder()
+                        .type_(ty)
+                        .descriptor_count(per_set * sets)
+                        .build(),
+                );
+            }
+        }
+
+        let info = vk::DescriptorPoolCreateInfo::builder()
+            .max_sets(sets)
+            .pool_sizes(&pool_sizes);
+        let pool = unsafe { device.create_descriptor_pool(&info, None) }
+            .map_err(|e| AppError::vk(e, "create descriptor pool"))?;
+
+        self.current = Some(pool);
+        info!(
+            target: RENDERER,
+            "✅ Descriptor pool #{} created ({sets} sets)",
+            self.pool_count()
+        );
+        Ok(pool)
+    }
+}
+
+fn allocate_from(
+    device: &Device,
+    pool: vk::DescriptorPool,
+    layout: vk::DescriptorSetLayout,
+) -> std::result::Result<vk::DescriptorSet, vk::ErrorCode> {
+    let layouts = [layout];
+    let info = vk::DescriptorSetAllocateInfo::builder()
+        .descriptor_pool(pool)
+        .set_layouts(&layouts);
+    unsafe { device.allocate_descriptor_sets(&info) }.map(|sets| sets[0])
+}
+
+/// Bindings of a descriptor set layout, optionally with immutable samplers.
+///
+/// ```ignore
+/// let linear = renderer.create_sampler(SamplerConfig::default())?;
+/// let layout = DescriptorLayoutBuilder::new()
+///     .binding(0, vk::DescriptorType::UNIFORM_BUFFER, vk::ShaderStageFlags::VERTEX)
+///     .immutable_sampler(1, linear, vk::ShaderStageFlags::FRAGMENT)
+///     .build(device)?;
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct DescriptorLayoutBuilder {
+    bindings: SmallVec<[vk::DescriptorSetLayoutBinding; 4]>,
+    // Per binding, the samplers baked into it (empty = written per set)
+    immutable: SmallVec<[SmallVec<[vk::Sampler; 1]>; 4]>,
+}
+
+impl DescriptorLayoutBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// One descriptor of `ty` at `binding`.
+    pub fn binding(
+        mut self,
+        binding: u32,
+        ty: vk::DescriptorType,
+        stages: vk::ShaderStageFlags,
+    ) -> Self {
+        self.bindings.push(
+            vk::DescriptorSetLayoutBinding::builder()
+                .binding(binding)
+                .descriptor_type(ty)
+                .descriptor_count(1)
+                .stage_flags(stages)
+                .build(),
+        );
+        self.immutable.push(SmallVec::new());
+        self
+    }
+
+    /// COMBINED_IMAGE_SAMPLER at `binding` with `sampler` baked into the layout:
+    /// writes to it only carry the image view (see `write_image`). The sampler
+    /// must outlive the layout and every set allocated with it; samplers from
+    /// `VulkanRenderer::create_sampler` live until the renderer is cleaned up.
+    pub fn immutable_sampler(
+        mut self,
+        binding: u32,
+        sampler: vk::Sampler,
+        stages: vk::ShaderStageFlags,
+    ) -> Self {
+        self.bindings.push(
+            vk::DescriptorSetLayoutBinding::builder()
+                .binding(binding)
+                .descriptor_type(vk::DescriptorType::COMBINED_IMAGE_SAMPLER)
+                .descriptor_count(1)
+                .stage_flags(stages)
+                .build(),
+        );
+        self.immutable.push(smallvec::smallvec![sampler]);
+        self
+    }
+
+    pub fn build(&self, device: &Device) -> Result<vk::DescriptorSetLayout> {
+        // Sampler pointers target self.immutable, which outlives the create call
+        let bindings: SmallVec<[vk::DescriptorSetLayoutBinding; 4]> = self
+            .bindings
+            .iter()
+            .zip(&self.immutable)
+            .map(|(binding, samplers)| {
+                let mut binding = *binding;
+                if !samplers.is_empty() {
+                    binding.descriptor_count = samplers.len() as u32;
+                    binding.immutable_samplers = samplers.as_ptr();
+                }
+                binding
+            })
+            .collect();
+        let info = vk::DescriptorSetLayoutCreateInfo::builder().bindings(&bindings);
+        unsafe { device.create_descriptor_set_layout(&info, None) }
+            .map_err(|e| AppError::vk(e, "create descriptor set layout"))
+    }
+}
+
+/// Point COMBINED_IMAGE_SAMPLER `binding` of `set` at `view` (SHADER_READ_ONLY_OPTIMAL).
+/// Pass `None` as sampler for bindings with an immutable sampler.
+pub fn write_image(
+    device: &Device,
+    set: vk::DescriptorSet,
+    binding: u32,
+    view: vk::ImageView,
+    sampler: Option<vk::Sampler>,
+) {
+    let mut image_info = vk::DescriptorImageInfo::builder()
+        .image_view(view)
+        .image_layout(vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL);
+    if let Some(sampler) = sampler {
+        image_info = image_info.sampler(sampler);
+    }
+    let image_info = [image_info.build()];
+    let write = vk::WriteDescriptorSet::builder()
+        .dst_set(set)
+        .dst_binding(binding)
+        .descriptor_type(vk::DescriptorType::COMBINED_IMAGE_SAMPLER)
+        .image_info(&image_info);
+    unsafe { device.update_descriptor_sets(&[write], &[] as &[vk::CopyDescriptorSet]) };
+}
+
+/// Point STORAGE_IMAGE `binding` of `set` at `view` (GENERAL layout).
+pub fn write_storage_image(
+    device: &Device,
+    set: vk::DescriptorSet,
+    binding: u32,
+    view: vk::ImageView,
+) {
+    let image_info = [vk::DescriptorImageInfo::builder()
+        .image_view(view)
+        .image_layout(vk::ImageLayout::GENERAL)
+        .build()];
+    let write = vk::WriteDescriptorSet::builder()
+        .dst_set(set)
+        .dst_binding(binding)
+        .descriptor_type(vk::DescriptorType::STORAGE_IMAGE)
+        .image_info(&image_info);
+    unsafe { device.update_descriptor_sets(&[write], &[] as &[vk::CopyDescriptorSet]) };
+}
@@ -0,0 +1,254 @@
+// src/core/renderer/backend/vulkan/device_info.rs
+use log::info;
+use std::ffi::CStr;
+use std::fmt;
+
+use vulkanalia::prelude::v1_0::*;
+use vulkanalia::vk::{KhrGetPhysicalDeviceProperties2Extension, KhrSurfaceExtension};
+
+use super::capabilities::sample_counts_from_limits;
+use super::features::{FeatureChain, FeatureExtension};
+use crate::core::renderer::log_targets::RENDERER;
+
+/// Identity of the chosen GPU and its driver, for bug reports.
+#[derive(Debug, Clone)]
+pub struct DeviceInfo {
+    pub name: String,
+    pub device_type: vk::PhysicalDeviceType,
+    pub vendor_id: u32,
+    pub device_id: u32,
+    pub api_version: u32,
+    pub driver_version: u32, // Vendor-encoded, not necessarily major.minor.patch
+
+    // Only filled when VkPhysicalDeviceDriverProperties could be queried
+    pub driver_name: Option<String>, // e.g. "radv" vs "AMD proprietary driver"
+    pub driver_info: Option<String>, // e.g. "Mesa 24.0.5"
+    pub conformance_version: Option<vk::ConformanceVersion>,
+}
+
+impl DeviceInfo {
+    /// Query basic properties, plus driver properties when
+    /// `VK_KHR_get_physical_device_properties2` was enabled on the instance.
+    pub fn query(instance: &Instance, physical_device: vk::PhysicalDevice, props2: bool) -> Self {
+        let props = unsafe { instance.get_physical_device_properties(physical_device) };
+
+        let mut info = Self {
+            name: unsafe { CStr::from_ptr(props.device_name.as_ptr()) }
+                .to_string_lossy()
+                .into_owned(),
+            device_type: props.device_type,
+            vendor_id: props.vendor_id,
+            device_id: props.device_id,
+            api_version: props.api_version,
+            driver_version: props.driver_version,
+            driver_name: None,
+            driver_info: None,
+            conformance_version: None,
+        };
+
+        // Driver properties are core in 1.2, otherwise need VK_KHR_driver_properties
+        let core_1_2 =
+            vk::version_major(props.api_version) > 1 || vk::version_minor(props.api_version) >= 2;
+        let has_driver_ext =
+            unsafe { instance.enumerate_device_extension_properties(physical_device, None) }
+                .map(|exts| {
+                    exts.iter()
+                        .any(|e| e.extension_name == vk::KHR_DRIVER_PROPERTIES_EXTENSION.name)
+                })
+                .unwrap_or(false);
+
+        if props2 && (core_1_2 || has_driver_ext) {
+            let mut driver = vk::PhysicalDeviceDriverProperties::default();
+            {
+                let mut chain = vk::PhysicalDeviceProperties2::builder().push_next(&mut driver);
+                unsafe {
+                    instance.get_physical_device_properties2_khr(physical_device, &mut chain)
+                };
+            }
+            info.driver_name = Some(
+                unsafe { CStr::from_ptr(driver.driver_name.as_ptr()) }
+                    .to_string_lossy()
+                    .into_owned(),
+            );
+            info.driver_info = Some(
+                unsafe { CStr::from_ptr(driver.driver_info.as_ptr()) }
+                    .to_string_lossy()
+                    .into_owned(),
+            );
+            info.conformance_version = Some(driver.conformance_version);
+        }
+
+        info
+    }
+
+    /// Log the full device/driver block.
+    pub fn log(&self) {
+        info!(
+            target: RENDERER,
+            "🖥️ GPU: {} ({:?}, vendor 0x{:04x}, device 0x{:04x})",
+            self.name, self.device_type, self.vendor_id, self.device_id
+        );
+        info!(
+            target: RENDERER,
+            "   Vulkan {}.{}.{}, driver version 0x{:08x}",
+            vk::version_major(self.api_version),
+            vk::version_minor(self.api_version),
+            vk::version_patch(self.api_version),
+            self.driver_version
+        );
+        match (
+            &self.driver_name,
+            &self.driver_info,
+            self.conformance_version,
+        ) {
+            (Some(name), Some(driver_info), Some(c)) => info!(
+                target: RENDERER,
+                "   Driver: {name} [{driver_info}], conformance {}.{}.{}.{}",
+                c.major, c.minor, c.subminor, c.patch
+            ),
+            _ => info!(target: RENDERER, "   Driver: details unavailable (no driver properties)"),
+        }
+    }
+}
+
+/// Everything about a GPU worth pasting into a bug report: identity, limits,
+/// notable features and, given a surface, what it can present. Only needs a
+/// physical device, so it can be built before (or without) creating a device.
+#[derive(Debug, Clone)]
+pub struct GpuReport {
+    pub info: DeviceInfo,
+    pub sample_counts: Vec<u32>, // Color + depth MSAA counts, ascending
+    pub max_image_dimension_2d: u32,
+    pub max_image_dimension_3d: u32,
+    pub max_image_array_layers: u32,
+    pub max_sampler_anisotropy: f32,
+    pub sampler_anisotropy: bool,
+    pub wide_lines: bool,
+    pub compute: bool, // A queue family supports compute
+    pub geometry_shader: bool,
+    pub tessellation_shader: bool,
+    pub descriptor_indexing: bool, // runtimeDescriptorArray (1.2 or VK_EXT_descriptor_indexing)
+
+    // Empty without a surface to query
+    pub present_modes: Vec<vk::PresentModeKHR>,
+    pub surface_formats: Vec<vk::SurfaceFormatKHR>,
+}
+
+impl GpuReport {
+    /// `props2` as for `DeviceInfo::query`; `surface` (if any) must come from the
+    /// same instance, failed surface queries leave the lists empty.
+    pub fn query(
+        instance: &Instance,
+        physical_device: vk::PhysicalDevice,
+        props2: bool,
+        surface: Option<vk::SurfaceKHR>,
+    ) -> Self {
+        let info = DeviceInfo::query(instance, physical_device, props2);
+        let limits = unsafe { instance.get_physical_device_properties(physical_device) }.limits;
+        let features = unsafe { instance.get_physical_device_features(physical_device) };
+        let compute =
+            unsafe { instance.get_physical_device_queue_family_properties(physical_device) }
+                .iter()
+                .any(|family| family.queue_flags.contains(vk::QueueFlags::COMPUTE));
+
+        // Core in 1.2, otherwise VK_EXT_descriptor_indexing; either way optional
+        let device_extensions =
+            unsafe { instance.enumerate_device_extension_properties(physical_device, None) }
+                .unwrap_or_default();
+        let mut indexing = FeatureChain::new(
+            &[FeatureExtension::DescriptorIndexing],
+            &device_extensions,
+            info.api_version,
+        );
+        indexing.query(instance, physical_device, props2);
+
+        let (present_modes, surface_formats) = match surface {
+            Some(surface) => unsafe {
+                (
+                    instance
+                        .get_physical_device_surface_present_modes_khr(physical_device, surface)
+                        .unwrap_or_default(),
+                    instance
+                        .get_physical_device_surface_formats_khr(physical_device, surface)
+                        .unwrap_or_default(),
+                )
+            },
+            None => (Vec::new(), Vec::new()),
+        };
+
+        Self {
+            info,
+            sample_counts: sample_counts_from_limits(&limits),
+            max_image_dimension_2d: limits.max_image_dimension_2d,
+            max_image_dimension_3d: limits.max_image_dimension_3d,
+            max_image_array_layers: limits.max_image_array_layers,
+            max_sampler_anisotropy: limits.max_sampler_anisotropy,
+            sampler_anisotropy: features.sampler_anisotropy == vk::TRUE,
+            wide_lines: features.wide_lines == vk::TRUE,
+            compute,
+            geometry_shader: features.geometry_shader == vk::TRUE,
+            tessellation_shader: features.tessellation_shader == vk::TRUE,
+            descriptor_indexing: indexing.contains(FeatureExtension::DescriptorIndexing),
+            present_modes,
+            surface_formats,
+        }
+    }
+}
+
+impl fmt::Display for GpuReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let info = &self.info;
+        let yes_no = |supported: bool| if supported { "yes" } else { "no" };
+        writeln!(
+            f,
+            "{} ({:?}, vendor 0x{:04x}, device 0x{:04x})",
+            info.name, info.device_type, info.vendor_id, info.device_id
+        )?;
+        writeln!(
+            f,
+            "  Vulkan {}.{}.{}, driver version 0x{:08x}",
+            vk::version_major(info.api_version),
+            vk::version_minor(info.api_version),
+            vk::version_patch(info.api_version),
+            info.driver_version
+        )?;
+        match (&info.driver_name, &info.driver_info) {
+            (Some(name), Some(driver_info)) => writeln!(f, "  Driver: {name} [{driver_info}]")?,
+            _ => writeln!(f, "  Driver: details unavailable")?,
+        }
+        writeln!(
+            f,
+            "  Max image size: 2D {}, 3D {}, {} array layers",
+            self.max_image_dimension_2d, self.max_image_dimension_3d, self.max_image_array_layers
+        )?;
+        writeln!(f, "  MSAA sample counts: {:?}", self.sample_counts)?;
+        writeln!(
+            f,
+            "  Anisotropic filtering: {} (max {}x)",
+            yes_no(self.sampler_anisotropy),
+            self.max_sampler_anisotropy
+        )?;
+        writeln!(f, "  Wide lines: {}", yes_no(self.wide_lines))?;
+        writeln!(f, "  Compute queue: {}", yes_no(self.compute))?;
+        writeln!(f, "  Geometry shaders: {}", yes_no(self.geometry_shader))?;
+        writeln!(
+            f,
+            "  Tessellation shaders: {}",
+            yes_no(self.tessellation_shader)
+        )?;
+        writeln!(
+            f,
+            "  Descriptor indexing: {}",
+            yes_no(self.descriptor_indexing)
+        )?;
+        if self.present_modes.is_empty() && self.surface_formats.is_empty() {
+            return writeln!(f, "  Present modes / surface formats: unknown (no surface)");
+        }
+        writeln!(f, "  Present modes: {:?}", self.present_modes)?;
+        writeln!(f, "  Surface formats:")?;
+        for format in &self.surface_formats {
+            writeln!(f, "    {:?} / {:?}", format.format, format.color_space)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,514 @@
+// src/core/renderer/backend/vulkan/draw.rs
+//
+// Callback-style drawing without implementing `Renderer`:
+//   App::run_with_draw(window, config, |ctx: &mut DrawContext| { ...; Ok(()) })
+// The callback runs once per frame, before the frame's command buffer is
+// recorded. Draws are collected and replayed in the scene subpass, so
+// `clear_color` still applies to the frame being built. Compute dispatches run
+// before the render pass, an optional blit of their output after it.
+// `VulkanRenderer::begin_frame` hands out the same context without a callback,
+// through a `Frame` guard that must be finished with `end_frame`.
+use std::hash::{Hash, Hasher};
+use std::path::Path;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use log::error;
+use vulkanalia::prelude::v1_0::*;
+use winit::event::WindowEvent;
+use winit::{event_loop::ActiveEventLoop, window::Window, window::WindowId};
+
+use super::compute::{ComputePipeline, Dispatch, StorageImage};
+use super::context::DeviceContext;
+use super::material::MaterialId;
+use super::mesh::{MESH_PUSH_SIZE, Mesh, MeshPipeline, Vertex};
+use super::pick::PICK_ID_OFFSET;
+use super::pipelines::PipelineDesc;
+use super::render_pass::ClearTarget;
+use super::shader::SpecializationMap;
+use super::viewport::{clamp_scissor, pre_rotation, rotate_extent};
+use super::vulkan::VulkanRenderer;
+use crate::core::renderer::api::{MemoryPressure, Renderer};
+use crate::core::renderer::config::RendererConfig;
+use crate::core::renderer::log_targets::RENDERER;
+use crate::error::{AppError, Result};
+
+/// One `draw_mesh` call, kept alive by its frame until the GPU is done with it.
+#[derive(Clone)]
+pub(crate) struct DrawCall {
+    mesh: Mesh,
+    push: [u8; MESH_PUSH_SIZE],
+    scissor: vk::Rect2D,
+    pick_id: u32,           // Written by the id pass of `VulkanRenderer::pick`
+    stencil_reference: u32, // Only set for pipelines with a stencil test
+    pipeline: Option<Arc<PipelineDesc>>, // None = the built-in mesh pipeline
+    material: Option<MaterialId>, // Bound if the pipeline has a material layout
+}
+
+impl DrawCall {
+    /// Set the scissor, push the constants and draw; `pipeline` must be bound in `cmd`.
+    pub(crate) fn record(&self, device: &Device, cmd: vk::CommandBuffer, pipeline: &MeshPipeline) {
+        unsafe { device.cmd_set_scissor(cmd, 0, &[self.scissor]) };
+        if pipeline.stencil_test() {
+            let faces = vk::StencilFaceFlags::FRONT_AND_BACK;
+            unsafe { device.cmd_set_stencil_reference(cmd, faces, self.stencil_reference) };
+        }
+        self.push_and_draw(device, cmd, pipeline, &self.push);
+    }
+
+    /// Draw into the id pass: same constants, with `pick_id` at `PICK_ID_OFFSET`.
+    /// The pass sets its own 1x1 scissor.
+    pub(crate) fn record_pick(
+        &self,
+        device: &Device,
+        cmd: vk::CommandBuffer,
+        pipeline: &MeshPipeline,
+    ) {
+        let mut push = self.push;
+        push[PICK_ID_OFFSET..PICK_ID_OFFSET + 4].copy_from_slice(&self.pick_id.to_ne_bytes());
+        self.push_and_draw(device, cmd, pipeline, &push);
+    }
+
+    /// Whether framebuffer pixel (`x`, `y`) is inside this draw's scissor.
+    pub(crate) fn covers(&self, x: u32, y: u32) -> bool {
+        let vk::Rect2D { offset, extent } = self.scissor;
+        let inside = |pos: u32, start: i32, size: u32| {
+            let pos = i64::from(pos);
+            pos >= i64::from(start) && pos < i64::from(start) + i64::from(size)
+        };
+        inside(x, offset.x, extent.width) && inside(y, offset.y, extent.height)
+    }
+
+    fn push_and_draw(
+        &self,
+        device: &Device,
+        cmd: vk::CommandBuffer,
+        pipeline: &MeshPipeline,
+        push: &[u8; MESH_PUSH_SIZE],
+    ) {
+        unsafe {
+            device.cmd_push_constants(cmd, pipeline.layout(), pipeline.push_stages(), 0, push);
+        }
+        self.mesh.draw(device, cmd);
+    }
+
+    pub(crate) fn index_count(&self) -> u32 {
+        self.mesh.index_count()
+    }
+
+    pub(crate) fn uses_restart(&self) -> bool {
+        self.mesh.uses_restart()
+    }
+
+    pub(crate) fn pipeline(&self) -> Option<&PipelineDesc> {
+        self.pipeline.as_deref()
+    }
+
+    pub(crate) fn material(&self) -> Option<MaterialId> {
+        self.material
+    }
+
+    /// Feed everything recording reads from this draw into `state`.
+    fn hash_content(&self, state: &mut impl Hasher) {
+        let vk::Rect2D { offset, extent } = self.scissor;
+        (self.mesh.id(), self.push).hash(state);
+        (self.pick_id, self.stencil_reference).hash(state);
+        (offset.x, offset.y, extent.width, extent.height).hash(state);
+        (self.pipeline.as_deref(), self.material).hash(state);
+    }
+}
+
+/// One `DrawContext::clear_attachment` call, replayed between the draws.
+pub(crate) struct QueuedClear {
+    pub(crate) before_draw: usize, // Index of the first draw recorded after it
+    pub(crate) target: ClearTarget,
+    pub(crate) value: vk::ClearValue,
+    pub(crate) rect: vk::Rect2D,
+}
+
+/// Work one `DrawContext` collected; the frame keeps it until its fence signals.
+#[derive(Default)]
+pub(crate) struct FrameDraws {
+    pub(crate) dispatches: Vec<Dispatch>,
+    pub(crate) draws: Vec<DrawCall>,
+    pub(crate) clears: Vec<QueuedClear>,   // In draw order
+    pub(crate) blit: Option<StorageImage>, // Scaled over the swapchain image
+}
+
+impl FrameDraws {
+    /// Hash of the draws and clears, for `RecordCache` signatures.
+    pub(crate) fn hash_content(&self, state: &mut impl Hasher) {
+        self.draws.len().hash(state);
+        for draw in &self.draws {
+            draw.hash_content(state);
+        }
+        for clear in &self.clears {
+            let vk::Rect2D { offset, extent } = clear.rect;
+            // The largest union member covers depth/stencil values too
+            let value = unsafe { clear.value.color.uint32 };
+            (clear.before_draw, clear.target, value).hash(state);
+            (offset.x, offset.y, extent.width, extent.height).hash(state);
+        }
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.dispatches.clear();
+        self.draws.clear();
+        self.clears.clear();
+        self.blit = None;
+    }
+}
+
+/// Per-frame drawing interface handed to the `App::run_with` callback.
+pub struct DrawContext<'a> {
+    context: &'a Arc<DeviceContext>,
+    shader_dir: &'a Path,
+    extent: vk::Extent2D,
+    area: vk::Rect2D, // Scene area (letterboxed with RendererConfig::target_aspect)
+    turns: u32,       // Clockwise quarter turns of RendererConfig::force_pre_transform
+    clear_color: [f32; 4],
+    push: [u8; MESH_PUSH_SIZE], // Used by every following draw_mesh/dispatch
+    scissor: vk::Rect2D,        // Used by every following draw_mesh
+    pick_id: u32,               // Used by every following draw_mesh
+    stencil_reference: u32,     // Used by every following draw_mesh
+    pipeline: Option<Arc<PipelineDesc>>, // Used by every following draw_mesh
+    material: Option<MaterialId>, // Used by every following draw_mesh
+    frame: FrameDraws,
+}
+
+impl<'a> DrawContext<'a> {
+    pub(crate) fn new(
+        context: &'a Arc<DeviceContext>,
+        shader_dir: &'a Path,
+        extent: vk::Extent2D,
+        area: vk::Rect2D,
+        clear_color: [f32; 4],
+    ) -> Self {
+        Self {
+            context,
+            shader_dir,
+            extent,
+            area,
+            turns: 0,
+            clear_color,
+            push: [0; MESH_PUSH_SIZE],
+            scissor: area,
+            pick_id: 0,
+            stencil_reference: 0,
+            pipeline: None,
+            material: None,
+            frame: FrameDraws::default(),
+        }
+    }
+
+    /// Size of the image being drawn, in pixels.
+    pub fn extent(&self) -> vk::Extent2D {
+        self.extent
+    }
+
+    /// Part of the image the scene is drawn into, in framebuffer pixels: all of
+    /// `extent()` unless `RendererConfig::target_aspect` letterboxes it. Its size
+    /// is what projection aspect ratios should use.
+    pub fn scene_area(&self) -> vk::Rect2D {
+        self.area
+    }
+
+    /// Column-major clip-space rotation to apply after the projection when
+    /// `RendererConfig::force_pre_transform` makes the scene pre-rotate itself
+    /// (identity otherwise).
+    pub fn pre_rotation(&self) -> [[f32; 4]; 4] {
+        pre_rotation(self.turns)
+    }
+
+    /// `extent()` the way the viewer sees it: width and height swapped while a
+    /// forced transform rotates by a quarter turn. Projections take their
+    /// aspect ratio from this.
+    pub fn display_extent(&self) -> vk::Extent2D {
+        rotate_extent(self.extent, self.turns)
+    }
+
+    /// Clear color for this frame and the following ones.
+    pub fn clear_color(&mut self, color: [f32; 4]) {
+        self.clear_color = color;
+    }
+
+    /// Push constants for the following `draw_mesh`/`dispatch` calls (zero-padded to
+    /// `MESH_PUSH_SIZE`). The built-in shader reads a column-major mat4 transform.
+    pub fn push_constants<T: Copy>(&mut self, value: &T) -> Result<()> {
+        let size = size_of::<T>();
+        if size > MESH_PUSH_SIZE {
+            return Err(AppError::Config(format!(
+                "push constants are {size} bytes, at most {MESH_PUSH_SIZE} fit"
+            )));
+        }
+        let bytes = unsafe { std::slice::from_raw_parts((value as *const T).cast::<u8>(), size) };
+        self.push = [0; MESH_PUSH_SIZE];
+        self.push[..size].copy_from_slice(bytes);
+        Ok(())
+    }
+
+    /// Draw `mesh` with the current push constants.
+    pub fn draw_mesh(&mut self, mesh: &Mesh) {
+        self.frame.draws.push(DrawCall {
+            mesh: mesh.clone(),
+            push: self.push,
+            scissor: self.scissor,
+            pick_id: self.pick_id,
+            stencil_reference: self.stencil_reference,
+            pipeline: self.pipeline.clone(),
+            material: self.material,
+        });
+    }
+
+    /// Draw the following `draw_mesh` calls with the pipeline `desc` describes,
+    /// created on first use unless `VulkanRenderer::precompile_pipelines` built
+    /// it. `reset_pipeline` goes back to the built-in one.
+    pub fn set_pipeline(&mut self, desc: PipelineDesc) {
+        self.pipeline = Some(Arc::new(desc));
+    }
+
+    pub fn reset_pipeline(&mut self) {
+        self.pipeline = None;
+    }
+
+    /// Bind `material`'s set for the following `draw_mesh` calls, on pipelines
+    /// with a material layout (`PipelineDesc::with_material_layout`).
+    pub fn set_material(&mut self, material: MaterialId) {
+        self.material = Some(material);
+    }
+
+    pub fn reset_material(&mut self) {
+        self.material = None;
+    }
+
+    /// Id that `VulkanRenderer::pick` reports for the following `draw_mesh`
+    /// calls; 0 (the default) is also what empty pixels report.
+    pub fn set_pick_id(&mut self, id: u32) {
+        self.pick_id = id;
+    }
+
+    /// Stencil reference of the following `draw_mesh` calls, for pipelines with
+    /// a `StencilState` (`PipelineDesc::with_stencil`); 0 by default.
+    pub fn set_stencil_reference(&mut self, reference: u8) {
+        self.stencil_reference = u32::from(reference);
+    }
+
+    /// Clip the following `draw_mesh` calls to `rect` (e.g. a scroll view), in
+    /// framebuffer pixels. Clamped to `extent()`; `reset_scissor` goes back to
+    /// `scene_area()`.
+    pub fn set_scissor(&mut self, rect: vk::Rect2D) {
+        self.scissor = clamp_scissor(rect, self.extent);
+    }
+
+    pub fn reset_scissor(&mut self) {
+        self.scissor = self.area;
+    }
+
+    /// Clear `rect` of a scene subpass attachment between the draws before and
+    /// after this call, e.g. `ClearTarget::Depth` so a HUD drawn next ends up on
+    /// top. `rect` is clamped to `extent()`; color attachment 0 is the scene.
+    pub fn clear_attachment(
+        &mut self,
+        target: ClearTarget,
+        value: impl Into<vk::ClearValue>,
+        rect: vk::Rect2D,
+    ) {
+        self.frame.clears.push(QueuedClear {
+            before_draw: self.frame.draws.len(),
+            target,
+            value: value.into(),
+            rect: clamp_scissor(rect, self.extent),
+        });
+    }
+
+    /// Run `pipeline` over `target` with the current push constants, before the
+    /// render pass. `groups` is the workgroup count per dimension.
+    pub fn dispatch(
+        &mut self,
+        pipeline: &ComputePipeline,
+        target: &StorageImage,
+        groups: [u32; 3],
+    ) {
+        let dispatch = Dispatch::new(pipeline, target, self.push, groups);
+        self.frame.dispatches.push(dispatch);
+    }
+
+    /// Scale `image` over the whole swapchain image after the render pass, so it
+    /// covers anything drawn this frame. The last call wins.
+    pub fn blit_to_swapchain(&mut self, image: &StorageImage) {
+        self.frame.blit = Some(image.clone());
+    }
+
+    /// Upload a mesh; keep it across frames rather than recreating it every call.
+    pub fn create_mesh(&self, vertices: &[Vertex], indices: &[u32]) -> Result<Mesh> {
+        Mesh::new(self.context, vertices, indices)
+    }
+
+    /// Compute pipeline from `name` (e.g. `"mandelbrot.comp.spv"`) in
+    /// `RendererConfig::shader_dir`; create it once, not every frame.
+    pub fn create_compute_pipeline(&self, name: &str) -> Result<ComputePipeline> {
+        ComputePipeline::new(self.context, &self.shader_dir.join(name))
+    }
+
+    /// `create_compute_pipeline` with specialization constants; one shader gives
+    /// one pipeline per distinct map.
+    pub fn create_specialized_compute_pipeline(
+        &self,
+        name: &str,
+        specialization: &SpecializationMap,
+    ) -> Result<ComputePipeline> {
+        let path = self.shader_dir.join(name);
+        ComputePipeline::with_specialization(self.context, &path, specialization)
+    }
+
+    /// Storage image for `dispatch`/`blit_to_swapchain`, e.g. of `extent()`.
+    pub fn create_storage_image(&self, extent: vk::Extent2D) -> Result<StorageImage> {
+        StorageImage::new(self.context, extent)
+    }
+
+    /// Pre-rotate by `turns` clockwise quarter turns (`pre_rotation`).
+    pub(crate) fn with_pre_rotation(mut self, turns: u32) -> Self {
+        self.turns = turns;
+        self
+    }
+
+    /// Continue a frame's earlier draws (`Frame::draw` called again).
+    pub(crate) fn with_draws(mut self, frame: FrameDraws) -> Self {
+        self.frame = frame;
+        self
+    }
+
+    pub(crate) fn finish(self) -> ([f32; 4], FrameDraws) {
+        (self.clear_color, self.frame)
+    }
+}
+
+/// Frame started by `VulkanRenderer::begin_frame`: fill it with `draw`, then
+/// submit and present it with `end_frame`. Dropping it unfinished skips the
+/// frame and logs an error; `abort` skips it on purpose.
+#[must_use = "the frame is skipped unless it is finished with `end_frame`"]
+pub struct Frame<'r> {
+    renderer: &'r mut VulkanRenderer,
+    draws: FrameDraws,
+    start: Instant,       // When `begin_frame` started, for frame timing
+    fence_wait: Duration, // Spent waiting for the frame slot
+    finished: bool,       // `end_frame` or `abort` ran
+}
+
+impl<'r> Frame<'r> {
+    pub(crate) fn new(
+        renderer: &'r mut VulkanRenderer,
+        start: Instant,
+        fence_wait: Duration,
+    ) -> Self {
+        Self {
+            renderer,
+            draws: FrameDraws::default(),
+            start,
+            fence_wait,
+            finished: false,
+        }
+    }
+
+    /// Add to the frame through a `DrawContext`, like the `render_with` callback.
+    /// May be called several times; push constants, scissor, pipeline and
+    /// material start over each time, the draws add up.
+    pub fn draw(&mut self, draw: impl FnOnce(&mut DrawContext<'_>) -> Result<()>) -> Result<()> {
+        self.renderer.collect_draws(&mut self.draws, draw)
+    }
+
+    /// Acquire a swapchain image, record the draws, submit and present.
+    pub fn end_frame(mut self) -> Result<()> {
+        self.finished = true;
+        let draws = std::mem::take(&mut self.draws);
+        self.renderer
+            .submit_frame(draws, self.start, self.fence_wait)
+    }
+
+    /// Skip the frame: nothing was acquired or submitted, so nothing is presented.
+    pub fn abort(mut self) {
+        self.finished = true;
+    }
+}
+
+impl Drop for Frame<'_> {
+    fn drop(&mut self) {
+        if !self.finished {
+            error!(target: RENDERER, "Frame dropped without end_frame, skipped");
+        }
+    }
+}
+
+/// Per-frame callback driven by `DrawCallback`.
+pub type DrawFn = Box<dyn FnMut(&mut DrawContext<'_>) -> Result<()>>;
+
+/// `VulkanRenderer` whose frames are filled by a user callback; what
+/// `App::run_with` runs. Callback errors stop the app and are returned by `run_with`.
+pub struct DrawCallback {
+    renderer: VulkanRenderer,
+    callback: Option<DrawFn>, // None (via `Renderer::new`) renders clear-only frames
+}
+
+impl DrawCallback {
+    pub fn with_callback(
+        config: RendererConfig,
+        callback: impl FnMut(&mut DrawContext<'_>) -> Result<()> + 'static,
+    ) -> Self {
+        Self {
+            renderer: VulkanRenderer::new(config),
+            callback: Some(Box::new(callback)),
+        }
+    }
+
+    pub fn renderer(&self) -> &VulkanRenderer {
+        &self.renderer
+    }
+}
+
+impl Renderer for DrawCallback {
+    fn new(config: RendererConfig) -> Self {
+        Self {
+            renderer: VulkanRenderer::new(config),
+            callback: None,
+        }
+    }
+
+    fn initialize(&mut self, window: &Window, event_loop: &ActiveEventLoop) -> Result<()> {
+        self.renderer.initialize(window, event_loop)
+    }
+
+    fn is_initialized(&self) -> bool {
+        self.renderer.is_initialized()
+    }
+
+    fn window_event(&mut self, event_loop: &ActiveEventLoop, id: WindowId, event: &WindowEvent) {
+        self.renderer.window_event(event_loop, id, event)
+    }
+
+    fn render(&mut self) -> Result<()> {
+        match &mut self.callback {
+            Some(callback) => self.renderer.render_with(|ctx| callback(ctx)),
+            None => self.renderer.render(),
+        }
+    }
+
+    fn pause(&mut self) {
+        self.renderer.pause()
+    }
+
+    fn resume(&mut self) -> Result<()> {
+        self.renderer.resume()
+    }
+
+    fn is_paused(&self) -> bool {
+        self.renderer.is_paused()
+    }
+
+    fn set_memory_pressure(&mut self, callback: MemoryPressure) {
+        self.renderer.set_memory_pressure(callback)
+    }
+
+    fn take_error(&mut self) -> Option<AppError> {
+        self.renderer.take_error()
+    }
+}
@@ -0,0 +1,157 @@
+// src/core/renderer/backend/vulkan/exclusive.rs
+//
+// Exclusive fullscreen on Windows (VK_EXT_full_screen_exclusive). With
+// `RendererConfig::full_screen_exclusive` the swapchain is created
+// APPLICATION_CONTROLLED, and `VulkanRenderer::acquire_full_screen_exclusive`
+// lets it bypass the compositor, which saves latency compared to borderless
+// fullscreen. Exclusive mode belongs to one swapchain and one monitor: it is
+// released when the window loses focus and taken again when it regains focus or
+// the swapchain is recreated, for as long as the application wants it.
+use log::{info, warn};
+use vulkanalia::prelude::v1_0::*;
+use vulkanalia::vk::ExtFullScreenExclusiveExtension;
+use winit::window::Window;
+
+use crate::core::renderer::log_targets::SWAPCHAIN;
+use crate::error::{AppError, Result};
+
+/// Whether the device can honor a `full_screen_exclusive` request: Windows only,
+/// and the device extension needs `VK_KHR_get_surface_capabilities2`.
+pub fn select_exclusive(
+    requested: bool,
+    device_extensions: &[vk::ExtensionProperties],
+    surface_capabilities2: bool,
+) -> bool {
+    let advertised = device_extensions
+        .iter()
+        .any(|e| e.extension_name == vk::EXT_FULL_SCREEN_EXCLUSIVE_EXTENSION.name);
+    requested && cfg!(windows) && advertised && surface_capabilities2
+}
+
+/// Exclusive-mode struct for the swapchain create chain; None leaves the
+/// driver's default behavior.
+pub fn exclusive_info(enabled: bool) -> Option<vk::SurfaceFullScreenExclusiveInfoEXT> {
+    enabled.then(|| {
+        vk::SurfaceFullScreenExclusiveInfoEXT::builder()
+            .full_screen_exclusive(vk::FullScreenExclusiveEXT::APPLICATION_CONTROLLED)
+            .build()
+    })
+}
+
+/// HMONITOR of the monitor `window` is on; Win32 surfaces need it for
+/// application-controlled exclusive mode.
+#[cfg(windows)]
+pub fn window_monitor(window: &Window) -> Option<isize> {
+    use winit::platform::windows::MonitorHandleExtWindows;
+    window.current_monitor().map(|monitor| monitor.hmonitor())
+}
+
+#[cfg(not(windows))]
+pub fn window_monitor(_window: &Window) -> Option<isize> {
+    None
+}
+
+/// Exclusive-mode state of the renderer's swapchain.
+#[derive(Debug)]
+pub struct FullScreenExclusive {
+    pub enabled: bool,          // Extension enabled (requested + supported)
+    pub monitor: Option<isize>, // HMONITOR of the window at `initialize`
+    wanted: bool,               // Asked for with `acquire` and not released since
+    acquired: bool,             // Held by the current swapchain
+    focused: bool,              // Last WindowEvent::Focused
+}
+
+impl Default for FullScreenExclusive {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            monitor: None,
+            wanted: false,
+            acquired: false,
+            focused: true,
+        }
+    }
+}
+
+impl FullScreenExclusive {
+    /// Swapchains are created APPLICATION_CONTROLLED (and can be acquired) only
+    /// with the extension and a known monitor.
+    pub fn available(&self) -> bool {
+        self.enabled && self.monitor.is_some()
+    }
+
+    pub fn is_acquired(&self) -> bool {
+        self.acquired
+    }
+
+    /// Take exclusive mode for `swapchain`, now and after recreation / refocus.
+    pub fn acquire(&mut self, device: &Device, swapchain: vk::SwapchainKHR) -> Result<()> {
+        if !self.available() {
+            return Err(AppError::Config(
+                "full_screen_exclusive is off or unsupported on this device".into(),
+            ));
+        }
+        self.wanted = true;
+        if self.acquired || !self.focused {
+            return Ok(());
+        }
+        unsafe { device.acquire_full_screen_exclusive_mode_ext(swapchain) }
+            .map_err(|e| AppError::vk(e, "acquire full-screen exclusive mode"))?;
+        self.acquired = true;
+        info!(target: SWAPCHAIN, "✅ Full-screen exclusive mode acquired");
+        Ok(())
+    }
+
+    /// Give exclusive mode back and stop re-taking it.
+    pub fn release(&mut self, device: &Device, swapchain: vk::SwapchainKHR) -> Result<()> {
+        self.wanted = false;
+        self.drop_mode(device, swapchain)
+    }
+
+    /// Focus changes: released on focus loss (Windows would minimize an exclusive
+    /// window on alt-tab anyway), taken back on refocus if still wanted.
+    pub fn set_focused(&mut self, device: &Device, swapchain: vk::SwapchainKHR, focused: bool) {
+        self.focused = focused;
+        let result = if focused {
+            self.reacquire(device, swapchain)
+        } else {
+            self.drop_mode(device, swapchain)
+        };
+        if let Err(e) = result {
+            warn!(target: SWAPCHAIN, "Full-screen exclusive mode: {e}");
+        }
+    }
+
+    /// The old swapchain's mode went away with it; take it again on the new one.
+    pub fn swapchain_recreated(&mut self, device: &Device, swapchain: vk::SwapchainKHR) {
+        self.acquired = false;
+        if let Err(e) = self.reacquire(device, swapchain) {
+            warn!(target: SWAPCHAIN, "Full-screen exclusive mode: {e}");
+        }
+    }
+
+    /// ERROR_FULL_SCREEN_EXCLUSIVE_MODE_LOST_EXT from acquire/present: the
+    /// swapchain stays valid, the mode is retaken on the next focus gain.
+    pub fn lost(&mut self) {
+        self.acquired = false;
+        warn!(target: SWAPCHAIN, "Full-screen exclusive mode lost");
+    }
+
+    fn reacquire(&mut self, device: &Device, swapchain: vk::SwapchainKHR) -> Result<()> {
+        if self.wanted && !self.acquired {
+            self.acquire(device, swapchain)?;
+        }
+        Ok(())
+    }
+
+    fn drop_mode(&mut self, device: &Device, swapchain: vk::SwapchainKHR) -> Result<()> {
+        if !self.acquired {
+            return Ok(());
+        }
+        self.acquired = false;
+        unsafe { device.release_full_screen_exclusive_mode_ext(swapchain) }
+            .map_err(|e| AppError::vk(e, "release full-screen exclusive mode"))?;
+        info!(target: SWAPCHAIN, "✅ Full-screen exclusive mode released");
+        Ok(())
+    }
+}
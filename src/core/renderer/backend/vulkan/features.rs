@@ -0,0 +1,207 @@
+// src/core/renderer/backend/vulkan/features.rs
+//
+// Feature structs of device extensions. Extensions like timeline semaphores or
+// descriptor indexing don't turn anything on by being enabled: their features
+// come in their own `*Features` struct, queried through the pNext chain of
+// `VkPhysicalDeviceFeatures2` and enabled through the pNext chain of
+// `VkDeviceCreateInfo` (the base `enabled_features` only covers 1.0 features).
+// `FeatureChain` owns those structs, so the chains can point into it.
+use std::ptr;
+
+use smallvec::SmallVec;
+use vulkanalia::prelude::v1_0::*;
+use vulkanalia::vk::KhrGetPhysicalDeviceProperties2Extension;
+
+/// Device extension whose features live in a pNext struct.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FeatureExtension {
+    TimelineSemaphore,     // VK_KHR_timeline_semaphore, core in 1.2
+    DynamicRendering,      // VK_KHR_dynamic_rendering, core in 1.3
+    DescriptorIndexing,    // VK_EXT_descriptor_indexing, core in 1.2
+    SwapchainMaintenance1, // VK_EXT_swapchain_maintenance1
+}
+
+impl FeatureExtension {
+    pub fn extension(self) -> vk::Extension {
+        match self {
+            Self::TimelineSemaphore => vk::KHR_TIMELINE_SEMAPHORE_EXTENSION,
+            Self::DynamicRendering => vk::KHR_DYNAMIC_RENDERING_EXTENSION,
+            Self::DescriptorIndexing => vk::EXT_DESCRIPTOR_INDEXING_EXTENSION,
+            Self::SwapchainMaintenance1 => vk::EXT_SWAPCHAIN_MAINTENANCE1_EXTENSION,
+        }
+    }
+
+    /// API version that promoted the extension to core, if any. From there on
+    /// the feature struct is valid without enabling the extension.
+    pub fn core_version(self) -> Option<u32> {
+        match self {
+            Self::TimelineSemaphore | Self::DescriptorIndexing => Some(vk::make_version(1, 2, 0)),
+            Self::DynamicRendering => Some(vk::make_version(1, 3, 0)),
+            Self::SwapchainMaintenance1 => None,
+        }
+    }
+
+    fn is_core(self, api_version: u32) -> bool {
+        self.core_version().is_some_and(|core| api_version >= core)
+    }
+}
+
+/// Feature structs of the requested extensions the device supports.
+#[derive(Debug, Default)]
+pub struct FeatureChain {
+    extensions: SmallVec<[FeatureExtension; 4]>,
+    api_version: u32, // Min of instance and device version
+    timeline_semaphore: vk::PhysicalDeviceTimelineSemaphoreFeatures,
+    dynamic_rendering: vk::PhysicalDeviceDynamicRenderingFeatures,
+    descriptor_indexing: vk::PhysicalDeviceDescriptorIndexingFeatures,
+    swapchain_maintenance1: vk::PhysicalDeviceSwapchainMaintenance1FeaturesEXT,
+}
+
+impl FeatureChain {
+    /// Chain for each of `requested` the device offers, as an advertised
+    /// extension or through `api_version`.
+    pub fn new(
+        requested: &[FeatureExtension],
+        device_extensions: &[vk::ExtensionProperties],
+        api_version: u32,
+    ) -> Self {
+        let offered = |ext: FeatureExtension| {
+            ext.is_core(api_version)
+                || device_extensions
+                    .iter()
+                    .any(|e| e.extension_name == ext.extension().name)
+        };
+        let mut extensions: SmallVec<[FeatureExtension; 4]> = SmallVec::new();
+        for &ext in requested {
+            if offered(ext) && !extensions.contains(&ext) {
+                extensions.push(ext);
+            }
+        }
+        Self {
+            extensions,
+            api_version,
+            ..Default::default()
+        }
+    }
+
+    pub fn contains(&self, ext: FeatureExtension) -> bool {
+        self.extensions.contains(&ext)
+    }
+
+    /// sType of each struct `query` and `extend` chain, in request order.
+    pub fn structure_types(&self) -> SmallVec<[vk::StructureType; 4]> {
+        self.extensions
+            .iter()
+            .map(|ext| match ext {
+                FeatureExtension::TimelineSemaphore => self.timeline_semaphore.s_type,
+                FeatureExtension::DynamicRendering => self.dynamic_rendering.s_type,
+                FeatureExtension::DescriptorIndexing => self.descriptor_indexing.s_type,
+                FeatureExtension::SwapchainMaintenance1 => self.swapchain_maintenance1.s_type,
+            })
+            .collect()
+    }
+
+    /// Fill the structs with what the device supports and drop extensions whose
+    /// main feature is missing. Feature chains need
+    /// VK_KHR_get_physical_device_properties2 (`props2`); without it nothing is
+    /// chained.
+    pub fn query(
+        &mut self,
+        instance: &Instance,
+        physical_device: vk::PhysicalDevice,
+        props2: bool,
+    ) {
+        if !props2 {
+            self.extensions.clear();
+            return;
+        }
+        self.unlink();
+        let [timeline, dynamic, indexing, maintenance1] = self.chained();
+        let mut features2 = vk::PhysicalDeviceFeatures2::builder();
+        if timeline {
+            features2 = features2.push_next(&mut self.timeline_semaphore);
+        }
+        if dynamic {
+            features2 = features2.push_next(&mut self.dynamic_rendering);
+        }
+        if indexing {
+            features2 = features2.push_next(&mut self.descriptor_indexing);
+        }
+        if maintenance1 {
+            features2 = features2.push_next(&mut self.swapchain_maintenance1);
+        }
+        unsafe { instance.get_physical_device_features2_khr(physical_device, &mut features2) };
+
+        let supported: SmallVec<[FeatureExtension; 4]> = self
+            .extensions
+            .iter()
+            .copied()
+            .filter(|&ext| self.supported(ext))
+            .collect();
+        self.extensions = supported;
+    }
+
+    /// Device extensions to enable for the chain: the ones not covered by core.
+    pub fn extension_names(&self) -> SmallVec<[*const i8; 4]> {
+        self.extensions
+            .iter()
+            .filter(|ext| !ext.is_core(self.api_version))
+            .map(|ext| ext.extension().name.as_ptr())
+            .collect()
+    }
+
+    /// Append the chained structs to device creation, enabling every feature
+    /// `query` found supported.
+    pub fn extend<'b>(
+        &'b mut self,
+        mut info: vk::DeviceCreateInfoBuilder<'b>,
+    ) -> vk::DeviceCreateInfoBuilder<'b> {
+        self.unlink();
+        let [timeline, dynamic, indexing, maintenance1] = self.chained();
+        if timeline {
+            info = info.push_next(&mut self.timeline_semaphore);
+        }
+        if dynamic {
+            info = info.push_next(&mut self.dynamic_rendering);
+        }
+        if indexing {
+            info = info.push_next(&mut self.descriptor_indexing);
+        }
+        if maintenance1 {
+            info = info.push_next(&mut self.swapchain_maintenance1);
+        }
+        info
+    }
+
+    fn chained(&self) -> [bool; 4] {
+        [
+            self.contains(FeatureExtension::TimelineSemaphore),
+            self.contains(FeatureExtension::DynamicRendering),
+            self.contains(FeatureExtension::DescriptorIndexing),
+            self.contains(FeatureExtension::SwapchainMaintenance1),
+        ]
+    }
+
+    fn supported(&self, ext: FeatureExtension) -> bool {
+        let feature = match ext {
+            FeatureExtension::TimelineSemaphore => self.timeline_semaphore.timeline_semaphore,
+            FeatureExtension::DynamicRendering => self.dynamic_rendering.dynamic_rendering,
+            FeatureExtension::DescriptorIndexing => {
+                self.descriptor_indexing.runtime_descriptor_array
+            }
+            FeatureExtension::SwapchainMaintenance1 => {
+                self.swapchain_maintenance1.swapchain_maintenance1
+            }
+        };
+        feature == vk::TRUE
+    }
+
+    /// Drop the links of a previous chain; push_next appends whatever a struct
+    /// already points to.
+    fn unlink(&mut self) {
+        self.timeline_semaphore.next = ptr::null_mut();
+        self.dynamic_rendering.next = ptr::null_mut();
+        self.descriptor_indexing.next = ptr::null_mut();
+        self.swapchain_maintenance1.next = ptr::null_mut();
+    }
+}
@@ -0,0 +1,140 @@
+// src/core/renderer/backend/vulkan/frame.rs
+//
+// Two kinds of per-frame state, never to be indexed with each other's index:
+//
+// Per frame in flight (`MAX_FRAMES_IN_FLIGHT`, fixed, indexed by `current_frame`):
+//   FrameSync (command buffer, semaphores, fence), material descriptor sets.
+// Per swapchain image (driver-chosen count, may change on every swapchain
+// recreation, indexed by the acquired image index):
+//   swapchain images + views, framebuffers, `images_in_flight` fences.
+//
+// Per-image vectors are rebuilt together after each swapchain (re)creation
+// (see `VulkanRenderer::resize_per_image_resources`).
+use std::time::Duration;
+
+use vulkanalia::VkResult;
+use vulkanalia::prelude::v1_0::*;
+
+use super::tracker::{ResourceKind, ResourceTracker};
+use crate::error::{AppError, Result};
+
+/// CPU may record this many frames ahead of the GPU.
+/// With 2, the CPU records frame N+1 while the GPU executes frame N.
+pub const MAX_FRAMES_IN_FLIGHT: usize = 2;
+
+/// Stage at which the frame submit waits for `image_available`. The swapchain
+/// image is first written as a color attachment (the render pass's external
+/// dependency starts at this stage too), so vertex shading, depth clears and the
+/// scene subpass may run while the presentation engine still owns the image.
+/// Waiting at TOP_OF_PIPE or ALL_COMMANDS would stall the whole frame on acquire.
+pub const ACQUIRE_WAIT_STAGE: vk::PipelineStageFlags =
+    vk::PipelineStageFlags::COLOR_ATTACHMENT_OUTPUT;
+
+/// `wait_for_fences` timeout in nanoseconds; anything past u64 waits forever.
+pub fn timeout_nanos(timeout: Duration) -> u64 {
+    u64::try_from(timeout.as_nanos()).unwrap_or(u64::MAX)
+}
+
+/// Map a fence wait's result to ours. Vulkan reports an expired timeout as a
+/// success code; it becomes `AppError::GpuTimeout`.
+pub fn check_fence_wait(
+    result: VkResult<vk::SuccessCode>,
+    timeout: Duration,
+    context: &'static str,
+) -> Result<()> {
+    match result {
+        Ok(vk::SuccessCode::TIMEOUT) => Err(AppError::GpuTimeout(context, timeout)),
+        Ok(_) => Ok(()),
+        Err(e) => Err(AppError::vk(e, context)),
+    }
+}
+
+/// Block until all `fences` signal, or fail after `timeout` (a hung GPU would
+/// otherwise freeze the app).
+pub fn wait_for_fences(
+    device: &Device,
+    fences: &[vk::Fence],
+    timeout: Duration,
+    context: &'static str,
+) -> Result<()> {
+    let result = unsafe { device.wait_for_fences(fences, true, timeout_nanos(timeout)) };
+    check_fence_wait(result, timeout, context)
+}
+
+/// Lengths of the per-swapchain-image vectors. All of them follow the image
+/// count `get_swapchain_images_khr` returned, which may exceed the
+/// `min_image_count` the swapchain was created with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PerImageCounts {
+    pub images: usize, // Returned by the driver
+    pub views: usize,
+    pub framebuffers: usize,
+    pub in_flight: usize, // `images_in_flight` fences
+}
+
+impl PerImageCounts {
+    /// First vector not sized to `images`, with its length.
+    pub fn mismatch(&self) -> Option<(&'static str, usize)> {
+        [
+            ("image views", self.views),
+            ("framebuffers", self.framebuffers),
+            ("image fences", self.in_flight),
+        ]
+        .into_iter()
+        .find(|&(_, len)| len != self.images)
+    }
+}
+
+/// Sync objects and command buffer owned by one frame in flight.
+#[derive(Debug, Clone, Copy)]
+pub struct FrameSync {
+    pub image_available: vk::Semaphore, // Signaled by acquire, waited by submit
+    pub render_finished: vk::Semaphore, // Signaled by submit, waited by present
+    pub in_flight: vk::Fence,           // Signaled when the GPU is done with this frame
+    pub cmd: vk::CommandBuffer,         // Re-recorded every frame
+}
+
+impl FrameSync {
+    pub fn create(
+        device: &Device,
+        cmd: vk::CommandBuffer,
+        tracker: &ResourceTracker,
+    ) -> Result<Self> {
+        let semaphore_info = vk::SemaphoreCreateInfo::builder();
+        // Start signaled so the first wait doesn't block forever
+        let fence_info = vk::FenceCreateInfo::builder().flags(vk::FenceCreateFlags::SIGNALED);
+
+        let frame = unsafe {
+            Self {
+                image_available: device
+                    .create_semaphore(&semaphore_info, None)
+                    .map_err(|e| AppError::vk(e, "create image-available semaphore"))?,
+                render_finished: device
+                    .create_semaphore(&semaphore_info, None)
+                    .map_err(|e| AppError::vk(e, "create render-finished semaphore"))?,
+                in_flight: device
+                    .create_fence(&fence_info, None)
+                    .map_err(|e| AppError::vk(e, "create in-flight fence"))?,
+                cmd,
+            }
+        };
+        tracker.created(ResourceKind::FRAME_SYNC);
+        Ok(frame)
+    }
+
+    /// Destroy the sync objects; the command buffer goes with its pool.
+    pub fn destroy(&self, device: &Device, tracker: &ResourceTracker) {
+        unsafe {
+            device.destroy_semaphore(self.image_available, None);
+            device.destroy_semaphore(self.render_finished, None);
+            device.destroy_fence(self.in_flight, None);
+        }
+        tracker.destroyed(ResourceKind::FRAME_SYNC);
+    }
+
+    /// Semaphores the frame submit waits on, each paired with the earliest stage
+    /// that needs it (`wait_semaphores` / `wait_dst_stage_mask`).
+    pub fn submit_waits(&self) -> ([vk::Semaphore; 1], [vk::PipelineStageFlags; 1]) {
+        ([self.image_available], [ACQUIRE_WAIT_STAGE])
+    }
+}
@@ -0,0 +1,31 @@
+// src/core/renderer/backend/vulkan/fullscreen.rs
+//
+// Full-screen passes (tonemapping, post-processing, composites) draw a single
+// triangle large enough to cover the whole target: `shaders/fullscreen.vert`
+// derives its positions and UVs from gl_VertexIndex, so there is no vertex
+// buffer to bind and no diagonal seam a two-triangle quad would have. Pipelines
+// of such passes use an empty vertex input state and the fragment shader reads
+// the UV at location 0. Compile the shader ahead of time:
+//   glslc shaders/fullscreen.vert -o shaders/fullscreen.vert.spv
+use std::path::Path;
+
+use vulkanalia::prelude::v1_0::*;
+
+use super::shader::load_shader_module;
+use crate::error::Result;
+
+/// File name of the compiled full-screen vertex shader inside a shader directory.
+pub const FULLSCREEN_VERTEX_SHADER: &str = "fullscreen.vert.spv";
+
+pub fn load_fullscreen_vertex_shader(
+    device: &Device,
+    shader_dir: &Path,
+) -> Result<vk::ShaderModule> {
+    load_shader_module(device, &shader_dir.join(FULLSCREEN_VERTEX_SHADER))
+}
+
+/// Record the full-screen draw: three vertices, nothing bound but the pipeline
+/// (and whatever descriptors / push constants its fragment shader needs).
+pub fn draw_fullscreen_triangle(device: &Device, cmd: vk::CommandBuffer) {
+    unsafe { device.cmd_draw(cmd, 3, 1, 0, 0) };
+}
@@ -0,0 +1,415 @@
+// src/core/renderer/backend/vulkan/headless.rs
+//
+// Renderer without a window: an instance and device without any surface or
+// swapchain extension, rendering into offscreen images that are copied back to
+// host memory. Builds without winit, so it's what the `headless` feature offers
+// for server-side rendering and render farms, and `SelectedRenderer` when the
+// windowed `vulkan` backend isn't compiled in.
+#[cfg(all(debug_assertions, feature = "debug-utils"))]
+use std::ffi::CStr;
+use std::sync::Arc;
+
+use log::info;
+use smallvec::SmallVec;
+use vulkanalia::loader::{LIBRARY, LibloadingLoader};
+use vulkanalia::prelude::v1_0::*;
+use vulkanalia::vk::Handle;
+
+use super::barrier::buffer_barrier;
+use super::buffer::Buffer;
+use super::context::DeviceContext;
+#[cfg(all(debug_assertions, feature = "debug-utils"))]
+use super::debug::{
+    build_debug_messenger_ci, create_debug_messenger, destroy_debug_messenger, open_debug_sink,
+};
+use super::frame::wait_for_fences;
+use super::image::ImageDesc;
+use super::init::{application_info, application_names};
+use super::render_pass::{ClearValue, ColorLoadOp, RenderPassBuilder};
+use super::texture::Texture;
+use super::transfer::color_layers;
+use crate::core::renderer::config::RendererConfig;
+use crate::core::renderer::log_targets::RENDERER;
+use crate::error::{AppError, Result};
+
+/// Format of the offscreen target; its byte order is plain RGBA8.
+pub const HEADLESS_FORMAT: vk::Format = vk::Format::R8G8B8A8_UNORM;
+
+/// Offscreen renderer: `render` draws into a fresh `HEADLESS_FORMAT` image and
+/// returns its pixels, tightly packed RGBA8 rows from the top.
+pub struct HeadlessRenderer {
+    config: RendererConfig,
+    context: Option<Arc<DeviceContext>>,
+    queue: vk::Queue,
+    queue_family: u32,
+    command_pool: vk::CommandPool,
+    fence: vk::Fence,
+    render_pass: vk::RenderPass, // One cleared color attachment, left in TRANSFER_SRC
+}
+
+impl HeadlessRenderer {
+    /// Construct an uninitialized renderer from a validated config.
+    pub fn new(config: RendererConfig) -> Self {
+        Self {
+            config,
+            context: None,
+            queue: vk::Queue::null(),
+            queue_family: 0,
+            command_pool: vk::CommandPool::null(),
+            fence: vk::Fence::null(),
+            render_pass: vk::RenderPass::null(),
+        }
+    }
+
+    /// Create the instance, pick the first GPU with a graphics queue and create
+    /// the device and the objects every `render` reuses.
+    pub fn initialize(&mut self) -> Result<()> {
+        if self.is_initialized() {
+            return Ok(());
+        }
+        let loader = match &self.config.vulkan_library {
+            Some(path) => unsafe { LibloadingLoader::new(path) }?,
+            None => unsafe { LibloadingLoader::new(LIBRARY) }?,
+        };
+        let entry = unsafe { Entry::new(loader) }?;
+
+        #[allow(unused_mut)]
+        let mut exts: SmallVec<[*const i8; 4]> = SmallVec::new();
+        #[allow(unused_mut)]
+        let mut layers: SmallVec<[*const i8; 1]> = SmallVec::new();
+        #[cfg(all(debug_assertions, feature = "debug-utils"))]
+        {
+            exts.push(vk::EXT_DEBUG_UTILS_EXTENSION.name.as_ptr());
+            let has_validation_layer = unsafe { entry.enumerate_instance_layer_properties() }
+                .unwrap_or_default()
+                .iter()
+                .any(|p| {
+                    let name = unsafe { CStr::from_ptr(p.layer_name.as_ptr()) };
+                    name.to_bytes() == b"VK_LAYER_KHRONOS_validation"
+                });
+            if has_validation_layer {
+                layers.push(b"VK_LAYER_KHRONOS_validation\0".as_ptr() as *const i8);
+                info!(target: RENDERER, "✅ Validation layer enabled");
+            }
+        }
+        #[allow(unused_mut)]
+        let mut flags = vk::InstanceCreateFlags::empty();
+        #[cfg(target_os = "macos")]
+        {
+            exts.push(vk::KHR_PORTABILITY_ENUMERATION_EXTENSION.name.as_ptr());
+            flags |= vk::InstanceCreateFlags::ENUMERATE_PORTABILITY_KHR;
+        }
+
+        let api_version = unsafe { entry.enumerate_instance_version() }
+            .map_err(|e| AppError::vk(e, "query instance version"))?;
+        let (app_name, engine_name) = application_names(&self.config)?;
+        let app_info = application_info(&self.config, &app_name, &engine_name, api_version);
+        #[allow(unused_mut)]
+        let mut create_info = vk::InstanceCreateInfo::builder()
+            .application_info(&app_info)
+            .enabled_extension_names(&exts)
+            .enabled_layer_names(&layers)
+            .flags(flags);
+        #[cfg(all(debug_assertions, feature = "debug-utils"))]
+        let debug_log = open_debug_sink(&self.config.debug)?;
+        #[cfg(all(debug_assertions, feature = "debug-utils"))]
+        let mut debug_ci = build_debug_messenger_ci(debug_log.as_deref());
+        #[cfg(all(debug_assertions, feature = "debug-utils"))]
+        {
+            create_info = create_info.push_next(&mut debug_ci);
+        }
+        let instance = unsafe { entry.create_instance(&create_info, None) }
+            .map_err(|e| AppError::vk(e, "create headless instance"))?;
+        #[cfg(all(debug_assertions, feature = "debug-utils"))]
+        let debug = match create_debug_messenger(&instance, &debug_ci) {
+            Ok(messenger) => Some(messenger),
+            Err(e) => {
+                unsafe { instance.destroy_instance(None) };
+                return Err(e);
+            }
+        };
+
+        // Nothing owns the instance until the context does
+        let destroy_instance = |instance: &Instance| unsafe {
+            #[cfg(all(debug_assertions, feature = "debug-utils"))]
+            if let Some(debug) = &debug {
+                destroy_debug_messenger(instance, debug);
+            }
+            instance.destroy_instance(None);
+        };
+        let (physical_device, queue_family) = match pick_graphics_device(&instance) {
+            Ok(picked) => picked,
+            Err(e) => {
+                destroy_instance(&instance);
+                return Err(e);
+            }
+        };
+        let priorities = [1.0];
+        let queue_infos = [vk::DeviceQueueCreateInfo::builder()
+            .queue_family_index(queue_family)
+            .queue_priorities(&priorities)
+            .build()];
+        let device_info = vk::DeviceCreateInfo::builder().queue_create_infos(&queue_infos);
+        let device = match unsafe { instance.create_device(physical_device, &device_info, None) } {
+            Ok(device) => device,
+            Err(e) => {
+                destroy_instance(&instance);
+                return Err(AppError::vk(e, "create headless device"));
+            }
+        };
+        let name = unsafe { instance.get_physical_device_properties(physical_device) }
+            .device_name
+            .to_string_lossy()
+            .into_owned();
+
+        // The context keeps the library loaded for as long as any clone lives
+        let context = DeviceContext::new(entry, instance, device, physical_device);
+        #[cfg(all(debug_assertions, feature = "debug-utils"))]
+        let context = context.with_debug_messenger(debug, debug_log);
+        self.queue = unsafe { context.device.get_device_queue(queue_family, 0) };
+        self.queue_family = queue_family;
+        self.context = Some(Arc::new(context));
+        if let Err(e) = self.create_frame_objects() {
+            self.cleanup();
+            return Err(e);
+        }
+        info!(target: RENDERER, "✅ Headless renderer ready on {name}");
+        Ok(())
+    }
+
+    pub fn is_initialized(&self) -> bool {
+        self.context.is_some()
+    }
+
+    /// Device context, for creating resources (`Texture`, `Buffer`) of the
+    /// renderer's device.
+    pub fn context(&self) -> Option<&Arc<DeviceContext>> {
+        self.context.as_ref()
+    }
+
+    /// Graphics queue `render` submits to and its family, for passes recorded
+    /// outside `render`. Their work must be finished before the next `render`.
+    pub fn queue(&self) -> (vk::Queue, u32) {
+        (self.queue, self.queue_family)
+    }
+
+    /// Render pass `render` begins; pipelines drawn in its `record` callback
+    /// must be created for subpass 0 of it.
+    pub fn render_pass(&self) -> vk::RenderPass {
+        self.render_pass
+    }
+
+    /// Clear an `extent` image to `clear`, run `record` inside the render pass,
+    /// then copy the image back. Blocks until the GPU is done.
+    pub fn render(
+        &mut self,
+        extent: vk::Extent2D,
+        clear: [f32; 4],
+        record: impl FnOnce(&Device, vk::CommandBuffer),
+    ) -> Result<Vec<u8>> {
+        let context = self.context.clone().ok_or(AppError::NotInitialized)?;
+        let device = &context.device;
+        let target = Texture::new(
+            &context,
+            ImageDesc {
+                extent,
+                format: HEADLESS_FORMAT,
+                samples: vk::SampleCountFlags::_1,
+                usage: vk::ImageUsageFlags::COLOR_ATTACHMENT | vk::ImageUsageFlags::TRANSFER_SRC,
+                aspect: vk::ImageAspectFlags::COLOR,
+            },
+        )?;
+        let size = u64::from(extent.width) * u64::from(extent.height) * 4;
+        let readback = Buffer::new(
+            &context,
+            size,
+            vk::BufferUsageFlags::TRANSFER_DST,
+            vk::MemoryPropertyFlags::HOST_VISIBLE | vk::MemoryPropertyFlags::HOST_COHERENT,
+        )?;
+        let attachments = [target.view()];
+        let fb_info = vk::FramebufferCreateInfo::builder()
+            .render_pass(self.render_pass)
+            .attachments(&attachments)
+            .width(extent.width)
+            .height(extent.height)
+            .layers(1);
+        let framebuffer = unsafe { device.create_framebuffer(&fb_info, None) }
+            .map_err(|e| AppError::vk(e, "create headless framebuffer"))?;
+
+        let result = self.submit(device, framebuffer, &target, &readback, clear, record);
+        if !matches!(result, Err(AppError::GpuTimeout(..))) {
+            // A timed-out frame may still be using it
+            unsafe { device.destroy_framebuffer(framebuffer, None) };
+        }
+        result?;
+        let mut pixels = vec![0; size as usize];
+        readback.read(&mut pixels)?;
+        Ok(pixels)
+    }
+
+    /// `render` with nothing drawn: every pixel is `clear` as RGBA8.
+    pub fn clear(&mut self, extent: vk::Extent2D, clear: [f32; 4]) -> Result<Vec<u8>> {
+        self.render(extent, clear, |_, _| {})
+    }
+
+    /// Wait for the GPU and destroy everything; the device, the instance and the
+    /// loaded library go with the last `Arc<DeviceContext>` clone. Safe to call twice.
+    pub fn cleanup(&mut self) {
+        let Some(context) = self.context.take() else {
+            return;
+        };
+        let device = &context.device;
+        unsafe {
+            device.device_wait_idle().ok();
+            device.destroy_render_pass(self.render_pass, None);
+            device.destroy_fence(self.fence, None);
+            device.destroy_command_pool(self.command_pool, None);
+        }
+        self.render_pass = vk::RenderPass::null();
+        self.fence = vk::Fence::null();
+        self.command_pool = vk::CommandPool::null();
+        self.queue = vk::Queue::null();
+        drop(context);
+    }
+
+    fn create_frame_objects(&mut self) -> Result<()> {
+        let device = &self
+            .context
+            .as_ref()
+            .ok_or(AppError::NotInitialized)?
+            .device;
+        let pool_info = vk::CommandPoolCreateInfo::builder()
+            .flags(vk::CommandPoolCreateFlags::TRANSIENT)
+            .queue_family_index(self.queue_family);
+        self.command_pool = unsafe { device.create_command_pool(&pool_info, None) }
+            .map_err(|e| AppError::vk(e, "create headless command pool"))?;
+        self.fence = unsafe { device.create_fence(&vk::FenceCreateInfo::default(), None) }
+            .map_err(|e| AppError::vk(e, "create headless fence"))?;
+
+        // The pass moves the image into TRANSFER_SRC for the copy
+        self.render_pass = RenderPassBuilder::new()
+            .color_attachment(
+                HEADLESS_FORMAT,
+                ColorLoadOp::Clear([0.0; 4]),
+                vk::ImageLayout::TRANSFER_SRC_OPTIMAL,
+            )
+            .subpass()
+            .color(0)
+            .dependency(
+                vk::SubpassDependency::builder()
+                    .src_subpass(0)
+                    .dst_subpass(vk::SUBPASS_EXTERNAL)
+                    .src_stage_mask(vk::PipelineStageFlags::COLOR_ATTACHMENT_OUTPUT)
+                    .src_access_mask(vk::AccessFlags::COLOR_ATTACHMENT_WRITE)
+                    .dst_stage_mask(vk::PipelineStageFlags::TRANSFER)
+                    .dst_access_mask(vk::AccessFlags::TRANSFER_READ)
+                    .build(),
+            )
+            .build(device)?;
+        Ok(())
+    }
+
+    /// Record the pass and the copy into `readback`, submit and wait.
+    fn submit(
+        &self,
+        device: &Device,
+        framebuffer: vk::Framebuffer,
+        target: &Texture,
+        readback: &Buffer,
+        clear: [f32; 4],
+        record: impl FnOnce(&Device, vk::CommandBuffer),
+    ) -> Result<()> {
+        let extent = target.extent();
+        unsafe {
+            device
+                .reset_command_pool(self.command_pool, vk::CommandPoolResetFlags::empty())
+                .map_err(|e| AppError::vk(e, "reset headless command pool"))?;
+            device
+                .reset_fences(&[self.fence])
+                .map_err(|e| AppError::vk(e, "reset headless fence"))?;
+            let alloc_info = vk::CommandBufferAllocateInfo::builder()
+                .command_pool(self.command_pool)
+                .level(vk::CommandBufferLevel::PRIMARY)
+                .command_buffer_count(1);
+            let cmd = device
+                .allocate_command_buffers(&alloc_info)
+                .map_err(|e| AppError::vk(e, "allocate headless command buffer"))?[0];
+            let begin_info = vk::CommandBufferBeginInfo::builder()
+                .flags(vk::CommandBufferUsageFlags::ONE_TIME_SUBMIT);
+            device
+                .begin_command_buffer(cmd, &begin_info)
+                .map_err(|e| AppError::vk(e, "begin headless command buffer"))?;
+
+            let clear_values: [vk::ClearValue; 1] = [ClearValue::Float(clear).into()];
+            let pass_info = vk::RenderPassBeginInfo::builder()
+                .render_pass(self.render_pass)
+                .framebuffer(framebuffer)
+                .render_area(vk::Rect2D::builder().extent(extent).build())
+                .clear_values(&clear_values);
+            device.cmd_begin_render_pass(cmd, &pass_info, vk::SubpassContents::INLINE);
+            record(device, cmd);
+            device.cmd_end_render_pass(cmd);
+
+            // Tightly packed rows (buffer_row_length 0), so the buffer is plain RGBA8
+            let region = vk::BufferImageCopy::builder()
+                .image_subresource(color_layers())
+                .image_extent(vk::Extent3D {
+                    width: extent.width,
+                    height: extent.height,
+                    depth: 1,
+                });
+            device.cmd_copy_image_to_buffer(
+                cmd,
+                target.image(),
+                vk::ImageLayout::TRANSFER_SRC_OPTIMAL,
+                readback.handle(),
+                &[region],
+            );
+            buffer_barrier(
+                device,
+                cmd,
+                readback.handle(),
+                vk::AccessFlags::TRANSFER_WRITE,
+                vk::AccessFlags::HOST_READ,
+            )?;
+            device
+                .end_command_buffer(cmd)
+                .map_err(|e| AppError::vk(e, "end headless command buffer"))?;
+
+            let cmds = [cmd];
+            let submit = vk::SubmitInfo::builder().command_buffers(&cmds);
+            device
+                .queue_submit(self.queue, &[submit], self.fence)
+                .map_err(|e| AppError::vk(e, "submit headless frame"))?;
+        }
+        wait_for_fences(
+            device,
+            &[self.fence],
+            self.config.fence_timeout,
+            "headless frame",
+        )
+    }
+}
+
+impl Drop for HeadlessRenderer {
+    fn drop(&mut self) {
+        self.cleanup();
+    }
+}
+
+/// First GPU with a graphics queue, and that queue's family. Software drivers
+/// (lavapipe) qualify, so this works on CI machines without a GPU.
+fn pick_graphics_device(instance: &Instance) -> Result<(vk::PhysicalDevice, u32)> {
+    let devices = unsafe { instance.enumerate_physical_devices() }
+        .map_err(|e| AppError::vk(e, "enumerate physical devices"))?;
+    devices
+        .into_iter()
+        .find_map(|dev| {
+            let families = unsafe { instance.get_physical_device_queue_family_properties(dev) };
+            families
+                .iter()
+                .position(|f| f.queue_flags.contains(vk::QueueFlags::GRAPHICS))
+                .map(|family| (dev, family as u32))
+        })
+        .ok_or_else(|| AppError::Config("no GPU with a graphics queue".into()))
+}
@@ -0,0 +1,146 @@
+// src/core/renderer/backend/vulkan/image.rs
+use vulkanalia::prelude::v1_0::*;
+
+use super::tracker::{ResourceKind, ResourceTracker};
+use crate::error::{AppError, Result};
+
+/// What to create in `AllocatedImage::new`.
+#[derive(Debug, Clone, Copy)]
+pub struct ImageDesc {
+    pub extent: vk::Extent2D,
+    pub format: vk::Format,
+    pub samples: vk::SampleCountFlags,
+    pub usage: vk::ImageUsageFlags,
+    pub aspect: vk::ImageAspectFlags,
+}
+
+/// 2D image + its own device-local memory + a view over the whole image.
+#[derive(Debug, Clone, Copy)]
+pub struct AllocatedImage {
+    pub image: vk::Image,
+    pub memory: vk::DeviceMemory,
+    pub view: vk::ImageView,
+    pub format: vk::Format,
+    pub extent: vk::Extent2D,
+    pub size: vk::DeviceSize, // Bytes of `memory`
+}
+
+impl AllocatedImage {
+    pub fn new(
+        instance: &Instance,
+        device: &Device,
+        physical_device: vk::PhysicalDevice,
+        desc: ImageDesc,
+        tracker: &ResourceTracker,
+    ) -> Result<Self> {
+        let image_info = vk::ImageCreateInfo::builder()
+            .image_type(vk::ImageType::_2D)
+            .format(desc.format)
+            .extent(vk::Extent3D {
+                width: desc.extent.width,
+                height: desc.extent.height,
+                depth: 1,
+            })
+            .mip_levels(1)
+            .array_layers(1)
+            .samples(desc.samples)
+            .tiling(vk::ImageTiling::OPTIMAL)
+            .usage(desc.usage)
+            .sharing_mode(vk::SharingMode::EXCLUSIVE)
+            .initial_layout(vk::ImageLayout::UNDEFINED);
+
+        let image = unsafe { device.create_image(&image_info, None) }
+            .map_err(|e| AppError::vk(e, "create image"))?;
+
+        let requirements = unsafe { device.get_image_memory_requirements(image) };
+        let memory_type = match find_memory_type(
+            instance,
+            physical_device,
+            requirements.memory_type_bits,
+            vk::MemoryPropertyFlags::DEVICE_LOCAL,
+        ) {
+            Ok(index) => index,
+            Err(e) => {
+                unsafe { device.destroy_image(image, None) };
+                return Err(e);
+            }
+        };
+
+        let alloc_info = vk::MemoryAllocateInfo::builder()
+            .allocation_size(requirements.size)
+            .memory_type_index(memory_type);
+        let memory = match unsafe { device.allocate_memory(&alloc_info, None) } {
+            Ok(memory) => memory,
+            Err(e) => {
+                unsafe { device.destroy_image(image, None) };
+                return Err(AppError::vk(e, "allocate image memory"));
+            }
+        };
+
+        let view = unsafe { device.bind_image_memory(image, memory, 0) }
+            .and_then(|_| {
+                let view_info = vk::ImageViewCreateInfo::builder()
+                    .image(image)
+                    .view_type(vk::ImageViewType::_2D)
+                    .format(desc.format)
+                    .subresource_range(
+                        vk::ImageSubresourceRange::builder()
+                            .aspect_mask(desc.aspect)
+                            .base_mip_level(0)
+                            .level_count(1)
+                            .base_array_layer(0)
+                            .layer_count(1)
+                            .build(),
+                    );
+                unsafe { device.create_image_view(&view_info, None) }
+            })
+            .map_err(|e| {
+                unsafe {
+                    device.destroy_image(image, None);
+                    device.free_memory(memory, None);
+                }
+                AppError::vk(e, "bind image memory / create view")
+            })?;
+
+        tracker.created(ResourceKind::ALLOCATED_IMAGE);
+        Ok(Self {
+            image,
+            memory,
+            view,
+            format: desc.format,
+            extent: desc.extent,
+            size: requirements.size,
+        })
+    }
+
+    pub fn destroy(&self, device: &Device, tracker: &ResourceTracker) {
+        unsafe {
+            device.destroy_image_view(self.view, None);
+            device.destroy_image(self.image, None);
+            device.free_memory(self.memory, None);
+        }
+        tracker.destroyed(ResourceKind::ALLOCATED_IMAGE);
+    }
+}
+
+/// Index of a memory type allowed by `type_bits` that has all of `properties`.
+pub fn find_memory_type(
+    instance: &Instance,
+    physical_device: vk::PhysicalDevice,
+    type_bits: u32,
+    properties: vk::MemoryPropertyFlags,
+) -> Result<u32> {
+    let memory = unsafe { instance.get_physical_device_memory_properties(physical_device) };
+    (0..memory.memory_type_count)
+        .find(|&i| {
+            type_bits & (1 << i) != 0
+                && memory.memory_types[i as usize]
+                    .property_flags
+                    .contains(properties)
+        })
+        .ok_or_else(|| {
+            AppError::Config(format!(
+                "no memory type with {properties:?} (mask {type_bits:#b})"
+            ))
+        })
+}
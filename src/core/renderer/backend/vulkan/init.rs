@@ -0,0 +1,97 @@
+// src/core/renderer/backend/vulkan/init.rs
+//
+// Phases of `VulkanRenderer::initialize`, reported to an optional progress
+// callback (`VulkanRenderer::set_init_progress`) as each one starts. On slow
+// drivers instance and device creation alone can take seconds; the callback
+// lets a loading screen or log show that something is happening, and the last
+// phase reported before an error is the one that failed. Also the application
+// info both `VulkanRenderer` and `HeadlessRenderer` create their instance with.
+use std::ffi::{CStr, CString};
+use std::fmt;
+
+use vulkanalia::prelude::v1_0::*;
+
+use crate::core::renderer::config::RendererConfig;
+use crate::error::{AppError, Result};
+
+/// Step of `initialize`, in execution order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum InitPhase {
+    Instance,       // Load the Vulkan library, create the instance (+ debug messenger)
+    Surface,        // Window surface
+    Device,         // Pick the GPU, create the logical device and queues
+    Swapchain,      // Swapchain and its image views
+    RenderTargets,  // MSAA / HDR / depth images
+    RenderPass,     // Render pass (and tonemap pipeline)
+    Framebuffers,   // One framebuffer per swapchain image
+    FrameResources, // Command pools, per-frame buffers and sync objects
+    Ready,          // Done, the first frame can be rendered
+}
+
+impl InitPhase {
+    /// Every phase, in the order `initialize` reports them.
+    pub const ALL: [InitPhase; 9] = [
+        InitPhase::Instance,
+        InitPhase::Surface,
+        InitPhase::Device,
+        InitPhase::Swapchain,
+        InitPhase::RenderTargets,
+        InitPhase::RenderPass,
+        InitPhase::Framebuffers,
+        InitPhase::FrameResources,
+        InitPhase::Ready,
+    ];
+
+    /// Number of phases, for progress bars (`index() + 1` of `COUNT`).
+    pub const COUNT: usize = Self::ALL.len();
+
+    /// Position in `ALL`.
+    pub fn index(self) -> usize {
+        self as usize
+    }
+}
+
+impl fmt::Display for InitPhase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            InitPhase::Instance => "instance",
+            InitPhase::Surface => "surface",
+            InitPhase::Device => "device",
+            InitPhase::Swapchain => "swapchain",
+            InitPhase::RenderTargets => "render targets",
+            InitPhase::RenderPass => "render pass",
+            InitPhase::Framebuffers => "framebuffers",
+            InitPhase::FrameResources => "frame resources",
+            InitPhase::Ready => "ready",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Called with each phase and its index as it starts.
+pub type InitProgress = Box<dyn FnMut(InitPhase, usize)>;
+
+/// NUL-terminated app and engine names of `config`, for `application_info`.
+pub fn application_names(config: &RendererConfig) -> Result<(CString, CString)> {
+    let app_name = CString::new(config.app_name.as_str())
+        .map_err(|_| AppError::Config("app_name must not contain NUL bytes".into()))?;
+    let engine_name = CString::new(config.engine_name.as_str())
+        .map_err(|_| AppError::Config("engine_name must not contain NUL bytes".into()))?;
+    Ok((app_name, engine_name))
+}
+
+/// `ApplicationInfo` with the names and versions of `config`, requesting
+/// `api_version`.
+pub fn application_info<'a>(
+    config: &RendererConfig,
+    app_name: &'a CStr,
+    engine_name: &'a CStr,
+    api_version: u32,
+) -> vk::ApplicationInfoBuilder<'a> {
+    vk::ApplicationInfo::builder()
+        .application_name(app_name.to_bytes_with_nul())
+        .application_version(config.app_version)
+        .engine_name(engine_name.to_bytes_with_nul())
+        .engine_version(config.engine_version)
+        .api_version(api_version)
+}
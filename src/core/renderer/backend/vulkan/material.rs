@@ -0,0 +1,96 @@
+// src/core/renderer/backend/vulkan/material.rs
+//
+// A material owns one descriptor set per frame in flight. Texture changes are
+// queued per frame slot and written only once that slot's fence has signaled,
+// so a set the GPU may still be reading is never updated.
+use vulkanalia::prelude::v1_0::*;
+use vulkanalia::vk::Handle;
+
+use super::descriptor::write_image;
+use super::frame::MAX_FRAMES_IN_FLIGHT;
+
+/// Handle returned by `VulkanRenderer::create_material`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MaterialId(usize);
+
+/// Image + sampler bound at binding 0 (COMBINED_IMAGE_SAMPLER) of a material set.
+/// The image must be in SHADER_READ_ONLY_OPTIMAL when sampled. Leave `sampler`
+/// null when the layout bakes in an immutable sampler.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MaterialTexture {
+    pub view: vk::ImageView,
+    pub sampler: vk::Sampler,
+}
+
+#[derive(Debug)]
+struct Material {
+    sets: [vk::DescriptorSet; MAX_FRAMES_IN_FLIGHT],
+    pending: [Option<MaterialTexture>; MAX_FRAMES_IN_FLIGHT], // Written at the slot's next flush
+}
+
+/// All materials of a renderer. Sets come from the renderer's descriptor
+/// allocator and are freed with its pools.
+#[derive(Debug, Default)]
+pub struct MaterialSets {
+    materials: Vec<Material>,
+}
+
+impl MaterialSets {
+    /// Register `sets` (one per frame slot), writing `texture` into all of them.
+    /// Only call while none of the sets is in use, i.e. right after allocation.
+    pub fn insert(
+        &mut self,
+        device: &Device,
+        sets: [vk::DescriptorSet; MAX_FRAMES_IN_FLIGHT],
+        texture: MaterialTexture,
+    ) -> MaterialId {
+        for set in sets {
+            write_texture(device, set, texture);
+        }
+        self.materials.push(Material {
+            sets,
+            pending: [None; MAX_FRAMES_IN_FLIGHT],
+        });
+        MaterialId(self.materials.len() - 1)
+    }
+
+    /// Queue `texture` for every frame slot; each slot picks it up in `flush`.
+    /// Returns false for an unknown id.
+    pub fn set_texture(&mut self, id: MaterialId, texture: MaterialTexture) -> bool {
+        let Some(material) = self.materials.get_mut(id.0) else {
+            return false;
+        };
+        material.pending = [Some(texture); MAX_FRAMES_IN_FLIGHT];
+        true
+    }
+
+    /// Apply queued updates of `frame`'s sets.
+    /// Only call once `frame`'s in-flight fence has signaled.
+    pub fn flush(&mut self, device: &Device, frame: usize) {
+        for material in &mut self.materials {
+            if let Some(texture) = material.pending[frame].take() {
+                write_texture(device, material.sets[frame], texture);
+            }
+        }
+    }
+
+    /// Set to bind for `id` while recording `frame`.
+    pub fn set(&self, id: MaterialId, frame: usize) -> Option<vk::DescriptorSet> {
+        self.materials.get(id.0).map(|m| m.sets[frame])
+    }
+
+    /// Whether `frame` still has updates that `flush` hasn't applied.
+    pub fn has_pending(&self, frame: usize) -> bool {
+        self.materials.iter().any(|m| m.pending[frame].is_some())
+    }
+
+    /// Forget every material; their sets go with the descriptor pools.
+    pub fn clear(&mut self) {
+        self.materials.clear();
+    }
+}
+
+fn write_texture(device: &Device, set: vk::DescriptorSet, texture: MaterialTexture) {
+    let sampler = (!texture.sampler.is_null()).then_some(texture.sampler);
+    write_image(device, set, 0, texture.view, sampler);
+}
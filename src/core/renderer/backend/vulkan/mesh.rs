@@ -0,0 +1,475 @@
+// src/core/renderer/backend/vulkan/mesh.rs
+//
+// Indexed meshes drawn by the built-in mesh pipeline into the scene subpass.
+// Shaders live in `shaders/` and are compiled ahead of time:
+//   glslc shaders/mesh.vert -o shaders/mesh.vert.spv
+//   glslc shaders/mesh.frag -o shaders/mesh.frag.spv
+// `PipelineDesc::fragment` swaps the fragment shader (e.g. `pick.frag` for
+// picking), `PipelineDesc::with_stage` adds geometry / tessellation shaders.
+use std::path::Path;
+use std::sync::Arc;
+
+use smallvec::SmallVec;
+use vulkanalia::prelude::v1_0::*;
+use vulkanalia::vk::Handle;
+
+use super::buffer::Buffer;
+use super::context::DeviceContext;
+use super::pipelines::PipelineDesc;
+use super::shader::{ShaderStage, SpecializationMap, load_shader_module};
+use crate::core::renderer::config::{
+    CompareOp, CullMode, PolygonMode, StencilOp, StencilState, Topology,
+};
+use crate::error::{AppError, Result};
+
+/// Push constant bytes the mesh pipeline layout reserves: the spec's guaranteed
+/// minimum of `maxPushConstantsSize`. `mesh.vert` reads a mat4 at offset 0.
+pub const MESH_PUSH_SIZE: usize = 128;
+
+/// Index that ends the current strip when `RendererConfig::primitive_restart` is on.
+pub const PRIMITIVE_RESTART_INDEX: u32 = u32::MAX;
+
+/// Vertex layout of `mesh.vert` (locations 0 and 1).
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Vertex {
+    pub position: [f32; 3],
+    pub color: [f32; 3],
+}
+
+impl Vertex {
+    pub const fn new(position: [f32; 3], color: [f32; 3]) -> Self {
+        Self { position, color }
+    }
+}
+
+struct MeshBuffers {
+    vertices: Buffer,
+    indices: Buffer,
+    index_count: u32,
+    restarts: bool, // Contains PRIMITIVE_RESTART_INDEX
+}
+
+/// Host-visible vertex + index buffers. Cheap to clone; frames that draw the mesh
+/// hold a clone until their fence signals, so dropping it early is safe.
+#[derive(Clone)]
+pub struct Mesh {
+    buffers: Arc<MeshBuffers>,
+}
+
+impl Mesh {
+    pub fn new(context: &Arc<DeviceContext>, vertices: &[Vertex], indices: &[u32]) -> Result<Self> {
+        if vertices.is_empty() || indices.is_empty() {
+            return Err(AppError::Config("mesh needs vertices and indices".into()));
+        }
+        let out_of_range = |i: u32| i as usize >= vertices.len() && i != PRIMITIVE_RESTART_INDEX;
+        if let Some(&bad) = indices.iter().find(|&&i| out_of_range(i)) {
+            return Err(AppError::Config(format!(
+                "mesh index {bad} out of range ({} vertices)",
+                vertices.len()
+            )));
+        }
+        let host = vk::MemoryPropertyFlags::HOST_VISIBLE | vk::MemoryPropertyFlags::HOST_COHERENT;
+
+        let vertex_bytes = as_bytes(vertices);
+        let vertex_buffer = Buffer::new(
+            context,
+            vertex_bytes.len() as vk::DeviceSize,
+            vk::BufferUsageFlags::VERTEX_BUFFER,
+            host,
+        )?;
+        vertex_buffer.write(vertex_bytes)?;
+
+        let index_bytes = as_bytes(indices);
+        let index_buffer = Buffer::new(
+            context,
+            index_bytes.len() as vk::DeviceSize,
+            vk::BufferUsageFlags::INDEX_BUFFER,
+            host,
+        )?;
+        index_buffer.write(index_bytes)?;
+
+        Ok(Self {
+            buffers: Arc::new(MeshBuffers {
+                vertices: vertex_buffer,
+                indices: index_buffer,
+                index_count: indices.len() as u32,
+                restarts: indices.contains(&PRIMITIVE_RESTART_INDEX),
+            }),
+        })
+    }
+
+    pub fn index_count(&self) -> u32 {
+        self.buffers.index_count
+    }
+
+    /// Identity of the uploaded buffers: equal for clones, distinct between
+    /// meshes alive at the same time.
+    pub(crate) fn id(&self) -> usize {
+        Arc::as_ptr(&self.buffers) as usize
+    }
+
+    /// Whether the indices contain `PRIMITIVE_RESTART_INDEX`.
+    pub fn uses_restart(&self) -> bool {
+        self.buffers.restarts
+    }
+
+    /// Bind both buffers and draw every index; a mesh pipeline must be bound.
+    pub fn draw(&self, device: &Device, cmd: vk::CommandBuffer) {
+        unsafe {
+            device.cmd_bind_vertex_buffers(cmd, 0, &[self.buffers.vertices.handle()], &[0]);
+            device.cmd_bind_index_buffer(
+                cmd,
+                self.buffers.indices.handle(),
+                0,
+                vk::IndexType::UINT32,
+            );
+            device.cmd_draw_indexed(cmd, self.buffers.index_count, 1, 0, 0, 0);
+        }
+    }
+}
+
+/// Input assembly for `topology`; restart is dropped for list topologies,
+/// where Vulkan forbids it.
+pub fn input_assembly(
+    topology: Topology,
+    primitive_restart: bool,
+) -> vk::PipelineInputAssemblyStateCreateInfo {
+    let topology_vk = match topology {
+        Topology::TriangleList => vk::PrimitiveTopology::TRIANGLE_LIST,
+        Topology::TriangleStrip => vk::PrimitiveTopology::TRIANGLE_STRIP,
+        Topology::LineList => vk::PrimitiveTopology::LINE_LIST,
+        Topology::LineStrip => vk::PrimitiveTopology::LINE_STRIP,
+        Topology::PointList => vk::PrimitiveTopology::POINT_LIST,
+    };
+    vk::PipelineInputAssemblyStateCreateInfo::builder()
+        .topology(topology_vk)
+        .primitive_restart_enable(primitive_restart && topology.is_strip())
+        .build()
+}
+
+/// Rasterization state for `polygon_mode` / `cull_mode`. Front faces are
+/// counter-clockwise; the viewport Y-flip reverses winding (see viewport.rs).
+pub fn rasterization(
+    polygon_mode: PolygonMode,
+    cull_mode: CullMode,
+) -> vk::PipelineRasterizationStateCreateInfo {
+    let polygon_mode_vk = match polygon_mode {
+        PolygonMode::Fill => vk::PolygonMode::FILL,
+        PolygonMode::Line => vk::PolygonMode::LINE,
+        PolygonMode::Point => vk::PolygonMode::POINT,
+    };
+    let cull_mode_vk = match cull_mode {
+        CullMode::None => vk::CullModeFlags::NONE,
+        CullMode::Front => vk::CullModeFlags::FRONT,
+        CullMode::Back => vk::CullModeFlags::BACK,
+    };
+    vk::PipelineRasterizationStateCreateInfo::builder()
+        .polygon_mode(polygon_mode_vk)
+        .cull_mode(cull_mode_vk)
+        .front_face(vk::FrontFace::COUNTER_CLOCKWISE)
+        .line_width(1.0)
+        .build()
+}
+
+/// Depth-stencil state of mesh pipelines: depth tested and written (LESS), plus
+/// `stencil` for both faces if given. The reference value is left 0 for
+/// `vk::DynamicState::STENCIL_REFERENCE` to set.
+pub fn depth_stencil_state(
+    stencil: Option<StencilState>,
+) -> vk::PipelineDepthStencilStateCreateInfo {
+    let info = vk::PipelineDepthStencilStateCreateInfo::builder()
+        .depth_test_enable(true)
+        .depth_write_enable(true)
+        .depth_compare_op(vk::CompareOp::LESS);
+    let Some(stencil) = stencil else {
+        return info.build();
+    };
+    let face = vk::StencilOpState::builder()
+        .compare_op(compare_op_vk(stencil.compare))
+        .fail_op(stencil_op_vk(stencil.fail))
+        .pass_op(stencil_op_vk(stencil.pass))
+        .depth_fail_op(stencil_op_vk(stencil.depth_fail))
+        .compare_mask(u32::from(stencil.compare_mask))
+        .write_mask(u32::from(stencil.write_mask))
+        .build();
+    info.stencil_test_enable(true)
+        .front(face)
+        .back(face)
+        .build()
+}
+
+fn compare_op_vk(op: CompareOp) -> vk::CompareOp {
+    match op {
+        CompareOp::Never => vk::CompareOp::NEVER,
+        CompareOp::Less => vk::CompareOp::LESS,
+        CompareOp::Equal => vk::CompareOp::EQUAL,
+        CompareOp::LessOrEqual => vk::CompareOp::LESS_OR_EQUAL,
+        CompareOp::Greater => vk::CompareOp::GREATER,
+        CompareOp::NotEqual => vk::CompareOp::NOT_EQUAL,
+        CompareOp::GreaterOrEqual => vk::CompareOp::GREATER_OR_EQUAL,
+        CompareOp::Always => vk::CompareOp::ALWAYS,
+    }
+}
+
+fn stencil_op_vk(op: StencilOp) -> vk::StencilOp {
+    match op {
+        StencilOp::Keep => vk::StencilOp::KEEP,
+        StencilOp::Zero => vk::StencilOp::ZERO,
+        StencilOp::Replace => vk::StencilOp::REPLACE,
+        StencilOp::IncrementAndClamp => vk::StencilOp::INCREMENT_AND_CLAMP,
+        StencilOp::DecrementAndClamp => vk::StencilOp::DECREMENT_AND_CLAMP,
+        StencilOp::Invert => vk::StencilOp::INVERT,
+        StencilOp::IncrementAndWrap => vk::StencilOp::INCREMENT_AND_WRAP,
+        StencilOp::DecrementAndWrap => vk::StencilOp::DECREMENT_AND_WRAP,
+    }
+}
+
+/// Split (buffer, byte offset) pairs into the parallel arrays
+/// `cmd_bind_vertex_buffers` takes.
+pub fn vertex_buffer_args(
+    buffers: &[(vk::Buffer, vk::DeviceSize)],
+) -> (SmallVec<[vk::Buffer; 4]>, SmallVec<[vk::DeviceSize; 4]>) {
+    buffers.iter().copied().unzip()
+}
+
+/// Check that `layout` (a pipeline's vertex input bindings) describes each of
+/// the `count` bindings from `first_binding` on, so buffers bound there are read.
+pub fn validate_vertex_bindings(
+    layout: &[vk::VertexInputBindingDescription],
+    first_binding: u32,
+    count: usize,
+) -> Result<()> {
+    for binding in (first_binding..).take(count) {
+        if !layout.iter().any(|desc| desc.binding == binding) {
+            return Err(AppError::Config(format!(
+                "vertex binding {binding} is bound but missing from the pipeline layout"
+            )));
+        }
+    }
+    Ok(())
+}
+
+fn as_bytes<T: Copy>(values: &[T]) -> &[u8] {
+    // Only used with padding-free #[repr(C)] vertex data and u32 indices
+    unsafe { std::slice::from_raw_parts(values.as_ptr().cast::<u8>(), size_of_val(values)) }
+}
+
+/// Pipeline drawing `Mesh`es with the shaders of a `PipelineDesc` (the built-in
+/// mesh shaders by default), tied to a render pass like `TonemapPass`.
+#[derive(Debug)]
+pub struct MeshPipeline {
+    modules: SmallVec<[(ShaderStage, vk::ShaderModule); 4]>, // `PipelineDesc::shader_stages`
+    push_stages: vk::ShaderStageFlags,                       // Every stage, push constants included
+    layout: vk::PipelineLayout,
+    pipeline: vk::Pipeline,
+    topology: Topology,
+    primitive_restart: bool,
+    polygon_mode: PolygonMode,
+    cull_mode: CullMode,
+    stencil: Option<StencilState>,
+    material_set: bool, // Layout has `PipelineDesc::material_layout` at set 0
+}
+
+impl MeshPipeline {
+    /// Load the shaders of `desc` from `shader_dir` and create the pipeline
+    /// layout. `build_pipeline` uses the fixed-function state of `desc` (meshes
+    /// are assembled as in `input_assembly`). Stages aren't checked against the
+    /// device features here, see `shader::validate_stages`.
+    pub fn new(device: &Device, shader_dir: &Path, desc: &PipelineDesc) -> Result<Self> {
+        let mut pipeline = Self {
+            modules: SmallVec::new(),
+            push_stages: vk::ShaderStageFlags::empty(),
+            layout: vk::PipelineLayout::null(),
+            pipeline: vk::Pipeline::null(),
+            topology: desc.topology,
+            primitive_restart: desc.primitive_restart,
+            polygon_mode: desc.polygon_mode,
+            cull_mode: desc.cull_mode,
+            stencil: desc.stencil,
+            material_set: desc.material_layout.is_some(),
+        };
+        for (stage, file) in desc.shader_stages() {
+            match load_shader_module(device, &shader_dir.join(file)) {
+                Ok(module) => pipeline.modules.push((stage, module)),
+                Err(e) => {
+                    pipeline.destroy(device);
+                    return Err(e);
+                }
+            }
+            pipeline.push_stages |= stage.flags();
+        }
+
+        let push_ranges = [vk::PushConstantRange::builder()
+            .stage_flags(pipeline.push_stages)
+            .offset(0)
+            .size(MESH_PUSH_SIZE as u32)
+            .build()];
+        let set_layouts: SmallVec<[vk::DescriptorSetLayout; 1]> =
+            desc.material_layout.into_iter().collect();
+        let layout_info = vk::PipelineLayoutCreateInfo::builder()
+            .set_layouts(&set_layouts)
+            .push_constant_ranges(&push_ranges);
+        match unsafe { device.create_pipeline_layout(&layout_info, None) } {
+            Ok(layout) => pipeline.layout = layout,
+            Err(e) => {
+                pipeline.destroy(device);
+                return Err(AppError::vk(e, "create mesh pipeline layout"));
+            }
+        }
+        Ok(pipeline)
+    }
+
+    /// (Re)create the pipeline for `subpass` of `render_pass`, drawn at `samples`
+    /// with depth testing, through `cache` (may be null). `specialization`
+    /// applies to every shader stage.
+    pub fn build_pipeline(
+        &mut self,
+        device: &Device,
+        cache: vk::PipelineCache,
+        render_pass: vk::RenderPass,
+        subpass: u32,
+        samples: vk::SampleCountFlags,
+        specialization: &SpecializationMap,
+    ) -> Result<()> {
+        self.destroy_pipeline(device);
+
+        let specialization = specialization.build();
+        let specialization_info = specialization.info();
+        let stages: SmallVec<[vk::PipelineShaderStageCreateInfo; 4]> = self
+            .modules
+            .iter()
+            .map(|&(stage, module)| {
+                vk::PipelineShaderStageCreateInfo::builder()
+                    .stage(stage.flags())
+                    .module(module)
+                    .name(b"main\0")
+                    .specialization_info(&specialization_info)
+                    .build()
+            })
+            .collect();
+        let bindings = [vk::VertexInputBindingDescription::builder()
+            .binding(0)
+            .stride(size_of::<Vertex>() as u32)
+            .input_rate(vk::VertexInputRate::VERTEX)
+            .build()];
+        let attributes = [
+            vk::VertexInputAttributeDescription::builder()
+                .location(0)
+                .binding(0)
+                .format(vk::Format::R32G32B32_SFLOAT)
+                .offset(0)
+                .build(),
+            vk::VertexInputAttributeDescription::builder()
+                .location(1)
+                .binding(0)
+                .format(vk::Format::R32G32B32_SFLOAT)
+                .offset(size_of::<[f32; 3]>() as u32)
+                .build(),
+        ];
+        let vertex_input = vk::PipelineVertexInputStateCreateInfo::builder()
+            .vertex_binding_descriptions(&bindings)
+            .vertex_attribute_descriptions(&attributes);
+        // Tessellation consumes patches: the triangle list, three vertices at a time
+        let tessellated = self
+            .modules
+            .iter()
+            .any(|(stage, _)| stage.is_tessellation());
+        let input_assembly = if tessellated {
+            vk::PipelineInputAssemblyStateCreateInfo::builder()
+                .topology(vk::PrimitiveTopology::PATCH_LIST)
+                .build()
+        } else {
+            input_assembly(self.topology, self.primitive_restart)
+        };
+        let tessellation =
+            vk::PipelineTessellationStateCreateInfo::builder().patch_control_points(3);
+        // Viewport/scissor are dynamic so resizes don't rebuild the pipeline
+        let viewports = [vk::Viewport::default()];
+        let scissors = [vk::Rect2D::default()];
+        let viewport_state = vk::PipelineViewportStateCreateInfo::builder()
+            .viewports(&viewports)
+            .scissors(&scissors);
+        let rasterization = rasterization(self.polygon_mode, self.cull_mode);
+        let multisample =
+            vk::PipelineMultisampleStateCreateInfo::builder().rasterization_samples(samples);
+        let depth_stencil = depth_stencil_state(self.stencil);
+        let blend_attachments = [vk::PipelineColorBlendAttachmentState::builder()
+            .color_write_mask(vk::ColorComponentFlags::all())
+            .blend_enable(false)
+            .build()];
+        let color_blend =
+            vk::PipelineColorBlendStateCreateInfo::builder().attachments(&blend_attachments);
+        let dynamic_states = [
+            vk::DynamicState::VIEWPORT,
+            vk::DynamicState::SCISSOR,
+            vk::DynamicState::STENCIL_REFERENCE,
+        ];
+        let dynamic_count = if self.stencil.is_some() { 3 } else { 2 };
+        let dynamic_state = vk::PipelineDynamicStateCreateInfo::builder()
+            .dynamic_states(&dynamic_states[..dynamic_count]);
+
+        let mut info = vk::GraphicsPipelineCreateInfo::builder()
+            .stages(&stages)
+            .vertex_input_state(&vertex_input)
+            .input_assembly_state(&input_assembly)
+            .viewport_state(&viewport_state)
+            .rasterization_state(&rasterization)
+            .multisample_state(&multisample)
+            .depth_stencil_state(&depth_stencil)
+            .color_blend_state(&color_blend)
+            .dynamic_state(&dynamic_state)
+            .layout(self.layout)
+            .render_pass(render_pass)
+            .subpass(subpass);
+        if tessellated {
+            info = info.tessellation_state(&tessellation);
+        }
+
+        self.pipeline = unsafe { device.create_graphics_pipelines(cache, &[info], None) }
+            .map_err(|e| AppError::vk(e, "create mesh pipeline"))?
+            .0[0];
+        Ok(())
+    }
+
+    pub fn pipeline(&self) -> vk::Pipeline {
+        self.pipeline
+    }
+
+    pub fn layout(&self) -> vk::PipelineLayout {
+        self.layout
+    }
+
+    /// Whether draws with a material bind its set (at set 0).
+    pub fn material_set(&self) -> bool {
+        self.material_set
+    }
+
+    /// Whether draws must set `vk::DynamicState::STENCIL_REFERENCE`.
+    pub fn stencil_test(&self) -> bool {
+        self.stencil.is_some()
+    }
+
+    /// Stage flags pushes into `layout` must use: every stage of the pipeline.
+    pub fn push_stages(&self) -> vk::ShaderStageFlags {
+        self.push_stages
+    }
+
+    fn destroy_pipeline(&mut self, device: &Device) {
+        if !self.pipeline.is_null() {
+            unsafe { device.destroy_pipeline(self.pipeline, None) };
+            self.pipeline = vk::Pipeline::null();
+        }
+    }
+
+    /// Destroy everything; null handles are ignored by Vulkan.
+    pub fn destroy(&mut self, device: &Device) {
+        self.destroy_pipeline(device);
+        unsafe {
+            device.destroy_pipeline_layout(self.layout, None);
+            for (_, module) in self.modules.drain(..) {
+                device.destroy_shader_module(module, None);
+            }
+        }
+    }
+}
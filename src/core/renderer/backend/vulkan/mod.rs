@@ -1,2 +1,73 @@
+pub mod barrier;
+pub mod buffer;
+pub mod capabilities;
+#[cfg(feature = "vulkan")]
+pub mod compute;
+pub mod context;
+#[cfg(all(debug_assertions, feature = "debug-utils"))]
+mod debug;
+pub mod descriptor;
+pub mod device_info;
+#[cfg(feature = "vulkan")]
+pub mod draw;
+#[cfg(feature = "vulkan")]
+pub mod exclusive;
+pub mod features;
+pub mod frame;
+pub mod fullscreen;
+pub mod headless;
+pub mod image;
+pub mod init;
+pub mod material;
+pub mod mesh;
+pub mod overdraw;
+#[cfg(feature = "vulkan")]
+pub mod pick;
+pub mod pipelines;
+#[cfg(feature = "vulkan")]
+pub mod present;
+pub mod recording;
+#[cfg(feature = "vulkan")]
+pub mod relay;
+pub mod render_pass;
+pub mod render_queue;
+pub mod sampler;
+pub mod screenshot;
+pub mod selection;
+pub mod shader;
+pub mod submit;
+#[cfg(feature = "vulkan")]
+pub mod surface;
+pub mod texture;
+pub mod tonemap;
+pub mod tracker;
+pub mod transfer;
+pub mod viewport;
+#[cfg(feature = "vulkan")]
 pub mod vulkan;
+pub use buffer::Buffer;
+pub use capabilities::{Capabilities, RenderTargetFormat};
+#[cfg(feature = "vulkan")]
+pub use compute::{ComputePipeline, StorageImage};
+pub use context::{DeviceContext, MemoryStats};
+pub use descriptor::{DescriptorAllocator, DescriptorLayoutBuilder};
+pub use device_info::{DeviceInfo, GpuReport};
+#[cfg(feature = "vulkan")]
+pub use draw::{DrawCallback, DrawContext, Frame};
+pub use features::{FeatureChain, FeatureExtension};
+pub use headless::{HEADLESS_FORMAT, HeadlessRenderer};
+pub use init::{InitPhase, InitProgress};
+pub use material::{MaterialId, MaterialTexture};
+pub use mesh::{Mesh, PRIMITIVE_RESTART_INDEX, Vertex};
+pub use pipelines::PipelineDesc;
+#[cfg(feature = "vulkan")]
+pub use present::{FallbackReason, PresentModeFallback};
+pub use render_pass::{ClearTarget, ClearValue, ColorLoadOp, RenderPassBuilder};
+pub use sampler::SamplerConfig;
+pub use screenshot::{Screenshot, ScreenshotToken};
+pub use shader::{ShaderStage, SpecializationMap};
+pub use texture::Texture;
+pub use tracker::{ResourceKind, ResourceTracker};
+pub use viewport::DepthRange;
+#[cfg(feature = "vulkan")]
 pub use vulkan::VulkanRenderer;
@@ -0,0 +1,103 @@
+// src/core/renderer/backend/vulkan/overdraw.rs
+//
+// Overdraw estimate for `RenderStats::overdraw`. Each frame slot has one
+// occlusion query around the scene subpass draws. Once the slot's fence has
+// signaled, its result (samples that passed the depth test) is read back without
+// waiting and compared to the samples of the scene area. Non-precise queries
+// only promise zero versus non-zero, so the estimate needs the
+// `occlusionQueryPrecise` feature; without it there is no pool and
+// `RenderStats::overdraw` stays None.
+use vulkanalia::prelude::v1_0::*;
+use vulkanalia::vk::Handle;
+
+use super::frame::MAX_FRAMES_IN_FLIGHT;
+use crate::core::renderer::stats::overdraw_estimate;
+use crate::error::{AppError, Result};
+
+/// One occlusion query per frame in flight.
+#[derive(Debug, Default)]
+pub struct OverdrawQueries {
+    pool: vk::QueryPool,
+    scene_samples: [u64; MAX_FRAMES_IN_FLIGHT], // Of the query recorded in each slot (0 = none)
+}
+
+impl OverdrawQueries {
+    /// No pool (and so no estimate) unless `precise`, i.e. `occlusionQueryPrecise`
+    /// was enabled on `device`.
+    pub fn create(device: &Device, precise: bool) -> Result<Self> {
+        if !precise {
+            return Ok(Self::default());
+        }
+        let info = vk::QueryPoolCreateInfo::builder()
+            .query_type(vk::QueryType::OCCLUSION)
+            .query_count(MAX_FRAMES_IN_FLIGHT as u32);
+        let pool = unsafe { device.create_query_pool(&info, None) }
+            .map_err(|e| AppError::vk(e, "create occlusion query pool"))?;
+        Ok(Self {
+            pool,
+            scene_samples: [0; MAX_FRAMES_IN_FLIGHT],
+        })
+    }
+
+    /// Record the reset of `slot`'s query; must be outside a render pass.
+    pub fn reset(&self, device: &Device, cmd: vk::CommandBuffer, slot: usize) {
+        if !self.pool.is_null() {
+            unsafe { device.cmd_reset_query_pool(cmd, self.pool, slot as u32, 1) };
+        }
+    }
+
+    /// Start counting in the current subpass; `end` must follow in the same one.
+    pub fn begin(&self, device: &Device, cmd: vk::CommandBuffer, slot: usize) {
+        if !self.pool.is_null() {
+            let flags = vk::QueryControlFlags::PRECISE;
+            unsafe { device.cmd_begin_query(cmd, self.pool, slot as u32, flags) };
+        }
+    }
+
+    pub fn end(&self, device: &Device, cmd: vk::CommandBuffer, slot: usize) {
+        if !self.pool.is_null() {
+            unsafe { device.cmd_end_query(cmd, self.pool, slot as u32) };
+        }
+    }
+
+    /// `slot`'s query was submitted for a scene of `scene_samples` samples.
+    pub fn submitted(&mut self, slot: usize, scene_samples: u64) {
+        if !self.pool.is_null() {
+            self.scene_samples[slot] = scene_samples;
+        }
+    }
+
+    /// Overdraw of the frame that last used `slot`, once its fence has signaled.
+    /// None if the slot had no query or the result isn't available.
+    pub fn take_result(&mut self, device: &Device, slot: usize) -> Option<f32> {
+        let scene_samples = std::mem::take(&mut self.scene_samples[slot]);
+        if scene_samples == 0 {
+            return None;
+        }
+        let mut passed = [0u8; size_of::<u64>()];
+        let result = unsafe {
+            device.get_query_pool_results(
+                self.pool,
+                slot as u32,
+                1,
+                &mut passed,
+                size_of::<u64>() as vk::DeviceSize,
+                vk::QueryResultFlags::_64,
+            )
+        };
+        match result {
+            Ok(vk::SuccessCode::SUCCESS) => {
+                overdraw_estimate(u64::from_ne_bytes(passed), scene_samples)
+            }
+            _ => None, // NOT_READY or lost; the next frame tries again
+        }
+    }
+
+    pub fn destroy(&mut self, device: &Device) {
+        if !self.pool.is_null() {
+            unsafe { device.destroy_query_pool(self.pool, None) };
+            self.pool = vk::QueryPool::null();
+        }
+        self.scene_samples = [0; MAX_FRAMES_IN_FLIGHT];
+    }
+}
@@ -0,0 +1,286 @@
+// src/core/renderer/backend/vulkan/pick.rs
+//
+// Object picking. `VulkanRenderer::pick` replays the mesh draws of the last
+// recorded frame into a 1x1 R32_UINT target, with the viewport shifted so the
+// only rasterized pixel is the one under the cursor. Every draw writes the id
+// set with `DrawContext::set_pick_id` (0 = background), depth testing keeps the
+// nearest one, and the pixel is copied to a host buffer. The pass reuses
+// `mesh.vert`; the id comes from push constant bytes 64..68:
+//   glslc shaders/pick.frag -o shaders/pick.frag.spv
+use std::path::Path;
+use std::sync::Arc;
+
+use vulkanalia::prelude::v1_0::*;
+use vulkanalia::vk::Handle;
+
+use super::barrier::buffer_barrier;
+use super::buffer::Buffer;
+use super::capabilities::{RenderTargetFormat, has_stencil};
+use super::context::DeviceContext;
+use super::draw::DrawCall;
+use super::image::ImageDesc;
+use super::mesh::MeshPipeline;
+use super::pipelines::PipelineDesc;
+use super::render_pass::{ClearValue, ColorLoadOp, RenderPassBuilder};
+use super::texture::Texture;
+use super::tracker::ResourceKind;
+use super::transfer::color_layers;
+use crate::error::{AppError, Result};
+
+/// Push constant offset of the id `pick.frag` reads, right after `mesh.vert`'s mat4.
+pub const PICK_ID_OFFSET: usize = 64;
+
+/// Window position (physical pixels, `window` sized) to framebuffer pixels.
+/// The two differ while a resize is pending or when the surface is scaled.
+/// None when the position is outside the window.
+pub fn window_to_framebuffer(
+    x: u32,
+    y: u32,
+    window: vk::Extent2D,
+    framebuffer: vk::Extent2D,
+) -> Option<(u32, u32)> {
+    if x >= window.width || y >= window.height {
+        return None;
+    }
+    let scale = |pos: u32, from: u32, to: u32| u64::from(pos) * u64::from(to) / u64::from(from);
+    let fx = scale(x, window.width, framebuffer.width) as u32;
+    let fy = scale(y, window.height, framebuffer.height) as u32;
+    (fx < framebuffer.width && fy < framebuffer.height).then_some((fx, fy))
+}
+
+/// Render pass, 1x1 targets, pipeline and readback buffer of the id pass.
+/// Independent of the swapchain, so it survives resizes; created on first pick
+/// and freed on Drop.
+pub struct PickPass {
+    context: Arc<DeviceContext>,
+    ids: Texture,
+    depth: Texture,
+    render_pass: vk::RenderPass,
+    framebuffer: vk::Framebuffer,
+    pipeline: Option<MeshPipeline>,
+    readback: Buffer,
+}
+
+impl PickPass {
+    pub fn new(
+        context: &Arc<DeviceContext>,
+        shader_dir: &Path,
+        depth_format: vk::Format,
+        desc: &PipelineDesc,
+    ) -> Result<Self> {
+        let pixel = vk::Extent2D {
+            width: 1,
+            height: 1,
+        };
+        // One object id per pixel, read back as a u32
+        let ids_format = RenderTargetFormat::Uint.resolve(|format| unsafe {
+            let instance = &context.instance;
+            instance.get_physical_device_format_properties(context.physical_device, format)
+        })?;
+        let ids = Texture::new(
+            context,
+            ImageDesc {
+                extent: pixel,
+                format: ids_format,
+                samples: vk::SampleCountFlags::_1,
+                usage: vk::ImageUsageFlags::COLOR_ATTACHMENT | vk::ImageUsageFlags::TRANSFER_SRC,
+                aspect: vk::ImageAspectFlags::COLOR,
+            },
+        )?;
+        let depth = Texture::new(
+            context,
+            ImageDesc {
+                extent: pixel,
+                format: depth_format,
+                samples: vk::SampleCountFlags::_1,
+                usage: vk::ImageUsageFlags::DEPTH_STENCIL_ATTACHMENT,
+                aspect: if has_stencil(depth_format) {
+                    vk::ImageAspectFlags::DEPTH | vk::ImageAspectFlags::STENCIL
+                } else {
+                    vk::ImageAspectFlags::DEPTH
+                },
+            },
+        )?;
+        let host = vk::MemoryPropertyFlags::HOST_VISIBLE | vk::MemoryPropertyFlags::HOST_COHERENT;
+        let readback = Buffer::new(
+            context,
+            size_of::<u32>() as vk::DeviceSize,
+            vk::BufferUsageFlags::TRANSFER_DST,
+            host,
+        )?;
+        let mut pass = Self {
+            context: Arc::clone(context),
+            ids,
+            depth,
+            render_pass: vk::RenderPass::null(),
+            framebuffer: vk::Framebuffer::null(),
+            pipeline: None,
+            readback,
+        };
+
+        let depth_attachment = vk::AttachmentDescription::builder()
+            .format(depth_format)
+            .samples(vk::SampleCountFlags::_1)
+            .load_op(vk::AttachmentLoadOp::CLEAR)
+            .store_op(vk::AttachmentStoreOp::DONT_CARE)
+            .stencil_load_op(vk::AttachmentLoadOp::DONT_CARE)
+            .stencil_store_op(vk::AttachmentStoreOp::DONT_CARE)
+            .initial_layout(vk::ImageLayout::UNDEFINED)
+            .final_layout(vk::ImageLayout::DEPTH_STENCIL_ATTACHMENT_OPTIMAL)
+            .build();
+        let to_copy = vk::SubpassDependency::builder()
+            .src_subpass(0)
+            .dst_subpass(vk::SUBPASS_EXTERNAL)
+            .src_stage_mask(vk::PipelineStageFlags::COLOR_ATTACHMENT_OUTPUT)
+            .dst_stage_mask(vk::PipelineStageFlags::TRANSFER)
+            .src_access_mask(vk::AccessFlags::COLOR_ATTACHMENT_WRITE)
+            .dst_access_mask(vk::AccessFlags::TRANSFER_READ)
+            .build();
+        // The id image ends up ready for the copy; 0 (cleared) means "nothing"
+        let transfer = vk::ImageLayout::TRANSFER_SRC_OPTIMAL;
+        let builder = RenderPassBuilder::new()
+            .color_attachment(ids_format, ColorLoadOp::Clear([0.0; 4]), transfer)
+            .attachment(depth_attachment)
+            .subpass()
+            .color(0)
+            .depth(1)
+            .dependency(to_copy);
+        // On failure, Drop releases whatever was created so far
+        let desc = desc.clone().with_fragment("pick.frag.spv");
+        pass.create_objects(builder, shader_dir, &desc)?;
+        Ok(pass)
+    }
+
+    fn create_objects(
+        &mut self,
+        builder: RenderPassBuilder,
+        shader_dir: &Path,
+        desc: &PipelineDesc,
+    ) -> Result<()> {
+        let device = &self.context.device;
+        self.render_pass = builder.build(device)?;
+
+        let attachments = [self.ids.view(), self.depth.view()];
+        let framebuffer_info = vk::FramebufferCreateInfo::builder()
+            .render_pass(self.render_pass)
+            .attachments(&attachments)
+            .width(1)
+            .height(1)
+            .layers(1);
+        self.framebuffer = unsafe { device.create_framebuffer(&framebuffer_info, None) }
+            .map_err(|e| AppError::vk(e, "create pick framebuffer"))?;
+        self.context
+            .resources()
+            .created(&[ResourceKind::Framebuffer]);
+
+        let pipeline = self
+            .pipeline
+            .insert(MeshPipeline::new(device, shader_dir, desc)?);
+        let samples = vk::SampleCountFlags::_1;
+        pipeline.build_pipeline(
+            device,
+            vk::PipelineCache::null(),
+            self.render_pass,
+            0,
+            samples,
+            &desc.specialization,
+        )
+    }
+
+    /// Record the id pass for framebuffer pixel (`x`, `y`) and the copy of its
+    /// result into the readback buffer. `viewport` is the frame's scene viewport.
+    pub(crate) fn record(
+        &self,
+        device: &Device,
+        cmd: vk::CommandBuffer,
+        draws: &[DrawCall],
+        viewport: vk::Viewport,
+        (x, y): (u32, u32),
+    ) -> Result<()> {
+        let Some(pipeline) = &self.pipeline else {
+            return Err(AppError::NotInitialized);
+        };
+        let clear_values = [
+            ClearValue::Uint([0; 4]).into(),
+            vk::ClearValue {
+                depth_stencil: vk::ClearDepthStencilValue {
+                    depth: 1.0,
+                    stencil: 0,
+                },
+            },
+        ];
+        let pixel = vk::Rect2D::builder()
+            .extent(vk::Extent2D {
+                width: 1,
+                height: 1,
+            })
+            .build();
+        let pass_info = vk::RenderPassBeginInfo::builder()
+            .render_pass(self.render_pass)
+            .framebuffer(self.framebuffer)
+            .render_area(pixel)
+            .clear_values(&clear_values);
+        // Shift the scene so pixel (x, y) lands on the target's only pixel
+        let shifted = vk::Viewport {
+            x: viewport.x - x as f32,
+            y: viewport.y - y as f32,
+            ..viewport
+        };
+        unsafe {
+            device.cmd_begin_render_pass(cmd, &pass_info, vk::SubpassContents::INLINE);
+            let bind_point = vk::PipelineBindPoint::GRAPHICS;
+            device.cmd_bind_pipeline(cmd, bind_point, pipeline.pipeline());
+            device.cmd_set_viewport(cmd, 0, &[shifted]);
+            device.cmd_set_scissor(cmd, 0, &[pixel]);
+        }
+        for draw in draws.iter().filter(|draw| draw.covers(x, y)) {
+            draw.record_pick(device, cmd, pipeline);
+        }
+
+        let region = vk::BufferImageCopy::builder()
+            .image_subresource(color_layers())
+            .image_extent(vk::Extent3D {
+                width: 1,
+                height: 1,
+                depth: 1,
+            });
+        let transfer = vk::ImageLayout::TRANSFER_SRC_OPTIMAL;
+        unsafe {
+            device.cmd_end_render_pass(cmd);
+            let buffer = self.readback.handle();
+            device.cmd_copy_image_to_buffer(cmd, self.ids.image(), transfer, buffer, &[region]);
+        }
+        buffer_barrier(
+            device,
+            cmd,
+            self.readback.handle(),
+            vk::AccessFlags::TRANSFER_WRITE,
+            vk::AccessFlags::HOST_READ,
+        )
+    }
+
+    /// Id copied by the last `record`, once its submit has completed.
+    pub fn read(&self) -> Result<u32> {
+        let mut bytes = [0; size_of::<u32>()];
+        self.readback.read(&mut bytes)?;
+        Ok(u32::from_ne_bytes(bytes))
+    }
+}
+
+impl Drop for PickPass {
+    // Null handles (from a failed `new`) are ignored by Vulkan; the images and
+    // buffer free themselves
+    fn drop(&mut self) {
+        let device = &self.context.device;
+        if let Some(mut pipeline) = self.pipeline.take() {
+            pipeline.destroy(device);
+        }
+        if !self.framebuffer.is_null() {
+            unsafe { device.destroy_framebuffer(self.framebuffer, None) };
+            self.context
+                .resources()
+                .destroyed(&[ResourceKind::Framebuffer]);
+        }
+        unsafe { device.destroy_render_pass(self.render_pass, None) };
+    }
+}
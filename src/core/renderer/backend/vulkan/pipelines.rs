@@ -0,0 +1,259 @@
+// src/core/renderer/backend/vulkan/pipelines.rs
+//
+// Mesh pipelines keyed by `PipelineDesc`. The built-in pipeline and the ones
+// `DrawContext::set_pipeline` selects are created on the first draw that needs
+// them, which compiles shaders mid-frame; `VulkanRenderer::precompile_pipelines`
+// creates them during a loading screen instead. Everything is compiled through
+// one `vk::PipelineCache`, so pipelines sharing shaders compile faster.
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::path::Path;
+
+use log::info;
+use smallvec::SmallVec;
+use vulkanalia::prelude::v1_0::*;
+use vulkanalia::vk::Handle;
+
+use super::capabilities::has_stencil;
+use super::mesh::MeshPipeline;
+use super::shader::{ShaderStage, SpecializationMap, validate_stages};
+use crate::core::renderer::config::{
+    CullMode, PolygonMode, RendererConfig, StencilState, Topology,
+};
+use crate::core::renderer::log_targets::RENDERER;
+use crate::error::{AppError, Result};
+
+/// Everything a scene-subpass mesh pipeline is built from besides the render
+/// pass. Equal descriptions share one pipeline.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PipelineDesc {
+    pub vertex: Cow<'static, str>, // SPIR-V file in `RendererConfig::shader_dir`
+    pub fragment: Cow<'static, str>, // Same
+    // Geometry / tessellation shaders in between, with their files
+    pub stages: Vec<(ShaderStage, Cow<'static, str>)>,
+    pub topology: Topology,
+    pub primitive_restart: bool,
+    pub polygon_mode: PolygonMode,
+    pub cull_mode: CullMode,
+    pub stencil: Option<StencilState>,     // None = no stencil test
+    pub specialization: SpecializationMap, // Applies to every stage
+    // Set 0, bound from the draw's material (`DrawContext::set_material`)
+    pub material_layout: Option<vk::DescriptorSetLayout>,
+}
+
+impl Default for PipelineDesc {
+    fn default() -> Self {
+        Self::new(Topology::default(), false)
+    }
+}
+
+impl PipelineDesc {
+    /// The built-in mesh shaders assembled as `topology`, filled and unculled.
+    pub fn new(topology: Topology, primitive_restart: bool) -> Self {
+        Self {
+            vertex: Cow::Borrowed("mesh.vert.spv"),
+            fragment: Cow::Borrowed("mesh.frag.spv"),
+            stages: Vec::new(),
+            topology,
+            primitive_restart,
+            polygon_mode: PolygonMode::Fill,
+            cull_mode: CullMode::None,
+            stencil: None,
+            specialization: SpecializationMap::new(),
+            material_layout: None,
+        }
+    }
+
+    /// The built-in mesh shaders with the graphics state of `config`.
+    pub fn from_config(config: &RendererConfig) -> Self {
+        Self::new(config.topology, config.primitive_restart)
+            .with_polygon_mode(config.polygon_mode)
+            .with_cull_mode(config.cull_mode)
+    }
+
+    /// `fragment` (a file in the shader dir) instead of `mesh.frag`.
+    pub fn with_fragment(mut self, fragment: impl Into<Cow<'static, str>>) -> Self {
+        self.fragment = fragment.into();
+        self
+    }
+
+    /// Run `file` (in the shader dir) as `stage`, replacing the built-in vertex
+    /// or fragment shader or a stage given before. Geometry and tessellation
+    /// stages need their device features (see `shader::validate_stages`);
+    /// tessellated pipelines read triangle lists as 3-point patches.
+    pub fn with_stage(mut self, stage: ShaderStage, file: impl Into<Cow<'static, str>>) -> Self {
+        let file = file.into();
+        match stage {
+            ShaderStage::Vertex => self.vertex = file,
+            ShaderStage::Fragment => self.fragment = file,
+            _ => {
+                self.stages.retain(|(existing, _)| *existing != stage);
+                self.stages.push((stage, file));
+            }
+        }
+        self
+    }
+
+    /// Every stage with its file: vertex, fragment, then the ones in between.
+    pub fn shader_stages(&self) -> impl Iterator<Item = (ShaderStage, &str)> {
+        [
+            (ShaderStage::Vertex, &*self.vertex),
+            (ShaderStage::Fragment, &*self.fragment),
+        ]
+        .into_iter()
+        .chain(self.stages.iter().map(|(stage, file)| (*stage, &**file)))
+    }
+
+    /// Check the stages against the device `features`, and a stencil test
+    /// against the `depth_format`, before creating anything.
+    pub fn validate(
+        &self,
+        features: &vk::PhysicalDeviceFeatures,
+        depth_format: vk::Format,
+    ) -> Result<()> {
+        if self.stencil.is_some() && !has_stencil(depth_format) {
+            return Err(AppError::Config(format!(
+                "stencil test on depth format {depth_format:?}, enable RendererBuilder::stencil"
+            )));
+        }
+        let stages: SmallVec<[ShaderStage; 6]> = self.shader_stages().map(|(s, _)| s).collect();
+        validate_stages(&stages, features)
+    }
+
+    pub fn with_polygon_mode(mut self, polygon_mode: PolygonMode) -> Self {
+        self.polygon_mode = polygon_mode;
+        self
+    }
+
+    pub fn with_cull_mode(mut self, cull_mode: CullMode) -> Self {
+        self.cull_mode = cull_mode;
+        self
+    }
+
+    /// Stencil-test draws with `stencil`; needs `RendererBuilder::stencil`.
+    pub fn with_stencil(mut self, stencil: StencilState) -> Self {
+        self.stencil = Some(stencil);
+        self
+    }
+
+    /// Give the pipeline a material set at set 0 with `layout` (the one its
+    /// materials were created with, see `VulkanRenderer::create_material`).
+    pub fn with_material_layout(mut self, layout: vk::DescriptorSetLayout) -> Self {
+        self.material_layout = Some(layout);
+        self
+    }
+
+    pub fn with_specialization(mut self, specialization: SpecializationMap) -> Self {
+        self.specialization = specialization;
+        self
+    }
+}
+
+/// Mesh pipelines created so far, one per distinct `PipelineDesc`, all built
+/// for the same render pass. Pipelines stay valid until `destroy`.
+#[derive(Debug, Default)]
+pub struct MeshPipelineCache {
+    pipelines: HashMap<PipelineDesc, MeshPipeline>,
+    cache: vk::PipelineCache, // Created with the first pipeline
+}
+
+impl MeshPipelineCache {
+    pub fn get(&self, desc: &PipelineDesc) -> Option<&MeshPipeline> {
+        self.pipelines.get(desc)
+    }
+
+    /// Cached pipeline for `desc`, created for subpass 0 of `render_pass` on
+    /// first use.
+    pub fn get_or_create(
+        &mut self,
+        device: &Device,
+        shader_dir: &Path,
+        desc: &PipelineDesc,
+        render_pass: vk::RenderPass,
+        samples: vk::SampleCountFlags,
+    ) -> Result<&MeshPipeline> {
+        if !self.pipelines.contains_key(desc) {
+            let pipeline = self.create(device, shader_dir, desc, render_pass, samples)?;
+            self.pipelines.insert(desc.clone(), pipeline);
+        }
+        Ok(&self.pipelines[desc])
+    }
+
+    /// Rebuild every pipeline for a new `render_pass` (swapchain recreation).
+    pub fn rebuild(
+        &mut self,
+        device: &Device,
+        render_pass: vk::RenderPass,
+        samples: vk::SampleCountFlags,
+    ) -> Result<()> {
+        for (desc, pipeline) in &mut self.pipelines {
+            pipeline.build_pipeline(
+                device,
+                self.cache,
+                render_pass,
+                0,
+                samples,
+                &desc.specialization,
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Destroy the pipeline of `desc`, if created. The GPU must be done with it.
+    pub fn remove(&mut self, device: &Device, desc: &PipelineDesc) -> bool {
+        match self.pipelines.remove(desc) {
+            Some(mut pipeline) => {
+                pipeline.destroy(device);
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.pipelines.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pipelines.is_empty()
+    }
+
+    pub fn destroy(&mut self, device: &Device) {
+        for (_, mut pipeline) in self.pipelines.drain() {
+            pipeline.destroy(device);
+        }
+        if !self.cache.is_null() {
+            unsafe { device.destroy_pipeline_cache(self.cache, None) };
+            self.cache = vk::PipelineCache::null();
+        }
+    }
+
+    fn create(
+        &mut self,
+        device: &Device,
+        shader_dir: &Path,
+        desc: &PipelineDesc,
+        render_pass: vk::RenderPass,
+        samples: vk::SampleCountFlags,
+    ) -> Result<MeshPipeline> {
+        if self.cache.is_null() {
+            let info = vk::PipelineCacheCreateInfo::builder();
+            self.cache = unsafe { device.create_pipeline_cache(&info, None) }
+                .map_err(|e| AppError::vk(e, "create pipeline cache"))?;
+        }
+        let mut pipeline = MeshPipeline::new(device, shader_dir, desc)?;
+        if let Err(e) = pipeline.build_pipeline(
+            device,
+            self.cache,
+            render_pass,
+            0,
+            samples,
+            &desc.specialization,
+        ) {
+            pipeline.destroy(device);
+            return Err(e);
+        }
+        info!(target: RENDERER, "✅ Mesh pipeline created ({})", desc.fragment);
+        Ok(pipeline)
+    }
+}
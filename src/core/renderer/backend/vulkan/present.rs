@@ -0,0 +1,324 @@
+// src/core/renderer/backend/vulkan/present.rs
+//
+// Retiring the swapchain on resize. Framebuffers and views of the old images
+// may only be destroyed once no frame or present uses them anymore. Core Vulkan
+// has no signal for "this present is done with its image and wait semaphore",
+// so the fallback drains the whole device (`device_wait_idle`), which shows up
+// as a hitch on every resize. VK_EXT_swapchain_maintenance1 (later promoted to
+// KHR) lets each present signal a fence; waiting on those plus the frame fences
+// releases the old images as soon as they are actually free.
+use std::fmt;
+use std::time::Duration;
+
+use smallvec::SmallVec;
+use vulkanalia::prelude::v1_0::*;
+
+use super::frame::{MAX_FRAMES_IN_FLIGHT, wait_for_fences};
+use super::surface::PresentScalingCaps;
+use super::tracker::{ResourceKind, ResourceTracker};
+use crate::core::renderer::config::{PresentMode, ScalingMode};
+use crate::error::{AppError, Result};
+
+/// How `recreate_swapchain` waits before destroying per-image resources.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SwapchainRetire {
+    PresentFences, // Frame fences + one fence per present (swapchain_maintenance1)
+    #[default]
+    WaitIdle, // device_wait_idle
+}
+
+/// Present fences need the device extension and its `swapchainMaintenance1` feature,
+/// plus `VK_EXT_surface_maintenance1` on the instance.
+pub fn select_retire_mode(
+    device_extensions: &[vk::ExtensionProperties],
+    surface_maintenance1: bool,
+    feature_supported: bool,
+) -> SwapchainRetire {
+    let advertised = device_extensions
+        .iter()
+        .any(|e| e.extension_name == vk::EXT_SWAPCHAIN_MAINTENANCE1_EXTENSION.name);
+    if advertised && surface_maintenance1 && feature_supported {
+        SwapchainRetire::PresentFences
+    } else {
+        SwapchainRetire::WaitIdle
+    }
+}
+
+pub fn present_mode_vk(mode: PresentMode) -> vk::PresentModeKHR {
+    match mode {
+        PresentMode::Fifo => vk::PresentModeKHR::FIFO,
+        PresentMode::FifoRelaxed => vk::PresentModeKHR::FIFO_RELAXED,
+        PresentMode::Mailbox => vk::PresentModeKHR::MAILBOX,
+        PresentMode::Immediate => vk::PresentModeKHR::IMMEDIATE,
+    }
+}
+
+/// Why a swapchain uses another present mode than the one asked for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FallbackReason {
+    Unsupported, // The surface doesn't offer the requested mode
+    PowerSaving, // `RendererConfig::power_saving` holds an uncapped mode back
+}
+
+/// Present mode downgrade of a swapchain, for settings UIs to explain
+/// ("MAILBOX unavailable, using FIFO").
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PresentModeFallback {
+    pub requested: vk::PresentModeKHR, // First preference of the config / platform
+    pub chosen: vk::PresentModeKHR,
+    pub reason: FallbackReason,
+}
+
+impl fmt::Display for PresentModeFallback {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (requested, chosen) = (self.requested, self.chosen);
+        match self.reason {
+            FallbackReason::Unsupported => write!(f, "{requested:?} unavailable, using {chosen:?}"),
+            FallbackReason::PowerSaving => {
+                write!(f, "{requested:?} paused to save power, using {chosen:?}")
+            }
+        }
+    }
+}
+
+/// First of `preferences` (see `present_preferences`) among the surface's
+/// `available` modes, else FIFO, which every surface supports. A fallback is
+/// reported when that isn't the first preference.
+pub fn select_present_mode(
+    available: &[vk::PresentModeKHR],
+    preferences: &[PresentMode],
+) -> (vk::PresentModeKHR, Option<PresentModeFallback>) {
+    let chosen = preferences
+        .iter()
+        .map(|&mode| present_mode_vk(mode))
+        .find(|mode| available.contains(mode))
+        .unwrap_or(vk::PresentModeKHR::FIFO);
+    let requested = preferences
+        .first()
+        .map_or(chosen, |&mode| present_mode_vk(mode));
+    let fallback = (chosen != requested).then_some(PresentModeFallback {
+        requested,
+        chosen,
+        reason: FallbackReason::Unsupported,
+    });
+    (chosen, fallback)
+}
+
+/// Images MAILBOX needs to replace a queued frame while another is on screen
+/// and a third is being drawn; with fewer it paces like FIFO.
+pub const MAILBOX_MIN_IMAGES: u32 = 3;
+
+/// Image count to request from a surface offering `min`..=`max` images
+/// (`max` 0 = no limit): one more than the minimum, at least
+/// `MAILBOX_MIN_IMAGES` for MAILBOX, within the maximum.
+pub fn desired_image_count(min: u32, max: u32, present_mode: vk::PresentModeKHR) -> u32 {
+    let mut count = min + 1;
+    if present_mode == vk::PresentModeKHR::MAILBOX {
+        count = count.max(MAILBOX_MIN_IMAGES);
+    }
+    if max > 0 { count.min(max) } else { count }
+}
+
+/// With present scaling, how long the window size must stay unchanged before the
+/// swapchain is recreated at the new size.
+pub const RESIZE_SETTLE: Duration = Duration::from_millis(100);
+
+/// Scaling create-info for the swapchain: Some when `mode` is requested, the
+/// device has swapchain_maintenance1 (`retire` is PresentFences) and the surface
+/// supports it for the chosen present mode. Gravity is centered where possible.
+pub fn present_scaling_info(
+    mode: Option<ScalingMode>,
+    retire: SwapchainRetire,
+    caps: Option<PresentScalingCaps>,
+) -> Option<vk::SwapchainPresentScalingCreateInfoEXT> {
+    let (Some(mode), SwapchainRetire::PresentFences, Some(caps)) = (mode, retire, caps) else {
+        return None;
+    };
+    let behavior = match mode {
+        ScalingMode::OneToOne => vk::PresentScalingFlagsEXT::ONE_TO_ONE,
+        ScalingMode::AspectRatioStretch => vk::PresentScalingFlagsEXT::ASPECT_RATIO_STRETCH,
+        ScalingMode::Stretch => vk::PresentScalingFlagsEXT::STRETCH,
+    };
+    let gravity = |supported: vk::PresentGravityFlagsEXT| {
+        [
+            vk::PresentGravityFlagsEXT::CENTERED,
+            vk::PresentGravityFlagsEXT::MIN,
+            vk::PresentGravityFlagsEXT::MAX,
+        ]
+        .into_iter()
+        .find(|&g| supported.contains(g))
+    };
+    let (Some(x), Some(y)) = (gravity(caps.gravity_x), gravity(caps.gravity_y)) else {
+        return None;
+    };
+    caps.scaling.contains(behavior).then(|| {
+        vk::SwapchainPresentScalingCreateInfoEXT::builder()
+            .scaling_behavior(behavior)
+            .present_gravity_x(x)
+            .present_gravity_y(y)
+            .build()
+    })
+}
+
+/// Whether a swapchain of `swapchain` extent no longer matches the surface, e.g.
+/// after a resize while rendering was paused. `current` is the surface's
+/// `current_extent` (u32::MAX = the window decides, then `window` is compared).
+pub fn swapchain_stale(
+    current: vk::Extent2D,
+    swapchain: vk::Extent2D,
+    window: vk::Extent2D,
+) -> bool {
+    let wanted = if current.width == u32::MAX {
+        window
+    } else {
+        current
+    };
+    wanted.width > 0 && wanted.height > 0 && wanted != swapchain
+}
+
+/// One fence per frame in flight, handed to that frame's present through
+/// `vk::SwapchainPresentFenceInfoEXT`.
+#[derive(Debug)]
+pub struct PresentFences {
+    fences: SmallVec<[vk::Fence; MAX_FRAMES_IN_FLIGHT]>,
+    pending: [bool; MAX_FRAMES_IN_FLIGHT], // Given to a present that may not have signaled
+}
+
+impl PresentFences {
+    pub fn create(device: &Device, tracker: &ResourceTracker) -> Result<Self> {
+        let mut fences = Self {
+            fences: SmallVec::new(),
+            pending: [false; MAX_FRAMES_IN_FLIGHT],
+        };
+        let info = vk::FenceCreateInfo::builder();
+        for _ in 0..MAX_FRAMES_IN_FLIGHT {
+            match unsafe { device.create_fence(&info, None) } {
+                Ok(fence) => {
+                    fences.fences.push(fence);
+                    tracker.created(&[ResourceKind::Fence]);
+                }
+                Err(e) => {
+                    fences.destroy(device, tracker);
+                    return Err(AppError::vk(e, "create present fence"));
+                }
+            }
+        }
+        Ok(fences)
+    }
+
+    /// Fence for the present of frame `slot`, after the previous present that used
+    /// it has finished (or `timeout` passed).
+    pub fn acquire(
+        &mut self,
+        device: &Device,
+        slot: usize,
+        timeout: Duration,
+    ) -> Result<vk::Fence> {
+        let fence = self.fences[slot];
+        if self.pending[slot] {
+            wait_for_fences(device, &[fence], timeout, "wait for present fence")?;
+            unsafe { device.reset_fences(&[fence]) }
+                .map_err(|e| AppError::vk(e, "reset present fence"))?;
+        }
+        self.pending[slot] = true;
+        Ok(fence)
+    }
+
+    /// The present of `slot` failed before queueing and will never signal.
+    pub fn abandon(&mut self, device: &Device, slot: usize) {
+        if std::mem::take(&mut self.pending[slot]) {
+            unsafe { device.reset_fences(&[self.fences[slot]]) }.ok();
+        }
+    }
+
+    /// Block until every queued present has released its image and semaphore.
+    pub fn wait_all(&mut self, device: &Device, timeout: Duration) -> Result<()> {
+        let pending: SmallVec<[vk::Fence; MAX_FRAMES_IN_FLIGHT]> = self
+            .fences
+            .iter()
+            .zip(self.pending)
+            .filter_map(|(&fence, pending)| pending.then_some(fence))
+            .collect();
+        if pending.is_empty() {
+            return Ok(());
+        }
+        wait_for_fences(device, &pending, timeout, "wait for present fences")?;
+        unsafe { device.reset_fences(&pending) }
+            .map_err(|e| AppError::vk(e, "reset present fences"))?;
+        self.pending = [false; MAX_FRAMES_IN_FLIGHT];
+        Ok(())
+    }
+
+    pub fn destroy(&mut self, device: &Device, tracker: &ResourceTracker) {
+        for fence in self.fences.drain(..) {
+            unsafe { device.destroy_fence(fence, None) };
+            tracker.destroyed(&[ResourceKind::Fence]);
+        }
+        self.pending = [false; MAX_FRAMES_IN_FLIGHT];
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FIFO: vk::PresentModeKHR = vk::PresentModeKHR::FIFO;
+    const MAILBOX: vk::PresentModeKHR = vk::PresentModeKHR::MAILBOX;
+    const IMMEDIATE: vk::PresentModeKHR = vk::PresentModeKHR::IMMEDIATE;
+
+    #[test]
+    fn unsupported_mode_reports_a_fallback() {
+        let (chosen, fallback) = select_present_mode(&[FIFO], &[PresentMode::Mailbox]);
+        assert_eq!(chosen, FIFO);
+        let fallback = fallback.unwrap();
+        assert_eq!((fallback.requested, fallback.chosen), (MAILBOX, FIFO));
+        assert_eq!(fallback.reason, FallbackReason::Unsupported);
+        assert_eq!(fallback.to_string(), "MAILBOX unavailable, using FIFO");
+    }
+
+    #[test]
+    fn fallback_takes_the_next_preference() {
+        let preferences = [
+            PresentMode::Mailbox,
+            PresentMode::Immediate,
+            PresentMode::Fifo,
+        ];
+        let (chosen, fallback) = select_present_mode(&[FIFO, IMMEDIATE], &preferences);
+        assert_eq!(chosen, IMMEDIATE);
+        assert_eq!(
+            fallback.map(|f| (f.requested, f.chosen)),
+            Some((MAILBOX, IMMEDIATE))
+        );
+    }
+
+    #[test]
+    fn supported_mode_has_no_fallback() {
+        let (chosen, fallback) = select_present_mode(&[FIFO, MAILBOX], &[PresentMode::Mailbox]);
+        assert_eq!((chosen, fallback), (MAILBOX, None));
+    }
+
+    #[test]
+    fn one_image_above_the_minimum() {
+        assert_eq!(desired_image_count(2, 8, FIFO), 3);
+        assert_eq!(desired_image_count(3, 8, FIFO), 4);
+    }
+
+    #[test]
+    fn mailbox_gets_at_least_three_images() {
+        assert_eq!(desired_image_count(1, 8, MAILBOX), MAILBOX_MIN_IMAGES);
+        assert_eq!(desired_image_count(1, 8, FIFO), 2);
+        assert_eq!(desired_image_count(3, 8, MAILBOX), 4);
+    }
+
+    #[test]
+    fn maximum_clamps_the_count() {
+        assert_eq!(desired_image_count(1, 2, MAILBOX), 2);
+        assert_eq!(desired_image_count(2, 2, FIFO), 2);
+    }
+
+    #[test]
+    fn zero_maximum_is_unbounded() {
+        assert_eq!(desired_image_count(1, 0, MAILBOX), MAILBOX_MIN_IMAGES);
+        assert_eq!(desired_image_count(7, 0, FIFO), 8);
+    }
+}
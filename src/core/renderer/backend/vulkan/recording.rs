@@ -0,0 +1,52 @@
+// src/core/renderer/backend/vulkan/recording.rs
+//
+// Bookkeeping for `CommandRecording::Cached`: one command buffer per swapchain
+// image, recorded once and then resubmitted as-is while the frame stays the
+// same. What a frame draws is summed up in a signature (its draws and clears,
+// the clear color and the other renderer state recording reads), so changed
+// geometry or colors re-record on their own; a new swapchain, rebuilt
+// pipelines or `VulkanRenderer::mark_dirty` drop every recording.
+use crate::core::renderer::stats::DrawCounts;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Recording {
+    signature: u64,
+    counts: DrawCounts, // Reported again on every resubmit
+}
+
+/// Which swapchain images have a recorded command buffer, and of what.
+#[derive(Debug, Default)]
+pub struct RecordCache {
+    images: Vec<Option<Recording>>, // None = record on the image's next frame
+}
+
+impl RecordCache {
+    /// Start over with `images` swapchain images, none recorded.
+    pub fn reset(&mut self, images: usize) {
+        self.images = vec![None; images];
+    }
+
+    /// Re-record every image on its next frame.
+    pub fn invalidate(&mut self) {
+        self.images.fill(None);
+    }
+
+    /// Draw counts of `image`'s recording if it still matches `signature`;
+    /// None when the buffer has to be recorded (again).
+    pub fn lookup(&self, image: usize, signature: u64) -> Option<DrawCounts> {
+        self.images
+            .get(image)
+            .copied()
+            .flatten()
+            .filter(|recording| recording.signature == signature)
+            .map(|recording| recording.counts)
+    }
+
+    /// `image`'s buffer now holds the frame with `signature`.
+    pub fn recorded(&mut self, image: usize, signature: u64, counts: DrawCounts) {
+        if image >= self.images.len() {
+            self.images.resize(image + 1, None);
+        }
+        self.images[image] = Some(Recording { signature, counts });
+    }
+}
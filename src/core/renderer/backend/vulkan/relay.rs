@@ -0,0 +1,708 @@
+// src/core/renderer/backend/vulkan/relay.rs
+//
+// Cross-device presentation for hybrid graphics. When the preferred render GPU
+// can't present to the window's surface (`PresentPath::CrossDevice`, e.g. the
+// dGPU of a PRIME / Optimus laptop whose panel hangs off the iGPU), frames are
+// relayed through host memory:
+//
+//   render GPU:     render pass -> stand-in image -> readback buffer (HOST_VISIBLE)
+//   host:           copy into the presenting GPU's staging buffer
+//   presenting GPU: staging buffer -> swapchain image -> present
+//
+// The renderer uses the stand-ins as its swapchain images (same count, format
+// and extent as the real swapchain, same layouts), so the render pass,
+// framebuffers and captures don't change. The relay plays the presentation
+// engine for them: `acquire` signals the frame's image-available semaphore and
+// `present` waits on its render-finished one. The readback is recorded into
+// the frame like a screenshot. Waiting for it on the CPU serializes the two
+// GPUs, so relayed frames don't overlap. Single-GPU systems never create a relay.
+use std::sync::Arc;
+use std::time::Duration;
+
+use smallvec::SmallVec;
+use vulkanalia::VkResult;
+use vulkanalia::prelude::v1_0::*;
+use vulkanalia::vk::{Handle, KhrSurfaceExtension, KhrSwapchainExtension};
+
+use super::barrier::{color_subresource, image_barrier};
+use super::buffer::Buffer;
+use super::capabilities::select_composite_alpha;
+use super::context::DeviceContext;
+use super::frame::wait_for_fences;
+use super::image::find_memory_type;
+use super::present::desired_image_count;
+use super::surface::swapchain_extent;
+use super::tracker::{ResourceKind, ResourceTracker};
+use super::transfer::{color_layers, copy_buffer_to_image};
+use crate::core::renderer::config::CompositeAlpha;
+use crate::error::{AppError, Result};
+
+/// Swapchain formats a relay can carry, preferred first. All have 4 bytes per
+/// texel, so a frame is `width * height * 4` bytes on both GPUs.
+pub const RELAY_FORMATS: [vk::Format; 6] = [
+    vk::Format::B8G8R8A8_SRGB,
+    vk::Format::R8G8B8A8_SRGB,
+    vk::Format::B8G8R8A8_UNORM,
+    vk::Format::R8G8B8A8_UNORM,
+    vk::Format::A2B10G10R10_UNORM_PACK32,
+    vk::Format::A2R10G10B10_UNORM_PACK32,
+];
+
+/// First of `RELAY_FORMATS` that the surface offers and the render GPU can draw
+/// into (`renderable`).
+pub fn select_relay_format(
+    surface_formats: &[vk::SurfaceFormatKHR],
+    renderable: impl Fn(vk::Format) -> bool,
+) -> Option<vk::SurfaceFormatKHR> {
+    RELAY_FORMATS.iter().find_map(|&format| {
+        surface_formats
+            .iter()
+            .copied()
+            .find(|f| f.format == format)
+            .filter(|_| renderable(format))
+    })
+}
+
+/// Bytes of one relayed frame.
+fn frame_size(extent: vk::Extent2D) -> vk::DeviceSize {
+    u64::from(extent.width) * u64::from(extent.height) * 4
+}
+
+/// Stand-in for one swapchain image on the render GPU, plus the buffer the
+/// frames rendered into it are read back into.
+struct StandIn {
+    image: vk::Image,
+    memory: vk::DeviceMemory,
+    readback: Buffer,
+}
+
+impl StandIn {
+    fn new(
+        context: &Arc<DeviceContext>,
+        format: vk::Format,
+        extent: vk::Extent2D,
+        usage: vk::ImageUsageFlags,
+    ) -> Result<Self> {
+        let device = &context.device;
+        let image_info = vk::ImageCreateInfo::builder()
+            .image_type(vk::ImageType::_2D)
+            .format(format)
+            .extent(vk::Extent3D {
+                width: extent.width,
+                height: extent.height,
+                depth: 1,
+            })
+            .mip_levels(1)
+            .array_layers(1)
+            .samples(vk::SampleCountFlags::_1)
+            .tiling(vk::ImageTiling::OPTIMAL)
+            .usage(usage)
+            .sharing_mode(vk::SharingMode::EXCLUSIVE)
+            .initial_layout(vk::ImageLayout::UNDEFINED);
+        let image = unsafe { device.create_image(&image_info, None) }
+            .map_err(|e| AppError::vk(e, "create relay image"))?;
+
+        let requirements = unsafe { device.get_image_memory_requirements(image) };
+        let memory = find_memory_type(
+            &context.instance,
+            context.physical_device,
+            requirements.memory_type_bits,
+            vk::MemoryPropertyFlags::DEVICE_LOCAL,
+        )
+        .and_then(|memory_type| {
+            let alloc_info = vk::MemoryAllocateInfo::builder()
+                .allocation_size(requirements.size)
+                .memory_type_index(memory_type);
+            unsafe { device.allocate_memory(&alloc_info, None) }
+                .map_err(|e| AppError::vk(e, "allocate relay image memory"))
+        });
+        let memory = match memory {
+            Ok(memory) => memory,
+            Err(e) => {
+                unsafe { device.destroy_image(image, None) };
+                return Err(e);
+            }
+        };
+
+        let readback = unsafe { device.bind_image_memory(image, memory, 0) }
+            .map_err(|e| AppError::vk(e, "bind relay image memory"))
+            .and_then(|()| {
+                Buffer::new(
+                    context,
+                    frame_size(extent),
+                    vk::BufferUsageFlags::TRANSFER_DST,
+                    vk::MemoryPropertyFlags::HOST_VISIBLE | vk::MemoryPropertyFlags::HOST_COHERENT,
+                )
+            });
+        let readback = match readback {
+            Ok(readback) => readback,
+            Err(e) => {
+                unsafe {
+                    device.destroy_image(image, None);
+                    device.free_memory(memory, None);
+                }
+                return Err(e);
+            }
+        };
+
+        context
+            .resources()
+            .created(&[ResourceKind::Image, ResourceKind::DeviceMemory]);
+        Ok(Self {
+            image,
+            memory,
+            readback,
+        })
+    }
+
+    /// Destroy the image; the readback buffer goes with `self`.
+    fn destroy(self, device: &Device, tracker: &ResourceTracker) {
+        unsafe {
+            device.destroy_image(self.image, None);
+            device.free_memory(self.memory, None);
+        }
+        tracker.destroyed(&[ResourceKind::Image, ResourceKind::DeviceMemory]);
+    }
+}
+
+/// HOST_VISIBLE | HOST_COHERENT upload buffer on the presenting GPU. That
+/// device has no `DeviceContext`, so this isn't a `Buffer`.
+#[derive(Debug, Clone, Copy, Default)]
+struct Staging {
+    buffer: vk::Buffer,
+    memory: vk::DeviceMemory,
+    size: vk::DeviceSize,
+}
+
+impl Staging {
+    fn new(
+        instance: &Instance,
+        device: &Device,
+        physical_device: vk::PhysicalDevice,
+        size: vk::DeviceSize,
+    ) -> Result<Self> {
+        let buffer_info = vk::BufferCreateInfo::builder()
+            .size(size)
+            .usage(vk::BufferUsageFlags::TRANSFER_SRC)
+            .sharing_mode(vk::SharingMode::EXCLUSIVE);
+        let buffer = unsafe { device.create_buffer(&buffer_info, None) }
+            .map_err(|e| AppError::vk(e, "create relay staging buffer"))?;
+
+        let requirements = unsafe { device.get_buffer_memory_requirements(buffer) };
+        let memory = find_memory_type(
+            instance,
+            physical_device,
+            requirements.memory_type_bits,
+            vk::MemoryPropertyFlags::HOST_VISIBLE | vk::MemoryPropertyFlags::HOST_COHERENT,
+        )
+        .and_then(|memory_type| {
+            let alloc_info = vk::MemoryAllocateInfo::builder()
+                .allocation_size(requirements.size)
+                .memory_type_index(memory_type);
+            unsafe { device.allocate_memory(&alloc_info, None) }
+                .map_err(|e| AppError::vk(e, "allocate relay staging memory"))
+        });
+        let memory = match memory {
+            Ok(memory) => memory,
+            Err(e) => {
+                unsafe { device.destroy_buffer(buffer, None) };
+                return Err(e);
+            }
+        };
+        let staging = Self {
+            buffer,
+            memory,
+            size,
+        };
+        if let Err(e) = unsafe { device.bind_buffer_memory(buffer, memory, 0) } {
+            staging.destroy(device);
+            return Err(AppError::vk(e, "bind relay staging memory"));
+        }
+        Ok(staging)
+    }
+
+    /// Map the buffer for `fill` to write a frame into.
+    fn fill(&self, device: &Device, fill: impl FnOnce(&mut [u8]) -> Result<()>) -> Result<()> {
+        let ptr =
+            unsafe { device.map_memory(self.memory, 0, self.size, vk::MemoryMapFlags::empty()) }
+                .map_err(|e| AppError::vk(e, "map relay staging buffer"))?;
+        let bytes = unsafe { std::slice::from_raw_parts_mut(ptr.cast::<u8>(), self.size as usize) };
+        let filled = fill(bytes);
+        unsafe { device.unmap_memory(self.memory) };
+        filled
+    }
+
+    fn destroy(&self, device: &Device) {
+        unsafe {
+            device.destroy_buffer(self.buffer, None);
+            device.free_memory(self.memory, None);
+        }
+    }
+}
+
+/// Presents frames rendered on another GPU (see the module docs). Created by
+/// `VulkanRenderer::initialize` for `PresentPath::CrossDevice` only.
+pub struct Relay {
+    // Presenting GPU: its own logical device (same instance), swapchain and upload
+    physical_device: vk::PhysicalDevice,
+    device: Device,
+    queue: vk::Queue, // Presents and copies
+    command_pool: vk::CommandPool,
+    cmd: vk::CommandBuffer,                 // Re-recorded for every upload
+    acquired: vk::Semaphore,                // Signaled by acquire, waited by the upload
+    uploaded: SmallVec<[vk::Semaphore; 4]>, // Per swapchain image, waited by present
+    upload_done: vk::Fence,                 // The staging buffer is free again
+    swapchain: Option<vk::SwapchainKHR>,
+    images: SmallVec<[vk::Image; 4]>,
+    format: vk::Format,
+    extent: vk::Extent2D,
+    staging: Staging,
+
+    // Render GPU: stand-ins for the swapchain images, handed out round-robin
+    stand_ins: SmallVec<[StandIn; 4]>,
+    usage: vk::ImageUsageFlags, // Of the stand-ins
+    next: usize,
+    readback_done: vk::Fence, // The frame, and with it the readback, finished
+}
+
+impl Relay {
+    /// Create a device on the presenting `physical_device` with one queue of
+    /// `family` (which presents to the surface), plus the readback fence on
+    /// the `render` device.
+    pub fn new(
+        instance: &Instance,
+        render: &Device,
+        physical_device: vk::PhysicalDevice,
+        family: u32,
+        tracker: &ResourceTracker,
+    ) -> Result<Self> {
+        let priorities = [1.0_f32];
+        let queue_infos = [vk::DeviceQueueCreateInfo::builder()
+            .queue_family_index(family)
+            .queue_priorities(&priorities)
+            .build()];
+        let extensions = [vk::KHR_SWAPCHAIN_EXTENSION.name.as_ptr()];
+        let device_info = vk::DeviceCreateInfo::builder()
+            .queue_create_infos(&queue_infos)
+            .enabled_extension_names(&extensions);
+        let device = unsafe { instance.create_device(physical_device, &device_info, None) }
+            .map_err(|e| AppError::vk(e, "create presenting device"))?;
+        let queue = unsafe { device.get_device_queue(family, 0) };
+
+        let mut relay = Self {
+            physical_device,
+            device,
+            queue,
+            command_pool: vk::CommandPool::null(),
+            cmd: vk::CommandBuffer::null(),
+            acquired: vk::Semaphore::null(),
+            uploaded: SmallVec::new(),
+            upload_done: vk::Fence::null(),
+            swapchain: None,
+            images: SmallVec::new(),
+            format: vk::Format::UNDEFINED,
+            extent: vk::Extent2D::default(),
+            staging: Staging::default(),
+            stand_ins: SmallVec::new(),
+            usage: vk::ImageUsageFlags::empty(),
+            next: 0,
+            readback_done: vk::Fence::null(),
+        };
+        match relay.create_sync(render, family, tracker) {
+            Ok(()) => Ok(relay),
+            Err(e) => {
+                relay.destroy(render, tracker);
+                Err(e)
+            }
+        }
+    }
+
+    fn create_sync(
+        &mut self,
+        render: &Device,
+        family: u32,
+        tracker: &ResourceTracker,
+    ) -> Result<()> {
+        let device = &self.device;
+        let pool_info = vk::CommandPoolCreateInfo::builder()
+            .queue_family_index(family)
+            .flags(vk::CommandPoolCreateFlags::RESET_COMMAND_BUFFER);
+        self.command_pool = unsafe { device.create_command_pool(&pool_info, None) }
+            .map_err(|e| AppError::vk(e, "create relay command pool"))?;
+        let alloc_info = vk::CommandBufferAllocateInfo::builder()
+            .command_pool(self.command_pool)
+            .level(vk::CommandBufferLevel::PRIMARY)
+            .command_buffer_count(1);
+        self.cmd = unsafe { device.allocate_command_buffers(&alloc_info) }
+            .map_err(|e| AppError::vk(e, "allocate relay command buffer"))?[0];
+        let semaphore_info = vk::SemaphoreCreateInfo::builder();
+        self.acquired = unsafe { device.create_semaphore(&semaphore_info, None) }
+            .map_err(|e| AppError::vk(e, "create relay acquire semaphore"))?;
+        // Signaled so the first upload doesn't wait forever
+        let signaled = vk::FenceCreateInfo::builder().flags(vk::FenceCreateFlags::SIGNALED);
+        self.upload_done = unsafe { device.create_fence(&signaled, None) }
+            .map_err(|e| AppError::vk(e, "create relay upload fence"))?;
+        let fence_info = vk::FenceCreateInfo::builder();
+        self.readback_done = unsafe { render.create_fence(&fence_info, None) }
+            .map_err(|e| AppError::vk(e, "create relay readback fence"))?;
+        tracker.created(&[ResourceKind::Fence]);
+        Ok(())
+    }
+
+    /// The GPU that presents, for surface queries.
+    pub fn physical_device(&self) -> vk::PhysicalDevice {
+        self.physical_device
+    }
+
+    pub fn has_swapchain(&self) -> bool {
+        self.swapchain.is_some()
+    }
+
+    /// Format and extent of the swapchain, which the stand-ins share.
+    pub fn format(&self) -> vk::Format {
+        self.format
+    }
+
+    pub fn extent(&self) -> vk::Extent2D {
+        self.extent
+    }
+
+    /// Usage of the stand-ins: color attachment, readback, and blits where the
+    /// render GPU can blit into the format.
+    pub fn usage(&self) -> vk::ImageUsageFlags {
+        self.usage
+    }
+
+    /// Present modes the surface offers on the presenting GPU.
+    pub fn present_modes(
+        &self,
+        instance: &Instance,
+        surface: vk::SurfaceKHR,
+    ) -> Result<Vec<vk::PresentModeKHR>> {
+        let modes = unsafe {
+            instance.get_physical_device_surface_present_modes_khr(self.physical_device, surface)
+        }
+        .map_err(|e| AppError::vk(e, "query relay present modes"))?;
+        if modes.is_empty() {
+            return Err(AppError::Surface(format!(
+                "surface {surface:?} reports no present modes on the presenting GPU"
+            )));
+        }
+        Ok(modes)
+    }
+
+    /// (Re)create the presenting swapchain for a `window`-sized surface and one
+    /// stand-in per swapchain image on the render GPU (`context`). Neither GPU
+    /// may still use the old ones. Returns the stand-in images.
+    pub fn create_swapchain(
+        &mut self,
+        context: &Arc<DeviceContext>,
+        surface: vk::SurfaceKHR,
+        window: vk::Extent2D,
+        present_mode: vk::PresentModeKHR,
+        composite_alpha: CompositeAlpha,
+    ) -> Result<SmallVec<[vk::Image; 4]>> {
+        let instance = &context.instance;
+        let physical_device = self.physical_device;
+        unsafe { self.device.device_wait_idle() }
+            .map_err(|e| AppError::vk(e, "wait for presenting GPU"))?;
+
+        let caps = unsafe {
+            instance.get_physical_device_surface_capabilities_khr(physical_device, surface)
+        }
+        .map_err(|e| AppError::vk(e, "query relay surface capabilities"))?;
+        if !caps
+            .supported_usage_flags
+            .contains(vk::ImageUsageFlags::TRANSFER_DST)
+        {
+            return Err(AppError::Surface(
+                "surface can't take frames copied from another GPU (no TRANSFER_DST)".into(),
+            ));
+        }
+        let surface_formats =
+            unsafe { instance.get_physical_device_surface_formats_khr(physical_device, surface) }
+                .map_err(|e| AppError::vk(e, "query relay surface formats"))?;
+        let render_features = |format: vk::Format| {
+            unsafe {
+                instance.get_physical_device_format_properties(context.physical_device, format)
+            }
+            .optimal_tiling_features
+        };
+        let format = select_relay_format(&surface_formats, |format| {
+            render_features(format).contains(vk::FormatFeatureFlags::COLOR_ATTACHMENT)
+        })
+        .ok_or_else(|| {
+            AppError::Surface(format!(
+                "no surface format both GPUs can use (surface offers {:?})",
+                surface_formats.iter().map(|f| f.format).collect::<Vec<_>>()
+            ))
+        })?;
+        let extent = swapchain_extent(&caps, window);
+        let image_count =
+            desired_image_count(caps.min_image_count, caps.max_image_count, present_mode);
+
+        let old_swapchain = self.swapchain.take();
+        let swapchain_info = vk::SwapchainCreateInfoKHR::builder()
+            .surface(surface)
+            .min_image_count(image_count)
+            .image_format(format.format)
+            .image_color_space(format.color_space)
+            .image_extent(extent)
+            .image_array_layers(1)
+            .image_usage(vk::ImageUsageFlags::TRANSFER_DST)
+            .image_sharing_mode(vk::SharingMode::EXCLUSIVE)
+            .pre_transform(caps.current_transform)
+            .composite_alpha(select_composite_alpha(
+                composite_alpha,
+                caps.supported_composite_alpha,
+            ))
+            .present_mode(present_mode)
+            .clipped(true)
+            .old_swapchain(old_swapchain.unwrap_or_default());
+        let created = unsafe { self.device.create_swapchain_khr(&swapchain_info, None) };
+        if let Some(old) = old_swapchain {
+            unsafe { self.device.destroy_swapchain_khr(old, None) };
+        }
+        let swapchain = created.map_err(|e| AppError::vk(e, "create relay swapchain"))?;
+        self.swapchain = Some(swapchain);
+        let images = unsafe { self.device.get_swapchain_images_khr(swapchain) }
+            .map_err(|e| AppError::vk(e, "get relay swapchain images"))?;
+        self.images = images.into_iter().collect();
+        self.format = format.format;
+        self.extent = extent;
+
+        // Present semaphores and the upload buffer follow the swapchain
+        for semaphore in self.uploaded.drain(..) {
+            unsafe { self.device.destroy_semaphore(semaphore, None) };
+        }
+        let semaphore_info = vk::SemaphoreCreateInfo::builder();
+        for _ in &self.images {
+            let semaphore = unsafe { self.device.create_semaphore(&semaphore_info, None) }
+                .map_err(|e| AppError::vk(e, "create relay present semaphore"))?;
+            self.uploaded.push(semaphore);
+        }
+        self.staging.destroy(&self.device);
+        self.staging = Staging::default();
+        self.staging = Staging::new(instance, &self.device, physical_device, frame_size(extent))?;
+
+        // Stand-ins on the render GPU, blit targets where the format allows it
+        let tracker = context.resources();
+        for stand_in in self.stand_ins.drain(..) {
+            stand_in.destroy(&context.device, tracker);
+        }
+        let mut usage = vk::ImageUsageFlags::COLOR_ATTACHMENT | vk::ImageUsageFlags::TRANSFER_SRC;
+        if render_features(format.format).contains(vk::FormatFeatureFlags::BLIT_DST) {
+            usage |= vk::ImageUsageFlags::TRANSFER_DST;
+        }
+        self.usage = usage;
+        for _ in &self.images {
+            let stand_in = StandIn::new(context, format.format, extent, usage)?;
+            self.stand_ins.push(stand_in);
+        }
+        self.next = 0;
+        Ok(self.stand_ins.iter().map(|s| s.image).collect())
+    }
+
+    /// Hand out the next stand-in, signaling `image_available` on the render
+    /// device's `queue` the way a swapchain acquire would.
+    pub fn acquire(
+        &mut self,
+        device: &Device,
+        queue: vk::Queue,
+        image_available: vk::Semaphore,
+    ) -> Result<usize> {
+        let index = self.next;
+        self.next = (index + 1) % self.stand_ins.len().max(1);
+        let signal = [image_available];
+        let submit_info = vk::SubmitInfo::builder().signal_semaphores(&signal);
+        unsafe { device.queue_submit(queue, &[submit_info], vk::Fence::null()) }
+            .map_err(|e| AppError::vk(e, "signal relay image"))?;
+        Ok(index)
+    }
+
+    /// Buffer the frame rendered into stand-in `index` is read back into.
+    pub fn readback(&self, index: usize) -> vk::Buffer {
+        self.stand_ins[index].readback.handle()
+    }
+
+    /// Present the frame rendered into stand-in `index`: wait for it on the
+    /// render device (consuming `render_finished` on its `queue`), then upload
+    /// it into the next swapchain image and queue that. The inner result is
+    /// the presenting GPU's acquire or present result, for the caller to handle
+    /// like its own.
+    pub fn present(
+        &mut self,
+        device: &Device,
+        queue: vk::Queue,
+        render_finished: vk::Semaphore,
+        index: usize,
+        timeout: Duration,
+    ) -> Result<VkResult<vk::SuccessCode>> {
+        let swapchain = self.swapchain.ok_or(AppError::NotInitialized)?;
+        // ALL_COMMANDS: there's no work in this submit to overlap with the wait
+        let waits = [render_finished];
+        let stages = [vk::PipelineStageFlags::ALL_COMMANDS];
+        let submit_info = vk::SubmitInfo::builder()
+            .wait_semaphores(&waits)
+            .wait_dst_stage_mask(&stages);
+        unsafe { device.reset_fences(&[self.readback_done]) }
+            .map_err(|e| AppError::vk(e, "reset relay readback fence"))?;
+        unsafe { device.queue_submit(queue, &[submit_info], self.readback_done) }
+            .map_err(|e| AppError::vk(e, "submit relay readback wait"))?;
+        wait_for_fences(
+            device,
+            &[self.readback_done],
+            timeout,
+            "wait for relay readback",
+        )?;
+
+        // The previous upload must be done with the staging buffer
+        wait_for_fences(
+            &self.device,
+            &[self.upload_done],
+            timeout,
+            "wait for relay upload",
+        )?;
+        let acquired = unsafe {
+            self.device.acquire_next_image_khr(
+                swapchain,
+                u64::MAX,
+                self.acquired,
+                vk::Fence::null(),
+            )
+        };
+        let image_index = match acquired {
+            Ok((image_index, _)) => image_index as usize,
+            Err(e) => return Ok(Err(e)),
+        };
+        let readback = &self.stand_ins[index].readback;
+        self.staging
+            .fill(&self.device, |bytes| readback.read(bytes))?;
+        self.record_upload(self.images[image_index])?;
+
+        // ALL_COMMANDS: the upload's first barrier starts at TOP_OF_PIPE
+        let waits = [self.acquired];
+        let stages = [vk::PipelineStageFlags::ALL_COMMANDS];
+        let command_buffers = [self.cmd];
+        let signal = [self.uploaded[image_index]];
+        let submit_info = vk::SubmitInfo::builder()
+            .wait_semaphores(&waits)
+            .wait_dst_stage_mask(&stages)
+            .command_buffers(&command_buffers)
+            .signal_semaphores(&signal);
+        unsafe { self.device.reset_fences(&[self.upload_done]) }
+            .map_err(|e| AppError::vk(e, "reset relay upload fence"))?;
+        unsafe {
+            self.device
+                .queue_submit(self.queue, &[submit_info], self.upload_done)
+        }
+        .map_err(|e| AppError::vk(e, "submit relay upload"))?;
+
+        let swapchains = [swapchain];
+        let image_indices = [image_index as u32];
+        let present_info = vk::PresentInfoKHR::builder()
+            .wait_semaphores(&signal)
+            .swapchains(&swapchains)
+            .image_indices(&image_indices);
+        Ok(unsafe { self.device.queue_present_khr(self.queue, &present_info) })
+    }
+
+    /// Record the staging buffer's copy into `image`, leaving it presentable.
+    fn record_upload(&self, image: vk::Image) -> Result<()> {
+        let (device, cmd) = (&self.device, self.cmd);
+        unsafe { device.reset_command_buffer(cmd, vk::CommandBufferResetFlags::empty()) }
+            .map_err(|e| AppError::vk(e, "reset relay command buffer"))?;
+        let begin_info = vk::CommandBufferBeginInfo::builder()
+            .flags(vk::CommandBufferUsageFlags::ONE_TIME_SUBMIT);
+        unsafe { device.begin_command_buffer(cmd, &begin_info) }
+            .map_err(|e| AppError::vk(e, "begin relay command buffer"))?;
+        let transfer = vk::ImageLayout::TRANSFER_DST_OPTIMAL;
+        let undefined = vk::ImageLayout::UNDEFINED;
+        image_barrier(device, cmd, image, undefined, transfer, color_subresource())?;
+        copy_buffer_to_image(
+            device,
+            cmd,
+            self.staging.buffer,
+            image,
+            self.extent,
+            color_layers(),
+        );
+        let present = vk::ImageLayout::PRESENT_SRC_KHR;
+        image_barrier(device, cmd, image, transfer, present, color_subresource())?;
+        unsafe { device.end_command_buffer(cmd) }
+            .map_err(|e| AppError::vk(e, "end relay command buffer"))
+    }
+
+    /// Destroy the stand-ins and readback fence (`render` must be idle), then
+    /// everything on the presenting GPU, its device last.
+    pub fn destroy(mut self, render: &Device, tracker: &ResourceTracker) {
+        for stand_in in self.stand_ins.drain(..) {
+            stand_in.destroy(render, tracker);
+        }
+        if !self.readback_done.is_null() {
+            unsafe { render.destroy_fence(self.readback_done, None) };
+            tracker.destroyed(&[ResourceKind::Fence]);
+        }
+        let device = &self.device;
+        unsafe {
+            device.device_wait_idle().ok();
+            self.staging.destroy(device);
+            for semaphore in self.uploaded.drain(..) {
+                device.destroy_semaphore(semaphore, None);
+            }
+            device.destroy_semaphore(self.acquired, None);
+            device.destroy_fence(self.upload_done, None);
+            device.destroy_command_pool(self.command_pool, None);
+            if let Some(swapchain) = self.swapchain.take() {
+                device.destroy_swapchain_khr(swapchain, None);
+            }
+            device.destroy_device(None);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn offered(formats: &[vk::Format]) -> Vec<vk::SurfaceFormatKHR> {
+        formats
+            .iter()
+            .map(|&format| vk::SurfaceFormatKHR {
+                format,
+                color_space: vk::ColorSpaceKHR::SRGB_NONLINEAR,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn srgb_is_preferred_whatever_the_surface_order() {
+        let surface = offered(&[vk::Format::B8G8R8A8_UNORM, vk::Format::B8G8R8A8_SRGB]);
+        let format = select_relay_format(&surface, |_| true).map(|f| f.format);
+        assert_eq!(format, Some(vk::Format::B8G8R8A8_SRGB));
+    }
+
+    #[test]
+    fn formats_the_render_gpu_cant_draw_are_skipped() {
+        let surface = offered(&[vk::Format::B8G8R8A8_SRGB, vk::Format::R8G8B8A8_UNORM]);
+        let renderable = |format| format != vk::Format::B8G8R8A8_SRGB;
+        let format = select_relay_format(&surface, renderable).map(|f| f.format);
+        assert_eq!(format, Some(vk::Format::R8G8B8A8_UNORM));
+    }
+
+    #[test]
+    fn wider_texels_cant_be_relayed() {
+        let surface = offered(&[vk::Format::R16G16B16A16_SFLOAT]);
+        assert_eq!(select_relay_format(&surface, |_| true), None);
+        assert_eq!(select_relay_format(&[], |_| true), None);
+    }
+
+    #[test]
+    fn frames_are_four_bytes_per_pixel() {
+        let extent = vk::Extent2D {
+            width: 1920,
+            height: 1080,
+        };
+        assert_eq!(frame_size(extent), 1920 * 1080 * 4);
+    }
+}
@@ -0,0 +1,568 @@
+// src/core/renderer/backend/vulkan/render_pass.rs
+use smallvec::SmallVec;
+
+use vulkanalia::prelude::v1_0::*;
+
+use crate::error::{AppError, Result};
+
+/// What happens to a color attachment's previous contents when a pass starts.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ColorLoadOp {
+    Clear([f32; 4]), // Overwrite with this color
+    Load,            // Keep what's there (e.g. UI drawn over a finished 3D frame)
+    DontCare,        // Every pixel gets overwritten anyway
+}
+
+impl ColorLoadOp {
+    pub fn load_op(&self) -> vk::AttachmentLoadOp {
+        match self {
+            Self::Clear(_) => vk::AttachmentLoadOp::CLEAR,
+            Self::Load => vk::AttachmentLoadOp::LOAD,
+            Self::DontCare => vk::AttachmentLoadOp::DONT_CARE,
+        }
+    }
+
+    /// Layout the image must be in when the pass begins. Clear/DontCare discard the
+    /// contents, so UNDEFINED is fine; Load must name the layout the previous pass
+    /// left it in: PRESENT_SRC_KHR for swapchain images, else COLOR_ATTACHMENT_OPTIMAL.
+    pub fn initial_layout(&self, final_layout: vk::ImageLayout) -> vk::ImageLayout {
+        match self {
+            Self::Load if final_layout == vk::ImageLayout::PRESENT_SRC_KHR => {
+                vk::ImageLayout::PRESENT_SRC_KHR
+            }
+            Self::Load => vk::ImageLayout::COLOR_ATTACHMENT_OPTIMAL,
+            Self::Clear(_) | Self::DontCare => vk::ImageLayout::UNDEFINED,
+        }
+    }
+
+    /// Value for `RenderPassBeginInfo::clear_values` of an attachment in `format`
+    /// (ignored unless `Clear`).
+    pub fn clear_value(&self, format: vk::Format) -> vk::ClearValue {
+        let color = match self {
+            Self::Clear(color) => *color,
+            Self::Load | Self::DontCare => [0.0; 4],
+        };
+        ClearValue::for_format(format, color).into()
+    }
+}
+
+/// Color clear value, tagged with the `vk::ClearColorValue` member it fills.
+/// Integer attachments (like the R32_UINT picking target) read the `uint32` /
+/// `int32` members; a float clear there is reinterpreted bit by bit.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ClearValue {
+    Float([f32; 4]), // UNORM, SNORM, SFLOAT and SRGB formats
+    Int([i32; 4]),   // *_SINT formats
+    Uint([u32; 4]),  // *_UINT formats
+}
+
+impl ClearValue {
+    /// `color` in the member `format` reads. Integer formats get each component
+    /// truncated (saturating), so ids and counters clear to whole numbers.
+    pub fn for_format(format: vk::Format, color: [f32; 4]) -> Self {
+        if is_uint_format(format) {
+            Self::Uint(color.map(|c| c as u32))
+        } else if is_sint_format(format) {
+            Self::Int(color.map(|c| c as i32))
+        } else {
+            Self::Float(color)
+        }
+    }
+}
+
+impl From<ClearValue> for vk::ClearValue {
+    fn from(value: ClearValue) -> Self {
+        let color = match value {
+            ClearValue::Float(float32) => vk::ClearColorValue { float32 },
+            ClearValue::Int(int32) => vk::ClearColorValue { int32 },
+            ClearValue::Uint(uint32) => vk::ClearColorValue { uint32 },
+        };
+        vk::ClearValue { color }
+    }
+}
+
+/// Attachment of the current subpass that `clear_attachment_info` clears.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ClearTarget {
+    Color(u32), // Index into the subpass's color attachments
+    Depth,
+    Stencil,
+    DepthStencil,
+}
+
+impl ClearTarget {
+    pub fn aspect(self) -> vk::ImageAspectFlags {
+        match self {
+            Self::Color(_) => vk::ImageAspectFlags::COLOR,
+            Self::Depth => vk::ImageAspectFlags::DEPTH,
+            Self::Stencil => vk::ImageAspectFlags::STENCIL,
+            Self::DepthStencil => vk::ImageAspectFlags::DEPTH | vk::ImageAspectFlags::STENCIL,
+        }
+    }
+}
+
+/// Arguments of `cmd_clear_attachments` clearing `rect` (layer 0) of `target`
+/// to `value`. Only valid inside a render pass; `rect` must lie in its render
+/// area.
+pub fn clear_attachment_info(
+    target: ClearTarget,
+    value: vk::ClearValue,
+    rect: vk::Rect2D,
+) -> (vk::ClearAttachment, vk::ClearRect) {
+    let attachment = vk::ClearAttachment {
+        aspect_mask: target.aspect(),
+        color_attachment: match target {
+            ClearTarget::Color(index) => index,
+            _ => 0, // Ignored for depth/stencil
+        },
+        clear_value: value,
+    };
+    let rect = vk::ClearRect {
+        rect,
+        base_array_layer: 0,
+        layer_count: 1,
+    };
+    (attachment, rect)
+}
+
+/// Color formats whose components are unsigned integers.
+pub fn is_uint_format(format: vk::Format) -> bool {
+    matches!(
+        format,
+        vk::Format::R8_UINT
+            | vk::Format::R8G8_UINT
+            | vk::Format::R8G8B8_UINT
+            | vk::Format::B8G8R8_UINT
+            | vk::Format::R8G8B8A8_UINT
+            | vk::Format::B8G8R8A8_UINT
+            | vk::Format::A8B8G8R8_UINT_PACK32
+            | vk::Format::A2R10G10B10_UINT_PACK32
+            | vk::Format::A2B10G10R10_UINT_PACK32
+            | vk::Format::R16_UINT
+            | vk::Format::R16G16_UINT
+            | vk::Format::R16G16B16_UINT
+            | vk::Format::R16G16B16A16_UINT
+            | vk::Format::R32_UINT
+            | vk::Format::R32G32_UINT
+            | vk::Format::R32G32B32_UINT
+            | vk::Format::R32G32B32A32_UINT
+            | vk::Format::R64_UINT
+            | vk::Format::R64G64_UINT
+            | vk::Format::R64G64B64_UINT
+            | vk::Format::R64G64B64A64_UINT
+    )
+}
+
+/// Color formats whose components are signed integers.
+pub fn is_sint_format(format: vk::Format) -> bool {
+    matches!(
+        format,
+        vk::Format::R8_SINT
+            | vk::Format::R8G8_SINT
+            | vk::Format::R8G8B8_SINT
+            | vk::Format::B8G8R8_SINT
+            | vk::Format::R8G8B8A8_SINT
+            | vk::Format::B8G8R8A8_SINT
+            | vk::Format::A8B8G8R8_SINT_PACK32
+            | vk::Format::A2R10G10B10_SINT_PACK32
+            | vk::Format::A2B10G10R10_SINT_PACK32
+            | vk::Format::R16_SINT
+            | vk::Format::R16G16_SINT
+            | vk::Format::R16G16B16_SINT
+            | vk::Format::R16G16B16A16_SINT
+            | vk::Format::R32_SINT
+            | vk::Format::R32G32_SINT
+            | vk::Format::R32G32B32_SINT
+            | vk::Format::R32G32B32A32_SINT
+            | vk::Format::R64_SINT
+            | vk::Format::R64G64_SINT
+            | vk::Format::R64G64B64_SINT
+            | vk::Format::R64G64B64A64_SINT
+    )
+}
+
+/// References used by one subpass.
+#[derive(Debug, Default, Clone)]
+struct SubpassDesc {
+    color: SmallVec<[vk::AttachmentReference; 4]>,
+    input: SmallVec<[vk::AttachmentReference; 4]>,
+    resolve: SmallVec<[vk::AttachmentReference; 4]>,
+    depth: Option<vk::AttachmentReference>,
+}
+
+/// Fluent render pass description.
+///
+/// Attachments are indexed in the order they are added. `subpass()` starts a new
+/// subpass; `color`/`input`/`resolve`/`depth` apply to the most recently started one.
+///
+/// ```ignore
+/// let rp = RenderPassBuilder::new()
+///     .attachment(gbuffer)   // 0
+///     .attachment(swapchain) // 1
+///     .subpass().color(0)              // geometry
+///     .subpass().input(0).color(1)     // lighting reads geometry output
+///     .chain_dependencies()
+///     .build(device)?;
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct RenderPassBuilder {
+    attachments: SmallVec<[vk::AttachmentDescription; 4]>,
+    clear_values: SmallVec<[vk::ClearValue; 4]>, // One per attachment
+    subpasses: SmallVec<[SubpassDesc; 2]>,
+    dependencies: SmallVec<[vk::SubpassDependency; 4]>,
+}
+
+impl RenderPassBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append an attachment; its index is the number of attachments added before it.
+    /// Its clear value is zero (color black, depth 0.0).
+    pub fn attachment(mut self, desc: vk::AttachmentDescription) -> Self {
+        self.attachments.push(desc);
+        self.clear_values.push(vk::ClearValue::default());
+        self
+    }
+
+    /// Append a single-sample color attachment whose load behavior is `load`;
+    /// the load op, initial layout and clear value all follow from it.
+    pub fn color_attachment(
+        mut self,
+        format: vk::Format,
+        load: ColorLoadOp,
+        final_layout: vk::ImageLayout,
+    ) -> Self {
+        self.attachments.push(
+            vk::AttachmentDescription::builder()
+                .format(format)
+                .samples(vk::SampleCountFlags::_1)
+                .load_op(load.load_op())
+                .store_op(vk::AttachmentStoreOp::STORE)
+                .stencil_load_op(vk::AttachmentLoadOp::DONT_CARE)
+                .stencil_store_op(vk::AttachmentStoreOp::DONT_CARE)
+                .initial_layout(load.initial_layout(final_layout))
+                .final_layout(final_layout)
+                .build(),
+        );
+        self.clear_values.push(load.clear_value(format));
+        self
+    }
+
+    /// Override the clear value of `attachment`, e.g. a typed integer clear for
+    /// an attachment added with `attachment`.
+    pub fn clear_value(mut self, attachment: u32, value: impl Into<vk::ClearValue>) -> Self {
+        self.clear_values[attachment as usize] = value.into();
+        self
+    }
+
+    /// Start a new graphics subpass.
+    pub fn subpass(mut self) -> Self {
+        self.subpasses.push(SubpassDesc::default());
+        self
+    }
+
+    /// Write `attachment` as a color output of the current subpass.
+    pub fn color(mut self, attachment: u32) -> Self {
+        self.current().color.push(reference(
+            attachment,
+            vk::ImageLayout::COLOR_ATTACHMENT_OPTIMAL,
+        ));
+        self
+    }
+
+    /// Read `attachment` as an input attachment (`subpassInput`) in the current subpass.
+    pub fn input(mut self, attachment: u32) -> Self {
+        self.current().input.push(reference(
+            attachment,
+            vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL,
+        ));
+        self
+    }
+
+    /// Resolve the next multisampled color output into `attachment`. Resolves pair
+    /// up with `color` calls in order, so add one per color attachment or none.
+    pub fn resolve(mut self, attachment: u32) -> Self {
+        self.current().resolve.push(reference(
+            attachment,
+            vk::ImageLayout::COLOR_ATTACHMENT_OPTIMAL,
+        ));
+        self
+    }
+
+    /// Use `attachment` as the depth/stencil target of the current subpass.
+    pub fn depth(mut self, attachment: u32) -> Self {
+        self.current().depth = Some(reference(
+            attachment,
+            vk::ImageLayout::DEPTH_STENCIL_ATTACHMENT_OPTIMAL,
+        ));
+        self
+    }
+
+    /// Add an explicit dependency.
+    pub fn dependency(mut self, dep: vk::SubpassDependency) -> Self {
+        self.dependencies.push(dep);
+        self
+    }
+
+    /// Add a by-region dependency between each pair of consecutive subpasses so
+    /// color writes of subpass N are visible to input-attachment reads in N+1.
+    pub fn chain_dependencies(mut self) -> Self {
+        for dst in 1..self.subpasses.len() as u32 {
+            self.dependencies.push(
+                vk::SubpassDependency::builder()
+                    .src_subpass(dst - 1)
+                    .dst_subpass(dst)
+                    .src_stage_mask(vk::PipelineStageFlags::COLOR_ATTACHMENT_OUTPUT)
+                    .dst_stage_mask(vk::PipelineStageFlags::FRAGMENT_SHADER)
+                    .src_access_mask(vk::AccessFlags::COLOR_ATTACHMENT_WRITE)
+                    .dst_access_mask(vk::AccessFlags::INPUT_ATTACHMENT_READ)
+                    .dependency_flags(vk::DependencyFlags::BY_REGION)
+                    .build(),
+            );
+        }
+        self
+    }
+
+    /// Add the dependency from the last subpass to the end of the pass that
+    /// orders its color writes before the transition to `final_layout` (e.g.
+    /// PRESENT_SRC_KHR). Without it the implicit one has no source access, so
+    /// the writes aren't made available before the transition. The destination
+    /// is COLOR_ATTACHMENT_OUTPUT with no access: presentation waits on the
+    /// submit's semaphore, which covers visibility, and barriers recorded after
+    /// the pass (readbacks, blits) start from that stage, so they chain onto it.
+    pub fn present_dependency(mut self) -> Self {
+        let last = self.subpasses.len().saturating_sub(1) as u32;
+        self.dependencies.push(
+            vk::SubpassDependency::builder()
+                .src_subpass(last)
+                .dst_subpass(vk::SUBPASS_EXTERNAL)
+                .src_stage_mask(vk::PipelineStageFlags::COLOR_ATTACHMENT_OUTPUT)
+                .dst_stage_mask(vk::PipelineStageFlags::COLOR_ATTACHMENT_OUTPUT)
+                .src_access_mask(vk::AccessFlags::COLOR_ATTACHMENT_WRITE)
+                .dst_access_mask(vk::AccessFlags::empty())
+                .build(),
+        );
+        self
+    }
+
+    pub fn dependencies(&self) -> &[vk::SubpassDependency] {
+        &self.dependencies
+    }
+
+    pub fn attachments(&self) -> &[vk::AttachmentDescription] {
+        &self.attachments
+    }
+
+    pub fn attachment_count(&self) -> usize {
+        self.attachments.len()
+    }
+
+    pub fn subpass_count(&self) -> usize {
+        self.subpasses.len()
+    }
+
+    /// Clear values to begin the pass with, one per attachment in index order.
+    pub fn clear_values(&self) -> &[vk::ClearValue] {
+        &self.clear_values
+    }
+
+    /// Create the render pass. Fails on references to attachments that don't exist.
+    pub fn build(&self, device: &Device) -> Result<vk::RenderPass> {
+        if self.subpasses.is_empty() {
+            return Err(AppError::Config(
+                "render pass needs at least one subpass".into(),
+            ));
+        }
+        let count = self.attachments.len() as u32;
+        for sp in &self.subpasses {
+            let mut refs = sp
+                .color
+                .iter()
+                .chain(&sp.input)
+                .chain(&sp.resolve)
+                .chain(sp.depth.iter());
+            if let Some(bad) = refs.find(|r| r.attachment >= count) {
+                return Err(AppError::Config(format!(
+                    "subpass references attachment {} but only {count} exist",
+                    bad.attachment
+                )));
+            }
+            if !sp.resolve.is_empty() && sp.resolve.len() != sp.color.len() {
+                return Err(AppError::Config(format!(
+                    "subpass has {} resolve attachments for {} color attachments",
+                    sp.resolve.len(),
+                    sp.color.len()
+                )));
+            }
+        }
+
+        // Descriptions point into self.subpasses, which outlives the create call
+        let subpasses: SmallVec<[vk::SubpassDescription; 2]> = self
+            .subpasses
+            .iter()
+            .map(|sp| {
+                let mut desc = vk::SubpassDescription::builder()
+                    .pipeline_bind_point(vk::PipelineBindPoint::GRAPHICS)
+                    .color_attachments(&sp.color)
+                    .input_attachments(&sp.input);
+                if !sp.resolve.is_empty() {
+                    desc = desc.resolve_attachments(&sp.resolve);
+                }
+                if let Some(depth) = &sp.depth {
+                    desc = desc.depth_stencil_attachment(depth);
+                }
+                desc.build()
+            })
+            .collect();
+
+        let info = vk::RenderPassCreateInfo::builder()
+            .attachments(&self.attachments)
+            .subpasses(&subpasses)
+            .dependencies(&self.dependencies);
+
+        unsafe { device.create_render_pass(&info, None) }
+            .map_err(|e| AppError::vk(e, "create render pass"))
+    }
+
+    fn current(&mut self) -> &mut SubpassDesc {
+        if self.subpasses.is_empty() {
+            self.subpasses.push(SubpassDesc::default());
+        }
+        self.subpasses.last_mut().unwrap()
+    }
+}
+
+fn reference(attachment: u32, layout: vk::ImageLayout) -> vk::AttachmentReference {
+    vk::AttachmentReference::builder()
+        .attachment(attachment)
+        .layout(layout)
+        .build()
+}
+
+/// Check that `clear_count` clear values fit a pass with `attachments`.
+/// Vulkan reads one value per attachment up to the last one loaded with CLEAR
+/// (depth or stencil); fewer is undefined, more means the two lists drifted apart.
+pub fn validate_clear_values(
+    attachments: &[vk::AttachmentDescription],
+    clear_count: usize,
+) -> Result<()> {
+    let clears = |a: &vk::AttachmentDescription| {
+        a.load_op == vk::AttachmentLoadOp::CLEAR || a.stencil_load_op == vk::AttachmentLoadOp::CLEAR
+    };
+    let required = attachments.iter().rposition(clears).map_or(0, |i| i + 1);
+    if clear_count < required {
+        return Err(AppError::Config(format!(
+            "{clear_count} clear values for a render pass whose attachment {} is cleared \
+             (need at least {required})",
+            required - 1
+        )));
+    }
+    if clear_count > attachments.len() {
+        return Err(AppError::Config(format!(
+            "{clear_count} clear values for a render pass with {} attachments",
+            attachments.len()
+        )));
+    }
+    Ok(())
+}
+
+/// Image views of one framebuffer, in the render pass's attachment order.
+pub type FramebufferAttachments = SmallVec<[vk::ImageView; 4]>;
+
+/// Views of the frame pass's framebuffer for one swapchain image, in the order
+/// `create_render_pass` declares them: [MSAA color], [HDR color], swapchain
+/// image, [depth]. Absent targets are features that are off.
+pub fn frame_attachments(
+    msaa: Option<vk::ImageView>,
+    hdr: Option<vk::ImageView>,
+    swapchain: vk::ImageView,
+    depth: Option<vk::ImageView>,
+) -> FramebufferAttachments {
+    let mut views = FramebufferAttachments::new();
+    views.extend(msaa);
+    views.extend(hdr);
+    views.push(swapchain);
+    views.extend(depth);
+    views
+}
+
+/// Check that a framebuffer with `view_count` views fits a pass with
+/// `attachments`: Vulkan needs exactly one view per attachment.
+pub fn validate_framebuffer_attachments(
+    attachments: &[vk::AttachmentDescription],
+    view_count: usize,
+) -> Result<()> {
+    if view_count != attachments.len() {
+        return Err(AppError::Config(format!(
+            "framebuffer with {view_count} views for a render pass with {} attachments",
+            attachments.len()
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use vulkanalia::vk::Handle;
+
+    use super::*;
+
+    fn view(raw: u64) -> vk::ImageView {
+        vk::ImageView::from_raw(raw)
+    }
+
+    #[test]
+    fn frame_attachments_follow_the_pass_order() {
+        let all = frame_attachments(Some(view(1)), Some(view(2)), view(3), Some(view(4)));
+        assert_eq!(&all[..], [view(1), view(2), view(3), view(4)]);
+        let plain = frame_attachments(None, None, view(3), None);
+        assert_eq!(&plain[..], [view(3)]);
+        let msaa_depth = frame_attachments(Some(view(1)), None, view(3), Some(view(4)));
+        assert_eq!(&msaa_depth[..], [view(1), view(3), view(4)]);
+    }
+
+    #[test]
+    fn framebuffer_needs_one_view_per_attachment() {
+        let attachments = [vk::AttachmentDescription::default(); 2];
+        assert!(validate_framebuffer_attachments(&attachments, 2).is_ok());
+        assert!(validate_framebuffer_attachments(&attachments, 1).is_err());
+        assert!(validate_framebuffer_attachments(&attachments, 3).is_err());
+    }
+
+    #[test]
+    fn uint_attachments_get_a_uint_clear() {
+        let value = ClearValue::for_format(vk::Format::R32_UINT, [7.0, 0.0, 0.0, 1.0]);
+        assert_eq!(value, ClearValue::Uint([7, 0, 0, 1]));
+        let raw: vk::ClearValue = value.into();
+        assert_eq!(unsafe { raw.color.uint32 }, [7, 0, 0, 1]);
+        // Negative colors saturate instead of wrapping
+        let negative = ClearValue::for_format(vk::Format::R8G8B8A8_UINT, [-1.0; 4]);
+        assert_eq!(negative, ClearValue::Uint([0; 4]));
+    }
+
+    #[test]
+    fn sint_attachments_get_an_int_clear() {
+        let value = ClearValue::for_format(vk::Format::R16G16_SINT, [-2.5, 3.0, 0.0, 0.0]);
+        assert_eq!(value, ClearValue::Int([-2, 3, 0, 0]));
+        let raw: vk::ClearValue = value.into();
+        assert_eq!(unsafe { raw.color.int32 }, [-2, 3, 0, 0]);
+    }
+
+    #[test]
+    fn float_attachments_get_a_float_clear() {
+        let color = [0.1, 0.2, 0.3, 1.0];
+        for format in [
+            vk::Format::B8G8R8A8_SRGB,
+            vk::Format::R8G8B8A8_UNORM,
+            vk::Format::R16G16B16A16_SFLOAT,
+        ] {
+            assert_eq!(
+                ClearValue::for_format(format, color),
+                ClearValue::Float(color)
+            );
+        }
+        let raw: vk::ClearValue = ClearValue::Float(color).into();
+        assert_eq!(unsafe { raw.color.float32 }, color);
+    }
+}
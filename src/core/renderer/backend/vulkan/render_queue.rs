@@ -0,0 +1,79 @@
+// src/core/renderer/backend/vulkan/render_queue.rs
+//
+// Draw order for `RendererConfig::sort_draws`. A frame's draws are recorded
+// grouped by pipeline, then by material within each pipeline, so every
+// pipeline and material set is bound once per run instead of on every switch.
+// Clears queued between draws (`DrawContext::clear_attachment`) split the
+// frame into segments that are sorted separately, keeping what a clear hides
+// on the right side of it.
+use vulkanalia::prelude::v1_0::*;
+use vulkanalia::vk::Handle;
+
+use crate::core::renderer::stats::BindCounts;
+
+/// What a draw binds, as raw handles so keys sort; `material` is 0 for draws
+/// without a material set.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DrawKey {
+    pub pipeline: u64,
+    pub material: u64,
+}
+
+impl DrawKey {
+    pub fn new(pipeline: vk::Pipeline, material: Option<vk::DescriptorSet>) -> Self {
+        Self {
+            pipeline: pipeline.as_raw(),
+            material: material.map_or(0, |set| set.as_raw()),
+        }
+    }
+}
+
+/// Recording order of draws with `keys`: each segment between `barriers` (draw
+/// indices a clear comes before, ascending) sorted by key. The sort is stable,
+/// so draws sharing a key keep their order.
+pub fn sorted_order<K: Ord>(keys: &[K], barriers: &[usize]) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..keys.len()).collect();
+    let mut start = 0;
+    for end in barriers.iter().copied().chain([keys.len()]) {
+        let end = end.clamp(start, keys.len());
+        order[start..end].sort_by(|&a, &b| keys[a].cmp(&keys[b]));
+        start = end;
+    }
+    order
+}
+
+/// Pipeline and material set bound while recording, to skip redundant binds.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BindState {
+    current: Option<DrawKey>,
+    pub counts: BindCounts, // Binds made so far
+}
+
+impl BindState {
+    /// Which of (pipeline, material set) must be bound before drawing with
+    /// `key`, counting them. A new pipeline may have another layout, so the
+    /// material set is bound again after it.
+    pub fn update(&mut self, key: DrawKey) -> (bool, bool) {
+        let pipeline = self
+            .current
+            .is_none_or(|bound| bound.pipeline != key.pipeline);
+        let material = key.material != 0
+            && (pipeline
+                || self
+                    .current
+                    .is_none_or(|bound| bound.material != key.material));
+        self.counts.pipelines += u32::from(pipeline);
+        self.counts.materials += u32::from(material);
+        self.current = Some(key);
+        (pipeline, material)
+    }
+}
+
+/// Binds needed to record draws with `keys` in the given order.
+pub fn count_binds(keys: impl IntoIterator<Item = DrawKey>) -> BindCounts {
+    let mut state = BindState::default();
+    for key in keys {
+        state.update(key);
+    }
+    state.counts
+}
@@ -0,0 +1,127 @@
+// src/core/renderer/backend/vulkan/sampler.rs
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+
+use vulkanalia::prelude::v1_0::*;
+
+use crate::error::{AppError, Result};
+
+/// Filtering and addressing of a sampler. Equal configs share one `vk::Sampler`.
+#[derive(Debug, Clone, Copy)]
+pub struct SamplerConfig {
+    pub mag_filter: vk::Filter,
+    pub min_filter: vk::Filter,
+    pub mipmap_mode: vk::SamplerMipmapMode,
+    pub address_mode_u: vk::SamplerAddressMode,
+    pub address_mode_v: vk::SamplerAddressMode,
+    pub address_mode_w: vk::SamplerAddressMode,
+    pub max_anisotropy: Option<f32>, // None = off; clamped to the device limit
+}
+
+impl Default for SamplerConfig {
+    fn default() -> Self {
+        Self {
+            mag_filter: vk::Filter::LINEAR,
+            min_filter: vk::Filter::LINEAR,
+            mipmap_mode: vk::SamplerMipmapMode::LINEAR,
+            address_mode_u: vk::SamplerAddressMode::REPEAT,
+            address_mode_v: vk::SamplerAddressMode::REPEAT,
+            address_mode_w: vk::SamplerAddressMode::REPEAT,
+            max_anisotropy: None,
+        }
+    }
+}
+
+impl SamplerConfig {
+    /// Same address mode on all three axes.
+    pub fn with_address_mode(mut self, mode: vk::SamplerAddressMode) -> Self {
+        self.address_mode_u = mode;
+        self.address_mode_v = mode;
+        self.address_mode_w = mode;
+        self
+    }
+
+    // Anisotropy compared bitwise so the config can key a HashMap
+    fn key(&self) -> (i32, i32, i32, i32, i32, i32, Option<u32>) {
+        (
+            self.mag_filter.as_raw(),
+            self.min_filter.as_raw(),
+            self.mipmap_mode.as_raw(),
+            self.address_mode_u.as_raw(),
+            self.address_mode_v.as_raw(),
+            self.address_mode_w.as_raw(),
+            self.max_anisotropy.map(f32::to_bits),
+        )
+    }
+}
+
+impl PartialEq for SamplerConfig {
+    fn eq(&self, other: &Self) -> bool {
+        self.key() == other.key()
+    }
+}
+
+impl Eq for SamplerConfig {}
+
+impl Hash for SamplerConfig {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.key().hash(state);
+    }
+}
+
+/// Samplers created so far, one per distinct `SamplerConfig`.
+/// Handles stay valid until `destroy`; callers must not destroy them.
+#[derive(Debug, Default)]
+pub struct SamplerCache {
+    samplers: HashMap<SamplerConfig, vk::Sampler>,
+}
+
+impl SamplerCache {
+    /// Cached sampler for `config`, created on first use.
+    /// `max_anisotropy` is the device limit, or None when `samplerAnisotropy` is off.
+    pub fn get_or_create(
+        &mut self,
+        device: &Device,
+        config: SamplerConfig,
+        max_anisotropy: Option<f32>,
+    ) -> Result<vk::Sampler> {
+        if let Some(&sampler) = self.samplers.get(&config) {
+            return Ok(sampler);
+        }
+
+        let anisotropy = match (config.max_anisotropy, max_anisotropy) {
+            (Some(requested), Some(limit)) if requested > 1.0 => Some(requested.min(limit)),
+            _ => None,
+        };
+        let info = vk::SamplerCreateInfo::builder()
+            .mag_filter(config.mag_filter)
+            .min_filter(config.min_filter)
+            .mipmap_mode(config.mipmap_mode)
+            .address_mode_u(config.address_mode_u)
+            .address_mode_v(config.address_mode_v)
+            .address_mode_w(config.address_mode_w)
+            .anisotropy_enable(anisotropy.is_some())
+            .max_anisotropy(anisotropy.unwrap_or(1.0))
+            .min_lod(0.0)
+            .max_lod(vk::LOD_CLAMP_NONE)
+            .border_color(vk::BorderColor::FLOAT_OPAQUE_BLACK);
+        let sampler = unsafe { device.create_sampler(&info, None) }
+            .map_err(|e| AppError::vk(e, "create sampler"))?;
+        self.samplers.insert(config, sampler);
+        Ok(sampler)
+    }
+
+    pub fn len(&self) -> usize {
+        self.samplers.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.samplers.is_empty()
+    }
+
+    pub fn destroy(&mut self, device: &Device) {
+        for (_, sampler) in self.samplers.drain() {
+            unsafe { device.destroy_sampler(sampler, None) };
+        }
+    }
+}
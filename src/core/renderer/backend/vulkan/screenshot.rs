@@ -0,0 +1,208 @@
+// src/core/renderer/backend/vulkan/screenshot.rs
+//
+// Non-blocking swapchain captures. A request is recorded into the next frame's
+// command buffer (after the render pass, before present), so completion is tied
+// to that frame's in-flight fence instead of a dedicated wait.
+use std::sync::Arc;
+
+use smallvec::SmallVec;
+use vulkanalia::prelude::v1_0::*;
+
+use super::barrier::{buffer_barrier, color_subresource, image_barrier};
+use super::buffer::Buffer;
+use super::context::DeviceContext;
+use super::transfer::color_layers;
+use crate::error::{AppError, Result};
+
+/// Handle of a capture requested with `VulkanRenderer::screenshot_async`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ScreenshotToken(u64);
+
+/// Pixels of a finished capture, rows tightly packed, top row first.
+#[derive(Debug, Clone)]
+pub struct Screenshot {
+    pub width: u32,
+    pub height: u32,
+    pub format: vk::Format, // Swapchain format, usually B8G8R8A8: swizzle before saving
+    pub pixels: Vec<u8>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CaptureState {
+    Requested,                 // Waiting for the next frame to record the copy
+    Submitted { slot: usize }, // Copy recorded in this frame-in-flight slot
+    Ready,                     // Slot's fence signaled, buffer holds the pixels
+}
+
+struct Capture {
+    token: ScreenshotToken,
+    state: CaptureState,
+    buffer: Option<Buffer>,
+    extent: vk::Extent2D,
+    format: vk::Format,
+}
+
+/// Pending captures of one renderer.
+#[derive(Default)]
+pub struct Screenshots {
+    next: u64,
+    captures: Vec<Capture>,
+}
+
+impl Screenshots {
+    pub fn request(&mut self) -> ScreenshotToken {
+        let token = ScreenshotToken(self.next);
+        self.next += 1;
+        self.captures.push(Capture {
+            token,
+            state: CaptureState::Requested,
+            buffer: None,
+            extent: vk::Extent2D::default(),
+            format: vk::Format::UNDEFINED,
+        });
+        token
+    }
+
+    /// Frame slot `slot` finished on the GPU: its captures are complete.
+    pub fn frame_completed(&mut self, slot: usize) {
+        for capture in &mut self.captures {
+            if capture.state == (CaptureState::Submitted { slot }) {
+                capture.state = CaptureState::Ready;
+            }
+        }
+    }
+
+    /// Claim requested captures for the frame recorded in `slot`, giving each a
+    /// readback buffer of the current size. Returns the buffers to copy into.
+    pub fn begin_frame(
+        &mut self,
+        context: &Arc<DeviceContext>,
+        extent: vk::Extent2D,
+        format: vk::Format,
+        slot: usize,
+    ) -> Result<SmallVec<[vk::Buffer; 1]>> {
+        // Allocate everything first so a failure leaves all requests pending
+        let size = u64::from(extent.width) * u64::from(extent.height) * 4;
+        let requested = self
+            .captures
+            .iter()
+            .filter(|c| c.state == CaptureState::Requested)
+            .count();
+        let buffers = (0..requested)
+            .map(|_| {
+                Buffer::new(
+                    context,
+                    size,
+                    vk::BufferUsageFlags::TRANSFER_DST,
+                    vk::MemoryPropertyFlags::HOST_VISIBLE | vk::MemoryPropertyFlags::HOST_COHERENT,
+                )
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut targets = SmallVec::new();
+        let pending = self
+            .captures
+            .iter_mut()
+            .filter(|c| c.state == CaptureState::Requested);
+        for (capture, buffer) in pending.zip(buffers) {
+            targets.push(buffer.handle());
+            capture.buffer = Some(buffer);
+            capture.extent = extent;
+            capture.format = format;
+            capture.state = CaptureState::Submitted { slot };
+        }
+        Ok(targets)
+    }
+
+    /// Slot whose fence decides whether `token` is done, if it was submitted.
+    pub fn submitted_slot(&self, token: ScreenshotToken) -> Option<usize> {
+        match self.find(token)?.state {
+            CaptureState::Submitted { slot } => Some(slot),
+            _ => None,
+        }
+    }
+
+    /// Mark `token` complete after its slot's fence was seen signaled.
+    pub fn mark_ready(&mut self, token: ScreenshotToken) {
+        if let Some(capture) = self.captures.iter_mut().find(|c| c.token == token) {
+            capture.state = CaptureState::Ready;
+        }
+    }
+
+    pub fn is_ready(&self, token: ScreenshotToken) -> bool {
+        self.find(token)
+            .is_some_and(|c| c.state == CaptureState::Ready)
+    }
+
+    /// Read back and forget a ready capture. `Ok(None)` if it isn't ready (or unknown).
+    pub fn take(&mut self, token: ScreenshotToken) -> Result<Option<Screenshot>> {
+        let Some(index) = self
+            .captures
+            .iter()
+            .position(|c| c.token == token && c.state == CaptureState::Ready)
+        else {
+            return Ok(None);
+        };
+        let capture = self.captures.swap_remove(index);
+        let buffer = capture
+            .buffer
+            .ok_or_else(|| AppError::Config("ready screenshot without a buffer".into()))?;
+        let mut pixels = vec![0; buffer.size() as usize];
+        buffer.read(&mut pixels)?;
+        Ok(Some(Screenshot {
+            width: capture.extent.width,
+            height: capture.extent.height,
+            format: capture.format,
+            pixels,
+        }))
+    }
+
+    /// Drop every capture (and its buffer); the GPU must be idle.
+    pub fn clear(&mut self) {
+        self.captures.clear();
+    }
+
+    fn find(&self, token: ScreenshotToken) -> Option<&Capture> {
+        self.captures.iter().find(|c| c.token == token)
+    }
+}
+
+/// Record copies of the presentable `image` into `targets`, leaving it in PRESENT_SRC.
+/// `layout` is what the frame left it in: PRESENT_SRC_KHR after the render pass,
+/// TRANSFER_DST_OPTIMAL after a compute blit.
+pub fn record_capture(
+    device: &Device,
+    cmd: vk::CommandBuffer,
+    image: vk::Image,
+    layout: vk::ImageLayout,
+    extent: vk::Extent2D,
+    targets: &[vk::Buffer],
+) -> Result<()> {
+    let present = vk::ImageLayout::PRESENT_SRC_KHR;
+    if targets.is_empty() {
+        if layout == present {
+            return Ok(());
+        }
+        return image_barrier(device, cmd, image, layout, present, color_subresource());
+    }
+    let transfer = vk::ImageLayout::TRANSFER_SRC_OPTIMAL;
+    image_barrier(device, cmd, image, layout, transfer, color_subresource())?;
+    let region = vk::BufferImageCopy::builder()
+        .image_subresource(color_layers())
+        .image_extent(vk::Extent3D {
+            width: extent.width,
+            height: extent.height,
+            depth: 1,
+        });
+    for &buffer in targets {
+        unsafe { device.cmd_copy_image_to_buffer(cmd, image, transfer, buffer, &[region]) };
+        buffer_barrier(
+            device,
+            cmd,
+            buffer,
+            vk::AccessFlags::TRANSFER_WRITE,
+            vk::AccessFlags::HOST_READ,
+        )?;
+    }
+    image_barrier(device, cmd, image, transfer, present, color_subresource())
+}
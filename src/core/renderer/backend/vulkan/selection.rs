@@ -0,0 +1,229 @@
+// src/core/renderer/backend/vulkan/selection.rs
+//
+// Physical device selection. The preferred render GPU (the first discrete one,
+// or the first integrated one with `RendererConfig::prefer_integrated`) isn't
+// always wired to the display: on hybrid (PRIME / Optimus) laptops the panel
+// hangs off the iGPU, and the dGPU reports no present support for the surface.
+// `select_gpu` detects that case, and `device_plan` then picks the copy path:
+// render on the dGPU, read back through a host buffer and upload into the
+// iGPU's swapchain image (`relay.rs`). Single-GPU systems stay on the direct
+// path, and a render GPU that can't take part in the relay gives way to the
+// best-ranked GPU that can present, so the window isn't left black.
+use vulkanalia::prelude::v1_0::*;
+
+/// Queue support of one physical device for the window surface.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GpuCandidate {
+    pub device_type: vk::PhysicalDeviceType,
+    pub graphics_family: Option<u32>,
+    pub present_family: Option<u32>, // Family able to present to the surface
+}
+
+impl GpuCandidate {
+    /// Graphics and present queue families, if the GPU has both.
+    pub fn families(&self) -> Option<(u32, u32)> {
+        self.graphics_family.zip(self.present_family)
+    }
+
+    fn can_present(&self) -> bool {
+        self.families().is_some()
+    }
+}
+
+/// Graphics and present families from each family's flags and surface support.
+/// A family that does both wins (swapchain images then stay EXCLUSIVE to one
+/// queue family); otherwise the first graphics and the first present family.
+pub fn pick_queue_families(families: &[(vk::QueueFlags, bool)]) -> (Option<u32>, Option<u32>) {
+    let graphics = |flags: &vk::QueueFlags| flags.contains(vk::QueueFlags::GRAPHICS);
+    if let Some(both) = families
+        .iter()
+        .position(|(flags, present)| graphics(flags) && *present)
+    {
+        return (Some(both as u32), Some(both as u32));
+    }
+    let graphics_family = families.iter().position(|(flags, _)| graphics(flags));
+    let present_family = families.iter().position(|&(_, present)| present);
+    (
+        graphics_family.map(|i| i as u32),
+        present_family.map(|i| i as u32),
+    )
+}
+
+/// How frames reach the surface, as indices into the candidate list.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PresentPath {
+    Direct(usize), // Render and present on the same GPU
+    // The preferred GPU `render` can't present (hybrid graphics); `present` is
+    // the best-ranked one that can, and frames are copied over to it
+    CrossDevice { render: usize, present: usize },
+}
+
+/// Rank of a device type as render GPU, lower is better: discrete before
+/// integrated, or the other way around with `prefer_integrated`. None for the
+/// rest (virtual, CPU), which are only used when they are the presenting GPU.
+pub fn device_type_rank(
+    device_type: vk::PhysicalDeviceType,
+    prefer_integrated: bool,
+) -> Option<u8> {
+    let (discrete, integrated) = if prefer_integrated { (1, 0) } else { (0, 1) };
+    match device_type {
+        vk::PhysicalDeviceType::DISCRETE_GPU => Some(discrete),
+        vk::PhysicalDeviceType::INTEGRATED_GPU => Some(integrated),
+        _ => None,
+    }
+}
+
+/// Pick the preferred render GPU (the best `device_type_rank` with a graphics
+/// queue, first on ties) and whether it can present itself. If it can't, frames
+/// go to the best-ranked presenting one (unranked types last).
+/// None if no GPU can render and present.
+pub fn select_gpu(candidates: &[GpuCandidate], prefer_integrated: bool) -> Option<PresentPath> {
+    let rank = |c: &GpuCandidate| device_type_rank(c.device_type, prefer_integrated);
+    let (presenter, _) = candidates
+        .iter()
+        .enumerate()
+        .filter(|(_, c)| c.can_present())
+        .min_by_key(|&(_, c)| rank(c).unwrap_or(u8::MAX))?;
+    let render = candidates
+        .iter()
+        .enumerate()
+        .filter(|(_, c)| c.graphics_family.is_some())
+        .filter_map(|(i, c)| Some((rank(c)?, i)))
+        .min_by_key(|&(rank, _)| rank)
+        .map_or(presenter, |(_, i)| i);
+    if candidates[render].can_present() {
+        Some(PresentPath::Direct(render))
+    } else {
+        Some(PresentPath::CrossDevice {
+            render,
+            present: presenter,
+        })
+    }
+}
+
+/// GPU to create the renderer's device on, and the presenting GPU to relay its
+/// frames to when that's another one. `can_relay` says whether a render GPU
+/// can feed the relay (its stand-in images go through VK_KHR_swapchain's
+/// layouts); if it can't, the presenting GPU renders the frames itself.
+pub fn device_plan(
+    path: PresentPath,
+    can_relay: impl FnOnce(usize) -> bool,
+) -> (usize, Option<usize>) {
+    match path {
+        PresentPath::Direct(index) => (index, None),
+        PresentPath::CrossDevice { render, present } if can_relay(render) => {
+            (render, Some(present))
+        }
+        PresentPath::CrossDevice { present, .. } => (present, None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DISCRETE: vk::PhysicalDeviceType = vk::PhysicalDeviceType::DISCRETE_GPU;
+    const INTEGRATED: vk::PhysicalDeviceType = vk::PhysicalDeviceType::INTEGRATED_GPU;
+
+    fn gpu(device_type: vk::PhysicalDeviceType, present: bool) -> GpuCandidate {
+        GpuCandidate {
+            device_type,
+            graphics_family: Some(0),
+            present_family: present.then_some(0),
+        }
+    }
+
+    #[test]
+    fn device_type_decides_not_position() {
+        let candidates = [gpu(INTEGRATED, true), gpu(DISCRETE, true)];
+        assert_eq!(select_gpu(&candidates, false), Some(PresentPath::Direct(1)));
+        assert_eq!(select_gpu(&candidates, true), Some(PresentPath::Direct(0)));
+        // First on ties
+        let twins = [gpu(DISCRETE, true), gpu(DISCRETE, true)];
+        assert_eq!(select_gpu(&twins, false), Some(PresentPath::Direct(0)));
+    }
+
+    #[test]
+    fn unranked_gpus_are_a_last_resort() {
+        let cpu = gpu(vk::PhysicalDeviceType::CPU, true);
+        assert_eq!(select_gpu(&[cpu], false), Some(PresentPath::Direct(0)));
+        let candidates = [cpu, gpu(INTEGRATED, true)];
+        assert_eq!(select_gpu(&candidates, false), Some(PresentPath::Direct(1)));
+    }
+
+    #[test]
+    fn hybrid_selects_the_copy_path() {
+        let candidates = [gpu(DISCRETE, false), gpu(INTEGRATED, true)];
+        let path = select_gpu(&candidates, false).unwrap();
+        assert_eq!(
+            path,
+            PresentPath::CrossDevice {
+                render: 0,
+                present: 1
+            }
+        );
+        // Rendered on the dGPU, relayed to the iGPU
+        assert_eq!(device_plan(path, |_| true), (0, Some(1)));
+        // Preferring the presenting iGPU needs no copy
+        assert_eq!(select_gpu(&candidates, true), Some(PresentPath::Direct(1)));
+    }
+
+    #[test]
+    fn single_gpu_never_relays() {
+        let path = select_gpu(&[gpu(DISCRETE, true)], false).unwrap();
+        assert_eq!(device_plan(path, |_| unreachable!()), (0, None));
+    }
+
+    #[test]
+    fn render_gpu_without_relay_support_gives_way() {
+        let candidates = [gpu(DISCRETE, false), gpu(INTEGRATED, true)];
+        let path = select_gpu(&candidates, false).unwrap();
+        assert_eq!(device_plan(path, |render| render != 0), (1, None));
+    }
+
+    #[test]
+    fn nothing_presents() {
+        assert_eq!(select_gpu(&[], false), None);
+        assert_eq!(select_gpu(&[gpu(DISCRETE, false)], false), None);
+        let compute_only = GpuCandidate {
+            graphics_family: None,
+            ..gpu(DISCRETE, true)
+        };
+        assert_eq!(select_gpu(&[compute_only], false), None);
+    }
+
+    const GRAPHICS: vk::QueueFlags = vk::QueueFlags::GRAPHICS;
+    const COMPUTE: vk::QueueFlags = vk::QueueFlags::COMPUTE;
+
+    #[test]
+    fn a_family_doing_both_is_used_for_both() {
+        // Even behind separate graphics and present families
+        let families = [
+            (GRAPHICS, false),
+            (COMPUTE, true),
+            (GRAPHICS | COMPUTE, true),
+        ];
+        assert_eq!(pick_queue_families(&families), (Some(2), Some(2)));
+    }
+
+    #[test]
+    fn split_families_when_none_does_both() {
+        let families = [(COMPUTE, true), (GRAPHICS, false)];
+        assert_eq!(pick_queue_families(&families), (Some(1), Some(0)));
+        assert_eq!(pick_queue_families(&[(GRAPHICS, false)]), (Some(0), None));
+        assert_eq!(pick_queue_families(&[]), (None, None));
+    }
+
+    #[test]
+    fn first_matching_family_wins() {
+        let both = [(COMPUTE, false), (GRAPHICS, true), (GRAPHICS, true)];
+        assert_eq!(pick_queue_families(&both), (Some(1), Some(1)));
+        let split = [
+            (GRAPHICS, false),
+            (COMPUTE, true),
+            (GRAPHICS, false),
+            (COMPUTE, true),
+        ];
+        assert_eq!(pick_queue_families(&split), (Some(0), Some(1)));
+    }
+}
@@ -0,0 +1,165 @@
+// src/core/renderer/backend/vulkan/shader.rs
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use vulkanalia::bytecode::Bytecode;
+use vulkanalia::prelude::v1_0::*;
+
+use crate::error::{AppError, Result};
+
+/// Create a shader module from SPIR-V bytes (any alignment).
+pub fn create_shader_module(device: &Device, spirv: &[u8]) -> Result<vk::ShaderModule> {
+    let bytecode =
+        Bytecode::new(spirv).map_err(|e| AppError::Config(format!("invalid SPIR-V: {e:?}")))?;
+    let info = vk::ShaderModuleCreateInfo::builder()
+        .code_size(bytecode.code_size())
+        .code(bytecode.code());
+    unsafe { device.create_shader_module(&info, None) }
+        .map_err(|e| AppError::vk(e, "create shader module"))
+}
+
+/// Read a compiled `.spv` file and create a shader module from it.
+pub fn load_shader_module(device: &Device, path: &Path) -> Result<vk::ShaderModule> {
+    let spirv = std::fs::read(path)
+        .map_err(|e| AppError::Config(format!("read shader {}: {e}", path.display())))?;
+    create_shader_module(device, &spirv)
+}
+
+/// Programmable pipeline stage a shader module runs in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ShaderStage {
+    Vertex,
+    Fragment,
+    Geometry,    // Needs the `geometryShader` feature
+    TessControl, // Tessellation stages come in pairs and need `tessellationShader`
+    TessEval,
+    Compute, // Alone in its pipeline
+}
+
+impl ShaderStage {
+    pub fn flags(self) -> vk::ShaderStageFlags {
+        match self {
+            Self::Vertex => vk::ShaderStageFlags::VERTEX,
+            Self::Fragment => vk::ShaderStageFlags::FRAGMENT,
+            Self::Geometry => vk::ShaderStageFlags::GEOMETRY,
+            Self::TessControl => vk::ShaderStageFlags::TESSELLATION_CONTROL,
+            Self::TessEval => vk::ShaderStageFlags::TESSELLATION_EVALUATION,
+            Self::Compute => vk::ShaderStageFlags::COMPUTE,
+        }
+    }
+
+    pub fn is_tessellation(self) -> bool {
+        matches!(self, Self::TessControl | Self::TessEval)
+    }
+}
+
+/// Check that `stages` form one valid pipeline on a device with `features`
+/// enabled: no stage twice, compute alone, graphics with a vertex stage, both
+/// tessellation stages or neither, and the device features geometry and
+/// tessellation shaders need.
+pub fn validate_stages(
+    stages: &[ShaderStage],
+    features: &vk::PhysicalDeviceFeatures,
+) -> Result<()> {
+    let has = |stage| stages.contains(&stage);
+    let config = |message: &str| Err(AppError::Config(message.into()));
+    for (index, stage) in stages.iter().enumerate() {
+        if stages[..index].contains(stage) {
+            return Err(AppError::Config(format!(
+                "shader stage {stage:?} given twice"
+            )));
+        }
+    }
+    if has(ShaderStage::Compute) {
+        return match stages.len() {
+            1 => Ok(()),
+            _ => config("a compute stage can't share a pipeline with other stages"),
+        };
+    }
+    if !has(ShaderStage::Vertex) {
+        return config("graphics pipelines need a vertex stage");
+    }
+    if has(ShaderStage::TessControl) != has(ShaderStage::TessEval) {
+        return config("tessellation needs both a control and an evaluation stage");
+    }
+    if has(ShaderStage::Geometry) && features.geometry_shader != vk::TRUE {
+        return config("geometry shaders need the geometryShader feature");
+    }
+    if has(ShaderStage::TessControl) && features.tessellation_shader != vk::TRUE {
+        return config("tessellation shaders need the tessellationShader feature");
+    }
+    Ok(())
+}
+
+/// Specialization constant values by `constant_id`, fixed when a pipeline is
+/// created, so one SPIR-V module covers several variants (`layout(constant_id
+/// = 0) const bool FOG = false;`). Every value takes 4 bytes, which covers the
+/// GLSL `bool`, `int`, `uint` and `float` constants. Ids the shader doesn't
+/// declare are ignored by Vulkan; the default (empty) map specializes nothing.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct SpecializationMap {
+    values: BTreeMap<u32, [u8; 4]>,
+}
+
+impl SpecializationMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_u32(mut self, constant_id: u32, value: u32) -> Self {
+        self.values.insert(constant_id, value.to_ne_bytes());
+        self
+    }
+
+    pub fn with_i32(mut self, constant_id: u32, value: i32) -> Self {
+        self.values.insert(constant_id, value.to_ne_bytes());
+        self
+    }
+
+    pub fn with_f32(mut self, constant_id: u32, value: f32) -> Self {
+        self.values.insert(constant_id, value.to_ne_bytes());
+        self
+    }
+
+    /// SPIR-V booleans are 32-bit (VkBool32).
+    pub fn with_bool(self, constant_id: u32, value: bool) -> Self {
+        self.with_u32(constant_id, u32::from(value))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Map entries and the data block they point into, ordered by constant id.
+    pub fn build(&self) -> Specialization {
+        let mut entries = Vec::with_capacity(self.values.len());
+        let mut data = Vec::with_capacity(self.values.len() * 4);
+        for (&constant_id, bytes) in &self.values {
+            entries.push(
+                vk::SpecializationMapEntry::builder()
+                    .constant_id(constant_id)
+                    .offset(data.len() as u32)
+                    .size(bytes.len())
+                    .build(),
+            );
+            data.extend_from_slice(bytes);
+        }
+        Specialization { entries, data }
+    }
+}
+
+/// Owned storage behind a `vk::SpecializationInfo`; keep it alive until the
+/// pipeline is created.
+#[derive(Debug, Clone, Default)]
+pub struct Specialization {
+    pub entries: Vec<vk::SpecializationMapEntry>,
+    pub data: Vec<u8>,
+}
+
+impl Specialization {
+    pub fn info(&self) -> vk::SpecializationInfoBuilder<'_> {
+        vk::SpecializationInfo::builder()
+            .map_entries(&self.entries)
+            .data(&self.data)
+    }
+}
@@ -0,0 +1,39 @@
+// src/core/renderer/backend/vulkan/submit.rs
+//
+// Command buffers queued with `VulkanRenderer::submit_with_frame` (uploads,
+// one-off compute) ride along with the next frame instead of blocking like
+// `immediate_submit`. `SubmitPolicy` decides whether each gets its own
+// `vkQueueSubmit` or they share the frame's. Either way they run before the
+// frame in submission order, and the frame fence covers them: a fence signal
+// from vkQueueSubmit includes all earlier work on the queue.
+use smallvec::{SmallVec, smallvec};
+use vulkanalia::prelude::v1_0::*;
+
+use crate::core::renderer::config::SubmitPolicy;
+
+/// Command buffers of one `vkQueueSubmit` call.
+pub type SubmitBatch = SmallVec<[vk::CommandBuffer; 4]>;
+
+/// Calls needed to submit `queued` and then `frame` under `policy`. The frame's
+/// buffer is always last in the last batch, which carries the frame's
+/// semaphores and fence. Batched, the acquire wait (at COLOR_ATTACHMENT_OUTPUT)
+/// also applies to the queued buffers, but transfer and compute work never
+/// reaches that stage and isn't held back.
+pub fn submit_batches(
+    policy: SubmitPolicy,
+    queued: &[vk::CommandBuffer],
+    frame: vk::CommandBuffer,
+) -> SmallVec<[SubmitBatch; 4]> {
+    match policy {
+        SubmitPolicy::Separate => queued
+            .iter()
+            .map(|&cmd| smallvec![cmd])
+            .chain([smallvec![frame]])
+            .collect(),
+        SubmitPolicy::Batched => {
+            let mut batch: SubmitBatch = queued.iter().copied().collect();
+            batch.push(frame);
+            smallvec![batch]
+        }
+    }
+}
@@ -0,0 +1,175 @@
+// src/core/renderer/backend/vulkan/surface.rs
+//
+// Surface capability queries. The core query only returns
+// `VkSurfaceCapabilitiesKHR`; VK_KHR_get_surface_capabilities2 takes pNext
+// chains on both the input (which present mode, which monitor) and the output,
+// which is how full-screen-exclusive support and present scaling
+// (VK_EXT_surface_maintenance1) are reported. `query_surface_capabilities`
+// uses it when the instance has it and falls back to the core query otherwise,
+// so callers get the same base capabilities either way.
+use vulkanalia::prelude::v1_0::*;
+use vulkanalia::vk::{KhrGetSurfaceCapabilities2Extension, KhrSurfaceExtension};
+
+use crate::error::{AppError, Result};
+
+/// Which entry point `query_surface_capabilities` goes through.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SurfaceQuery {
+    #[default]
+    V1, // vkGetPhysicalDeviceSurfaceCapabilitiesKHR, base capabilities only
+    V2, // vkGetPhysicalDeviceSurfaceCapabilities2KHR with extension structs
+}
+
+impl SurfaceQuery {
+    /// V2 whenever VK_KHR_get_surface_capabilities2 is enabled on the instance.
+    pub fn select(surface_capabilities2: bool) -> Self {
+        if surface_capabilities2 {
+            Self::V2
+        } else {
+            Self::V1
+        }
+    }
+}
+
+/// What extensions beyond the base capabilities to ask for (V2 only).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SurfaceQueryExtras {
+    /// Ask about present scaling for this mode; needs VK_EXT_surface_maintenance1.
+    pub scaling_for: Option<vk::PresentModeKHR>,
+    /// Ask about exclusive fullscreen on this HMONITOR (VK_EXT_full_screen_exclusive).
+    pub exclusive_monitor: Option<isize>,
+}
+
+/// Present scaling of one present mode, from `VkSurfacePresentScalingCapabilitiesEXT`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PresentScalingCaps {
+    pub scaling: vk::PresentScalingFlagsEXT,
+    pub gravity_x: vk::PresentGravityFlagsEXT,
+    pub gravity_y: vk::PresentGravityFlagsEXT,
+    pub min_scaled_extent: vk::Extent2D,
+    pub max_scaled_extent: vk::Extent2D,
+}
+
+/// Surface capabilities; the optional parts are None when not asked for or
+/// when the V1 query was used.
+#[derive(Debug, Clone, Copy)]
+pub struct SurfaceCaps {
+    pub base: vk::SurfaceCapabilitiesKHR,
+    pub full_screen_exclusive: Option<bool>,
+    pub present_scaling: Option<PresentScalingCaps>,
+}
+
+pub fn query_surface_capabilities(
+    instance: &Instance,
+    physical_device: vk::PhysicalDevice,
+    surface: vk::SurfaceKHR,
+    query: SurfaceQuery,
+    extras: SurfaceQueryExtras,
+) -> Result<SurfaceCaps> {
+    if query == SurfaceQuery::V1 {
+        let base = unsafe {
+            instance.get_physical_device_surface_capabilities_khr(physical_device, surface)
+        }
+        .map_err(|e| AppError::vk(e, "query surface capabilities"))?;
+        return Ok(SurfaceCaps {
+            base,
+            full_screen_exclusive: None,
+            present_scaling: None,
+        });
+    }
+
+    let mut present_mode = vk::SurfacePresentModeEXT::builder()
+        .present_mode(extras.scaling_for.unwrap_or(vk::PresentModeKHR::FIFO));
+    let mut monitor = vk::SurfaceFullScreenExclusiveWin32InfoEXT::builder()
+        .hmonitor(extras.exclusive_monitor.unwrap_or_default() as vk::HMONITOR);
+    let mut surface_info = vk::PhysicalDeviceSurfaceInfo2KHR::builder().surface(surface);
+    if extras.scaling_for.is_some() {
+        surface_info = surface_info.push_next(&mut present_mode);
+    }
+    if extras.exclusive_monitor.is_some() {
+        surface_info = surface_info.push_next(&mut monitor);
+    }
+
+    let mut scaling = vk::SurfacePresentScalingCapabilitiesEXT::default();
+    let mut exclusive = vk::SurfaceCapabilitiesFullScreenExclusiveEXT::default();
+    let mut caps = vk::SurfaceCapabilities2KHR::builder();
+    if extras.scaling_for.is_some() {
+        caps = caps.push_next(&mut scaling);
+    }
+    if extras.exclusive_monitor.is_some() {
+        caps = caps.push_next(&mut exclusive);
+    }
+    unsafe {
+        instance.get_physical_device_surface_capabilities2_khr(
+            physical_device,
+            &surface_info,
+            &mut caps,
+        )
+    }
+    .map_err(|e| AppError::vk(e, "query surface capabilities (v2)"))?;
+    let base = caps.surface_capabilities;
+
+    Ok(SurfaceCaps {
+        base,
+        full_screen_exclusive: extras
+            .exclusive_monitor
+            .map(|_| exclusive.full_screen_exclusive_supported == vk::TRUE),
+        present_scaling: extras.scaling_for.map(|_| PresentScalingCaps {
+            scaling: scaling.supported_present_scaling,
+            gravity_x: scaling.supported_present_gravity_x,
+            gravity_y: scaling.supported_present_gravity_y,
+            min_scaled_extent: scaling.min_scaled_image_extent,
+            max_scaled_extent: scaling.max_scaled_image_extent,
+        }),
+    })
+}
+
+/// Swapchain size on a surface with `caps`: its `current_extent` when it
+/// dictates one, else the `window` size within the surface's limits.
+pub fn swapchain_extent(caps: &vk::SurfaceCapabilitiesKHR, window: vk::Extent2D) -> vk::Extent2D {
+    if caps.current_extent.width != u32::MAX {
+        return caps.current_extent;
+    }
+    vk::Extent2D {
+        width: window
+            .width
+            .clamp(caps.min_image_extent.width, caps.max_image_extent.width),
+        height: window
+            .height
+            .clamp(caps.min_image_extent.height, caps.max_image_extent.height),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn extent(width: u32, height: u32) -> vk::Extent2D {
+        vk::Extent2D { width, height }
+    }
+
+    fn caps(current: vk::Extent2D) -> vk::SurfaceCapabilitiesKHR {
+        vk::SurfaceCapabilitiesKHR {
+            current_extent: current,
+            min_image_extent: extent(64, 64),
+            max_image_extent: extent(4096, 2048),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn fixed_surfaces_dictate_the_extent() {
+        let fixed = caps(extent(1920, 1080));
+        assert_eq!(
+            swapchain_extent(&fixed, extent(800, 600)),
+            extent(1920, 1080)
+        );
+    }
+
+    #[test]
+    fn window_size_is_clamped_to_the_surface_limits() {
+        let free = caps(extent(u32::MAX, u32::MAX));
+        assert_eq!(swapchain_extent(&free, extent(800, 600)), extent(800, 600));
+        assert_eq!(swapchain_extent(&free, extent(8000, 10)), extent(4096, 64));
+    }
+}
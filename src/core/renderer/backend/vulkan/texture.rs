@@ -0,0 +1,65 @@
+// src/core/renderer/backend/vulkan/texture.rs
+use std::sync::Arc;
+
+use vulkanalia::prelude::v1_0::*;
+
+use super::context::DeviceContext;
+use super::image::{AllocatedImage, ImageDesc};
+use crate::error::Result;
+
+/// Device-local image that destroys itself on Drop.
+/// Holds the device context, so it may outlive the renderer that created it.
+pub struct Texture {
+    image: AllocatedImage,
+    context: Arc<DeviceContext>,
+}
+
+impl Texture {
+    /// Retries once after the context's memory-pressure hook, like `Buffer::new`.
+    pub fn new(context: &Arc<DeviceContext>, desc: ImageDesc) -> Result<Self> {
+        let image = context.try_alloc(|| {
+            AllocatedImage::new(
+                &context.instance,
+                &context.device,
+                context.physical_device,
+                desc,
+                context.resources(),
+            )
+        })?;
+        context.memory().record_alloc(image.size);
+        Ok(Self {
+            image,
+            context: Arc::clone(context),
+        })
+    }
+
+    pub fn image(&self) -> vk::Image {
+        self.image.image
+    }
+
+    pub fn view(&self) -> vk::ImageView {
+        self.image.view
+    }
+
+    pub fn format(&self) -> vk::Format {
+        self.image.format
+    }
+
+    pub fn extent(&self) -> vk::Extent2D {
+        self.image.extent
+    }
+
+    /// Bytes of device memory backing the image.
+    pub fn size(&self) -> vk::DeviceSize {
+        self.image.size
+    }
+}
+
+impl Drop for Texture {
+    // The caller must make sure no in-flight frame still samples the image
+    fn drop(&mut self) {
+        self.image
+            .destroy(&self.context.device, self.context.resources());
+        self.context.memory().record_free(self.image.size);
+    }
+}
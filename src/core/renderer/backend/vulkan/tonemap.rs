@@ -0,0 +1,268 @@
+// src/core/renderer/backend/vulkan/tonemap.rs
+//
+// Final subpass of the HDR render pass: reads the HDR scene as an input
+// attachment and writes the tonemapped, gamma-encoded result to the swapchain.
+// Shaders live in `shaders/` and are compiled ahead of time:
+//   glslc shaders/fullscreen.vert -o shaders/fullscreen.vert.spv
+//   glslc shaders/tonemap.frag -o shaders/tonemap.frag.spv
+use std::path::Path;
+
+use vulkanalia::prelude::v1_0::*;
+use vulkanalia::vk::Handle;
+
+use super::fullscreen::{draw_fullscreen_triangle, load_fullscreen_vertex_shader};
+use super::shader::load_shader_module;
+use crate::core::renderer::config::TonemapMode;
+use crate::error::{AppError, Result};
+
+/// Push constants of `tonemap.frag`, same layout as its `Push` block.
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct TonemapPush {
+    pub exposure: f32,
+    pub gamma: f32, // Applied as pow(c, 1/gamma); 1.0 when the swapchain is sRGB
+    pub mode: u32,  // TonemapMode discriminant
+}
+
+impl TonemapPush {
+    pub fn new(mode: TonemapMode, exposure: f32, swapchain_format: vk::Format) -> Self {
+        let srgb = matches!(
+            swapchain_format,
+            vk::Format::B8G8R8A8_SRGB
+                | vk::Format::R8G8B8A8_SRGB
+                | vk::Format::A8B8G8R8_SRGB_PACK32
+        );
+        Self {
+            exposure,
+            gamma: if srgb { 1.0 } else { 2.2 },
+            mode: mode as u32,
+        }
+    }
+}
+
+/// Pipeline + descriptor state of the tonemap subpass.
+/// The pipeline is tied to a render pass and rebuilt with it (`build_pipeline`).
+#[derive(Debug)]
+pub struct TonemapPass {
+    vert: vk::ShaderModule,
+    frag: vk::ShaderModule,
+    set_layout: vk::DescriptorSetLayout,
+    pipeline_layout: vk::PipelineLayout,
+    pool: vk::DescriptorPool,
+    set: vk::DescriptorSet,
+    pipeline: vk::Pipeline,
+}
+
+impl TonemapPass {
+    /// Load the shaders from `shader_dir` and create layouts + descriptor set.
+    pub fn new(device: &Device, shader_dir: &Path) -> Result<Self> {
+        let vert = load_fullscreen_vertex_shader(device, shader_dir)?;
+        let frag = match load_shader_module(device, &shader_dir.join("tonemap.frag.spv")) {
+            Ok(frag) => frag,
+            Err(e) => {
+                unsafe { device.destroy_shader_module(vert, None) };
+                return Err(e);
+            }
+        };
+        // From here every handle lives in `pass`, so `destroy` cleans up on failure
+        let mut pass = Self {
+            vert,
+            frag,
+            set_layout: vk::DescriptorSetLayout::null(),
+            pipeline_layout: vk::PipelineLayout::null(),
+            pool: vk::DescriptorPool::null(),
+            set: vk::DescriptorSet::null(),
+            pipeline: vk::Pipeline::null(),
+        };
+        if let Err(e) = pass.create_layouts(device) {
+            pass.destroy(device);
+            return Err(e);
+        }
+        Ok(pass)
+    }
+
+    fn create_layouts(&mut self, device: &Device) -> Result<()> {
+        let bindings = [vk::DescriptorSetLayoutBinding::builder()
+            .binding(0)
+            .descriptor_type(vk::DescriptorType::INPUT_ATTACHMENT)
+            .descriptor_count(1)
+            .stage_flags(vk::ShaderStageFlags::FRAGMENT)
+            .build()];
+        let set_info = vk::DescriptorSetLayoutCreateInfo::builder().bindings(&bindings);
+        self.set_layout = unsafe { device.create_descriptor_set_layout(&set_info, None) }
+            .map_err(|e| AppError::vk(e, "create tonemap set layout"))?;
+
+        let push_ranges = [vk::PushConstantRange::builder()
+            .stage_flags(vk::ShaderStageFlags::FRAGMENT)
+            .offset(0)
+            .size(size_of::<TonemapPush>() as u32)
+            .build()];
+        let set_layouts = [self.set_layout];
+        let layout_info = vk::PipelineLayoutCreateInfo::builder()
+            .set_layouts(&set_layouts)
+            .push_constant_ranges(&push_ranges);
+        self.pipeline_layout = unsafe { device.create_pipeline_layout(&layout_info, None) }
+            .map_err(|e| AppError::vk(e, "create tonemap pipeline layout"))?;
+
+        // Private single-set pool: input attachments aren't in DescriptorPoolSizes
+        let pool_sizes = [vk::DescriptorPoolSize::builder()
+            .type_(vk::DescriptorType::INPUT_ATTACHMENT)
+            .descriptor_count(1)
+            .build()];
+        let pool_info = vk::DescriptorPoolCreateInfo::builder()
+            .max_sets(1)
+            .pool_sizes(&pool_sizes);
+        self.pool = unsafe { device.create_descriptor_pool(&pool_info, None) }
+            .map_err(|e| AppError::vk(e, "create tonemap descriptor pool"))?;
+
+        let alloc_info = vk::DescriptorSetAllocateInfo::builder()
+            .descriptor_pool(self.pool)
+            .set_layouts(&set_layouts);
+        self.set = unsafe { device.allocate_descriptor_sets(&alloc_info) }
+            .map_err(|e| AppError::vk(e, "allocate tonemap descriptor set"))?[0];
+        Ok(())
+    }
+
+    /// (Re)create the pipeline for `subpass` of `render_pass`.
+    pub fn build_pipeline(
+        &mut self,
+        device: &Device,
+        render_pass: vk::RenderPass,
+        subpass: u32,
+    ) -> Result<()> {
+        self.destroy_pipeline(device);
+
+        let stages = [
+            vk::PipelineShaderStageCreateInfo::builder()
+                .stage(vk::ShaderStageFlags::VERTEX)
+                .module(self.vert)
+                .name(b"main\0")
+                .build(),
+            vk::PipelineShaderStageCreateInfo::builder()
+                .stage(vk::ShaderStageFlags::FRAGMENT)
+                .module(self.frag)
+                .name(b"main\0")
+                .build(),
+        ];
+        // Positions come from gl_VertexIndex, no vertex buffer
+        let vertex_input = vk::PipelineVertexInputStateCreateInfo::builder();
+        let input_assembly = vk::PipelineInputAssemblyStateCreateInfo::builder()
+            .topology(vk::PrimitiveTopology::TRIANGLE_LIST);
+        // Viewport/scissor are dynamic so resizes don't rebuild the pipeline
+        let viewports = [vk::Viewport::default()];
+        let scissors = [vk::Rect2D::default()];
+        let viewport_state = vk::PipelineViewportStateCreateInfo::builder()
+            .viewports(&viewports)
+            .scissors(&scissors);
+        let rasterization = vk::PipelineRasterizationStateCreateInfo::builder()
+            .polygon_mode(vk::PolygonMode::FILL)
+            .cull_mode(vk::CullModeFlags::NONE)
+            .front_face(vk::FrontFace::COUNTER_CLOCKWISE)
+            .line_width(1.0);
+        let multisample = vk::PipelineMultisampleStateCreateInfo::builder()
+            .rasterization_samples(vk::SampleCountFlags::_1);
+        let blend_attachments = [vk::PipelineColorBlendAttachmentState::builder()
+            .color_write_mask(vk::ColorComponentFlags::all())
+            .blend_enable(false)
+            .build()];
+        let color_blend =
+            vk::PipelineColorBlendStateCreateInfo::builder().attachments(&blend_attachments);
+        let dynamic_states = [vk::DynamicState::VIEWPORT, vk::DynamicState::SCISSOR];
+        let dynamic_state =
+            vk::PipelineDynamicStateCreateInfo::builder().dynamic_states(&dynamic_states);
+
+        let info = vk::GraphicsPipelineCreateInfo::builder()
+            .stages(&stages)
+            .vertex_input_state(&vertex_input)
+            .input_assembly_state(&input_assembly)
+            .viewport_state(&viewport_state)
+            .rasterization_state(&rasterization)
+            .multisample_state(&multisample)
+            .color_blend_state(&color_blend)
+            .dynamic_state(&dynamic_state)
+            .layout(self.pipeline_layout)
+            .render_pass(render_pass)
+            .subpass(subpass);
+
+        self.pipeline =
+            unsafe { device.create_graphics_pipelines(vk::PipelineCache::null(), &[info], None) }
+                .map_err(|e| AppError::vk(e, "create tonemap pipeline"))?
+                .0[0];
+        Ok(())
+    }
+
+    /// Point the input attachment descriptor at `view` (the single-sample HDR image).
+    /// Only call while no frame using the set is in flight.
+    pub fn bind_input(&self, device: &Device, view: vk::ImageView) {
+        let image_info = [vk::DescriptorImageInfo::builder()
+            .image_view(view)
+            .image_layout(vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL)
+            .build()];
+        let write = vk::WriteDescriptorSet::builder()
+            .dst_set(self.set)
+            .dst_binding(0)
+            .descriptor_type(vk::DescriptorType::INPUT_ATTACHMENT)
+            .image_info(&image_info);
+        unsafe { device.update_descriptor_sets(&[write], &[] as &[vk::CopyDescriptorSet]) };
+    }
+
+    /// Record the fullscreen draw; the tonemap subpass must be current in `cmd`.
+    pub fn draw(
+        &self,
+        device: &Device,
+        cmd: vk::CommandBuffer,
+        extent: vk::Extent2D,
+        push: TonemapPush,
+    ) {
+        let viewport = vk::Viewport::builder()
+            .width(extent.width as f32)
+            .height(extent.height as f32)
+            .max_depth(1.0)
+            .build();
+        let scissor = vk::Rect2D::builder().extent(extent).build();
+        unsafe {
+            let bytes = std::slice::from_raw_parts(
+                (&push as *const TonemapPush).cast::<u8>(),
+                size_of::<TonemapPush>(),
+            );
+            device.cmd_bind_pipeline(cmd, vk::PipelineBindPoint::GRAPHICS, self.pipeline);
+            device.cmd_set_viewport(cmd, 0, &[viewport]);
+            device.cmd_set_scissor(cmd, 0, &[scissor]);
+            device.cmd_bind_descriptor_sets(
+                cmd,
+                vk::PipelineBindPoint::GRAPHICS,
+                self.pipeline_layout,
+                0,
+                &[self.set],
+                &[],
+            );
+            device.cmd_push_constants(
+                cmd,
+                self.pipeline_layout,
+                vk::ShaderStageFlags::FRAGMENT,
+                0,
+                bytes,
+            );
+        }
+        draw_fullscreen_triangle(device, cmd);
+    }
+
+    fn destroy_pipeline(&mut self, device: &Device) {
+        if !self.pipeline.is_null() {
+            unsafe { device.destroy_pipeline(self.pipeline, None) };
+            self.pipeline = vk::Pipeline::null();
+        }
+    }
+
+    /// Destroy everything; null handles are ignored by Vulkan.
+    pub fn destroy(&mut self, device: &Device) {
+        self.destroy_pipeline(device);
+        unsafe {
+            device.destroy_descriptor_pool(self.pool, None); // frees `set`
+            device.destroy_pipeline_layout(self.pipeline_layout, None);
+            device.destroy_descriptor_set_layout(self.set_layout, None);
+            device.destroy_shader_module(self.frag, None);
+            device.destroy_shader_module(self.vert, None);
+        }
+    }
+}
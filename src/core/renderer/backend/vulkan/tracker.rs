@@ -0,0 +1,121 @@
+// src/core/renderer/backend/vulkan/tracker.rs
+//
+// Debug-build leak detection: live object counts per Vulkan object type,
+// bumped by the create/destroy helpers and reported when the device goes away
+// (`DeviceContext::drop`). In release builds the tracker has no fields and
+// every method compiles to nothing.
+#[cfg(debug_assertions)]
+use std::sync::atomic::{AtomicI64, Ordering};
+
+use log::{info, warn};
+use smallvec::SmallVec;
+
+use crate::core::renderer::log_targets::RENDERER;
+
+/// Object types the tracker counts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResourceKind {
+    Buffer,
+    Image,
+    ImageView,
+    DeviceMemory,
+    Framebuffer,
+    Semaphore,
+    Fence,
+}
+
+impl ResourceKind {
+    pub const ALL: [Self; 7] = [
+        Self::Buffer,
+        Self::Image,
+        Self::ImageView,
+        Self::DeviceMemory,
+        Self::Framebuffer,
+        Self::Semaphore,
+        Self::Fence,
+    ];
+
+    /// Objects owned by one `AllocatedImage`.
+    pub const ALLOCATED_IMAGE: &[Self] = &[Self::Image, Self::DeviceMemory, Self::ImageView];
+    /// Objects owned by one `Buffer`.
+    pub const BUFFER: &[Self] = &[Self::Buffer, Self::DeviceMemory];
+    /// Sync objects of one `FrameSync`.
+    pub const FRAME_SYNC: &[Self] = &[Self::Semaphore, Self::Semaphore, Self::Fence];
+}
+
+/// Live object counts of one device (debug builds only).
+#[derive(Debug, Default)]
+pub struct ResourceTracker {
+    #[cfg(debug_assertions)]
+    live: [AtomicI64; ResourceKind::ALL.len()],
+}
+
+impl ResourceTracker {
+    pub const fn new() -> Self {
+        Self {
+            #[cfg(debug_assertions)]
+            live: [const { AtomicI64::new(0) }; ResourceKind::ALL.len()],
+        }
+    }
+
+    /// Shared tracker for objects created before a device context exists.
+    #[cfg(feature = "vulkan")]
+    pub(crate) fn detached() -> &'static Self {
+        static DETACHED: ResourceTracker = ResourceTracker::new();
+        &DETACHED
+    }
+
+    /// Record one object of each kind in `kinds` as created.
+    #[cfg(debug_assertions)]
+    pub fn created(&self, kinds: &[ResourceKind]) {
+        for &kind in kinds {
+            self.live[kind as usize].fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// Record one object of each kind in `kinds` as destroyed.
+    #[cfg(debug_assertions)]
+    pub fn destroyed(&self, kinds: &[ResourceKind]) {
+        for &kind in kinds {
+            self.live[kind as usize].fetch_sub(1, Ordering::Relaxed);
+        }
+    }
+
+    /// Objects of `kind` created and not yet destroyed.
+    #[cfg(debug_assertions)]
+    pub fn live(&self, kind: ResourceKind) -> i64 {
+        self.live[kind as usize].load(Ordering::Relaxed)
+    }
+
+    #[cfg(not(debug_assertions))]
+    pub fn created(&self, _kinds: &[ResourceKind]) {}
+
+    #[cfg(not(debug_assertions))]
+    pub fn destroyed(&self, _kinds: &[ResourceKind]) {}
+
+    #[cfg(not(debug_assertions))]
+    pub fn live(&self, _kind: ResourceKind) -> i64 {
+        0
+    }
+
+    /// Kinds with a non-zero live count. Negative counts mean double destroys.
+    pub fn leaks(&self) -> SmallVec<[(ResourceKind, i64); 7]> {
+        ResourceKind::ALL
+            .into_iter()
+            .map(|kind| (kind, self.live(kind)))
+            .filter(|&(_, count)| count != 0)
+            .collect()
+    }
+
+    /// Log residual counts as leak warnings; returns true when nothing leaked.
+    pub fn report(&self) -> bool {
+        let leaks = self.leaks();
+        for (kind, count) in &leaks {
+            warn!(target: RENDERER, "Leaked Vulkan objects: {count} x {kind:?}");
+        }
+        if leaks.is_empty() && cfg!(debug_assertions) {
+            info!(target: RENDERER, "✅ No leaked Vulkan objects");
+        }
+        leaks.is_empty()
+    }
+}
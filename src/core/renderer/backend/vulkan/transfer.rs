@@ -0,0 +1,46 @@
+// src/core/renderer/backend/vulkan/transfer.rs
+use vulkanalia::prelude::v1_0::*;
+
+/// Mip 0, layer 0 of a color image, the common upload target.
+pub fn color_layers() -> vk::ImageSubresourceLayers {
+    vk::ImageSubresourceLayers::builder()
+        .aspect_mask(vk::ImageAspectFlags::COLOR)
+        .mip_level(0)
+        .base_array_layer(0)
+        .layer_count(1)
+        .build()
+}
+
+/// Record a copy of tightly packed texels at the start of `buffer` into
+/// `subresource` of `image`, covering `extent` from the origin.
+/// `image` must be in TRANSFER_DST_OPTIMAL (see `barrier::image_barrier`).
+pub fn copy_buffer_to_image(
+    device: &Device,
+    cmd: vk::CommandBuffer,
+    buffer: vk::Buffer,
+    image: vk::Image,
+    extent: vk::Extent2D,
+    subresource: vk::ImageSubresourceLayers,
+) {
+    // Row length / image height 0 = rows are packed at `extent.width` texels
+    let region = vk::BufferImageCopy::builder()
+        .buffer_offset(0)
+        .buffer_row_length(0)
+        .buffer_image_height(0)
+        .image_subresource(subresource)
+        .image_offset(vk::Offset3D::default())
+        .image_extent(vk::Extent3D {
+            width: extent.width,
+            height: extent.height,
+            depth: 1,
+        });
+    unsafe {
+        device.cmd_copy_buffer_to_image(
+            cmd,
+            buffer,
+            image,
+            vk::ImageLayout::TRANSFER_DST_OPTIMAL,
+            &[region],
+        );
+    }
+}
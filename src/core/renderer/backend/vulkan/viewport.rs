@@ -0,0 +1,261 @@
+// src/core/renderer/backend/vulkan/viewport.rs
+//
+// Y-flip: Vulkan's NDC has +Y pointing down. Either flip the projection matrix
+// (`proj[1][1] *= -1`) or use a negative-height viewport as done here with
+// `RendererConfig::flip_viewport_y`. Pick one: doing both cancels out. The
+// viewport flip keeps GL-style matrices untouched but also reverses triangle
+// winding as seen by the rasterizer, so front faces become CLOCKWISE.
+use vulkanalia::prelude::v1_0::*;
+
+use crate::error::{AppError, Result};
+
+/// Window-space depth the viewport maps NDC z in [0, 1] onto (`min_depth` /
+/// `max_depth`), e.g. a narrow range for a HUD layered in front of the scene.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DepthRange {
+    pub min: f32,
+    pub max: f32,
+}
+
+impl DepthRange {
+    pub const FULL: Self = Self { min: 0.0, max: 1.0 };
+
+    /// Checked range: both ends in [0, 1] (wider needs
+    /// `VK_EXT_depth_range_unrestricted`) and `min <= max`.
+    pub fn new(min: f32, max: f32) -> Result<Self> {
+        let unit = 0.0..=1.0;
+        if !unit.contains(&min) || !unit.contains(&max) {
+            return Err(AppError::Config(format!(
+                "depth range {min}..{max} must lie within 0..1"
+            )));
+        }
+        if min > max {
+            return Err(AppError::Config(format!(
+                "depth range min {min} is greater than max {max}"
+            )));
+        }
+        Ok(Self { min, max })
+    }
+}
+
+impl Default for DepthRange {
+    fn default() -> Self {
+        Self::FULL
+    }
+}
+
+/// Viewport covering `area` (see `letterbox`), Y-up when `flip_y` (origin moved
+/// to the bottom edge). Negative heights need Vulkan 1.1 or `VK_KHR_maintenance1`.
+pub fn scene_viewport(area: vk::Rect2D, flip_y: bool, depth: DepthRange) -> vk::Viewport {
+    let (top, height) = (area.offset.y as f32, area.extent.height as f32);
+    let (y, height) = if flip_y {
+        (top + height, -height)
+    } else {
+        (top, height)
+    };
+    vk::Viewport::builder()
+        .x(area.offset.x as f32)
+        .y(y)
+        .width(area.extent.width as f32)
+        .height(height)
+        .min_depth(depth.min)
+        .max_depth(depth.max)
+        .build()
+}
+
+/// Largest rect of `aspect` (width / height) centered in `extent`: bars above
+/// and below (letterbox) in a wider window, left and right (pillarbox) in a
+/// narrower one. The whole extent for a degenerate extent or aspect.
+pub fn letterbox(extent: vk::Extent2D, aspect: f32) -> vk::Rect2D {
+    let full = vk::Rect2D::builder().extent(extent).build();
+    if extent.width == 0 || extent.height == 0 || !aspect.is_finite() || aspect <= 0.0 {
+        return full;
+    }
+    let (width, height) = (extent.width as f32, extent.height as f32);
+    let inner = if width / height > aspect {
+        let inner_width = ((height * aspect).round() as u32).clamp(1, extent.width);
+        vk::Extent2D {
+            width: inner_width,
+            height: extent.height,
+        }
+    } else {
+        let inner_height = ((width / aspect).round() as u32).clamp(1, extent.height);
+        vk::Extent2D {
+            width: extent.width,
+            height: inner_height,
+        }
+    };
+    vk::Rect2D {
+        offset: vk::Offset2D {
+            x: ((extent.width - inner.width) / 2) as i32,
+            y: ((extent.height - inner.height) / 2) as i32,
+        },
+        extent: inner,
+    }
+}
+
+/// Column-major clip-space rotation by `turns` clockwise quarter turns, applied
+/// after the projection to pre-rotate the scene for a forced surface transform.
+pub fn pre_rotation(turns: u32) -> [[f32; 4]; 4] {
+    // Exact for quarter turns, unlike sin_cos; +Y points down, so +90° is clockwise
+    let (sin, cos) = match turns % 4 {
+        0 => (0.0, 1.0),
+        1 => (1.0, 0.0),
+        2 => (0.0, -1.0),
+        _ => (-1.0, 0.0),
+    };
+    [
+        [cos, sin, 0.0, 0.0],
+        [-sin, cos, 0.0, 0.0],
+        [0.0, 0.0, 1.0, 0.0],
+        [0.0, 0.0, 0.0, 1.0],
+    ]
+}
+
+/// `extent` as seen after `turns` quarter turns: width and height swap for odd turns.
+pub fn rotate_extent(extent: vk::Extent2D, turns: u32) -> vk::Extent2D {
+    if turns % 2 == 1 {
+        vk::Extent2D {
+            width: extent.height,
+            height: extent.width,
+        }
+    } else {
+        extent
+    }
+}
+
+/// `rect` clipped to `extent`; negative offsets shrink the rect instead of
+/// being rejected by validation. Fully outside gives an empty rect.
+pub fn clamp_scissor(rect: vk::Rect2D, extent: vk::Extent2D) -> vk::Rect2D {
+    let clamp = |offset: i32, size: u32, limit: u32| {
+        let start = i64::from(offset).clamp(0, i64::from(limit));
+        let end = (i64::from(offset) + i64::from(size)).clamp(start, i64::from(limit));
+        (start as i32, (end - start) as u32)
+    };
+    let (x, width) = clamp(rect.offset.x, rect.extent.width, extent.width);
+    let (y, height) = clamp(rect.offset.y, rect.extent.height, extent.height);
+    vk::Rect2D {
+        offset: vk::Offset2D { x, y },
+        extent: vk::Extent2D { width, height },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rect(x: i32, y: i32, width: u32, height: u32) -> vk::Rect2D {
+        vk::Rect2D {
+            offset: vk::Offset2D { x, y },
+            extent: vk::Extent2D { width, height },
+        }
+    }
+
+    const EXTENT: vk::Extent2D = vk::Extent2D {
+        width: 800,
+        height: 600,
+    };
+
+    #[test]
+    fn scissor_inside_is_unchanged() {
+        let inside = rect(10, 20, 100, 50);
+        assert_eq!(clamp_scissor(inside, EXTENT), inside);
+    }
+
+    #[test]
+    fn scissor_is_clipped_to_the_extent() {
+        assert_eq!(
+            clamp_scissor(rect(-10, -20, 100, 50), EXTENT),
+            rect(0, 0, 90, 30)
+        );
+        assert_eq!(
+            clamp_scissor(rect(750, 580, 100, 50), EXTENT),
+            rect(750, 580, 50, 20)
+        );
+        assert_eq!(
+            clamp_scissor(rect(i32::MIN, 0, u32::MAX, 1), EXTENT),
+            rect(0, 0, 800, 1)
+        );
+    }
+
+    #[test]
+    fn scissor_outside_is_empty() {
+        assert_eq!(clamp_scissor(rect(900, 0, 10, 10), EXTENT).extent.width, 0);
+        assert_eq!(clamp_scissor(rect(0, -50, 10, 10), EXTENT).extent.height, 0);
+    }
+
+    #[test]
+    fn depth_range_must_be_ordered_within_unit() {
+        assert_eq!(
+            DepthRange::new(0.0, 0.1).ok(),
+            Some(DepthRange { min: 0.0, max: 0.1 })
+        );
+        assert!(DepthRange::new(0.5, 0.5).is_ok());
+        assert!(DepthRange::new(0.6, 0.4).is_err());
+        assert!(DepthRange::new(-0.1, 0.5).is_err());
+        assert!(DepthRange::new(0.0, 1.5).is_err());
+        assert!(DepthRange::new(f32::NAN, 1.0).is_err());
+        assert_eq!(DepthRange::default(), DepthRange::FULL);
+    }
+
+    #[test]
+    fn letterbox_centers_the_aspect() {
+        let wide = vk::Extent2D {
+            width: 1920,
+            height: 1080,
+        };
+        assert_eq!(letterbox(wide, 4.0 / 3.0), rect(240, 0, 1440, 1080));
+        assert_eq!(letterbox(wide, 16.0 / 9.0), rect(0, 0, 1920, 1080));
+        let square = vk::Extent2D {
+            width: 800,
+            height: 800,
+        };
+        assert_eq!(letterbox(square, 2.0), rect(0, 200, 800, 400));
+    }
+
+    #[test]
+    fn letterbox_degenerate_input() {
+        let full = rect(0, 0, 800, 600);
+        assert_eq!(letterbox(EXTENT, 0.0), full);
+        assert_eq!(letterbox(EXTENT, f32::NAN), full);
+        assert_eq!(letterbox(EXTENT, f32::INFINITY), full);
+        let empty = vk::Extent2D {
+            width: 0,
+            height: 600,
+        };
+        assert_eq!(letterbox(empty, 1.0), rect(0, 0, 0, 600));
+        // Never narrower than a pixel
+        let strip = vk::Extent2D {
+            width: 1000,
+            height: 1,
+        };
+        assert_eq!(letterbox(strip, 0.001), rect(499, 0, 1, 1));
+    }
+
+    /// Column-major `matrix` applied to the clip-space point (x, y, 0, 1).
+    fn rotate(matrix: [[f32; 4]; 4], x: f32, y: f32) -> [f32; 2] {
+        [0, 1].map(|row| matrix[0][row] * x + matrix[1][row] * y + matrix[3][row])
+    }
+
+    #[test]
+    fn pre_rotation_turns_clockwise() {
+        assert_eq!(rotate(pre_rotation(0), 1.0, 0.5), [1.0, 0.5]);
+        // +Y points down, so a clockwise turn takes +X to +Y
+        assert_eq!(rotate(pre_rotation(1), 1.0, 0.5), [-0.5, 1.0]);
+        assert_eq!(rotate(pre_rotation(2), 1.0, 0.5), [-1.0, -0.5]);
+        assert_eq!(rotate(pre_rotation(3), 1.0, 0.5), [0.5, -1.0]);
+        assert_eq!(pre_rotation(5), pre_rotation(1));
+    }
+
+    #[test]
+    fn rotate_extent_swaps_on_odd_turns() {
+        assert_eq!(rotate_extent(EXTENT, 0), EXTENT);
+        assert_eq!(rotate_extent(EXTENT, 2), EXTENT);
+        let portrait = vk::Extent2D {
+            width: 600,
+            height: 800,
+        };
+        assert_eq!(rotate_extent(EXTENT, 1), portrait);
+        assert_eq!(rotate_extent(EXTENT, 3), portrait);
+    }
+}
@@ -1,20 +1,88 @@
-// Import Vulkan debug utils extension only in debug builds
-#[cfg(debug_assertions)]
+// Import Vulkan debug utils extension only in debug builds with `debug-utils`
+#[cfg(all(debug_assertions, feature = "debug-utils"))]
 use vulkanalia::vk::ExtDebugUtilsExtension;
 
-// Only pull in error/warn when debug assertions are on
-#[cfg(debug_assertions)]
-use log::{error, warn};
-
-use crate::core::renderer::api::Renderer;
-use crate::error::Result;
-use log::info;
+use super::capabilities::{
+    Capabilities, DEPTH_FORMATS, RenderTargetFormat, clamp_line_width, composite_alpha_flag,
+    content_turns, has_stencil, sample_counts_from_limits, select_composite_alpha,
+    select_pre_transform, supported_depth_formats, surface_transform_flag,
+};
+use super::compute::record_blit;
+use super::context::DeviceContext;
+#[cfg(all(debug_assertions, feature = "debug-utils"))]
+use super::debug::{
+    build_debug_messenger_ci, create_debug_messenger, debug_label, destroy_debug_messenger,
+    open_debug_sink,
+};
+use super::descriptor::DescriptorAllocator;
+use super::device_info::{DeviceInfo, GpuReport};
+use super::draw::{DrawCall, DrawContext, Frame, FrameDraws};
+use super::exclusive::{FullScreenExclusive, exclusive_info, select_exclusive, window_monitor};
+use super::features::{FeatureChain, FeatureExtension};
+use super::frame::{
+    FrameSync, MAX_FRAMES_IN_FLIGHT, PerImageCounts, check_fence_wait, timeout_nanos,
+    wait_for_fences,
+};
+use super::image::{AllocatedImage, ImageDesc};
+use super::init::{InitPhase, InitProgress, application_info, application_names};
+use super::material::{MaterialId, MaterialSets, MaterialTexture};
+use super::mesh::{Mesh, MeshPipeline, Vertex, vertex_buffer_args};
+use super::overdraw::OverdrawQueries;
+use super::pick::{PickPass, window_to_framebuffer};
+use super::pipelines::{MeshPipelineCache, PipelineDesc};
+use super::present::{
+    FallbackReason, MAILBOX_MIN_IMAGES, PresentFences, PresentModeFallback, RESIZE_SETTLE,
+    SwapchainRetire, desired_image_count, present_scaling_info, select_present_mode,
+    select_retire_mode, swapchain_stale,
+};
+use super::recording::RecordCache;
+use super::relay::Relay;
+use super::render_pass::{
+    ClearTarget, FramebufferAttachments, RenderPassBuilder, clear_attachment_info,
+    frame_attachments, validate_clear_values, validate_framebuffer_attachments,
+};
+use super::render_queue::{BindState, DrawKey, count_binds, sorted_order};
+use super::sampler::{SamplerCache, SamplerConfig};
+use super::screenshot::{Screenshot, ScreenshotToken, Screenshots, record_capture};
+use super::selection::{GpuCandidate, PresentPath, device_plan, pick_queue_families, select_gpu};
+use super::submit::submit_batches;
+use super::surface::{
+    SurfaceCaps, SurfaceQuery, SurfaceQueryExtras, query_surface_capabilities, swapchain_extent,
+};
+use super::texture::Texture;
+use super::tonemap::{TonemapPass, TonemapPush};
+use super::tracker::{ResourceKind, ResourceTracker};
+use super::transfer::{color_layers, copy_buffer_to_image};
+use super::viewport::{
+    DepthRange, clamp_scissor, letterbox, pre_rotation, rotate_extent, scene_viewport,
+};
+use crate::core::renderer::api::{MemoryPressure, Renderer};
+use crate::core::renderer::config::{
+    CommandRecording, CullMode, Platform, PolygonMode, RendererConfig, TonemapMode, Topology,
+    present_preferences,
+};
+use crate::core::renderer::log_targets::{RENDERER, SWAPCHAIN};
+use crate::core::renderer::pacing::{FrameLimiter, PowerSaver};
+use crate::core::renderer::stats::{DrawCounts, RenderStats};
+use crate::error::{AppError, Result};
+use log::{info, warn};
 use smallvec::SmallVec;
+use std::cell::Cell;
+#[cfg(all(debug_assertions, feature = "debug-utils"))]
+use std::collections::HashMap;
+use std::collections::hash_map::DefaultHasher;
 use std::ffi::CStr;
+#[cfg(all(debug_assertions, feature = "debug-utils"))]
+use std::ffi::CString;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
+use vulkanalia::VkResult;
 use vulkanalia::loader::{LIBRARY, LibloadingLoader};
 use vulkanalia::prelude::v1_0::*;
 use vulkanalia::vk::EntryV1_1;
+use vulkanalia::vk::Handle;
 
 use vulkanalia::vk::KhrSurfaceExtension;
 use vulkanalia::vk::{self, KhrSwapchainExtension};
@@ -27,6 +95,9 @@ use winit::{
     window::{Window, WindowId},
 };
 
+// Khronos validation layer, enabled in debug builds when installed
+#[cfg(debug_assertions)]
+const VALIDATION_LAYER: &[u8] = b"VK_LAYER_KHRONOS_validation\0";
 // Portability extension needed on some platforms (e.g., macOS + MoltenVK)
 const KHR_PORTABILITY_SUBSET_EXTENSION_NAME: &std::ffi::CStr =
     unsafe { std::ffi::CStr::from_bytes_with_nul_unchecked(b"VK_KHR_portability_subset\0") };
@@ -35,15 +106,25 @@ const KHR_PORTABILITY_SUBSET_EXTENSION_NAME: &std::ffi::CStr =
 /// Holds all Vulkan objects and resources needed to draw.
 #[derive(Default)]
 pub struct VulkanRenderer {
-    entry: Option<Entry>,                      // Vulkan entry point (library handle)
-    instance: Option<Instance>,                // Vulkan instance
-    debug: Option<vk::DebugUtilsMessengerEXT>, // Debug messenger (only in debug builds)
-    surface: Option<vk::SurfaceKHR>,           // Window surface
+    // User settings, fixed at construction
+    config: RendererConfig,
+
+    instance: Option<Instance>,      // Vulkan instance (owned by `context`)
+    surface: Option<vk::SurfaceKHR>, // Window surface
     physical_device: Option<vk::PhysicalDevice>, // Chosen physical GPU
-    device: Option<Device>,                    // Logical device
-    graphics_queue: Option<vk::Queue>,         // Graphics queue
-    present_queue: Option<vk::Queue>,          // Presentation queue
-    queue_family_indices: Option<(u32, u32)>,  // Queue family indices
+    device_info: Option<DeviceInfo>, // Name/driver of the chosen GPU
+    has_props2: bool,                // KHR_get_physical_device_properties2 on the instance
+    capabilities: Option<Capabilities>, // Snapshot taken at the end of `initialize`
+    limits: vk::PhysicalDeviceLimits, // Limits of the chosen GPU
+    enabled_features: vk::PhysicalDeviceFeatures, // Features turned on at device creation
+    flip_viewport_y: bool,           // Negative-height viewport active (config + support)
+    depth_range: DepthRange,         // min/max_depth of the scene viewport
+    device: Option<Device>,          // Logical device (owned by `context`)
+    context: Option<Arc<DeviceContext>>, // Shared with RAII resources; destroys both
+    graphics_queue: Option<vk::Queue>, // Graphics queue
+    present_queue: Option<vk::Queue>, // Presentation queue
+    queue_family_indices: Option<(u32, u32)>, // Queue family indices
+    relay: Option<Relay>,             // Copy-and-present on another GPU (hybrid graphics)
 
     swapchain: Option<vk::SwapchainKHR>, // Swapchain for presenting images
 
@@ -52,11 +133,103 @@ pub struct VulkanRenderer {
     swapchain_image_views: SmallVec<[vk::ImageView; 4]>,
     swapchain_format: Option<vk::Format>,   // Image format
     swapchain_extent: Option<vk::Extent2D>, // Image resolution
+    content_turns: u32,                     // Scene pre-rotation, quarter turns (forced transform)
+    // Surface transform a forced pre-transform deliberately differs from, which
+    // makes every present SUBOPTIMAL
+    forced_mismatch: Option<vk::SurfaceTransformFlagsKHR>,
+    swapchain_usage: vk::ImageUsageFlags,   // TRANSFER_SRC/DST when captures/blits are possible
+    swapchain_retire: SwapchainRetire,      // How recreation waits for the old images
+    exclusive: FullScreenExclusive,         // VK_EXT_full_screen_exclusive state (Windows)
+    surface_query: SurfaceQuery,            // Capabilities query, v2 when the instance has it
+    present_mode: vk::PresentModeKHR,       // Mode of the current swapchain
+    present_modes: SmallVec<[vk::PresentModeKHR; 4]>, // Offered by the surface
+    present_fallback: Option<PresentModeFallback>, // Of the current swapchain
+    power: PowerSaver,                      // RendererConfig::power_saving state
+
+    // Present scaling (RendererConfig::present_scaling): whether the current
+    // swapchain has it, and when the last resize it is covering happened
+    present_scaling: bool,
+    resize_pending: Option<Instant>,
+    window_extent: vk::Extent2D, // Last known window size (fallback extent)
 
     render_pass: Option<vk::RenderPass>, // Render pass object
+    // Its attachments, to check clear values against before every begin
+    render_pass_attachments: SmallVec<[vk::AttachmentDescription; 4]>,
+
+    // MSAA: rendered into msaa_target, resolved into the HDR or swapchain image
+    msaa_samples: u32,                   // Active sample count (<= 1 = off)
+    msaa_target: Option<AllocatedImage>, // Multisampled color image, None when off
+
+    // HDR: scene rendered into hdr_target, tonemapped into the swapchain by a 2nd subpass
+    hdr_target: Option<AllocatedImage>, // In hdr_format, None when tonemapping is off
+    hdr_format: Option<vk::Format>,     // RenderTargetFormat::Hdr, picked at init
+    tonemap: Option<TonemapPass>,       // Pipeline of the tonemap subpass
+    tonemap_mode: TonemapMode,          // Operator pushed to the shader each frame
+    exposure: f32,                      // Scene color multiplier before tonemapping
+
+    // Depth buffer of the scene subpass (same sample count as the scene color)
+    depth_format: Option<vk::Format>, // RenderTargetFormat::Depth, picked at init
+    depth_target: Option<AllocatedImage>, // Shared by all frames in flight
 
     // One framebuffer per swapchain image
     framebuffers: SmallVec<[vk::Framebuffer; 4]>,
+
+    // Descriptor pool chain sized from RendererConfig
+    descriptor_allocator: Option<DescriptorAllocator>,
+    samplers: SamplerCache,  // One sampler per distinct SamplerConfig
+    materials: MaterialSets, // Per-frame material sets + deferred texture updates
+
+    // Frame loop: per-frame-in-flight command buffers + sync objects
+    frame_command_pool: Option<vk::CommandPool>, // RESET_COMMAND_BUFFER, re-recorded per frame
+    transient_command_pool: Option<vk::CommandPool>, // TRANSIENT, one-shot immediate submits
+    frames: SmallVec<[FrameSync; MAX_FRAMES_IN_FLIGHT]>,
+    current_frame: usize,
+    images_in_flight: SmallVec<[vk::Fence; 4]>, // Fence of the frame using each swapchain image
+    present_fences: Option<PresentFences>,      // SwapchainRetire::PresentFences only
+    clear_color: [f32; 4],
+
+    // `submit_with_frame` buffers (transient pool): waiting for the next frame,
+    // then held per frame slot until its fence signals
+    queued_submits: SmallVec<[vk::CommandBuffer; 4]>,
+    frame_submits: [SmallVec<[vk::CommandBuffer; 4]>; MAX_FRAMES_IN_FLIGHT],
+
+    // CommandRecording::Cached: one frame buffer per swapchain image (frame pool),
+    // what each one holds, and the draws it references
+    record_cache: RecordCache,
+    cached_commands: SmallVec<[vk::CommandBuffer; 4]>,
+    cached_draws: Vec<Vec<DrawCall>>,
+
+    // `DrawContext` draws: mesh pipelines (created on the first draw using them or
+    // by `precompile_pipelines`) and each frame slot's draws/dispatches, holding
+    // their resources until the slot's fence signals
+    pipelines: MeshPipelineCache,
+    mesh_desc: PipelineDesc, // Built-in pipeline, from the config's graphics state
+    frame_draws: [FrameDraws; MAX_FRAMES_IN_FLIGHT],
+    pick: Option<PickPass>, // Object-id pass, created on the first `pick`
+    // Frame command buffer while it records the scene subpass (`clear_attachment`)
+    scene_pass: Cell<Option<vk::CommandBuffer>>,
+
+    // Async swapchain captures, recorded into frames and completed by their fences
+    screenshots: Screenshots,
+
+    // Timing / bottleneck / draw diagnostics
+    stats: RenderStats,
+    overdraw: OverdrawQueries, // Occlusion query per frame slot (`RenderStats::overdraw`)
+    #[cfg(all(debug_assertions, feature = "debug-utils"))]
+    label_colors: HashMap<String, [f32; 4]>, // `set_debug_utils_label_color` palette
+    last_frame_start: Option<Instant>,
+    limiter: Option<FrameLimiter>, // RendererConfig::max_fps
+
+    // Set once `initialize` succeeded, cleared by `cleanup`
+    initialized: bool,
+    init_progress: Option<InitProgress>, // Told about each `initialize` phase
+    memory_pressure: Option<MemoryPressure>, // Handed to the device context once created
+    // Set on CloseRequested; no new frames are submitted once true
+    closing: bool,
+    // Set by `pause` (window hidden); `render` is a no-op until `resume`
+    paused: bool,
+    // Failure inside window_event, handed to App through take_error
+    pending_error: Option<AppError>,
 }
 
 impl VulkanRenderer {
@@ -68,20 +241,71 @@ impl VulkanRenderer {
                 // Wait until GPU is idle before tearing down
                 device.device_wait_idle().ok();
 
-                // Destroy framebuffers
+                // Destroy per-frame sync objects, then both pools (frees command buffers)
+                let tracker = tracker(self.context.as_ref());
+                for frame in self.frames.drain(..) {
+                    frame.destroy(device, tracker);
+                }
+                if let Some(mut fences) = self.present_fences.take() {
+                    fences.destroy(device, tracker);
+                }
+                self.overdraw.destroy(device);
+                // The transient pool frees the queued and in-flight submit buffers
+                self.queued_submits.clear();
+                self.frame_submits.iter_mut().for_each(SmallVec::clear);
+                // The frame pool frees the cached frame buffers
+                self.cached_commands.clear();
+                self.record_cache.reset(0);
+                if let Some(pool) = self.frame_command_pool.take() {
+                    device.destroy_command_pool(pool, None);
+                }
+                if let Some(pool) = self.transient_command_pool.take() {
+                    device.destroy_command_pool(pool, None);
+                }
+                self.images_in_flight.clear();
+
+                // Destroy descriptor pools (frees every set allocated from them)
+                if let Some(mut allocator) = self.descriptor_allocator.take() {
+                    allocator.destroy(device);
+                }
+                self.materials.clear();
+                self.screenshots.clear();
+                self.frame_draws.iter_mut().for_each(FrameDraws::clear);
+                self.cached_draws.clear();
+                self.pick = None;
+                self.samplers.destroy(device);
+
+                // Destroy framebuffers, then the depth/MSAA/HDR images they referenced
                 for fb in self.framebuffers.drain(..) {
                     device.destroy_framebuffer(fb, None);
+                    tracker.destroyed(&[ResourceKind::Framebuffer]);
+                }
+                for target in [
+                    self.msaa_target.take(),
+                    self.hdr_target.take(),
+                    self.depth_target.take(),
+                ]
+                .into_iter()
+                .flatten()
+                {
+                    target.destroy(device, tracker);
                 }
 
-                // Destroy render pass
+                // Destroy the tonemap/mesh pipelines, then the render pass they were built for
+                if let Some(mut pass) = self.tonemap.take() {
+                    pass.destroy(device);
+                }
+                self.pipelines.destroy(device);
                 if let Some(rp) = self.render_pass {
                     device.destroy_render_pass(rp, None);
                 }
                 self.render_pass = None;
+                self.render_pass_attachments.clear();
 
                 // Destroy swapchain image views
                 for iv in self.swapchain_image_views.drain(..) {
                     device.destroy_image_view(iv, None);
+                    tracker.destroyed(&[ResourceKind::ImageView]);
                 }
 
                 // Destroy swapchain
@@ -89,234 +313,2304 @@ impl VulkanRenderer {
                     device.destroy_swapchain_khr(swapchain, None);
                 }
                 self.swapchain = None;
-            }
 
-            // Destroy debug messenger (only created in debug builds)
-            #[cfg(debug_assertions)]
-            if let (Some(instance), Some(debug)) = (&self.instance, &self.debug) {
-                destroy_debug_messenger(instance, debug);
+                // Or the relay's stand-in images (their views went above) and the
+                // presenting GPU's device with its swapchain
+                if let Some(relay) = self.relay.take() {
+                    relay.destroy(device, tracker);
+                }
             }
-            self.debug = None;
 
             // Destroy surface
             if let (Some(instance), Some(surface)) = (&self.instance, self.surface) {
                 instance.destroy_surface_khr(surface, None);
             }
             self.surface = None;
-
-            // Destroy logical device
-            if let Some(device) = &self.device {
-                device.destroy_device(None);
-            }
-            self.device = None;
-
-            // Destroy Vulkan instance
-            if let Some(instance) = &self.instance {
-                instance.destroy_instance(None);
-            }
-            self.instance = None;
         }
 
+        // Device and instance go with the last context clone, so textures and
+        // buffers still held by the app stay valid until they are dropped
+        self.device = None;
+        self.instance = None;
+        self.context = None;
+
         // Clear CPU-side state
-        self.entry = None;
+        self.initialized = false;
         self.physical_device = None;
+        self.device_info = None;
+        self.capabilities = None;
+        self.limits = vk::PhysicalDeviceLimits::default();
+        self.enabled_features = vk::PhysicalDeviceFeatures::default();
+        self.flip_viewport_y = false;
         self.graphics_queue = None;
         self.present_queue = None;
         self.queue_family_indices = None;
         self.swapchain_images.clear();
         self.swapchain_format = None;
         self.swapchain_extent = None;
+        self.content_turns = 0;
+        self.forced_mismatch = None;
+        self.swapchain_usage = vk::ImageUsageFlags::empty();
+        self.swapchain_retire = SwapchainRetire::default();
+        self.present_mode = vk::PresentModeKHR::default();
+        self.present_fallback = None;
+        self.present_modes.clear();
+        self.msaa_samples = 0;
+        self.depth_format = None;
+        self.hdr_format = None;
+        self.tonemap_mode = TonemapMode::None;
     }
 
-    /// Creates the swapchain and image views.
-    fn create_swapchain(&mut self) {
+    /// Creates the swapchain and image views. Returns the present mode fallback,
+    /// if the config's mode couldn't be used (also kept for `present_mode_fallback`).
+    /// Fails with `AppError::Surface` if the surface reports no formats or present
+    /// modes (seen on some virtual/remote display setups).
+    fn create_swapchain(&mut self) -> Result<Option<PresentModeFallback>> {
+        if self.relay.is_some() {
+            return self.create_relay_swapchain();
+        }
         let instance = self.instance.as_ref().unwrap();
         let device = self.device.as_ref().unwrap();
         let surface = self.surface.unwrap();
         let physical_device = self.physical_device.unwrap();
+        let gpu = self
+            .device_info
+            .as_ref()
+            .map_or("unknown GPU", |i| i.name.as_str());
+
+        // Query surface capabilities (and whether exclusive fullscreen works here)
+        let extras = SurfaceQueryExtras {
+            exclusive_monitor: self
+                .exclusive
+                .monitor
+                .filter(|_| self.exclusive.available()),
+            ..Default::default()
+        };
+        let SurfaceCaps {
+            base: surface_caps,
+            full_screen_exclusive,
+            ..
+        } = query_surface_capabilities(
+            instance,
+            physical_device,
+            surface,
+            self.surface_query,
+            extras,
+        )?;
+
+        // Query supported formats
+        let surface_formats =
+            unsafe { instance.get_physical_device_surface_formats_khr(physical_device, surface) }
+                .map_err(|e| AppError::vk(e, "query surface formats"))?;
+
+        // Prefer SRGB, fallback to first format
+        let format = surface_formats
+            .iter()
+            .find(|f| f.format == vk::Format::B8G8R8A8_SRGB)
+            .or(surface_formats.first())
+            .ok_or_else(|| {
+                AppError::Surface(format!("surface {surface:?} on {gpu} reports no formats"))
+            })?;
+
+        // Pick swapchain resolution (use current_extent if fixed, else the window size)
+        let extent = swapchain_extent(&surface_caps, self.window_extent);
+
+        // Forced pre-transform (rotated panels): the scene rotates by the difference
+        // to the surface's own, in an image whose sides swap for quarter turns
+        let forced = self.config.force_pre_transform;
+        let current_transform = surface_caps.current_transform;
+        let pre_transform =
+            select_pre_transform(forced, surface_caps.supported_transforms, current_transform);
+        if let Some(forced) = forced
+            && pre_transform != surface_transform_flag(forced)
+        {
+            warn!(
+                target: SWAPCHAIN,
+                "force_pre_transform {forced:?} unsupported (surface offers {:?}), using {:?}",
+                surface_caps.supported_transforms,
+                current_transform
+            );
+        }
+        let turns = content_turns(pre_transform, current_transform);
+        let extent = rotate_extent(extent, turns);
+        let forced_mismatch =
+            (forced.is_some() && pre_transform != current_transform).then_some(current_transform);
+
+        // Query present modes (FIFO is mandatory, so an empty list means a broken surface)
+        let present_modes = unsafe {
+            instance.get_physical_device_surface_present_modes_khr(physical_device, surface)
+        }
+        .map_err(|e| AppError::vk(e, "query surface present modes"))?;
+        if present_modes.is_empty() {
+            return Err(AppError::Surface(format!(
+                "surface {surface:?} on {gpu} reports no present modes"
+            )));
+        }
+
+        let (present_mode, fallback) = self.choose_present_mode(&present_modes);
+        self.log_present_fallback(fallback);
+
+        // Split graphics/present families share the images without ownership transfers
+        let (graphics_family, present_family) = self.queue_family_indices.unwrap();
+        let family_indices = [graphics_family, present_family];
+        let (sharing_mode, shared_families): (_, &[u32]) = if graphics_family == present_family {
+            (vk::SharingMode::EXCLUSIVE, &[])
+        } else {
+            (vk::SharingMode::CONCURRENT, &family_indices)
+        };
+
+        // Present scaling for the chosen mode, if requested and supported
+        let scaling_caps = match (self.config.present_scaling, self.swapchain_retire) {
+            (Some(_), SwapchainRetire::PresentFences) => {
+                let extras = SurfaceQueryExtras {
+                    scaling_for: Some(present_mode),
+                    ..Default::default()
+                };
+                let query = self.surface_query;
+                query_surface_capabilities(instance, physical_device, surface, query, extras)?
+                    .present_scaling
+            }
+            _ => None,
+        };
+        let mut scaling = present_scaling_info(
+            self.config.present_scaling,
+            self.swapchain_retire,
+            scaling_caps,
+        );
+        if self.config.present_scaling.is_some() && scaling.is_none() {
+            warn!(
+                target: SWAPCHAIN,
+                "present_scaling unsupported here, recreating the swapchain on every resize"
+            );
+        }
+        self.present_scaling = scaling.is_some();
+
+        // One more image than the minimum, three for MAILBOX, if the surface allows
+        let image_count = desired_image_count(
+            surface_caps.min_image_count,
+            surface_caps.max_image_count,
+            present_mode,
+        );
+
+        // Readback (screenshots) needs TRANSFER_SRC, compute blits TRANSFER_DST plus
+        // BLIT_DST on the format; surfaces needn't support either
+        let blit_dst = unsafe {
+            instance.get_physical_device_format_properties(physical_device, format.format)
+        }
+        .optimal_tiling_features
+        .contains(vk::FormatFeatureFlags::BLIT_DST);
+        let transfer = if blit_dst {
+            vk::ImageUsageFlags::TRANSFER_SRC | vk::ImageUsageFlags::TRANSFER_DST
+        } else {
+            vk::ImageUsageFlags::TRANSFER_SRC
+        };
+        let usage =
+            vk::ImageUsageFlags::COLOR_ATTACHMENT | (surface_caps.supported_usage_flags & transfer);
+
+        // Requested compositing, else whatever the surface offers
+        let requested_alpha = self.config.composite_alpha;
+        let composite_alpha =
+            select_composite_alpha(requested_alpha, surface_caps.supported_composite_alpha);
+        if composite_alpha != composite_alpha_flag(requested_alpha) {
+            warn!(
+                target: SWAPCHAIN,
+                "composite_alpha {requested_alpha:?} unsupported (surface offers {:?}), \
+                 using {composite_alpha:?}",
+                surface_caps.supported_composite_alpha
+            );
+        }
+
+        // Hand the previous swapchain (if recreating) to the driver so it can reuse resources
+        let old_swapchain = self.swapchain.take();
+
+        // Exclusive fullscreen: application-controlled on the window's monitor
+        let exclusive_ok = full_screen_exclusive != Some(false);
+        if !exclusive_ok && self.exclusive.available() {
+            warn!(target: SWAPCHAIN, "surface can't go full-screen exclusive on this monitor");
+        }
+        let mut exclusive = exclusive_info(self.exclusive.available() && exclusive_ok);
+        let mut exclusive_monitor = vk::SurfaceFullScreenExclusiveWin32InfoEXT::builder()
+            .hmonitor(self.exclusive.monitor.unwrap_or_default() as vk::HMONITOR);
+
+        // Swapchain creation info
+        let mut swapchain_info = vk::SwapchainCreateInfoKHR::builder()
+            .surface(surface)
+            .min_image_count(image_count)
+            .image_format(format.format)
+            .image_color_space(format.color_space)
+            .image_extent(extent)
+            .image_array_layers(1)
+            .image_usage(usage)
+            .image_sharing_mode(sharing_mode)
+            .queue_family_indices(shared_families)
+            .pre_transform(pre_transform)
+            .composite_alpha(composite_alpha)
+            .present_mode(present_mode)
+            .clipped(true)
+            .old_swapchain(old_swapchain.unwrap_or_default());
+        if let Some(exclusive) = &mut exclusive {
+            swapchain_info = swapchain_info
+                .push_next(exclusive)
+                .push_next(&mut exclusive_monitor);
+        }
+        if let Some(scaling) = &mut scaling {
+            swapchain_info = swapchain_info.push_next(scaling);
+        }
+
+        // Create swapchain
+        let created = unsafe { device.create_swapchain_khr(&swapchain_info, None) };
+
+        // Old swapchain is retired now (even if creation failed); destroy it only
+        // after the new one exists
+        if let Some(old) = old_swapchain {
+            unsafe { device.destroy_swapchain_khr(old, None) };
+        }
+        let swapchain = created.map_err(|e| AppError::vk(e, "create swapchain"))?;
+        self.swapchain = Some(swapchain);
+
+        // Retrieve swapchain images
+        let images_raw = unsafe { device.get_swapchain_images_khr(swapchain) }
+            .map_err(|e| AppError::vk(e, "get swapchain images"))?;
+
+        // The driver may hand out more than requested; per-image state follows its count
+        if images_raw.len() != image_count as usize {
+            info!(
+                target: SWAPCHAIN,
+                "Swapchain has {} images ({image_count} requested)",
+                images_raw.len()
+            );
+        }
+        if present_mode == vk::PresentModeKHR::MAILBOX
+            && images_raw.len() < MAILBOX_MIN_IMAGES as usize
+        {
+            warn!(
+                target: SWAPCHAIN,
+                "MAILBOX with only {} swapchain images may not triple-buffer, pacing like FIFO",
+                images_raw.len()
+            );
+        }
+        let mut images: SmallVec<[vk::Image; 4]> = SmallVec::with_capacity(images_raw.len());
+        images.extend_from_slice(&images_raw);
+
+        // Create image views for each swapchain image
+        let tracker = tracker(self.context.as_ref());
+        let image_views = create_swapchain_views(device, &images, format.format, tracker)?;
+
+        // Save swapchain state
+        self.swapchain_images = images;
+        self.swapchain_image_views = image_views;
+        self.swapchain_format = Some(format.format);
+        self.swapchain_extent = Some(extent);
+        self.content_turns = turns;
+        self.forced_mismatch = forced_mismatch;
+        self.swapchain_usage = usage;
+        self.present_modes = present_modes.into_iter().collect();
+        self.present_mode = present_mode;
+        self.present_fallback = fallback;
+        self.exclusive.swapchain_recreated(device, swapchain);
+
+        info!(target: SWAPCHAIN, "✅ Swapchain and image views created!");
+        Ok(fallback)
+    }
+
+    /// `create_swapchain` for a relay: the swapchain lives on the presenting GPU,
+    /// and the images rendered into here are its stand-ins.
+    fn create_relay_swapchain(&mut self) -> Result<Option<PresentModeFallback>> {
+        let (Some(context), Some(surface), Some(relay)) =
+            (self.context.clone(), self.surface, self.relay.as_ref())
+        else {
+            return Err(AppError::NotInitialized);
+        };
+        let present_modes = relay.present_modes(&context.instance, surface)?;
+        let (present_mode, fallback) = self.choose_present_mode(&present_modes);
+        self.log_present_fallback(fallback);
+
+        let (window, composite_alpha) = (self.window_extent, self.config.composite_alpha);
+        let relay = self.relay.as_mut().unwrap();
+        let images =
+            relay.create_swapchain(&context, surface, window, present_mode, composite_alpha)?;
+        let (format, extent, usage) = (relay.format(), relay.extent(), relay.usage());
+        let image_views =
+            create_swapchain_views(&context.device, &images, format, context.resources())?;
+
+        // Copied frames are never rotated, scaled or taken exclusive by the driver
+        self.swapchain_images = images;
+        self.swapchain_image_views = image_views;
+        self.swapchain_format = Some(format);
+        self.swapchain_extent = Some(extent);
+        self.content_turns = 0;
+        self.forced_mismatch = None;
+        self.swapchain_usage = usage;
+        self.present_modes = present_modes.into_iter().collect();
+        self.present_mode = present_mode;
+        self.present_fallback = fallback;
+        self.present_scaling = false;
+
+        info!(
+            target: SWAPCHAIN,
+            "✅ Relay swapchain created on the presenting GPU ({} stand-in images)",
+            self.swapchain_images.len()
+        );
+        Ok(fallback)
+    }
+
+    /// Log a present mode fallback the first time the swapchain gets it.
+    fn log_present_fallback(&self, fallback: Option<PresentModeFallback>) {
+        if let Some(fallback) = fallback
+            && self.present_fallback != Some(fallback)
+        {
+            match fallback.reason {
+                FallbackReason::Unsupported => warn!(target: SWAPCHAIN, "Present mode: {fallback}"),
+                FallbackReason::PowerSaving => info!(target: SWAPCHAIN, "Present mode: {fallback}"),
+            }
+        }
+    }
+
+    /// Whether frames have somewhere to go: a swapchain, here or on the relay's GPU.
+    fn has_swapchain(&self) -> bool {
+        self.swapchain.is_some() || self.relay.as_ref().is_some_and(Relay::has_swapchain)
+    }
+
+    /// GPU the window's surface is queried on: the relay's presenting one, if any.
+    fn presenting_gpu(&self) -> Option<vk::PhysicalDevice> {
+        self.relay
+            .as_ref()
+            .map(Relay::physical_device)
+            .or(self.physical_device)
+    }
+
+    /// Present mode for a swapchain on a surface offering `available`: the
+    /// config's (or platform default) preference, vsync while saving power.
+    /// Also returns the fallback if that isn't the mode the config asks for.
+    fn choose_present_mode(
+        &self,
+        available: &[vk::PresentModeKHR],
+    ) -> (vk::PresentModeKHR, Option<PresentModeFallback>) {
+        let platform = Platform::current();
+        let preferences =
+            present_preferences(self.config.present_mode, self.config.vsync, platform);
+        let (chosen, fallback) = select_present_mode(available, preferences);
+        if !self.power.saving() {
+            return (chosen, fallback);
+        }
+        let (vsync, _) = select_present_mode(available, present_preferences(None, true, platform));
+        if vsync == chosen {
+            return (chosen, fallback);
+        }
+        let fallback = PresentModeFallback {
+            requested: fallback.map_or(chosen, |fallback| fallback.requested),
+            chosen: vsync,
+            reason: FallbackReason::PowerSaving,
+        };
+        (vsync, Some(fallback))
+    }
+
+    /// Recreate the swapchain if power saving changed which present mode it
+    /// should use (uncapped ↔ vsync).
+    fn apply_power_saving(&mut self) -> Result<()> {
+        let saving = self.power.saving();
+        let (wanted, _) = self.choose_present_mode(&self.present_modes);
+        if !self.has_swapchain() || wanted == self.present_mode {
+            return Ok(());
+        }
+        info!(
+            target: SWAPCHAIN,
+            "Power saving {}: present mode {:?} → {wanted:?}",
+            if saving { "on" } else { "off" },
+            self.present_mode
+        );
+        self.recreate_swapchain()
+    }
+
+    /// Rebuilds the swapchain and everything sized from it (e.g. after a resize).
+    /// The old swapchain is passed as `old_swapchain` and destroyed by `create_swapchain`.
+    fn recreate_swapchain(&mut self) -> Result<()> {
+        let Some(device) = &self.device else {
+            return Ok(());
+        };
+        // Minimized: a zero-sized swapchain is invalid, wait for the next resize
+        if self.window_extent.width == 0 || self.window_extent.height == 0 {
+            return Ok(());
+        }
+        self.resize_pending = None;
+        self.wait_for_swapchain_release();
+        let device = self.device.as_ref().unwrap();
+
+        // Framebuffers and views reference the old images; the old swapchain
+        // handle itself stays alive until its replacement is created.
+        let tracker = tracker(self.context.as_ref());
+        for fb in self.framebuffers.drain(..) {
+            unsafe { device.destroy_framebuffer(fb, None) };
+            tracker.destroyed(&[ResourceKind::Framebuffer]);
+        }
+        for target in [
+            self.msaa_target.take(),
+            self.hdr_target.take(),
+            self.depth_target.take(),
+        ]
+        .into_iter()
+        .flatten()
+        {
+            target.destroy(device, tracker);
+        }
+        for iv in self.swapchain_image_views.drain(..) {
+            unsafe { device.destroy_image_view(iv, None) };
+            tracker.destroyed(&[ResourceKind::ImageView]);
+        }
+        self.swapchain_images.clear();
+
+        let old_format = self.swapchain_format;
+        self.create_swapchain()?;
+        self.resize_per_image_resources();
+
+        // Render pass only depends on the format, rebuild it if that changed
+        if self.swapchain_format != old_format {
+            if let (Some(device), Some(rp)) = (&self.device, self.render_pass.take()) {
+                unsafe { device.destroy_render_pass(rp, None) };
+            }
+            self.create_render_pass()?;
+        }
+        self.create_render_targets()?;
+        self.create_framebuffers()?;
+        Ok(())
+    }
+
+    /// Creates a render pass for rendering into the swapchain images.
+    ///
+    /// Attachments, in framebuffer order: [MSAA color], [HDR color], swapchain, depth.
+    /// With MSAA the scene resolves into the next attachment; with tonemapping a
+    /// second subpass reads the HDR image as an input attachment and writes the
+    /// swapchain image.
+    fn create_render_pass(&mut self) -> Result<()> {
+        let device = self.device.as_ref().unwrap();
+        let format = self.swapchain_format.unwrap();
+        let msaa = self.msaa_samples > 1;
+        let hdr = self.tonemap.is_some();
+
+        // The swapchain image; cleared only when the scene is drawn straight into it
+        let color_attachment = vk::AttachmentDescription::builder()
+            .format(format)
+            .samples(vk::SampleCountFlags::_1)
+            .load_op(if msaa || hdr {
+                vk::AttachmentLoadOp::DONT_CARE // fully overwritten by resolve/tonemap
+            } else {
+                vk::AttachmentLoadOp::CLEAR
+            })
+            .store_op(vk::AttachmentStoreOp::STORE)
+            .stencil_load_op(vk::AttachmentLoadOp::DONT_CARE)
+            .stencil_store_op(vk::AttachmentStoreOp::DONT_CARE)
+            .initial_layout(vk::ImageLayout::UNDEFINED)
+            .final_layout(vk::ImageLayout::PRESENT_SRC_KHR)
+            .build();
+
+        // Single-sample HDR scene color, consumed inside the pass by the tonemap subpass
+        let hdr_attachment = vk::AttachmentDescription {
+            format: self.scene_format(),
+            load_op: if msaa {
+                vk::AttachmentLoadOp::DONT_CARE
+            } else {
+                vk::AttachmentLoadOp::CLEAR
+            },
+            store_op: vk::AttachmentStoreOp::DONT_CARE,
+            final_layout: vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL,
+            ..color_attachment
+        };
+
+        // Multisampled attachment is cleared and drawn into, then resolved;
+        // its samples never need to leave the tile
+        let msaa_attachment = vk::AttachmentDescription::builder()
+            .format(self.scene_format())
+            .samples(vk::SampleCountFlags::from_bits_truncate(self.msaa_samples))
+            .load_op(vk::AttachmentLoadOp::CLEAR)
+            .store_op(vk::AttachmentStoreOp::DONT_CARE)
+            .stencil_load_op(vk::AttachmentLoadOp::DONT_CARE)
+            .stencil_store_op(vk::AttachmentStoreOp::DONT_CARE)
+            .initial_layout(vk::ImageLayout::UNDEFINED)
+            .final_layout(vk::ImageLayout::COLOR_ATTACHMENT_OPTIMAL)
+            .build();
+
+        let mut attachments: SmallVec<[vk::AttachmentDescription; 3]> = SmallVec::new();
+        if msaa {
+            attachments.push(msaa_attachment);
+        }
+        if hdr {
+            attachments.push(hdr_attachment);
+        }
+        attachments.push(color_attachment);
+        let present = attachments.len() as u32 - 1;
+        let scene = if hdr { present - 1 } else { present }; // Single-sample scene color
+
+        // Depth is only needed while drawing the scene, never stored
+        let depth_format = self.depth_format.unwrap();
+        let stencil_load = if has_stencil(depth_format) {
+            vk::AttachmentLoadOp::CLEAR
+        } else {
+            vk::AttachmentLoadOp::DONT_CARE
+        };
+        attachments.push(
+            vk::AttachmentDescription::builder()
+                .format(depth_format)
+                .samples(vk::SampleCountFlags::from_bits_truncate(
+                    self.msaa_samples.max(1),
+                ))
+                .load_op(vk::AttachmentLoadOp::CLEAR)
+                .store_op(vk::AttachmentStoreOp::DONT_CARE)
+                .stencil_load_op(stencil_load)
+                .stencil_store_op(vk::AttachmentStoreOp::DONT_CARE)
+                .initial_layout(vk::ImageLayout::UNDEFINED)
+                .final_layout(vk::ImageLayout::DEPTH_STENCIL_ATTACHMENT_OPTIMAL)
+                .build(),
+        );
+        let depth = present + 1;
+
+        // The depth (and MSAA/HDR) images are shared by all frames in flight: order
+        // this frame's clears after the previous frame's color and depth writes
+        // (write-after-write on every one of them)
+        let external = vk::SubpassDependency::builder()
+            .src_subpass(vk::SUBPASS_EXTERNAL)
+            .dst_subpass(0)
+            .src_stage_mask(
+                vk::PipelineStageFlags::COLOR_ATTACHMENT_OUTPUT
+                    | vk::PipelineStageFlags::LATE_FRAGMENT_TESTS,
+            )
+            .dst_stage_mask(
+                vk::PipelineStageFlags::COLOR_ATTACHMENT_OUTPUT
+                    | vk::PipelineStageFlags::EARLY_FRAGMENT_TESTS,
+            )
+            .src_access_mask(
+                vk::AccessFlags::COLOR_ATTACHMENT_WRITE
+                    | vk::AccessFlags::DEPTH_STENCIL_ATTACHMENT_WRITE,
+            )
+            .dst_access_mask(
+                vk::AccessFlags::COLOR_ATTACHMENT_WRITE
+                    | vk::AccessFlags::DEPTH_STENCIL_ATTACHMENT_WRITE,
+            )
+            .build();
+
+        // Subpass 0 draws the scene
+        let mut builder = attachments
+            .into_iter()
+            .fold(RenderPassBuilder::new(), RenderPassBuilder::attachment)
+            .dependency(external)
+            .subpass()
+            .depth(depth);
+        builder = if msaa {
+            builder.color(0).resolve(scene)
+        } else {
+            builder.color(scene)
+        };
+        // Subpass 1 tonemaps it into the swapchain image
+        if hdr {
+            builder = builder
+                .subpass()
+                .input(scene)
+                .color(present)
+                .chain_dependencies();
+        }
+        // The last subpass's writes to the swapchain image, before its PRESENT_SRC_KHR
+        builder = builder.present_dependency();
+
+        let render_pass = builder.build(device)?;
+        let samples = vk::SampleCountFlags::from_bits_truncate(self.msaa_samples.max(1));
+        let pipelines = self
+            .tonemap
+            .as_mut()
+            .map_or(Ok(()), |pass| pass.build_pipeline(device, render_pass, 1))
+            .and_then(|()| self.pipelines.rebuild(device, render_pass, samples));
+        if let Err(e) = pipelines {
+            unsafe { device.destroy_render_pass(render_pass, None) };
+            return Err(e);
+        }
+        self.record_cache.invalidate();
+
+        self.render_pass = Some(render_pass);
+        self.render_pass_attachments = builder.attachments().iter().copied().collect();
+        info!(
+            target: RENDERER,
+            "✅ Render pass created ({}x MSAA, tonemap {:?})",
+            self.msaa_samples.max(1),
+            self.tonemap_mode
+        );
+        Ok(())
+    }
+
+    /// Block until every submitted frame has finished on the GPU.
+    fn wait_for_frames_in_flight(&self) {
+        let Some(device) = &self.device else {
+            return;
+        };
+        // Idle rather than waiting on the frame fences: a fence reset before a
+        // failed submit would never signal and the wait would hang
+        if let Err(e) = unsafe { device.device_wait_idle() } {
+            warn!(target: RENDERER, "device_wait_idle on close failed: {e}");
+        }
+        info!(target: RENDERER, "✅ GPU idle, shutting down");
+    }
+
+    /// Creates the offscreen images the render pass uses besides the swapchain
+    /// image: the depth buffer, the multisampled target (MSAA on) and the HDR
+    /// target (tonemapping on). All only live inside the pass, so they are transient.
+    fn create_render_targets(&mut self) -> Result<()> {
+        let (Some(instance), Some(device), Some(physical_device)) =
+            (&self.instance, &self.device, self.physical_device)
+        else {
+            return Err(AppError::NotInitialized);
+        };
+        let extent = self.swapchain_extent.unwrap();
+        let samples = vk::SampleCountFlags::from_bits_truncate(self.msaa_samples.max(1));
+        let tracker = tracker(self.context.as_ref());
+
+        let depth_format = self.depth_format.unwrap();
+        let depth_desc = ImageDesc {
+            extent,
+            format: depth_format,
+            samples,
+            usage: vk::ImageUsageFlags::DEPTH_STENCIL_ATTACHMENT
+                | vk::ImageUsageFlags::TRANSIENT_ATTACHMENT,
+            aspect: if has_stencil(depth_format) {
+                vk::ImageAspectFlags::DEPTH | vk::ImageAspectFlags::STENCIL
+            } else {
+                vk::ImageAspectFlags::DEPTH
+            },
+        };
+        self.depth_target = Some(AllocatedImage::new(
+            instance,
+            device,
+            physical_device,
+            depth_desc,
+            tracker,
+        )?);
+
+        if let Some(pass) = &self.tonemap {
+            let desc = ImageDesc {
+                extent,
+                format: self.scene_format(),
+                samples: vk::SampleCountFlags::_1,
+                usage: vk::ImageUsageFlags::COLOR_ATTACHMENT
+                    | vk::ImageUsageFlags::INPUT_ATTACHMENT
+                    | vk::ImageUsageFlags::TRANSIENT_ATTACHMENT,
+                aspect: vk::ImageAspectFlags::COLOR,
+            };
+            let target = AllocatedImage::new(instance, device, physical_device, desc, tracker)?;
+            pass.bind_input(device, target.view);
+            self.hdr_target = Some(target);
+        }
+
+        if self.msaa_samples > 1 {
+            let desc = ImageDesc {
+                extent,
+                format: self.scene_format(),
+                samples,
+                // Only ever resolved, never read back: let tilers keep it on-chip
+                usage: vk::ImageUsageFlags::COLOR_ATTACHMENT
+                    | vk::ImageUsageFlags::TRANSIENT_ATTACHMENT,
+                aspect: vk::ImageAspectFlags::COLOR,
+            };
+            self.msaa_target = Some(AllocatedImage::new(
+                instance,
+                device,
+                physical_device,
+                desc,
+                tracker,
+            )?);
+        }
+        Ok(())
+    }
+
+    /// Size the per-swapchain-image state to the current image count, which the
+    /// driver may change on recreation. Framebuffers are rebuilt separately
+    /// (`create_framebuffers`); the GPU must be idle.
+    fn resize_per_image_resources(&mut self) {
+        let count = self.swapchain_images.len();
+        if !self.images_in_flight.is_empty() && self.images_in_flight.len() != count {
+            info!(
+                target: SWAPCHAIN,
+                "Swapchain image count changed {} -> {count}",
+                self.images_in_flight.len()
+            );
+        }
+        // Old fences belong to frames that have finished; nothing to wait on
+        self.images_in_flight = smallvec::smallvec![vk::Fence::null(); count];
+        // Cached buffers were recorded for the old framebuffers
+        self.record_cache.reset(count);
+        self.cached_draws.clear();
+    }
+
+    fn per_image_counts(&self) -> PerImageCounts {
+        PerImageCounts {
+            images: self.swapchain_images.len(),
+            views: self.swapchain_image_views.len(),
+            framebuffers: self.framebuffers.len(),
+            in_flight: self.images_in_flight.len(),
+        }
+    }
+
+    /// Wait until no frame or queued present still uses the swapchain images:
+    /// precisely through present fences when available, else by draining the device.
+    fn wait_for_swapchain_release(&mut self) {
+        let Some(device) = &self.device else {
+            return;
+        };
+        if let (SwapchainRetire::PresentFences, Some(present_fences)) =
+            (self.swapchain_retire, &mut self.present_fences)
+        {
+            let frame_fences: SmallVec<[vk::Fence; MAX_FRAMES_IN_FLIGHT]> =
+                self.frames.iter().map(|frame| frame.in_flight).collect();
+            let timeout = self.config.fence_timeout;
+            let waited = wait_for_fences(device, &frame_fences, timeout, "wait for frame fences")
+                .and_then(|()| present_fences.wait_all(device, timeout));
+            match waited {
+                Ok(()) => return,
+                Err(e) => warn!(target: SWAPCHAIN, "{e}, falling back to device_wait_idle"),
+            }
+        }
+        unsafe { device.device_wait_idle() }.ok();
+    }
+
+    /// Creates the command pools plus command buffer and sync objects per frame in flight.
+    fn create_frame_resources(&mut self) -> Result<()> {
+        let device = self.device.as_ref().unwrap();
+        let (graphics_family, _) = self.queue_family_indices.unwrap();
+
+        // Frame buffers are re-recorded every frame, so allow resetting them individually
+        let pool_info = vk::CommandPoolCreateInfo::builder()
+            .queue_family_index(graphics_family)
+            .flags(vk::CommandPoolCreateFlags::RESET_COMMAND_BUFFER);
+        let pool = unsafe { device.create_command_pool(&pool_info, None) }
+            .map_err(|e| AppError::vk(e, "create frame command pool"))?;
+        self.frame_command_pool = Some(pool);
+
+        // Short-lived buffers for uploads/immediate submits; TRANSIENT lets the
+        // driver optimize allocation for buffers freed right after one use
+        let transient_info = vk::CommandPoolCreateInfo::builder()
+            .queue_family_index(graphics_family)
+            .flags(vk::CommandPoolCreateFlags::TRANSIENT);
+        let transient = unsafe { device.create_command_pool(&transient_info, None) }
+            .map_err(|e| AppError::vk(e, "create transient command pool"))?;
+        self.transient_command_pool = Some(transient);
+
+        let alloc_info = vk::CommandBufferAllocateInfo::builder()
+            .command_pool(pool)
+            .level(vk::CommandBufferLevel::PRIMARY)
+            .command_buffer_count(MAX_FRAMES_IN_FLIGHT as u32);
+        let buffers = unsafe { device.allocate_command_buffers(&alloc_info) }
+            .map_err(|e| AppError::vk(e, "allocate frame command buffers"))?;
+
+        let tracker = tracker(self.context.as_ref());
+        for cmd in buffers {
+            self.frames.push(FrameSync::create(device, cmd, tracker)?);
+        }
+        if self.swapchain_retire == SwapchainRetire::PresentFences {
+            self.present_fences = Some(PresentFences::create(device, tracker)?);
+        }
+        let precise = self.enabled_features.occlusion_query_precise == vk::TRUE;
+        self.overdraw = OverdrawQueries::create(device, precise)?;
+        self.resize_per_image_resources();
+
+        info!(target: RENDERER, "✅ {MAX_FRAMES_IN_FLIGHT} frames in flight ready");
+        Ok(())
+    }
+
+    /// Record commands with `record`, submit them on the graphics queue and block
+    /// until they finished. For uploads and other one-off work outside the frame loop.
+    pub fn immediate_submit(&self, record: impl FnOnce(&Device, vk::CommandBuffer)) -> Result<()> {
+        let (Some(device), Some(pool), Some(queue)) = (
+            &self.device,
+            self.transient_command_pool,
+            self.graphics_queue,
+        ) else {
+            return Err(AppError::NotInitialized);
+        };
+
+        let alloc_info = vk::CommandBufferAllocateInfo::builder()
+            .command_pool(pool)
+            .level(vk::CommandBufferLevel::PRIMARY)
+            .command_buffer_count(1);
+        let cmd = unsafe { device.allocate_command_buffers(&alloc_info) }
+            .map_err(|e| AppError::vk(e, "allocate immediate command buffer"))?[0];
+
+        let result = submit_and_wait(device, queue, cmd, self.config.fence_timeout, record);
+        // A timed-out command buffer may still be executing
+        if !matches!(result, Err(AppError::GpuTimeout(..))) {
+            unsafe { device.free_command_buffers(pool, &[cmd]) };
+        }
+        result
+    }
+
+    /// Record commands with `record` now and submit them with the next frame,
+    /// ahead of its own commands, without blocking (see `SubmitPolicy`). Whatever
+    /// they use must stay alive until that frame finishes; end with the barrier
+    /// the frame's reads need.
+    pub fn submit_with_frame(
+        &mut self,
+        record: impl FnOnce(&Device, vk::CommandBuffer),
+    ) -> Result<()> {
+        let (Some(device), Some(pool)) = (&self.device, self.transient_command_pool) else {
+            return Err(AppError::NotInitialized);
+        };
+        let alloc_info = vk::CommandBufferAllocateInfo::builder()
+            .command_pool(pool)
+            .level(vk::CommandBufferLevel::PRIMARY)
+            .command_buffer_count(1);
+        let cmd = unsafe { device.allocate_command_buffers(&alloc_info) }
+            .map_err(|e| AppError::vk(e, "allocate queued command buffer"))?[0];
+
+        let begin_info = vk::CommandBufferBeginInfo::builder()
+            .flags(vk::CommandBufferUsageFlags::ONE_TIME_SUBMIT);
+        let recorded = unsafe { device.begin_command_buffer(cmd, &begin_info) }
+            .map_err(|e| AppError::vk(e, "begin queued command buffer"))
+            .and_then(|()| {
+                record(device, cmd);
+                unsafe { device.end_command_buffer(cmd) }
+                    .map_err(|e| AppError::vk(e, "end queued command buffer"))
+            });
+        match recorded {
+            Ok(()) => {
+                self.queued_submits.push(cmd);
+                Ok(())
+            }
+            Err(e) => {
+                unsafe { device.free_command_buffers(pool, &[cmd]) };
+                Err(e)
+            }
+        }
+    }
+
+    /// Submit the work queued with `submit_with_frame` now, on the graphics queue
+    /// and without touching the swapchain, and block until it finished. For
+    /// offscreen tools (baking, thumbnails) that read results back without ever
+    /// presenting; unlike `immediate_submit` it runs whatever was queued so far.
+    /// Nothing queued is a no-op.
+    pub fn flush(&mut self) -> Result<()> {
+        let (Some(device), Some(pool), Some(queue)) = (
+            &self.device,
+            self.transient_command_pool,
+            self.graphics_queue,
+        ) else {
+            return Err(AppError::NotInitialized);
+        };
+        if self.queued_submits.is_empty() {
+            return Ok(());
+        }
+        let queued = std::mem::take(&mut self.queued_submits);
+        let timeout = self.config.fence_timeout;
+        let result = submit_recorded_and_wait(device, queue, &queued, timeout, "flush");
+        // Timed-out command buffers may still be executing
+        if !matches!(result, Err(AppError::GpuTimeout(..))) {
+            unsafe { device.free_command_buffers(pool, &queued) };
+        }
+        result
+    }
+
+    /// Have every cached frame recorded again on its image's next frame, for
+    /// changes the frame signature doesn't cover (e.g. new contents of a buffer
+    /// the shaders read). Only matters with `CommandRecording::Cached`.
+    pub fn mark_dirty(&mut self) {
+        self.record_cache.invalidate();
+    }
+
+    /// Allocate a cached frame buffer for every swapchain image that has none.
+    fn ensure_cached_commands(&mut self) -> Result<()> {
+        let (Some(device), Some(pool)) = (&self.device, self.frame_command_pool) else {
+            return Ok(());
+        };
+        let missing = self
+            .swapchain_images
+            .len()
+            .saturating_sub(self.cached_commands.len());
+        if missing == 0 {
+            return Ok(());
+        }
+        let alloc_info = vk::CommandBufferAllocateInfo::builder()
+            .command_pool(pool)
+            .level(vk::CommandBufferLevel::PRIMARY)
+            .command_buffer_count(missing as u32);
+        let buffers = unsafe { device.allocate_command_buffers(&alloc_info) }
+            .map_err(|e| AppError::vk(e, "allocate cached frame command buffers"))?;
+        self.cached_commands.extend(buffers);
+        Ok(())
+    }
+
+    /// Signature of everything `record_frame` reads for `frame`, besides state
+    /// that only changes with the swapchain or the pipelines.
+    fn frame_signature(&self, frame: &FrameDraws) -> u64 {
+        let mut state = DefaultHasher::new();
+        frame.hash_content(&mut state);
+        self.clear_color.map(f32::to_bits).hash(&mut state);
+        let vk::Rect2D { offset, extent } = self.scene_area();
+        (offset.x, offset.y, extent.width, extent.height).hash(&mut state);
+        (self.tonemap_mode as u32, self.exposure.to_bits()).hash(&mut state);
+        let DepthRange { min, max } = self.depth_range;
+        (min.to_bits(), max.to_bits()).hash(&mut state);
+        state.finish()
+    }
+
+    /// Records the render pass for one swapchain image into `cmd`. A `cached`
+    /// buffer is submitted again on later frames, so it leaves out the overdraw
+    /// query and binds the materials' slot 0 sets whatever the frame slot.
+    fn record_frame(
+        &self,
+        cmd: vk::CommandBuffer,
+        image_index: usize,
+        captures: &[vk::Buffer],
+        frame: &FrameDraws,
+        cached: bool,
+    ) -> Result<DrawCounts> {
+        let device = self.device.as_ref().unwrap();
+        let extent = self.swapchain_extent.unwrap();
+
+        unsafe {
+            device
+                .reset_command_buffer(cmd, vk::CommandBufferResetFlags::empty())
+                .map_err(|e| AppError::vk(e, "reset frame command buffer"))?;
+
+            let flags = if cached {
+                vk::CommandBufferUsageFlags::empty()
+            } else {
+                vk::CommandBufferUsageFlags::ONE_TIME_SUBMIT
+            };
+            let begin_info = vk::CommandBufferBeginInfo::builder().flags(flags);
+            device
+                .begin_command_buffer(cmd, &begin_info)
+                .map_err(|e| AppError::vk(e, "begin frame command buffer"))?;
+
+            // One value per attachment, in render pass order; color values of
+            // attachments that aren't cleared are ignored. Letterboxed, the pass
+            // clears to the bar color and the scene area is cleared again below
+            let area = self.scene_area();
+            let letterboxed = area.extent != extent;
+            let scene_clear = vk::ClearValue {
+                color: vk::ClearColorValue {
+                    float32: self.clear_color,
+                },
+            };
+            let clear = if letterboxed {
+                vk::ClearValue {
+                    color: vk::ClearColorValue {
+                        float32: self.config.bar_color,
+                    },
+                }
+            } else {
+                scene_clear
+            };
+            let color_count = 1
+                + usize::from(self.msaa_target.is_some())
+                + usize::from(self.hdr_target.is_some());
+            let mut clear_values: SmallVec<[vk::ClearValue; 4]> =
+                smallvec::smallvec![clear; color_count];
+            clear_values.push(vk::ClearValue {
+                depth_stencil: vk::ClearDepthStencilValue {
+                    depth: 1.0,
+                    stencil: 0,
+                },
+            });
+            // A mismatch here is a bug in this function, so stop loudly in debug builds
+            let checked = validate_clear_values(&self.render_pass_attachments, clear_values.len());
+            debug_assert!(checked.is_ok(), "{checked:?}");
+            checked?;
+
+            // Compute runs first; its barriers order it against earlier blits
+            for dispatch in &frame.dispatches {
+                dispatch.record(device, cmd)?;
+            }
+            if !cached {
+                self.overdraw.reset(device, cmd, self.current_frame);
+            }
+
+            let render_area = vk::Rect2D::builder()
+                .offset(vk::Offset2D::default())
+                .extent(extent);
+            let pass_info = vk::RenderPassBeginInfo::builder()
+                .render_pass(self.render_pass.unwrap())
+                .framebuffer(self.framebuffers[image_index])
+                .render_area(render_area)
+                .clear_values(&clear_values);
+
+            device.cmd_begin_render_pass(cmd, &pass_info, vk::SubpassContents::INLINE);
+            self.scene_pass.set(Some(cmd));
+            if letterboxed {
+                self.clear_attachment(cmd, ClearTarget::Color(0), scene_clear, area)?;
+            }
+            if !frame.draws.is_empty() {
+                self.set_viewport(cmd);
+            }
+            if !cached {
+                self.overdraw.begin(device, cmd, self.current_frame);
+            }
+            let material_slot = if cached { 0 } else { self.current_frame };
+            let mesh_desc = self.mesh_desc();
+            // Pipelines were created by `render_with` before recording; draws
+            // without one are skipped
+            let resolved: Vec<Option<(&MeshPipeline, DrawKey)>> = frame
+                .draws
+                .iter()
+                .map(|draw| {
+                    let pipeline = self.pipelines.get(draw.pipeline().unwrap_or(&mesh_desc))?;
+                    let material = draw
+                        .material()
+                        .filter(|_| pipeline.material_set())
+                        .and_then(|material| self.materials.set(material, material_slot));
+                    Some((pipeline, DrawKey::new(pipeline.pipeline(), material)))
+                })
+                .collect();
+            let keys: Vec<Option<DrawKey>> =
+                resolved.iter().map(|r| r.map(|(_, key)| key)).collect();
+            let order = if self.config.sort_draws {
+                let barriers: SmallVec<[usize; 4]> =
+                    frame.clears.iter().map(|clear| clear.before_draw).collect();
+                sorted_order(&keys, &barriers)
+            } else {
+                (0..frame.draws.len()).collect()
+            };
+            let mut counts = DrawCounts::default();
+            let mut binds = BindState::default();
+            let mut clears = frame.clears.iter().peekable();
+            // Sorting keeps draws within their segment, so clears stay at the same positions
+            for (position, &index) in order.iter().enumerate() {
+                while let Some(clear) = clears.next_if(|clear| clear.before_draw == position) {
+                    self.clear_attachment(cmd, clear.target, clear.value, clear.rect)?;
+                }
+                let (draw, Some((pipeline, key))) = (&frame.draws[index], resolved[index]) else {
+                    continue;
+                };
+                let (bind_pipeline, bind_material) = binds.update(key);
+                if bind_pipeline {
+                    let bind_point = vk::PipelineBindPoint::GRAPHICS;
+                    device.cmd_bind_pipeline(cmd, bind_point, pipeline.pipeline());
+                }
+                if bind_material {
+                    let set = vk::DescriptorSet::from_raw(key.material);
+                    device.cmd_bind_descriptor_sets(
+                        cmd,
+                        vk::PipelineBindPoint::GRAPHICS,
+                        pipeline.layout(),
+                        0,
+                        &[set],
+                        &[],
+                    );
+                }
+                draw.record(device, cmd, pipeline);
+                counts.add_indexed(draw.index_count());
+            }
+            counts.binds = binds.counts;
+            let unsorted = count_binds(keys.iter().flatten().copied());
+            counts.binds_saved = unsorted.total().saturating_sub(binds.counts.total());
+            for clear in clears {
+                self.clear_attachment(cmd, clear.target, clear.value, clear.rect)?;
+            }
+            if !cached {
+                self.overdraw.end(device, cmd, self.current_frame);
+            }
+            self.scene_pass.set(None);
+            if let Some(tonemap) = &self.tonemap {
+                device.cmd_next_subpass(cmd, vk::SubpassContents::INLINE);
+                let push = TonemapPush::new(
+                    self.tonemap_mode,
+                    self.exposure,
+                    self.swapchain_format.unwrap(),
+                );
+                tonemap.draw(device, cmd, extent, push);
+            }
+            device.cmd_end_render_pass(cmd);
+
+            let image = self.swapchain_images[image_index];
+            let mut layout = vk::ImageLayout::PRESENT_SRC_KHR;
+            if let Some(source) = &frame.blit {
+                record_blit(device, cmd, source, image, extent)?;
+                layout = vk::ImageLayout::TRANSFER_DST_OPTIMAL;
+            }
+            // Screenshots copy the finished image before it's handed to present
+            record_capture(device, cmd, image, layout, extent, captures)?;
+
+            device
+                .end_command_buffer(cmd)
+                .map_err(|e| AppError::vk(e, "end frame command buffer"))?;
+            Ok(counts)
+        }
+    }
+
+    /// Creates one framebuffer per swapchain image.
+    fn create_framebuffers(&mut self) -> Result<()> {
+        let device = self.device.as_ref().unwrap();
+        // None after a failed `set_msaa` couldn't restore the old pass either
+        let render_pass = self.render_pass.ok_or(AppError::NotInitialized)?;
+        let extent = self.swapchain_extent.unwrap();
+        let tracker = tracker(self.context.as_ref());
+
+        // One ordered view list per swapchain image, matching the render pass
+        let view = |target: &Option<AllocatedImage>| target.as_ref().map(|t| t.view);
+        let per_image: SmallVec<[FramebufferAttachments; 4]> = self
+            .swapchain_image_views
+            .iter()
+            .map(|&image| {
+                let (msaa, hdr) = (view(&self.msaa_target), view(&self.hdr_target));
+                frame_attachments(msaa, hdr, image, view(&self.depth_target))
+            })
+            .collect();
+        for attachments in &per_image {
+            validate_framebuffer_attachments(&self.render_pass_attachments, attachments.len())?;
+        }
+
+        let mut framebuffers: SmallVec<[vk::Framebuffer; 4]> =
+            SmallVec::with_capacity(per_image.len());
+
+        for attachments in &per_image {
+            let framebuffer_info = vk::FramebufferCreateInfo::builder()
+                .render_pass(render_pass)
+                .attachments(attachments)
+                .width(extent.width)
+                .height(extent.height)
+                .layers(1);
+
+            match unsafe { device.create_framebuffer(&framebuffer_info, None) } {
+                Ok(fb) => {
+                    framebuffers.push(fb);
+                    tracker.created(&[ResourceKind::Framebuffer]);
+                }
+                Err(e) => {
+                    // Destroy the ones built for earlier images, nothing else owns them yet
+                    for fb in framebuffers {
+                        unsafe { device.destroy_framebuffer(fb, None) };
+                        tracker.destroyed(&[ResourceKind::Framebuffer]);
+                    }
+                    return Err(AppError::vk(e, "create framebuffer"));
+                }
+            }
+        }
+
+        self.framebuffers = framebuffers;
+
+        info!(target: RENDERER, "✅ Framebuffers created!");
+        Ok(())
+    }
+
+    /// Format the scene is drawn in: the HDR target's with tonemapping, else the
+    /// swapchain's.
+    fn scene_format(&self) -> vk::Format {
+        match (&self.tonemap, self.hdr_format) {
+            (Some(_), Some(hdr_format)) => hdr_format,
+            _ => self.swapchain_format.unwrap(),
+        }
+    }
+
+    /// Pipeline of draws that didn't `set_pipeline`: the built-in mesh shaders.
+    fn mesh_desc(&self) -> PipelineDesc {
+        self.mesh_desc.clone()
+    }
+
+    /// Adopt the config's graphics state: the built-in pipeline is rebuilt
+    /// once initialized, else just described for the first draw.
+    fn apply_graphics_state(&mut self) -> Result<()> {
+        if self.device.is_some() {
+            self.recreate_pipeline()
+        } else {
+            self.mesh_desc = PipelineDesc::from_config(&self.config);
+            Ok(())
+        }
+    }
+}
+
+impl VulkanRenderer {
+    /// Chosen GPU and driver details, available after `initialize`.
+    pub fn device_info(&self) -> Option<&DeviceInfo> {
+        self.device_info.as_ref()
+    }
+
+    /// What the GPU and surface support, queried once by `initialize`.
+    pub fn capabilities(&self) -> Option<&Capabilities> {
+        self.capabilities.as_ref()
+    }
+
+    /// Fresh capability report of the chosen GPU, including what the window's
+    /// surface can present. None before `initialize`.
+    pub fn gpu_report(&self) -> Option<GpuReport> {
+        let (Some(instance), Some(physical_device)) = (&self.instance, self.physical_device)
+        else {
+            return None;
+        };
+        // A relay's render GPU can't query the surface, the presenting one reports it
+        let surface = self.surface.filter(|_| self.relay.is_none());
+        let mut report = GpuReport::query(instance, physical_device, self.has_props2, surface);
+        self.relay_surface_support(&mut report);
+        Some(report)
+    }
+
+    /// With a relay, fill the surface half of the render GPU's `report` with
+    /// what the presenting GPU offers.
+    fn relay_surface_support(&self, report: &mut GpuReport) {
+        let (Some(relay), Some(instance)) = (&self.relay, &self.instance) else {
+            return;
+        };
+        let presenting = GpuReport::query(
+            instance,
+            relay.physical_device(),
+            self.has_props2,
+            self.surface,
+        );
+        report.present_modes = presenting.present_modes;
+        report.surface_formats = presenting.surface_formats;
+    }
+
+    /// MSAA sample counts valid for color + depth framebuffers on the chosen GPU,
+    /// ascending (e.g. `[1, 2, 4, 8]`). Empty before `initialize`.
+    pub fn supported_sample_counts(&self) -> Vec<u32> {
+        let (Some(instance), Some(physical_device)) = (&self.instance, self.physical_device)
+        else {
+            return Vec::new();
+        };
+        let props = unsafe { instance.get_physical_device_properties(physical_device) };
+        sample_counts_from_limits(&props.limits)
+    }
+
+    /// Depth and depth-stencil formats the chosen GPU can use as optimal-tiling
+    /// depth attachments, out of `DEPTH_FORMATS` and preferred first, for
+    /// picking one explicitly. Empty before `initialize`.
+    pub fn supported_depth_formats(&self) -> Vec<vk::Format> {
+        let (Some(instance), Some(physical_device)) = (&self.instance, self.physical_device)
+        else {
+            return Vec::new();
+        };
+        supported_depth_formats(&DEPTH_FORMATS, |format| unsafe {
+            instance.get_physical_device_format_properties(physical_device, format)
+        })
+    }
+
+    /// Active MSAA sample count (1 = off). Pipelines drawing into the main render
+    /// pass must use this as their `rasterization_samples`.
+    pub fn msaa_samples(&self) -> u32 {
+        self.msaa_samples.max(1)
+    }
+
+    /// Switch MSAA to `samples` at runtime, rebuilding the render pass, the
+    /// multisampled target and the framebuffers. Unsupported counts (see
+    /// `supported_sample_counts`) return `AppError::Config` and leave the current
+    /// setting untouched. Pipelines built for the old count must be recreated.
+    pub fn set_msaa(&mut self, samples: u32) -> Result<()> {
+        let Some(device) = &self.device else {
+            return Err(AppError::NotInitialized);
+        };
+        let supported = self.supported_sample_counts();
+        if !supported.contains(&samples) {
+            return Err(AppError::Config(format!(
+                "{samples}x MSAA not supported by this GPU (supported: {supported:?})"
+            )));
+        }
+        if samples == self.msaa_samples() {
+            return Ok(());
+        }
+
+        unsafe { device.device_wait_idle() }
+            .map_err(|e| AppError::vk(e, "wait idle before MSAA change"))?;
+        let previous = self.msaa_samples;
+        self.destroy_pass_objects();
+        self.msaa_samples = samples;
+        if let Err(e) = self.create_pass_objects() {
+            // Frames index the framebuffers, so never leave the pass half-built
+            warn!(
+                target: RENDERER,
+                "{samples}x MSAA setup failed, restoring {}x: {e}",
+                previous.max(1)
+            );
+            self.destroy_pass_objects();
+            self.msaa_samples = previous;
+            if let Err(restore) = self.create_pass_objects() {
+                warn!(target: RENDERER, "Restoring the render pass failed too: {restore}");
+                self.destroy_pass_objects();
+                self.initialized = false; // Frames return NotInitialized from here on
+            }
+            return Err(e);
+        }
+        info!(target: RENDERER, "✅ MSAA set to {samples}x");
+        Ok(())
+    }
+
+    /// Render targets, render pass and framebuffers for the current settings.
+    /// On error some of them may exist; `destroy_pass_objects` cleans up.
+    fn create_pass_objects(&mut self) -> Result<()> {
+        self.create_render_targets()?;
+        self.create_render_pass()?;
+        self.create_framebuffers()
+    }
+
+    /// Destroy whatever `create_pass_objects` created. The GPU must be idle.
+    fn destroy_pass_objects(&mut self) {
+        let Some(device) = &self.device else {
+            return;
+        };
+        let tracker = tracker(self.context.as_ref());
+        for fb in self.framebuffers.drain(..) {
+            unsafe { device.destroy_framebuffer(fb, None) };
+            tracker.destroyed(&[ResourceKind::Framebuffer]);
+        }
+        for target in [
+            self.msaa_target.take(),
+            self.hdr_target.take(),
+            self.depth_target.take(),
+        ]
+        .into_iter()
+        .flatten()
+        {
+            target.destroy(device, tracker);
+        }
+        if let Some(rp) = self.render_pass.take() {
+            unsafe { device.destroy_render_pass(rp, None) };
+        }
+    }
+
+    /// Scale scene color before tonemapping (1.0 = unchanged). Takes effect next
+    /// frame; ignored when tonemapping is off.
+    pub fn set_exposure(&mut self, exposure: f32) {
+        self.exposure = exposure.max(0.0);
+    }
+
+    pub fn exposure(&self) -> f32 {
+        self.exposure
+    }
+
+    /// Switch the tonemap operator at runtime (a push constant, no rebuild).
+    /// The HDR path itself is chosen at startup via `RendererConfig::tonemap`;
+    /// if it isn't active this returns `AppError::Config`.
+    pub fn set_tonemap(&mut self, mode: TonemapMode) -> Result<()> {
+        if self.tonemap.is_none() {
+            return Err(AppError::Config(
+                "tonemapping is off: start with RendererConfig::tonemap set".into(),
+            ));
+        }
+        self.tonemap_mode = mode;
+        Ok(())
+    }
+
+    /// Active tonemap operator (`None` also when the HDR path is off).
+    pub fn tonemap(&self) -> TonemapMode {
+        self.tonemap_mode
+    }
+
+    /// Format of the scene depth buffer, available after `initialize`.
+    /// Pipelines drawing into the main render pass need it for depth testing.
+    pub fn depth_format(&self) -> Option<vk::Format> {
+        self.depth_format
+    }
+
+    /// Set the rasterized line width for subsequent draws in `cmd`.
+    /// Pipelines must list `vk::DynamicState::LINE_WIDTH`. The width is clamped to
+    /// `lineWidthRange` (or 1.0 without `wideLines`) so it never trips validation;
+    /// returns the width actually applied.
+    pub fn set_line_width(&self, cmd: vk::CommandBuffer, width: f32) -> f32 {
+        let wide_lines = self.enabled_features.wide_lines == vk::TRUE;
+        let applied = clamp_line_width(width, self.limits.line_width_range, wide_lines);
+        if let Some(device) = &self.device {
+            unsafe { device.cmd_set_line_width(cmd, applied) };
+        }
+        applied
+    }
+
+    /// Set the stencil reference for subsequent draws in `cmd`, both faces.
+    /// Pipelines must list `vk::DynamicState::STENCIL_REFERENCE`; mesh pipelines
+    /// with a stencil test do, and take it from `DrawContext::set_stencil_reference`.
+    pub fn set_stencil_reference(&self, cmd: vk::CommandBuffer, reference: u32) {
+        if let Some(device) = &self.device {
+            let faces = vk::StencilFaceFlags::FRONT_AND_BACK;
+            unsafe { device.cmd_set_stencil_reference(cmd, faces, reference) };
+        }
+    }
+
+    /// Scene viewport over `scene_area()`, negative height when `flip_viewport_y`
+    /// is active.
+    pub fn viewport(&self) -> vk::Viewport {
+        scene_viewport(self.scene_area(), self.flip_viewport_y, self.depth_range)
+    }
+
+    /// Part of the swapchain image the scene covers: centered at
+    /// `RendererConfig::target_aspect` when set, else the whole image.
+    pub fn scene_area(&self) -> vk::Rect2D {
+        let extent = self.swapchain_extent.unwrap_or_default();
+        match self.config.target_aspect {
+            Some(aspect) => letterbox(extent, aspect),
+            None => vk::Rect2D::builder().extent(extent).build(),
+        }
+    }
+
+    /// Depth range of `viewport()` for the following frames; both ends in [0, 1],
+    /// `min <= max`. Out-of-range values return `AppError::Config` and keep the
+    /// current range.
+    pub fn set_depth_range(&mut self, min: f32, max: f32) -> Result<()> {
+        self.depth_range = DepthRange::new(min, max)?;
+        Ok(())
+    }
+
+    pub fn depth_range(&self) -> DepthRange {
+        self.depth_range
+    }
+
+    /// Record `viewport()` into `cmd`; pipelines must list `vk::DynamicState::VIEWPORT`.
+    pub fn set_viewport(&self, cmd: vk::CommandBuffer) {
+        if let Some(device) = &self.device {
+            unsafe { device.cmd_set_viewport(cmd, 0, &[self.viewport()]) };
+        }
+    }
+
+    /// Record a scissor clipping later draws to `rect` (clamped to the swapchain
+    /// extent); pipelines must list `vk::DynamicState::SCISSOR`.
+    pub fn set_scissor(&self, cmd: vk::CommandBuffer, rect: vk::Rect2D) {
+        if let (Some(device), Some(extent)) = (&self.device, self.swapchain_extent) {
+            let scissor = clamp_scissor(rect, extent);
+            unsafe { device.cmd_set_scissor(cmd, 0, &[scissor]) };
+        }
+    }
+
+    /// Bind `buffers` (handle, byte offset) to the vertex input bindings from
+    /// `first_binding` on in one call, e.g. positions and UVs kept in separate
+    /// buffers. The pipeline bound in `cmd` must describe each of those bindings
+    /// (see `mesh::validate_vertex_bindings`). An empty list or bindings past
+    /// `maxVertexInputBindings` return `AppError::Config`.
+    pub fn bind_vertex_buffers(
+        &self,
+        cmd: vk::CommandBuffer,
+        first_binding: u32,
+        buffers: &[(vk::Buffer, vk::DeviceSize)],
+    ) -> Result<()> {
+        let Some(device) = &self.device else {
+            return Err(AppError::NotInitialized);
+        };
+        let end = u64::from(first_binding) + buffers.len() as u64;
+        let max = self.limits.max_vertex_input_bindings;
+        if buffers.is_empty() || end > u64::from(max) {
+            return Err(AppError::Config(format!(
+                "vertex bindings {first_binding}..{end} outside the GPU's 0..{max}"
+            )));
+        }
+        let (handles, offsets) = vertex_buffer_args(buffers);
+        unsafe { device.cmd_bind_vertex_buffers(cmd, first_binding, &handles, &offsets) };
+        Ok(())
+    }
+
+    /// Whether the scene viewport is Y-flipped (front faces turn CLOCKWISE).
+    pub fn flip_viewport_y(&self) -> bool {
+        self.flip_viewport_y
+    }
+
+    /// Clip-space rotation for `RendererConfig::force_pre_transform`, see
+    /// `DrawContext::pre_rotation`.
+    pub fn pre_rotation(&self) -> [[f32; 4]; 4] {
+        pre_rotation(self.content_turns)
+    }
+
+    /// Frame timings and CPU/GPU bottleneck estimate.
+    pub fn stats(&self) -> RenderStats {
+        self.stats
+    }
+
+    /// Render one frame, calling `draw` to fill it before recording, and present it.
+    /// `draw`'s error aborts the frame before anything is submitted.
+    ///
+    /// Pipelined: the fence wait only blocks on the frame that used this slot
+    /// `MAX_FRAMES_IN_FLIGHT` frames ago, so recording overlaps GPU execution.
+    pub fn render_with(
+        &mut self,
+        draw: impl FnOnce(&mut DrawContext<'_>) -> Result<()>,
+    ) -> Result<()> {
+        let Some(mut frame) = self.begin_frame()? else {
+            return Ok(());
+        };
+        if let Err(e) = frame.draw(draw) {
+            frame.abort();
+            return Err(e);
+        }
+        frame.end_frame()
+    }
+
+    /// Start a frame, to fill with `Frame::draw` and finish with
+    /// `Frame::end_frame`, which acquires, records, submits and presents. Waits
+    /// for the frame slot first (see `render_with`). None when there is nothing
+    /// to draw into: paused, minimized or shutting down.
+    ///
+    /// The swapchain image is only acquired by `end_frame`, so a `Frame` dropped
+    /// without it holds none: the frame is skipped (and an error logged) and the
+    /// next `begin_frame` starts over on the same slot.
+    #[must_use = "the frame is skipped unless it is finished with `Frame::end_frame`"]
+    pub fn begin_frame(&mut self) -> Result<Option<Frame<'_>>> {
+        if !self.initialized {
+            return Err(AppError::NotInitialized);
+        }
+        if self.closing || self.paused {
+            return Ok(None); // Shutting down (GPU is being drained) or hidden
+        }
+        // Pace before timing the frame so stats report the capped rate
+        if let Some(limiter) = &mut self.limiter {
+            limiter.wait(&*self.config.clock);
+        }
+        let now = self.config.clock.now();
+        if self.power.poll(now) {
+            self.apply_power_saving()?;
+        }
+        // Present scaling covered the resize so far; rebuild once the size settles
+        if self
+            .resize_pending
+            .is_some_and(|resized| now - resized >= RESIZE_SETTLE)
+        {
+            self.recreate_swapchain()?;
+        }
+        let (Some(device), true) = (&self.device, self.has_swapchain()) else {
+            return Ok(None); // Not initialized (or minimized before first swapchain)
+        };
+        let Some(&frame) = self.frames.get(self.current_frame) else {
+            return Ok(None); // Frame resources not created yet
+        };
+        let frame_start = self.config.clock.now();
+
+        // Wait until the GPU has finished the previous use of this frame slot
+        let timeout = self.config.fence_timeout;
+        wait_for_fences(device, &[frame.in_flight], timeout, "wait for frame fence")?;
+        let fence_wait = self.config.clock.now() - frame_start;
+        if let Some(overdraw) = self.overdraw.take_result(device, self.current_frame) {
+            self.stats.overdraw = Some(overdraw);
+        }
+        let submitted = std::mem::take(&mut self.frame_submits[self.current_frame]);
+        if let (false, Some(pool)) = (submitted.is_empty(), self.transient_command_pool) {
+            unsafe { device.free_command_buffers(pool, &submitted) };
+        }
+
+        // Cached frame buffers bind slot 0 sets from any frame slot, so an update
+        // has to wait until no frame is in flight at all
+        if self.config.command_recording == CommandRecording::Cached
+            && self.materials.has_pending(self.current_frame)
+        {
+            unsafe { device.device_wait_idle() }
+                .map_err(|e| AppError::vk(e, "wait idle before material update"))?;
+        }
+        // The GPU is done with this slot: queued texture swaps can land, captures are done
+        self.materials.flush(device, self.current_frame);
+        self.screenshots.frame_completed(self.current_frame);
+        self.frame_draws[self.current_frame].clear();
+        Ok(Some(Frame::new(self, frame_start, fence_wait)))
+    }
+
+    /// Run `draw` on a `DrawContext` continuing `draws`, for `Frame::draw`. The
+    /// draws collected before an error are kept.
+    pub(crate) fn collect_draws(
+        &mut self,
+        draws: &mut FrameDraws,
+        draw: impl FnOnce(&mut DrawContext<'_>) -> Result<()>,
+    ) -> Result<()> {
+        let (Some(context), Some(extent)) = (&self.context, self.swapchain_extent) else {
+            return Err(AppError::NotInitialized);
+        };
+        let shader_dir = &self.config.shader_dir;
+        let area = self.scene_area();
+        let mut ctx = DrawContext::new(context, shader_dir, extent, area, self.clear_color)
+            .with_pre_rotation(self.content_turns)
+            .with_draws(std::mem::take(draws));
+        let result = draw(&mut ctx);
+        let (clear_color, collected) = ctx.finish();
+        self.clear_color = clear_color;
+        *draws = collected;
+        result
+    }
+
+    /// Record `draws` into the current frame slot, submit and present it, for
+    /// `Frame::end_frame`. Nothing is reset or acquired if the draws are invalid.
+    pub(crate) fn submit_frame(
+        &mut self,
+        draws: FrameDraws,
+        frame_start: Instant,
+        fence_wait: Duration,
+    ) -> Result<()> {
+        if self.config.command_recording == CommandRecording::Cached {
+            self.ensure_cached_commands()?;
+        }
+        let (Some(device), true) = (&self.device, self.has_swapchain()) else {
+            return Ok(()); // Swapchain lost since `begin_frame` (e.g. minimized)
+        };
+        let Some(&frame) = self.frames.get(self.current_frame) else {
+            return Ok(());
+        };
+        let timeout = self.config.fence_timeout;
+        let shader_dir = &self.config.shader_dir;
+        let area = self.scene_area();
+        // Without restart the GPU would fetch vertex 0xFFFFFFFF
+        if !self.config.primitive_restart && draws.draws.iter().any(DrawCall::uses_restart) {
+            return Err(AppError::Config(
+                "mesh uses PRIMITIVE_RESTART_INDEX but primitive_restart is off".into(),
+            ));
+        }
+        let render_pass = self.render_pass.ok_or(AppError::NotInitialized)?;
+        let samples = vk::SampleCountFlags::from_bits_truncate(self.msaa_samples.max(1));
+        let mesh_desc = self.mesh_desc();
+        let depth_format = self.depth_format.ok_or(AppError::NotInitialized)?;
+        for draw in &draws.draws {
+            let desc = draw.pipeline().unwrap_or(&mesh_desc);
+            desc.validate(&self.enabled_features, depth_format)?;
+            self.pipelines
+                .get_or_create(device, shader_dir, desc, render_pass, samples)?;
+        }
+        // Recording can't fail on a queued clear once the image is acquired and
+        // the fence reset, which would leave both unusable for the next frame
+        for clear in &draws.clears {
+            self.check_clear_target(clear.target)?;
+        }
+        let blit_usage = vk::ImageUsageFlags::TRANSFER_DST;
+        if draws.blit.is_some() && !self.swapchain_usage.contains(blit_usage) {
+            return Err(AppError::Config(
+                "surface doesn't support blits to swapchain images".into(),
+            ));
+        }
+
+        let queue = self.graphics_queue.unwrap();
+        let acquired = match (&mut self.relay, self.swapchain) {
+            // The relay hands out its stand-ins and signals the semaphore itself
+            (Some(relay), _) => Ok(relay.acquire(device, queue, frame.image_available)?),
+            (None, Some(swapchain)) => unsafe {
+                device.acquire_next_image_khr(
+                    swapchain,
+                    u64::MAX,
+                    frame.image_available,
+                    vk::Fence::null(),
+                )
+            }
+            .map(|(index, _)| index as usize),
+            (None, None) => return Ok(()),
+        };
+        let image_index = match acquired {
+            Ok(index) => index,
+            Err(vk::ErrorCode::OUT_OF_DATE_KHR) => {
+                return self.recreate_swapchain();
+            }
+            Err(vk::ErrorCode::FULL_SCREEN_EXCLUSIVE_MODE_LOST_EXT) => {
+                self.exclusive.lost();
+                return Ok(());
+            }
+            Err(e) => return Err(AppError::vk(e, "acquire swapchain image")),
+        };
 
-        // Query surface capabilities
-        let surface_caps = unsafe {
-            instance
-                .get_physical_device_surface_capabilities_khr(physical_device, surface)
-                .unwrap()
+        let counts = self.per_image_counts();
+        debug_assert_eq!(
+            counts.mismatch(),
+            None,
+            "per-image state out of sync: {counts:?}"
+        );
+
+        // An older frame may still be rendering into this image
+        let image_fence = self.images_in_flight[image_index];
+        if !image_fence.is_null() {
+            wait_for_fences(device, &[image_fence], timeout, "wait for image fence")?;
+        }
+        self.images_in_flight[image_index] = frame.in_flight;
+
+        // Readback buffers for captures requested since the last frame
+        let mut captures = match &self.context {
+            Some(context) => self.screenshots.begin_frame(
+                context,
+                self.swapchain_extent.unwrap(),
+                self.swapchain_format.unwrap(),
+                self.current_frame,
+            )?,
+            None => SmallVec::new(),
+        };
+        // A relayed frame is read back like a capture (so it's never cached)
+        if let Some(relay) = &self.relay {
+            captures.push(relay.readback(image_index));
+        }
+
+        // Cached buffers are resubmitted while the frame's signature matches;
+        // frames with work that changes every time are recorded from scratch
+        let cached = self.config.command_recording == CommandRecording::Cached
+            && captures.is_empty()
+            && draws.dispatches.is_empty()
+            && draws.blit.is_none();
+        let cmd = if cached {
+            let cmd = self.cached_commands[image_index];
+            let signature = self.frame_signature(&draws);
+            match self.record_cache.lookup(image_index, signature) {
+                Some(counts) => self.stats.draws = counts,
+                None => {
+                    let counts = self.record_frame(cmd, image_index, &captures, &draws, true)?;
+                    self.record_cache.recorded(image_index, signature, counts);
+                    // The image's previous frame has finished, so its draws can go
+                    if self.cached_draws.len() <= image_index {
+                        self.cached_draws.resize_with(image_index + 1, Vec::new);
+                    }
+                    self.cached_draws[image_index] = draws.draws.clone();
+                    self.stats.draws = counts;
+                    self.stats.recordings += 1;
+                }
+            }
+            cmd
+        } else {
+            // Counted while recording, so they cover every frame from scratch
+            self.stats.draws =
+                self.record_frame(frame.cmd, image_index, &captures, &draws, false)?;
+            self.stats.recordings += 1;
+            frame.cmd
+        };
+        self.frame_draws[self.current_frame] = draws;
+
+        // Queued `submit_with_frame` work goes first; the last batch ends with the
+        // frame and carries its semaphores and fence
+        let queued = std::mem::take(&mut self.queued_submits);
+        let batches = submit_batches(self.config.submit_policy, &queued, cmd);
+        self.frame_submits[self.current_frame] = queued;
+        let (wait_semaphores, wait_stages) = frame.submit_waits();
+        let signal_semaphores = [frame.render_finished];
+        let (last, earlier) = batches.split_last().unwrap();
+        for batch in earlier {
+            let submit_info = vk::SubmitInfo::builder().command_buffers(batch);
+            unsafe { device.queue_submit(queue, &[submit_info], vk::Fence::null()) }
+                .map_err(|e| AppError::vk(e, "submit queued commands"))?;
+        }
+        let submit_info = vk::SubmitInfo::builder()
+            .wait_semaphores(&wait_semaphores)
+            .wait_dst_stage_mask(&wait_stages)
+            .command_buffers(last)
+            .signal_semaphores(&signal_semaphores);
+        // Only reset once recording succeeded and the frame is about to be
+        // submitted; a fence reset without a submit times out the next wait
+        unsafe { device.reset_fences(&[frame.in_flight]) }
+            .map_err(|e| AppError::vk(e, "reset frame fence"))?;
+        unsafe { device.queue_submit(queue, &[submit_info], frame.in_flight) }
+            .map_err(|e| AppError::vk(e, "submit frame"))?;
+        self.stats.queue_submits = batches.len() as u32;
+        if !cached {
+            let scene = area.extent;
+            let scene_samples = u64::from(scene.width)
+                * u64::from(scene.height)
+                * u64::from(self.msaa_samples.max(1));
+            self.overdraw.submitted(self.current_frame, scene_samples);
+        }
+
+        let presented = if let Some(relay) = &mut self.relay {
+            relay.present(device, queue, frame.render_finished, image_index, timeout)?
+        } else {
+            self.queue_present(image_index, &signal_semaphores)?
+        };
+
+        self.current_frame = (self.current_frame + 1) % MAX_FRAMES_IN_FLIGHT;
+
+        let frame_time = self
+            .last_frame_start
+            .map_or(Duration::ZERO, |last| frame_start - last);
+        self.last_frame_start = Some(frame_start);
+        self.stats.record_frame(frame_time, fence_wait);
+
+        match presented {
+            Ok(vk::SuccessCode::SUBOPTIMAL_KHR) if self.suboptimal_as_forced() => Ok(()),
+            Ok(vk::SuccessCode::SUBOPTIMAL_KHR) | Err(vk::ErrorCode::OUT_OF_DATE_KHR) => {
+                self.recreate_swapchain()
+            }
+            Ok(_) => Ok(()),
+            Err(vk::ErrorCode::FULL_SCREEN_EXCLUSIVE_MODE_LOST_EXT) => {
+                self.exclusive.lost();
+                Ok(())
+            }
+            Err(e) => Err(AppError::vk(e, "present")),
+        }
+    }
+
+    /// Queue swapchain image `image_index` for presentation once `wait` signals.
+    /// The present's own result is returned for the caller to act on.
+    fn queue_present(
+        &mut self,
+        image_index: usize,
+        wait: &[vk::Semaphore],
+    ) -> Result<VkResult<vk::SuccessCode>> {
+        let (Some(device), Some(swapchain)) = (&self.device, self.swapchain) else {
+            return Err(AppError::NotInitialized);
+        };
+        let timeout = self.config.fence_timeout;
+
+        // With swapchain_maintenance1 the present signals a fence once it's done
+        // with the image, letting recreation skip device_wait_idle
+        let present_fence = match &mut self.present_fences {
+            Some(fences) => Some(fences.acquire(device, self.current_frame, timeout)?),
+            None => None,
+        };
+        let fences = [present_fence.unwrap_or_default()];
+        let mut fence_info = vk::SwapchainPresentFenceInfoEXT::builder().fences(&fences);
+
+        let swapchains = [swapchain];
+        let image_indices = [image_index as u32];
+        let mut present_info = vk::PresentInfoKHR::builder()
+            .wait_semaphores(wait)
+            .swapchains(&swapchains)
+            .image_indices(&image_indices);
+        if present_fence.is_some() {
+            present_info = present_info.push_next(&mut fence_info);
+        }
+        let presented =
+            unsafe { device.queue_present_khr(self.present_queue.unwrap(), &present_info) };
+        // Out-of-date presents still signal; other failures never queued anything
+        if let (Err(e), Some(fences)) = (presented, &mut self.present_fences)
+            && e != vk::ErrorCode::OUT_OF_DATE_KHR
+        {
+            fences.abandon(device, self.current_frame);
+        }
+        Ok(presented)
+    }
+
+    /// Whether a SUBOPTIMAL present only reports the forced pre-transform: the
+    /// surface's transform is still the one the swapchain was made against, so
+    /// recreating would build the same swapchain, SUBOPTIMAL again, every frame.
+    /// A rotated display still gets a new swapchain.
+    fn suboptimal_as_forced(&self) -> bool {
+        let (Some(expected), Some(instance), Some(physical_device), Some(surface)) = (
+            self.forced_mismatch,
+            &self.instance,
+            self.presenting_gpu(),
+            self.surface,
+        ) else {
+            return false;
         };
+        unsafe { instance.get_physical_device_surface_capabilities_khr(physical_device, surface) }
+            .is_ok_and(|caps| caps.current_transform == expected)
+    }
+
+    /// Take exclusive fullscreen (Windows, `RendererConfig::full_screen_exclusive`),
+    /// bypassing the compositor. The window should already cover its monitor.
+    /// Kept across swapchain recreation; dropped while the window is unfocused
+    /// and taken back when it regains focus.
+    pub fn acquire_full_screen_exclusive(&mut self) -> Result<()> {
+        let (Some(device), Some(swapchain)) = (&self.device, self.swapchain) else {
+            return Err(AppError::NotInitialized);
+        };
+        self.exclusive.acquire(device, swapchain)
+    }
+
+    /// Leave exclusive fullscreen until the next `acquire_full_screen_exclusive`.
+    pub fn release_full_screen_exclusive(&mut self) -> Result<()> {
+        let (Some(device), Some(swapchain)) = (&self.device, self.swapchain) else {
+            return Err(AppError::NotInitialized);
+        };
+        self.exclusive.release(device, swapchain)
+    }
+
+    /// Whether the current swapchain holds exclusive fullscreen.
+    pub fn is_full_screen_exclusive(&self) -> bool {
+        self.exclusive.is_acquired()
+    }
+
+    /// Upload a mesh for `DrawContext::draw_mesh`; freed once dropped and no
+    /// frame in flight still draws it.
+    pub fn create_mesh(&self, vertices: &[Vertex], indices: &[u32]) -> Result<Mesh> {
+        let context = self.context.as_ref().ok_or(AppError::NotInitialized)?;
+        Mesh::new(context, vertices, indices)
+    }
+
+    /// Id (`DrawContext::set_pick_id`) of the nearest mesh drawn at window position
+    /// (`x`, `y`) in physical pixels, e.g. from `WindowEvent::CursorMoved`; 0 for
+    /// the background. Replays the last rendered frame's draws into a 1x1 id
+    /// target and blocks until the pixel is read back.
+    pub fn pick(&mut self, x: u32, y: u32) -> Result<u32> {
+        let (Some(context), Some(extent), Some(depth_format)) =
+            (&self.context, self.swapchain_extent, self.depth_format)
+        else {
+            return Err(AppError::NotInitialized);
+        };
+        let Some(pixel) = window_to_framebuffer(x, y, self.window_extent, extent) else {
+            return Err(AppError::Config(format!(
+                "pick position ({x}, {y}) is outside the window"
+            )));
+        };
+        // The slot recorded last; `render_with` advances `current_frame` after it
+        let last = (self.current_frame + MAX_FRAMES_IN_FLIGHT - 1) % MAX_FRAMES_IN_FLIGHT;
+        if self.frame_draws[last].draws.is_empty() {
+            return Ok(0);
+        }
+        if self.pick.is_none() {
+            let shader_dir = &self.config.shader_dir;
+            self.pick = Some(PickPass::new(
+                context,
+                shader_dir,
+                depth_format,
+                &self.mesh_desc,
+            )?);
+            info!(target: RENDERER, "✅ Pick pass created");
+        }
+
+        let pass = self.pick.as_ref().unwrap();
+        let draws = &self.frame_draws[last].draws;
+        let viewport = self.viewport();
+        let mut recorded = Ok(());
+        self.immediate_submit(|device, cmd| {
+            recorded = pass.record(device, cmd, draws, viewport, pixel);
+        })?;
+        recorded?;
+        pass.read()
+    }
+
+    /// Clear `rect` of `attachment` in the scene subpass of `cmd`, e.g. the depth
+    /// of a HUD's area so it draws on top. Only valid while `cmd` records that
+    /// subpass; `DrawContext::clear_attachment` queues one at the right spot.
+    pub fn clear_attachment(
+        &self,
+        cmd: vk::CommandBuffer,
+        attachment: ClearTarget,
+        clear_value: impl Into<vk::ClearValue>,
+        rect: vk::Rect2D,
+    ) -> Result<()> {
+        let Some(device) = &self.device else {
+            return Err(AppError::NotInitialized);
+        };
+        if self.scene_pass.get() != Some(cmd) {
+            return Err(AppError::Config(
+                "clear_attachment outside the scene render pass".into(),
+            ));
+        }
+        self.check_clear_target(attachment)?;
+        // Nothing left inside the framebuffer; an empty rect is invalid to clear
+        let rect = clamp_scissor(rect, self.swapchain_extent.unwrap_or_default());
+        if rect.extent.width == 0 || rect.extent.height == 0 {
+            return Ok(());
+        }
+        let (attachment, rect) = clear_attachment_info(attachment, clear_value.into(), rect);
+        unsafe { device.cmd_clear_attachments(cmd, &[attachment], &[rect]) };
+        Ok(())
+    }
+
+    /// Whether the scene subpass has `target` to clear.
+    fn check_clear_target(&self, target: ClearTarget) -> Result<()> {
+        let stencil = target.aspect().contains(vk::ImageAspectFlags::STENCIL);
+        if stencil && !self.depth_format.is_some_and(has_stencil) {
+            return Err(AppError::Config(
+                "clear_attachment: the depth format has no stencil".into(),
+            ));
+        }
+        if let ClearTarget::Color(index) = target
+            && index > 0
+        {
+            return Err(AppError::Config(format!(
+                "clear_attachment: the scene subpass has no color attachment {index}"
+            )));
+        }
+        Ok(())
+    }
 
-        // Query supported formats
-        let surface_formats = unsafe {
-            instance
-                .get_physical_device_surface_formats_khr(physical_device, surface)
-                .unwrap()
-        };
+    /// Color the swapchain image is cleared to each frame.
+    pub fn set_clear_color(&mut self, color: [f32; 4]) {
+        self.clear_color = color;
+    }
 
-        // Prefer SRGB, fallback to first format
-        let format = surface_formats
-            .iter()
-            .find(|f| f.format == vk::Format::B8G8R8A8_SRGB)
-            .unwrap_or(&surface_formats[0]);
+    /// Logical device, available after `initialize` (for custom render passes etc).
+    pub fn device(&self) -> Option<&Device> {
+        self.device.as_ref()
+    }
 
-        // Pick swapchain resolution (use current_extent if fixed)
-        let extent = match surface_caps.current_extent.width {
-            std::u32::MAX => vk::Extent2D {
-                width: 800,
-                height: 600,
-            },
-            _ => surface_caps.current_extent,
+    /// Sampler for `config`; identical configs return the same cached handle.
+    /// The renderer owns it: never destroy it yourself.
+    pub fn create_sampler(&mut self, config: SamplerConfig) -> Result<vk::Sampler> {
+        let Some(device) = &self.device else {
+            return Err(AppError::NotInitialized);
         };
+        let max_anisotropy = (self.enabled_features.sampler_anisotropy == vk::TRUE)
+            .then_some(self.limits.max_sampler_anisotropy);
+        self.samplers.get_or_create(device, config, max_anisotropy)
+    }
 
-        // Query present modes
-        let present_modes = unsafe {
-            instance
-                .get_physical_device_surface_present_modes_khr(physical_device, surface)
-                .unwrap()
+    /// Create the mesh pipelines of `descriptions` now (e.g. during a loading
+    /// screen) instead of on the first frame drawing with them, which would
+    /// hitch. Already created ones are kept, so calling it again is cheap.
+    pub fn precompile_pipelines(&mut self, descriptions: &[PipelineDesc]) -> Result<()> {
+        let (Some(device), Some(render_pass)) = (&self.device, self.render_pass) else {
+            return Err(AppError::NotInitialized);
         };
+        let samples = vk::SampleCountFlags::from_bits_truncate(self.msaa_samples.max(1));
+        let shader_dir = &self.config.shader_dir;
+        let depth_format = self.depth_format.ok_or(AppError::NotInitialized)?;
+        for desc in descriptions {
+            desc.validate(&self.enabled_features, depth_format)?;
+            self.pipelines
+                .get_or_create(device, shader_dir, desc, render_pass, samples)?;
+        }
+        info!(
+            target: RENDERER,
+            "✅ {} pipeline(s) precompiled, {} in the cache",
+            descriptions.len(),
+            self.pipelines.len()
+        );
+        Ok(())
+    }
 
-        // Prefer MAILBOX (triple buffering), else fallback to FIFO (vsync)
-        let present_mode = if present_modes.contains(&vk::PresentModeKHR::MAILBOX) {
-            vk::PresentModeKHR::MAILBOX
-        } else {
-            vk::PresentModeKHR::FIFO
+    /// Mesh pipelines created so far, built-in one included.
+    pub fn pipeline_count(&self) -> usize {
+        self.pipelines.len()
+    }
+
+    /// Rebuild the built-in mesh pipeline from the config's graphics state
+    /// (topology, polygon and cull mode), for state pipelines can't take
+    /// dynamically. Waits for the GPU, destroys the old pipeline and creates
+    /// the new one; the pick pass follows on its next use.
+    pub fn recreate_pipeline(&mut self) -> Result<()> {
+        let (Some(device), Some(render_pass)) = (&self.device, self.render_pass) else {
+            return Err(AppError::NotInitialized);
         };
+        let desc = PipelineDesc::from_config(&self.config);
+        unsafe { device.device_wait_idle() }
+            .map_err(|e| AppError::vk(e, "wait idle before pipeline recreation"))?;
+        self.pipelines.remove(device, &self.mesh_desc);
+        self.pick = None;
+        self.record_cache.invalidate();
+
+        let samples = vk::SampleCountFlags::from_bits_truncate(self.msaa_samples.max(1));
+        let shader_dir = &self.config.shader_dir;
+        self.pipelines
+            .get_or_create(device, shader_dir, &desc, render_pass, samples)?;
+        info!(
+            target: RENDERER,
+            "✅ Mesh pipeline recreated ({:?}, {:?}, cull {:?})",
+            desc.topology,
+            desc.polygon_mode,
+            desc.cull_mode
+        );
+        self.mesh_desc = desc;
+        Ok(())
+    }
 
-        let _queue_family_indices = self.queue_family_indices.unwrap();
+    /// Handle of the built-in mesh pipeline, None until the first draw (or
+    /// `recreate_pipeline`) created it.
+    pub fn mesh_pipeline(&self) -> Option<vk::Pipeline> {
+        self.pipelines
+            .get(&self.mesh_desc)
+            .map(MeshPipeline::pipeline)
+    }
 
-        // Request one more image than minimum if possible
-        let mut image_count = surface_caps.min_image_count + 1;
-        if surface_caps.max_image_count > 0 && image_count > surface_caps.max_image_count {
-            image_count = surface_caps.max_image_count;
+    /// Draw meshes filled, as wireframe or as points. Line / Point need the
+    /// `fillModeNonSolid` feature, else this returns `AppError::Config` and
+    /// keeps the current mode.
+    pub fn set_polygon_mode(&mut self, mode: PolygonMode) -> Result<()> {
+        let non_solid = self.enabled_features.fill_mode_non_solid == vk::TRUE;
+        if mode != PolygonMode::Fill && self.device.is_some() && !non_solid {
+            return Err(AppError::Config(format!(
+                "{mode:?} polygon mode needs fillModeNonSolid, unsupported by this GPU"
+            )));
         }
+        if mode == self.config.polygon_mode {
+            return Ok(());
+        }
+        self.config.polygon_mode = mode;
+        self.apply_graphics_state()
+    }
 
-        // Swapchain creation info
-        let swapchain_info = vk::SwapchainCreateInfoKHR::builder()
-            .surface(surface)
-            .min_image_count(image_count)
-            .image_format(format.format)
-            .image_color_space(format.color_space)
-            .image_extent(extent)
-            .image_array_layers(1)
-            .image_usage(vk::ImageUsageFlags::COLOR_ATTACHMENT)
-            .image_sharing_mode(vk::SharingMode::EXCLUSIVE)
-            .pre_transform(surface_caps.current_transform)
-            .composite_alpha(vk::CompositeAlphaFlagsKHR::OPAQUE)
-            .present_mode(present_mode)
-            .clipped(true);
+    pub fn polygon_mode(&self) -> PolygonMode {
+        self.config.polygon_mode
+    }
 
-        // Create swapchain
-        let swapchain = unsafe { device.create_swapchain_khr(&swapchain_info, None) }
-            .expect("Failed to create swapchain");
+    pub fn set_cull_mode(&mut self, mode: CullMode) -> Result<()> {
+        if mode == self.config.cull_mode {
+            return Ok(());
+        }
+        self.config.cull_mode = mode;
+        self.apply_graphics_state()
+    }
 
-        // Retrieve swapchain images
-        let images_raw = unsafe { device.get_swapchain_images_khr(swapchain).unwrap() };
+    pub fn cull_mode(&self) -> CullMode {
+        self.config.cull_mode
+    }
 
-        // Store images
-        let mut images: SmallVec<[vk::Image; 4]> = SmallVec::with_capacity(images_raw.len());
-        images.extend_from_slice(&images_raw);
+    /// Assemble meshes as `topology` from the next frame on; `primitive_restart`
+    /// needs a strip topology, else this returns `AppError::Config`.
+    pub fn set_topology(&mut self, topology: Topology, primitive_restart: bool) -> Result<()> {
+        if primitive_restart && !topology.is_strip() {
+            return Err(AppError::Config(format!(
+                "primitive_restart needs a strip topology, got {topology:?}"
+            )));
+        }
+        let config = &self.config;
+        if (topology, primitive_restart) == (config.topology, config.primitive_restart) {
+            return Ok(());
+        }
+        self.config.topology = topology;
+        self.config.primitive_restart = primitive_restart;
+        self.apply_graphics_state()
+    }
 
-        // Create image views for each swapchain image
-        let mut image_views: SmallVec<[vk::ImageView; 4]> = SmallVec::with_capacity(images.len());
-        for &image in &images {
-            let view_info = vk::ImageViewCreateInfo::builder()
-                .image(image)
-                .view_type(vk::ImageViewType::_2D)
-                .format(format.format)
-                .components(vk::ComponentMapping::default())
-                .subresource_range(
-                    vk::ImageSubresourceRange::builder()
-                        .aspect_mask(vk::ImageAspectFlags::COLOR)
-                        .base_mip_level(0)
-                        .level_count(1)
-                        .base_array_layer(0)
-                        .layer_count(1)
-                        .build(),
-                );
+    /// Record a copy of tightly packed texels in `buffer` into mip 0 / layer 0 of
+    /// the color `image` (in TRANSFER_DST_OPTIMAL). For other mips, layers or
+    /// aspects use `transfer::copy_buffer_to_image` with an explicit subresource.
+    pub fn copy_buffer_to_image(
+        &self,
+        cmd: vk::CommandBuffer,
+        buffer: vk::Buffer,
+        image: vk::Image,
+        width: u32,
+        height: u32,
+    ) {
+        if let Some(device) = &self.device {
+            let extent = vk::Extent2D { width, height };
+            copy_buffer_to_image(device, cmd, buffer, image, extent, color_layers());
+        }
+    }
 
-            let view = unsafe { device.create_image_view(&view_info, None).unwrap() };
-            image_views.push(view);
+    /// Capture the next presented frame without stalling: the copy is recorded
+    /// into that frame and completes with its fence. Poll `is_screenshot_ready`,
+    /// then fetch the pixels with `take_screenshot`.
+    pub fn screenshot_async(&mut self) -> Result<ScreenshotToken> {
+        if self.context.is_none() {
+            return Err(AppError::NotInitialized);
+        }
+        if !self
+            .swapchain_usage
+            .contains(vk::ImageUsageFlags::TRANSFER_SRC)
+        {
+            return Err(AppError::Config(
+                "surface doesn't support TRANSFER_SRC swapchain images, can't capture".into(),
+            ));
         }
+        Ok(self.screenshots.request())
+    }
 
-        // Save swapchain state
-        self.swapchain = Some(swapchain);
-        self.swapchain_images = images;
-        self.swapchain_image_views = image_views;
-        self.swapchain_format = Some(format.format);
-        self.swapchain_extent = Some(extent);
+    /// Whether the capture behind `token` has finished on the GPU.
+    pub fn is_screenshot_ready(&mut self, token: ScreenshotToken) -> bool {
+        if self.screenshots.is_ready(token) {
+            return true;
+        }
+        // Submitted but its slot not reused yet: ask the fence directly
+        let (Some(device), Some(slot)) = (&self.device, self.screenshots.submitted_slot(token))
+        else {
+            return false;
+        };
+        let fence = self.frames[slot].in_flight;
+        if let Ok(vk::SuccessCode::SUCCESS) = unsafe { device.get_fence_status(fence) } {
+            self.screenshots.mark_ready(token);
+            return true;
+        }
+        false
+    }
 
-        info!("✅ Swapchain and image views created!");
+    /// Pixels of a finished capture (consumed), `Ok(None)` while it's still pending.
+    pub fn take_screenshot(&mut self, token: ScreenshotToken) -> Result<Option<Screenshot>> {
+        if !self.is_screenshot_ready(token) {
+            return Ok(None);
+        }
+        self.screenshots.take(token)
     }
 
-    /// Creates a render pass for rendering into the swapchain images.
-    fn create_render_pass(&mut self) {
-        let device = self.device.as_ref().unwrap();
-        let format = self.swapchain_format.unwrap();
+    /// Material with one descriptor set per frame in flight, all showing `texture`.
+    /// `layout` must have a COMBINED_IMAGE_SAMPLER at binding 0.
+    pub fn create_material(
+        &mut self,
+        layout: vk::DescriptorSetLayout,
+        texture: MaterialTexture,
+    ) -> Result<MaterialId> {
+        let mut sets = [vk::DescriptorSet::null(); MAX_FRAMES_IN_FLIGHT];
+        for set in &mut sets {
+            *set = self.allocate_descriptor_set(layout)?;
+        }
+        let device = self.device.as_ref().ok_or(AppError::NotInitialized)?;
+        Ok(self.materials.insert(device, sets, texture))
+    }
 
-        // Single color attachment (the swapchain image)
-        let color_attachment = vk::AttachmentDescription::builder()
-            .format(format)
-            .samples(vk::SampleCountFlags::_1)
-            .load_op(vk::AttachmentLoadOp::CLEAR)
-            .store_op(vk::AttachmentStoreOp::STORE)
-            .stencil_load_op(vk::AttachmentLoadOp::DONT_CARE)
-            .stencil_store_op(vk::AttachmentStoreOp::DONT_CARE)
-            .initial_layout(vk::ImageLayout::UNDEFINED)
-            .final_layout(vk::ImageLayout::PRESENT_SRC_KHR);
+    /// Swap the texture of `material` without touching sets of frames in flight:
+    /// each frame slot's set is rewritten once that slot's fence has signaled.
+    pub fn set_material_texture(
+        &mut self,
+        material: MaterialId,
+        texture: MaterialTexture,
+    ) -> Result<()> {
+        if self.materials.set_texture(material, texture) {
+            Ok(())
+        } else {
+            Err(AppError::Config(format!("unknown material {material:?}")))
+        }
+    }
 
-        // Reference to attachment in subpass
-        let color_attachment_ref = vk::AttachmentReference::builder()
-            .attachment(0)
-            .layout(vk::ImageLayout::COLOR_ATTACHMENT_OPTIMAL);
+    /// Descriptor set of `material` for the frame being recorded.
+    pub fn material_set(&self, material: MaterialId) -> Option<vk::DescriptorSet> {
+        self.materials.set(material, self.current_frame)
+    }
 
-        // Subpass that writes to the color attachment
-        let subpass = vk::SubpassDescription::builder()
-            .pipeline_bind_point(vk::PipelineBindPoint::GRAPHICS)
-            .color_attachments(std::slice::from_ref(&color_attachment_ref));
+    /// Shared device owner, available after `initialize`.
+    /// Clones keep the device alive past the renderer; see `Texture` / `Buffer`.
+    pub fn context(&self) -> Option<&Arc<DeviceContext>> {
+        self.context.as_ref()
+    }
 
-        // Render pass creation info
-        let render_pass_info = vk::RenderPassCreateInfo::builder()
-            .attachments(std::slice::from_ref(&color_attachment))
-            .subpasses(std::slice::from_ref(&subpass));
+    /// Create a device-local image that frees itself on Drop.
+    pub fn create_texture(&self, desc: ImageDesc) -> Result<Texture> {
+        let context = self.context.as_ref().ok_or(AppError::NotInitialized)?;
+        Texture::new(context, desc)
+    }
 
-        // Create render pass
-        let render_pass = unsafe { device.create_render_pass(&render_pass_info, None) }
-            .expect("Failed to create render pass");
+    /// Call `progress` with each phase of `initialize` as it starts, e.g. to
+    /// drive a loading screen. Set it before `initialize`.
+    pub fn set_init_progress(&mut self, progress: impl FnMut(InitPhase, usize) + 'static) {
+        self.init_progress = Some(Box::new(progress));
+    }
 
-        self.render_pass = Some(render_pass);
-        info!("✅ Render pass created!");
+    /// Log `phase` and hand it to the progress callback.
+    fn report_phase(&mut self, phase: InitPhase) {
+        let index = phase.index();
+        info!(target: RENDERER, "Initializing {}/{}: {phase}", index + 1, InitPhase::COUNT);
+        if let Some(progress) = &mut self.init_progress {
+            progress(phase, index);
+        }
     }
 
-    /// Creates one framebuffer per swapchain image.
-    fn create_framebuffers(&mut self) {
-        let device = self.device.as_ref().unwrap();
-        let render_pass = self.render_pass.unwrap();
-        let extent = self.swapchain_extent.unwrap();
+    /// Format of the swapchain images, available after `initialize`.
+    pub fn swapchain_format(&self) -> Option<vk::Format> {
+        self.swapchain_format
+    }
 
-        let mut framebuffers: SmallVec<[vk::Framebuffer; 4]> =
-            SmallVec::with_capacity(self.swapchain_image_views.len());
+    /// Present mode of the current swapchain (FIFO while `power_saving` holds
+    /// MAILBOX back).
+    pub fn present_mode(&self) -> Option<vk::PresentModeKHR> {
+        self.has_swapchain().then_some(self.present_mode)
+    }
 
-        for &view in &self.swapchain_image_views {
-            let attachments = [view];
-            let framebuffer_info = vk::FramebufferCreateInfo::builder()
-                .render_pass(render_pass)
-                .attachments(&attachments)
-                .width(extent.width)
-                .height(extent.height)
-                .layers(1);
+    /// Why the current swapchain doesn't use the configured present mode, if it
+    /// doesn't; logged as well whenever it changes.
+    pub fn present_mode_fallback(&self) -> Option<PresentModeFallback> {
+        self.present_fallback.filter(|_| self.has_swapchain())
+    }
 
-            let fb = unsafe { device.create_framebuffer(&framebuffer_info, None) }
-                .expect("Failed to create framebuffer");
-            framebuffers.push(fb);
+    /// Advance to the next subpass of the render pass being recorded in `cmd`.
+    pub fn next_subpass(&self, cmd: vk::CommandBuffer) {
+        if let Some(device) = &self.device {
+            unsafe { device.cmd_next_subpass(cmd, vk::SubpassContents::INLINE) };
         }
+    }
 
-        self.framebuffers = framebuffers;
-
-        info!("✅ Framebuffers created!");
+    /// Allocate a descriptor set, transparently chaining a new pool when full.
+    pub fn allocate_descriptor_set(
+        &mut self,
+        layout: vk::DescriptorSetLayout,
+    ) -> Result<vk::DescriptorSet> {
+        let (Some(device), Some(allocator)) = (&self.device, &mut self.descriptor_allocator)
+        else {
+            return Err(AppError::NotInitialized);
+        };
+        allocator.allocate(device, layout)
     }
 }
 
 impl Renderer for VulkanRenderer {
+    fn new(config: RendererConfig) -> Self {
+        Self {
+            limiter: config.max_fps.and_then(FrameLimiter::new),
+            power: PowerSaver::new(config.power_saving),
+            mesh_desc: PipelineDesc::from_config(&config),
+            config,
+            exposure: 1.0,
+            ..Default::default()
+        }
+    }
+
     /// Initialize Vulkan: create instance, device, swapchain, render pass, etc.
     fn initialize(&mut self, window: &Window, _event_loop: &ActiveEventLoop) -> Result<()> {
+        let size = window.inner_size();
+        self.window_extent = vk::Extent2D {
+            width: size.width,
+            height: size.height,
+        };
+
+        self.report_phase(InitPhase::Instance);
+
         // Load Vulkan library
-        let loader = unsafe { LibloadingLoader::new(LIBRARY) }?;
+        // A bad path surfaces as AppError::Lib; the loader never panics here
+        let loader = match &self.config.vulkan_library {
+            Some(path) => {
+                info!(target: RENDERER, "Loading Vulkan from {}", path.display());
+                unsafe { LibloadingLoader::new(path) }?
+            }
+            None => unsafe { LibloadingLoader::new(LIBRARY) }?,
+        };
         let entry = unsafe { Entry::new(loader) }?;
 
         // Query required instance extensions from winit
@@ -326,12 +2620,54 @@ impl Renderer for VulkanRenderer {
                 .map(|e| e.as_ptr())
                 .collect();
 
-        // Add debug utils extension in debug builds
-        #[cfg(debug_assertions)]
+        // Add debug utils extension in debug builds (unless `debug-utils` is off)
+        #[cfg(all(debug_assertions, feature = "debug-utils"))]
         {
             exts.push(vk::EXT_DEBUG_UTILS_EXTENSION.name.as_ptr());
         }
 
+        // Needed to query driver properties on 1.0 instances (harmless on newer ones)
+        let has_props2 = unsafe { entry.enumerate_instance_extension_properties(None) }
+            .map(|props| {
+                props.iter().any(|e| {
+                    e.extension_name == vk::KHR_GET_PHYSICAL_DEVICE_PROPERTIES2_EXTENSION.name
+                })
+            })
+            .unwrap_or(false);
+        if has_props2 {
+            exts.push(vk::KHR_GET_PHYSICAL_DEVICE_PROPERTIES2_EXTENSION.name.as_ptr());
+        }
+
+        // Prerequisites of VK_EXT_swapchain_maintenance1 (present fences)
+        let surface_maintenance1 = unsafe { entry.enumerate_instance_extension_properties(None) }
+            .map(|props| {
+                [
+                    vk::KHR_GET_SURFACE_CAPABILITIES2_EXTENSION.name,
+                    vk::EXT_SURFACE_MAINTENANCE1_EXTENSION.name,
+                ]
+                .iter()
+                .all(|name| props.iter().any(|e| e.extension_name == *name))
+            })
+            .unwrap_or(false);
+        if surface_maintenance1 {
+            exts.push(vk::KHR_GET_SURFACE_CAPABILITIES2_EXTENSION.name.as_ptr());
+            exts.push(vk::EXT_SURFACE_MAINTENANCE1_EXTENSION.name.as_ptr());
+        }
+
+        // Richer surface queries (also a prerequisite of VK_EXT_full_screen_exclusive)
+        let surface_capabilities2 = surface_maintenance1
+            || unsafe { entry.enumerate_instance_extension_properties(None) }
+                .map(|props| {
+                    props.iter().any(|e| {
+                        e.extension_name == vk::KHR_GET_SURFACE_CAPABILITIES2_EXTENSION.name
+                    })
+                })
+                .unwrap_or(false);
+        if surface_capabilities2 && !surface_maintenance1 {
+            exts.push(vk::KHR_GET_SURFACE_CAPABILITIES2_EXTENSION.name.as_ptr());
+        }
+        self.surface_query = SurfaceQuery::select(surface_capabilities2);
+
         // On macOS, require portability extension
         #[cfg(target_os = "macos")]
         exts.push(vk::KHR_PORTABILITY_ENUMERATION_EXTENSION.name.as_ptr());
@@ -341,7 +2677,7 @@ impl Renderer for VulkanRenderer {
         let has_validation_layer = unsafe {
             entry
                 .enumerate_instance_layer_properties()
-                .unwrap()
+                .map_err(|e| AppError::vk(e, "enumerate instance layers"))?
                 .iter()
                 .any(|p| {
                     CStr::from_ptr(p.layer_name.as_ptr()).to_bytes()
@@ -358,8 +2694,28 @@ impl Renderer for VulkanRenderer {
 
         #[cfg(debug_assertions)]
         if has_validation_layer {
-            layer_pointers.push(b"VK_LAYER_KHRONOS_validation\0".as_ptr() as *const i8);
-            info!("✅ Validation layer enabled");
+            layer_pointers.push(VALIDATION_LAYER.as_ptr() as *const i8);
+            info!(target: RENDERER, "✅ Validation layer enabled");
+        }
+
+        // Synchronization validation comes through VK_EXT_validation_features,
+        // which the layer itself provides
+        #[cfg(all(debug_assertions, feature = "debug-utils"))]
+        let sync_validation = self.config.debug.sync_validation
+            && has_validation_layer
+            && unsafe { entry.enumerate_instance_extension_properties(Some(VALIDATION_LAYER)) }
+                .map(|props| {
+                    props
+                        .iter()
+                        .any(|e| e.extension_name == vk::EXT_VALIDATION_FEATURES_EXTENSION.name)
+                })
+                .unwrap_or(false);
+        #[cfg(all(debug_assertions, feature = "debug-utils"))]
+        if sync_validation {
+            exts.push(vk::EXT_VALIDATION_FEATURES_EXTENSION.name.as_ptr());
+            info!(target: RENDERER, "✅ Synchronization validation enabled");
+        } else if self.config.debug.sync_validation {
+            warn!(target: RENDERER, "Synchronization validation needs the validation layer");
         }
 
         // macOS portability flag
@@ -370,27 +2726,22 @@ impl Renderer for VulkanRenderer {
         }
 
         // Query supported Vulkan version
-        let supported = unsafe {
-            entry
-                .enumerate_instance_version()
-                .expect("Failed to query Vulkan instance version")
-        };
+        let supported = unsafe { entry.enumerate_instance_version() }
+            .map_err(|e| AppError::vk(e, "query instance version"))?;
 
-        // Application info
-        let app_info = vk::ApplicationInfo::builder()
-            .application_name(b"Wolf Engine\0")
-            .engine_name(b"Wolf Engine\0")
-            .api_version(supported);
+        // Application info (names validated NUL-free by RendererConfig::validate)
+        let (app_name, engine_name) = application_names(&self.config)?;
+        let app_info = application_info(&self.config, &app_name, &engine_name, supported);
 
         // Instance creation info
-        #[cfg(debug_assertions)]
+        #[cfg(all(debug_assertions, feature = "debug-utils"))]
         let mut create_info = vk::InstanceCreateInfo::builder()
             .application_info(&app_info)
             .enabled_extension_names(&exts)
             .enabled_layer_names(&layer_pointers)
             .flags(flags);
 
-        #[cfg(not(debug_assertions))]
+        #[cfg(not(all(debug_assertions, feature = "debug-utils")))]
         let create_info = vk::InstanceCreateInfo::builder()
             .application_info(&app_info)
             .enabled_extension_names(&exts)
@@ -398,28 +2749,64 @@ impl Renderer for VulkanRenderer {
             .flags(flags);
 
         // --- Debug messenger setup now lives in helper fns ---
-        #[cfg(debug_assertions)]
-        let mut debug_ci = build_debug_messenger_ci();
-        #[cfg(debug_assertions)]
+        #[cfg(all(debug_assertions, feature = "debug-utils"))]
+        let debug_log = open_debug_sink(&self.config.debug)?;
+        #[cfg(all(debug_assertions, feature = "debug-utils"))]
+        let mut debug_ci = build_debug_messenger_ci(debug_log.as_deref());
+        #[cfg(all(debug_assertions, feature = "debug-utils"))]
         {
             create_info = create_info.push_next(&mut debug_ci);
         }
+        #[cfg(all(debug_assertions, feature = "debug-utils"))]
+        let enabled_validation = [vk::ValidationFeatureEnableEXT::SYNCHRONIZATION_VALIDATION];
+        #[cfg(all(debug_assertions, feature = "debug-utils"))]
+        let mut validation_features =
+            vk::ValidationFeaturesEXT::builder().enabled_validation_features(&enabled_validation);
+        #[cfg(all(debug_assertions, feature = "debug-utils"))]
+        if sync_validation {
+            create_info = create_info.push_next(&mut validation_features);
+        }
 
         // Create Vulkan instance
-        let instance =
-            unsafe { entry.create_instance(&create_info, None) }.expect("vkCreateInstance failed");
-        info!("🎉 Vulkan instance ready");
-
-        // Create debug messenger in debug builds (using helper)
-        #[cfg(debug_assertions)]
-        let debug = Some(create_debug_messenger(&instance, &debug_ci));
+        let instance = unsafe { entry.create_instance(&create_info, None) }
+            .map_err(|e| AppError::vk(e, instance_error_context(e)))?;
+        info!(target: RENDERER, "🎉 Vulkan instance ready");
+
+        // Create debug messenger in debug builds with `debug-utils` (using helper)
+        #[cfg(all(debug_assertions, feature = "debug-utils"))]
+        let debug = match create_debug_messenger(&instance, &debug_ci) {
+            Ok(messenger) => Some(messenger),
+            Err(e) => {
+                unsafe { instance.destroy_instance(None) };
+                return Err(e);
+            }
+        };
 
-        #[cfg(not(debug_assertions))]
-        let debug = None;
+        // Nothing owns the instance and surface until the device context does, so
+        // every early return below tears them down first (a null surface is a no-op)
+        let fail = |e: AppError, surface: vk::SurfaceKHR| {
+            unsafe {
+                instance.destroy_surface_khr(surface, None);
+                #[cfg(all(debug_assertions, feature = "debug-utils"))]
+                if let Some(debug) = &debug {
+                    destroy_debug_messenger(&instance, debug);
+                }
+                instance.destroy_instance(None);
+            }
+            e
+        };
 
         // Create window surface
-        let window_handle = window.window_handle().unwrap();
-        let display_handle = window.display_handle().unwrap();
+        self.report_phase(InitPhase::Surface);
+        let handles = window
+            .window_handle()
+            .and_then(|w| Ok((w, window.display_handle()?)));
+        let (window_handle, display_handle) = handles.map_err(|e| {
+            fail(
+                AppError::Surface(format!("window handle: {e}")),
+                vk::SurfaceKHR::null(),
+            )
+        })?;
         let surface = unsafe {
             vk_window::create_surface(
                 &instance,
@@ -427,55 +2814,171 @@ impl Renderer for VulkanRenderer {
                 &window_handle as &dyn HasWindowHandle,
             )
         }
-        .expect("Failed to create Vulkan surface");
+        .map_err(|e| {
+            fail(
+                AppError::vk(e, "create window surface"),
+                vk::SurfaceKHR::null(),
+            )
+        })?;
 
         // Pick physical device + queue families
+        self.report_phase(InitPhase::Device);
         let devices = unsafe { instance.enumerate_physical_devices() }
-            .expect("Failed to enumerate physical devices");
-        let (physical_device, graphics_family, present_family) = devices
+            .map_err(|e| fail(AppError::vk(e, "enumerate physical devices"), surface))?;
+        let candidates: SmallVec<[GpuCandidate; 4]> = devices
             .iter()
-            .find_map(|&dev| {
+            .map(|&dev| {
                 let props = unsafe { instance.get_physical_device_queue_family_properties(dev) };
-                let mut graphics_index = None;
-                let mut present_index = None;
-                for (i, info) in props.iter().enumerate() {
-                    if info.queue_flags.contains(vk::QueueFlags::GRAPHICS) {
-                        graphics_index = Some(i as u32);
-                    }
-                    let present_support = unsafe {
-                        instance
-                            .get_physical_device_surface_support_khr(dev, i as u32, surface)
-                            .unwrap()
-                    };
-                    if present_support {
-                        present_index = Some(i as u32);
-                    }
-                }
-                if let (Some(g), Some(p)) = (graphics_index, present_index) {
-                    Some((dev, g, p))
-                } else {
-                    None
+                let families: SmallVec<[(vk::QueueFlags, bool); 8]> = props
+                    .iter()
+                    .enumerate()
+                    .map(|(i, info)| {
+                        let present_support = unsafe {
+                            instance.get_physical_device_surface_support_khr(dev, i as u32, surface)
+                        };
+                        (info.queue_flags, present_support.unwrap_or(false))
+                    })
+                    .collect();
+                let (graphics_family, present_family) = pick_queue_families(&families);
+                GpuCandidate {
+                    device_type: unsafe { instance.get_physical_device_properties(dev) }
+                        .device_type,
+                    graphics_family,
+                    present_family,
                 }
             })
-            .expect("No suitable GPU found");
+            .collect();
+        let Some(path) = select_gpu(&candidates, self.config.prefer_integrated) else {
+            let e = AppError::Surface("no GPU can both render and present to this window".into());
+            return Err(fail(e, surface));
+        };
+        // Hybrid graphics: the render GPU's stand-in images use VK_KHR_swapchain's layouts
+        let has_swapchain = |i: usize| {
+            unsafe { instance.enumerate_device_extension_properties(devices[i], None) }.is_ok_and(
+                |exts| {
+                    exts.iter()
+                        .any(|e| e.extension_name == vk::KHR_SWAPCHAIN_EXTENSION.name)
+                },
+            )
+        };
+        let (chosen, relay_gpu) = device_plan(path, has_swapchain);
+        if let PresentPath::CrossDevice { render, present } = path {
+            let name = |i: usize| DeviceInfo::query(&instance, devices[i], has_props2).name;
+            if relay_gpu.is_some() {
+                info!(
+                    target: RENDERER,
+                    "{} can't present to this window (hybrid graphics?), copying frames to {}",
+                    name(render),
+                    name(present)
+                );
+            } else {
+                warn!(
+                    target: RENDERER,
+                    "{} can't present to this window or copy to another GPU, rendering on {}",
+                    name(render),
+                    name(present)
+                );
+            }
+        }
+        let physical_device = devices[chosen];
+        // `select_gpu` only returns presenting GPUs, besides the render GPU of a
+        // relay, which then needs just its graphics family
+        let families = match relay_gpu {
+            Some(_) => candidates[chosen]
+                .graphics_family
+                .map(|family| (family, family)),
+            None => candidates[chosen].families(),
+        };
+        let (graphics_family, present_family) = families.ok_or_else(|| {
+            fail(
+                AppError::Surface("chosen GPU can't present".into()),
+                surface,
+            )
+        })?;
+
+        // Report GPU + driver for bug triage
+        let device_info = DeviceInfo::query(&instance, physical_device, has_props2);
+        self.has_props2 = has_props2;
+        device_info.log();
 
         // Enable device extensions (always need swapchain, maybe portability)
-        let has_portability_subset = unsafe {
-            instance
-                .enumerate_device_extension_properties(physical_device, None)
-                .expect("Failed to enumerate device extensions")
+        let device_extensions =
+            unsafe { instance.enumerate_device_extension_properties(physical_device, None) }
+                .map_err(|e| fail(AppError::vk(e, "enumerate device extensions"), surface))?;
+        let has_device_extension = |name: &CStr| {
+            device_extensions
                 .iter()
-                .any(|e| {
-                    CStr::from_ptr(e.extension_name.as_ptr())
-                        == KHR_PORTABILITY_SUBSET_EXTENSION_NAME
-                })
+                .any(|e| unsafe { CStr::from_ptr(e.extension_name.as_ptr()) } == name)
         };
+        let has_portability_subset = has_device_extension(KHR_PORTABILITY_SUBSET_EXTENSION_NAME);
 
-        let mut device_exts: SmallVec<[*const i8; 4]> = SmallVec::new();
+        let mut device_exts: SmallVec<[*const i8; 8]> = SmallVec::new();
         device_exts.push(vk::KHR_SWAPCHAIN_EXTENSION.name.as_ptr());
         if has_portability_subset {
             device_exts.push(KHR_PORTABILITY_SUBSET_EXTENSION_NAME.as_ptr());
-            info!("✅ VK_KHR_portability_subset enabled");
+            info!(target: RENDERER, "✅ VK_KHR_portability_subset enabled");
+        }
+
+        // Negative viewport heights: core in 1.1 (if both instance and device are 1.1+),
+        // otherwise VK_KHR_maintenance1
+        let mut flip_viewport_y = false;
+        if self.config.flip_viewport_y {
+            let version = supported.min(device_info.api_version);
+            if vk::version_major(version) > 1 || vk::version_minor(version) >= 1 {
+                flip_viewport_y = true;
+            } else if device_extensions
+                .iter()
+                .any(|e| e.extension_name == vk::KHR_MAINTENANCE1_EXTENSION.name)
+            {
+                device_exts.push(vk::KHR_MAINTENANCE1_EXTENSION.name.as_ptr());
+                flip_viewport_y = true;
+                info!(target: RENDERER, "✅ VK_KHR_maintenance1 enabled");
+            } else {
+                warn!(
+                    target: RENDERER,
+                    "flip_viewport_y needs Vulkan 1.1 or VK_KHR_maintenance1, keeping Y down"
+                );
+            }
+        }
+
+        // Extensions with a features struct, chained into device creation
+        let mut requested_features: SmallVec<[FeatureExtension; 4]> = SmallVec::new();
+        if surface_maintenance1 {
+            requested_features.push(FeatureExtension::SwapchainMaintenance1);
+        }
+        let device_version = supported.min(device_info.api_version);
+        let mut features =
+            FeatureChain::new(&requested_features, &device_extensions, device_version);
+        features.query(&instance, physical_device, has_props2);
+        device_exts.extend(features.extension_names());
+
+        // Present fences: lets swapchain recreation wait only for the old images
+        // (relayed frames are never presented from this device)
+        let swapchain_retire = match relay_gpu {
+            Some(_) => SwapchainRetire::WaitIdle,
+            None => select_retire_mode(
+                &device_extensions,
+                surface_maintenance1 && has_props2,
+                features.contains(FeatureExtension::SwapchainMaintenance1),
+            ),
+        };
+        if swapchain_retire == SwapchainRetire::PresentFences {
+            info!(target: RENDERER, "✅ VK_EXT_swapchain_maintenance1 enabled (present fences)");
+        }
+
+        // Exclusive fullscreen: swapchains get created application-controlled
+        let requested = self.config.full_screen_exclusive;
+        self.exclusive.enabled = relay_gpu.is_none()
+            && select_exclusive(requested, &device_extensions, surface_capabilities2);
+        if self.exclusive.enabled {
+            device_exts.push(vk::EXT_FULL_SCREEN_EXCLUSIVE_EXTENSION.name.as_ptr());
+            self.exclusive.monitor = window_monitor(window);
+            info!(target: RENDERER, "✅ VK_EXT_full_screen_exclusive enabled");
+        } else if requested {
+            info!(
+                target: RENDERER,
+                "full_screen_exclusive requested but unsupported, using borderless"
+            );
         }
 
         // Setup queue creation (graphics + present)
@@ -498,47 +3001,395 @@ impl Renderer for VulkanRenderer {
             );
         }
 
+        // Optional features: only enable what was requested and is supported
+        let supported_features = unsafe { instance.get_physical_device_features(physical_device) };
+        let limits = unsafe { instance.get_physical_device_properties(physical_device) }.limits;
+        let mut enabled_features = vk::PhysicalDeviceFeatures::default();
+        if self.config.wide_lines {
+            if supported_features.wide_lines == vk::TRUE {
+                enabled_features.wide_lines = vk::TRUE;
+                info!(
+                    target: RENDERER,
+                    "✅ wideLines enabled (range {:?})",
+                    limits.line_width_range
+                );
+            } else {
+                info!(target: RENDERER, "wideLines requested but unsupported, lines stay 1px");
+            }
+        }
+        // Anisotropic filtering is opt-in per sampler, so enable it whenever available
+        enabled_features.sampler_anisotropy = supported_features.sampler_anisotropy;
+        // Same for Line / Point polygon modes, which `set_polygon_mode` may pick later
+        enabled_features.fill_mode_non_solid = supported_features.fill_mode_non_solid;
+        // And for the extra stages a `PipelineDesc` may add (`PipelineDesc::validate`)
+        enabled_features.geometry_shader = supported_features.geometry_shader;
+        enabled_features.tessellation_shader = supported_features.tessellation_shader;
+        // Exact sample counts for `RenderStats::overdraw`, which is None without them
+        enabled_features.occlusion_query_precise = supported_features.occlusion_query_precise;
+        if supported_features.occlusion_query_precise != vk::TRUE {
+            info!(target: RENDERER, "occlusionQueryPrecise unsupported, no overdraw estimate");
+        }
+        if self.config.polygon_mode != PolygonMode::Fill
+            && supported_features.fill_mode_non_solid != vk::TRUE
+        {
+            warn!(
+                target: RENDERER,
+                "{:?} polygon mode needs fillModeNonSolid, drawing filled",
+                self.config.polygon_mode
+            );
+            self.config.polygon_mode = PolygonMode::Fill;
+            self.mesh_desc = PipelineDesc::from_config(&self.config);
+        }
+
         // Create logical device
-        let device_create_info = vk::DeviceCreateInfo::builder()
+        let mut device_create_info = vk::DeviceCreateInfo::builder()
             .queue_create_infos(&queue_create_infos)
-            .enabled_extension_names(&device_exts);
+            .enabled_extension_names(&device_exts)
+            .enabled_features(&enabled_features);
+        device_create_info = features.extend(device_create_info);
 
         let device = unsafe { instance.create_device(physical_device, &device_create_info, None) }
-            .expect("Failed to create logical device");
+            .map_err(|e| fail(AppError::vk(e, "create logical device"), surface))?;
 
         // Retrieve queues
         let graphics_queue = unsafe { device.get_device_queue(graphics_family, 0) };
         let present_queue = unsafe { device.get_device_queue(present_family, 0) };
 
         // Save state
-        self.entry = Some(entry);
-        self.instance = Some(instance);
-        self.debug = debug; // Debug messenger in debug builds
+        let context = DeviceContext::new(entry, instance, device, physical_device);
+        #[cfg(all(debug_assertions, feature = "debug-utils"))]
+        let context = context.with_debug_messenger(debug, debug_log);
+        let context = Arc::new(context);
+        context.set_memory_pressure(self.memory_pressure.take());
+        self.instance = Some(context.instance.clone());
         self.surface = Some(surface);
         self.physical_device = Some(physical_device);
+        self.device_info = Some(device_info);
+        self.limits = limits;
+        self.enabled_features = enabled_features;
+        self.flip_viewport_y = flip_viewport_y;
+        self.swapchain_retire = swapchain_retire;
         self.queue_family_indices = Some((graphics_family, present_family));
-        self.device = Some(device);
+        self.device = Some(context.device.clone());
+        self.context = Some(context);
         self.graphics_queue = Some(graphics_queue);
         self.present_queue = Some(present_queue);
+        self.descriptor_allocator = Some(DescriptorAllocator::new(
+            self.config.max_descriptor_sets,
+            self.config.descriptor_pool_sizes,
+        ));
+
+        // Hybrid graphics: a device on the presenting GPU takes the frames over
+        if let Some(present) = relay_gpu
+            && let (Some((_, family)), Some(context)) =
+                (candidates[present].families(), &self.context)
+        {
+            let relay = Relay::new(
+                &context.instance,
+                &context.device,
+                devices[present],
+                family,
+                context.resources(),
+            )?;
+            self.relay = Some(relay);
+        }
+
+        // Use the requested MSAA level, or the highest supported one below it
+        let supported = sample_counts_from_limits(&limits);
+        self.msaa_samples = supported
+            .iter()
+            .copied()
+            .filter(|&n| n <= self.config.msaa)
+            .max()
+            .unwrap_or(1);
+        if self.msaa_samples != self.config.msaa {
+            warn!(
+                target: RENDERER,
+                "{}x MSAA unsupported (supported: {supported:?}), using {}x",
+                self.config.msaa, self.msaa_samples
+            );
+        }
+
+        // HDR path: only when requested, renderable on this GPU and its shaders are built
+        if self.config.tonemap != TonemapMode::None {
+            let instance = self.instance.as_ref().unwrap();
+            let device = self.device.as_ref().unwrap();
+            let hdr_format = RenderTargetFormat::Hdr.resolve(|format| unsafe {
+                instance.get_physical_device_format_properties(physical_device, format)
+            });
+            match hdr_format.and_then(|hdr_format| {
+                TonemapPass::new(device, &self.config.shader_dir).map(|pass| (pass, hdr_format))
+            }) {
+                Ok((pass, hdr_format)) => {
+                    info!(target: RENDERER, "✅ HDR format: {hdr_format:?}");
+                    self.tonemap = Some(pass);
+                    self.tonemap_mode = self.config.tonemap;
+                    self.hdr_format = Some(hdr_format);
+                }
+                Err(e) => warn!(target: RENDERER, "Tonemapping disabled: {e}"),
+            }
+        }
+
+        // Depth format: first candidate the GPU can use as a depth attachment
+        let instance = self.instance.as_ref().unwrap();
+        let depth_target = if self.config.stencil {
+            RenderTargetFormat::DepthStencil
+        } else {
+            RenderTargetFormat::Depth
+        };
+        let depth_format = depth_target.resolve(|format| unsafe {
+            instance.get_physical_device_format_properties(physical_device, format)
+        })?;
+        info!(target: RENDERER, "✅ Depth format: {depth_format:?}");
+        self.depth_format = Some(depth_format);
 
         // Continue with swapchain/rendering setup
-        self.create_swapchain();
-        self.create_render_pass();
-        self.create_framebuffers();
+        self.report_phase(InitPhase::Swapchain);
+        self.create_swapchain()?;
+        self.report_phase(InitPhase::RenderTargets);
+        self.create_render_targets()?;
+        self.report_phase(InitPhase::RenderPass);
+        self.create_render_pass()?;
+        self.report_phase(InitPhase::Framebuffers);
+        self.create_framebuffers()?;
+        self.report_phase(InitPhase::FrameResources);
+        self.create_frame_resources()?;
+
+        // A relay's render GPU can't query the surface, the presenting one reports it
+        let mut capabilities = Capabilities::query(
+            self.instance.as_ref().unwrap(),
+            physical_device,
+            has_props2,
+            self.surface.filter(|_| self.relay.is_none()),
+        );
+        self.relay_surface_support(&mut capabilities.gpu);
+        // A mismatch here is a bug in the setup above, so stop loudly in debug builds
+        let checked = capabilities.check_active(
+            self.swapchain_format.unwrap(),
+            depth_format,
+            self.msaa_samples.max(1),
+        );
+        debug_assert!(checked.is_ok(), "{checked:?}");
+        self.capabilities = Some(capabilities);
+        self.initialized = true;
+        self.report_phase(InitPhase::Ready);
         Ok(())
     }
 
-    /// Handle window events (currently just close)
+    /// Handle window events (close, resize)
     fn window_event(&mut self, event_loop: &ActiveEventLoop, _id: WindowId, event: &WindowEvent) {
-        if matches!(event, WindowEvent::CloseRequested) {
-            event_loop.exit();
+        match event {
+            WindowEvent::CloseRequested => {
+                // Stop submitting, then let in-flight frames drain so `cleanup`
+                // never destroys semaphores/fences the GPU is still using
+                self.closing = true;
+                self.wait_for_frames_in_flight();
+                event_loop.exit();
+            }
+            WindowEvent::Resized(size) => {
+                self.window_extent = vk::Extent2D {
+                    width: size.width,
+                    height: size.height,
+                };
+                // Minimized windows report 0x0; nothing to present into
+                let visible = size.width > 0 && size.height > 0;
+                if visible && self.present_scaling {
+                    // The driver scales the old images meanwhile, see `render_with`
+                    self.resize_pending = Some(self.config.clock.now());
+                } else if visible
+                    && self.has_swapchain()
+                    && let Err(e) = self.recreate_swapchain()
+                {
+                    self.pending_error.get_or_insert(e);
+                }
+            }
+            WindowEvent::Focused(focused) => {
+                if let (Some(device), Some(swapchain)) = (&self.device, self.swapchain) {
+                    self.exclusive.set_focused(device, swapchain, *focused);
+                }
+                if self.power.set_focused(*focused)
+                    && let Err(e) = self.apply_power_saving()
+                {
+                    self.pending_error.get_or_insert(e);
+                }
+            }
+            _ => {}
         }
     }
 
-    /// Render one frame (currently empty placeholder)
+    /// Render one frame: clear the next swapchain image and present it.
     fn render(&mut self) -> Result<()> {
+        self.render_with(|_| Ok(()))
+    }
+
+    fn is_initialized(&self) -> bool {
+        self.initialized
+    }
+
+    fn pause(&mut self) {
+        if !self.paused {
+            self.paused = true;
+            info!(target: RENDERER, "Rendering paused, window hidden");
+        }
+    }
+
+    /// Recreates the swapchain if the surface changed size while hidden; an
+    /// out-of-date swapchain the extent check misses is caught by the next acquire.
+    fn resume(&mut self) -> Result<()> {
+        if !std::mem::take(&mut self.paused) {
+            return Ok(());
+        }
+        info!(target: RENDERER, "Rendering resumed");
+        // The hidden stretch isn't a frame time
+        self.last_frame_start = None;
+        let (Some(instance), Some(physical_device), Some(surface), Some(extent)) = (
+            &self.instance,
+            self.presenting_gpu(),
+            self.surface,
+            self.swapchain_extent,
+        ) else {
+            return Ok(());
+        };
+        let extras = SurfaceQueryExtras::default();
+        let caps = query_surface_capabilities(
+            instance,
+            physical_device,
+            surface,
+            self.surface_query,
+            extras,
+        )?
+        .base;
+        if swapchain_stale(caps.current_extent, extent, self.window_extent) {
+            self.recreate_swapchain()?;
+        }
         Ok(())
     }
+
+    fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Lives in the device context once initialized, so it goes away with
+    /// `cleanup`.
+    fn set_memory_pressure(&mut self, callback: MemoryPressure) {
+        match &self.context {
+            Some(context) => context.set_memory_pressure(Some(callback)),
+            None => self.memory_pressure = Some(callback),
+        }
+    }
+
+    fn take_error(&mut self) -> Option<AppError> {
+        self.pending_error.take()
+    }
+}
+
+/// Context for a failed `vkCreateInstance`. Broken or missing drivers are the
+/// usual first-run failure, so point the user at them instead of the API call.
+fn instance_error_context(code: vk::ErrorCode) -> &'static str {
+    match code {
+        vk::ErrorCode::INITIALIZATION_FAILED => {
+            "create Vulkan instance: the driver failed to initialize, try updating your GPU drivers"
+        }
+        vk::ErrorCode::INCOMPATIBLE_DRIVER => {
+            "create Vulkan instance: no compatible Vulkan driver, try updating your GPU drivers"
+        }
+        vk::ErrorCode::LAYER_NOT_PRESENT | vk::ErrorCode::EXTENSION_NOT_PRESENT => {
+            "create Vulkan instance: a requested layer or extension is not installed"
+        }
+        _ => "create Vulkan instance",
+    }
+}
+
+/// Leak tracker of `context`, or the detached one before a device exists.
+fn tracker(context: Option<&Arc<DeviceContext>>) -> &ResourceTracker {
+    context.map_or(ResourceTracker::detached(), |context| context.resources())
+}
+
+/// One color view per swapchain (or relay stand-in) image; none are left on failure.
+fn create_swapchain_views(
+    device: &Device,
+    images: &[vk::Image],
+    format: vk::Format,
+    tracker: &ResourceTracker,
+) -> Result<SmallVec<[vk::ImageView; 4]>> {
+    let mut image_views: SmallVec<[vk::ImageView; 4]> = SmallVec::with_capacity(images.len());
+    for &image in images {
+        let view_info = vk::ImageViewCreateInfo::builder()
+            .image(image)
+            .view_type(vk::ImageViewType::_2D)
+            .format(format)
+            .components(vk::ComponentMapping::default())
+            .subresource_range(
+                vk::ImageSubresourceRange::builder()
+                    .aspect_mask(vk::ImageAspectFlags::COLOR)
+                    .base_mip_level(0)
+                    .level_count(1)
+                    .base_array_layer(0)
+                    .layer_count(1)
+                    .build(),
+            );
+
+        match unsafe { device.create_image_view(&view_info, None) } {
+            Ok(view) => {
+                image_views.push(view);
+                tracker.created(&[ResourceKind::ImageView]);
+            }
+            Err(e) => {
+                for view in image_views {
+                    unsafe { device.destroy_image_view(view, None) };
+                    tracker.destroyed(&[ResourceKind::ImageView]);
+                }
+                return Err(AppError::vk(e, "create swapchain image view"));
+            }
+        }
+    }
+    Ok(image_views)
+}
+
+/// Record `cmd` once, submit it to `queue` and block on a temporary fence.
+fn submit_and_wait(
+    device: &Device,
+    queue: vk::Queue,
+    cmd: vk::CommandBuffer,
+    timeout: Duration,
+    record: impl FnOnce(&Device, vk::CommandBuffer),
+) -> Result<()> {
+    unsafe {
+        let begin_info = vk::CommandBufferBeginInfo::builder()
+            .flags(vk::CommandBufferUsageFlags::ONE_TIME_SUBMIT);
+        device
+            .begin_command_buffer(cmd, &begin_info)
+            .map_err(|e| AppError::vk(e, "begin immediate command buffer"))?;
+        record(device, cmd);
+        device
+            .end_command_buffer(cmd)
+            .map_err(|e| AppError::vk(e, "end immediate command buffer"))?;
+    }
+    submit_recorded_and_wait(device, queue, &[cmd], timeout, "immediate submit")
+}
+
+/// Submit the already recorded `cmds` in one batch and block until they finished.
+fn submit_recorded_and_wait(
+    device: &Device,
+    queue: vk::Queue,
+    cmds: &[vk::CommandBuffer],
+    timeout: Duration,
+    context: &'static str,
+) -> Result<()> {
+    unsafe {
+        let fence = device
+            .create_fence(&vk::FenceCreateInfo::builder(), None)
+            .map_err(|e| AppError::vk(e, "create submit fence"))?;
+        let submit_info = vk::SubmitInfo::builder().command_buffers(cmds);
+        let waited = device
+            .queue_submit(queue, &[submit_info], fence)
+            .and_then(|_| device.wait_for_fences(&[fence], true, timeout_nanos(timeout)));
+        // On timeout the fence is still in use, so it's leaked rather than destroyed
+        if waited != Ok(vk::SuccessCode::TIMEOUT) {
+            device.destroy_fence(fence, None);
+        }
+        check_fence_wait(waited, timeout, context)
+    }
 }
 
 impl Drop for VulkanRenderer {
@@ -550,55 +3401,60 @@ impl Drop for VulkanRenderer {
 }
 
 //
-// ===== Debug Utils helpers (only compiled in debug builds) =====
+// ===== Object names & command labels (no-ops without `debug-utils`) =====
 //
 
-#[cfg(debug_assertions)]
-unsafe extern "system" fn debug_callback(
-    sev: vk::DebugUtilsMessageSeverityFlagsEXT,
-    ty: vk::DebugUtilsMessageTypeFlagsEXT,
-    data: *const vk::DebugUtilsMessengerCallbackDataEXT,
-    _ud: *mut std::ffi::c_void,
-) -> vk::Bool32 {
-    // Convert C string to Rust string
-    let message = unsafe { std::ffi::CStr::from_ptr((*data).message).to_string_lossy() };
-
-    // Log with appropriate severity
-    if sev.contains(vk::DebugUtilsMessageSeverityFlagsEXT::ERROR) {
-        error!("[{ty:?}] {message}");
-    } else if sev.contains(vk::DebugUtilsMessageSeverityFlagsEXT::WARNING) {
-        warn!("[{ty:?}] {message}");
-    } else {
-        info!("[{ty:?}] {message}");
-    }
-    vk::FALSE
-}
+#[cfg(all(debug_assertions, feature = "debug-utils"))]
+impl VulkanRenderer {
+    /// Attach a human-readable name to a Vulkan handle (shown by RenderDoc/validation).
+    pub fn set_object_name(&self, object_type: vk::ObjectType, handle: u64, name: &str) {
+        let (Some(instance), Some(device), Ok(name)) =
+            (&self.instance, &self.device, CString::new(name))
+        else {
+            return;
+        };
+        let info = vk::DebugUtilsObjectNameInfoEXT::builder()
+            .object_type(object_type)
+            .object_handle(handle)
+            .object_name(name.as_bytes_with_nul());
+        unsafe { instance.set_debug_utils_object_name_ext(device.handle(), &info) }.ok();
+    }
 
-#[cfg(debug_assertions)]
-fn build_debug_messenger_ci() -> vk::DebugUtilsMessengerCreateInfoEXTBuilder<'static> {
-    vk::DebugUtilsMessengerCreateInfoEXT::builder()
-        .message_severity(
-            vk::DebugUtilsMessageSeverityFlagsEXT::WARNING
-                | vk::DebugUtilsMessageSeverityFlagsEXT::ERROR,
-        )
-        .message_type(
-            vk::DebugUtilsMessageTypeFlagsEXT::GENERAL
-                | vk::DebugUtilsMessageTypeFlagsEXT::VALIDATION
-                | vk::DebugUtilsMessageTypeFlagsEXT::PERFORMANCE,
-        )
-        .user_callback(Some(debug_callback))
-}
+    /// Open a named region in a command buffer, tinted `color` in capture tools
+    /// (RenderDoc, Nsight); None takes the palette color of `name`, if any.
+    pub fn push_debug_label(&self, cmd: vk::CommandBuffer, name: &str, color: Option<[f32; 4]>) {
+        let color = color
+            .or_else(|| self.label_colors.get(name).copied())
+            .unwrap_or_default();
+        let (Some(instance), Ok(name)) = (&self.instance, CString::new(name)) else {
+            return;
+        };
+        let label = debug_label(&name, color);
+        unsafe { instance.cmd_begin_debug_utils_label_ext(cmd, &label) };
+    }
 
-#[cfg(debug_assertions)]
-fn create_debug_messenger(
-    instance: &Instance,
-    ci: &vk::DebugUtilsMessengerCreateInfoEXT,
-) -> vk::DebugUtilsMessengerEXT {
-    unsafe { instance.create_debug_utils_messenger_ext(ci, None) }
-        .expect("debug utils messenger")
+    /// Color every later `push_debug_label(name, None)` region, e.g. red for UI
+    /// and green for the world.
+    pub fn set_debug_utils_label_color(&mut self, name: &str, color: [f32; 4]) {
+        self.label_colors.insert(name.to_owned(), color);
+    }
+
+    /// Close the region opened by the last `push_debug_label`.
+    pub fn pop_debug_label(&self, cmd: vk::CommandBuffer) {
+        if let Some(instance) = &self.instance {
+            unsafe { instance.cmd_end_debug_utils_label_ext(cmd) };
+        }
+    }
 }
 
-#[cfg(debug_assertions)]
-fn destroy_debug_messenger(instance: &Instance, messenger: &vk::DebugUtilsMessengerEXT) {
-    unsafe { instance.destroy_debug_utils_messenger_ext(*messenger, None) };
+#[cfg(not(all(debug_assertions, feature = "debug-utils")))]
+impl VulkanRenderer {
+    pub fn set_object_name(&self, _object_type: vk::ObjectType, _handle: u64, _name: &str) {}
+
+    pub fn push_debug_label(&self, _cmd: vk::CommandBuffer, _name: &str, _color: Option<[f32; 4]>) {
+    }
+
+    pub fn set_debug_utils_label_color(&mut self, _name: &str, _color: [f32; 4]) {}
+
+    pub fn pop_debug_label(&self, _cmd: vk::CommandBuffer) {}
 }
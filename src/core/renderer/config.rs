@@ -0,0 +1,903 @@
+// src/core/renderer/config.rs
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::core::renderer::time::{Clock, RealClock, TimeSource};
+use crate::core::renderer::validation::ValidationLog;
+use crate::error::{AppError, Result};
+
+/// Renderer settings consumed by `Renderer::new`.
+/// Prefer building it through `RendererBuilder`, which validates on `build()`.
+#[derive(Debug, Clone)]
+pub struct RendererConfig {
+    pub app_name: String,      // Reported to the driver in ApplicationInfo
+    pub app_version: u32,      // Same, packed with `pack_version`
+    pub engine_name: String,   // Same; drivers key per-application workarounds on these
+    pub engine_version: u32,   // Same, `ENGINE_VERSION` by default
+    pub msaa: u32,             // MSAA sample count (1 = off)
+    pub vsync: bool,           // Capped to the display rate (see `present_preferences`)
+    pub max_fps: Option<u32>,  // CPU-side frame cap on top of the present mode (None = off)
+    pub power_saving: bool,    // Vsync while unfocused / on battery (`battery` feature)
+    pub wide_lines: bool,      // Enable `wideLines` (line widths != 1.0) if the GPU has it
+    pub flip_viewport_y: bool, // Negative-height scene viewport: NDC +Y up, like OpenGL
+    pub tonemap: TonemapMode,  // HDR → SDR operator; anything but None renders to HDR first
+    pub shader_dir: PathBuf,   // Compiled SPIR-V for built-in passes (`*.spv`)
+    pub sort_draws: bool,      // Record draws grouped by pipeline, then material
+
+    // Rank integrated GPUs above discrete ones when picking the device (battery
+    // life on laptops; see `selection::device_type_rank`)
+    pub prefer_integrated: bool,
+
+    // Keep the scene at this width / height ratio, centered, with `bar_color`
+    // filling the rest of the window (None = use the whole window)
+    pub target_aspect: Option<f32>,
+    pub bar_color: [f32; 4],
+
+    // How mesh indices are assembled into primitives; restart (index 0xFFFFFFFF
+    // starts a new strip) is only valid for strip topologies
+    pub topology: Topology,
+    pub primitive_restart: bool,
+
+    // Rasterization of the mesh pipeline; Line / Point need the
+    // `fillModeNonSolid` feature
+    pub polygon_mode: PolygonMode,
+    pub cull_mode: CullMode,
+    // Depth buffer with a stencil aspect, for pipelines with a `StencilState`
+    pub stencil: bool,
+
+    // Present mode overriding vsync's platform default (see
+    // `present_preferences`); unsupported modes fall back to FIFO
+    pub present_mode: Option<PresentMode>,
+
+    // Longest wait on a GPU fence (frame, present, upload) before giving up with
+    // AppError::GpuTimeout; a hung GPU would otherwise freeze the app
+    pub fence_timeout: Duration,
+
+    // Where frame pacing, frame stats and resize settling read the time from
+    // (a `FakeClock` makes them deterministic in tests)
+    pub clock: Clock,
+
+    // How work queued with `VulkanRenderer::submit_with_frame` reaches the queue
+    pub submit_policy: SubmitPolicy,
+    // Re-record frame command buffers every frame, or only when the frame changes
+    pub command_recording: CommandRecording,
+
+    // Let the presentation engine scale images to the window while it is being
+    // resized, recreating the swapchain once the size settles (needs
+    // VK_EXT_swapchain_maintenance1; None = recreate on every resize)
+    pub present_scaling: Option<ScalingMode>,
+
+    // How the compositor blends the window with what's behind it; anything but
+    // Opaque needs a transparent window (`WindowConfig::transparent`)
+    pub composite_alpha: CompositeAlpha,
+
+    // Swapchain pre-transform instead of the one the surface reports, for
+    // physically rotated panels (kiosks); the scene is rotated to match, see
+    // `DrawContext::pre_rotation`. Ignored with a warning if unsupported
+    pub force_pre_transform: Option<SurfaceTransform>,
+
+    // Create the swapchain able to take exclusive fullscreen (Windows with
+    // VK_EXT_full_screen_exclusive; ignored elsewhere)
+    pub full_screen_exclusive: bool,
+
+    // Vulkan loader to dlopen instead of the platform default (sandboxes, bundled SDKs)
+    pub vulkan_library: Option<PathBuf>,
+
+    // Descriptor pools: sets per pool, and descriptors of each type per set
+    pub max_descriptor_sets: u32,
+    pub descriptor_pool_sizes: DescriptorPoolSizes,
+
+    pub debug: DebugConfig, // Diagnostics (debug builds with `debug-utils`)
+}
+
+/// Operator mapping the HDR scene into the display range.
+/// The discriminant is what the tonemap shader receives as `mode`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TonemapMode {
+    #[default]
+    None = 0, // Clamp only; as the startup mode, skips the HDR target entirely
+    Reinhard = 1, // c / (1 + c)
+    Aces = 2,     // Narkowicz ACES filmic fit
+}
+
+/// Primitive topology of the mesh pipeline's input assembly.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Topology {
+    #[default]
+    TriangleList,
+    TriangleStrip,
+    LineList,
+    LineStrip,
+    PointList, // Shaders must write gl_PointSize (mesh.vert does)
+}
+
+impl Topology {
+    pub fn is_strip(self) -> bool {
+        matches!(self, Self::TriangleStrip | Self::LineStrip)
+    }
+}
+
+/// How the mesh pipeline fills triangles.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum PolygonMode {
+    #[default]
+    Fill,
+    Line,  // Wireframe: triangle edges only
+    Point, // Triangle vertices only
+}
+
+/// Triangles the mesh pipeline discards. Front faces are counter-clockwise in
+/// framebuffer coordinates; `flip_viewport_y` reverses the winding.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum CullMode {
+    #[default]
+    None,
+    Front,
+    Back,
+}
+
+/// Comparison of a stencil test: reference value against the stored one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CompareOp {
+    Never,
+    Less,
+    Equal,
+    LessOrEqual,
+    Greater,
+    NotEqual,
+    GreaterOrEqual,
+    Always,
+}
+
+/// What a stencil test writes into the stencil buffer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StencilOp {
+    Keep,
+    Zero,
+    Replace, // The reference value
+    IncrementAndClamp,
+    DecrementAndClamp,
+    Invert,
+    IncrementAndWrap,
+    DecrementAndWrap,
+}
+
+/// Stencil test of a mesh pipeline, the same for front and back faces. The
+/// reference value is dynamic (`DrawContext::set_stencil_reference`); the depth
+/// buffer needs a stencil aspect (`RendererBuilder::stencil`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct StencilState {
+    pub compare: CompareOp,    // reference & compare_mask vs stored & compare_mask
+    pub fail: StencilOp,       // Stencil test failed
+    pub pass: StencilOp,       // Stencil and depth test passed
+    pub depth_fail: StencilOp, // Stencil passed, depth failed
+    pub compare_mask: u8,
+    pub write_mask: u8,
+}
+
+impl StencilState {
+    /// Always passes and writes the reference where drawn: marks the pixels an
+    /// object covers.
+    pub const fn write() -> Self {
+        Self {
+            compare: CompareOp::Always,
+            fail: StencilOp::Keep,
+            pass: StencilOp::Replace,
+            depth_fail: StencilOp::Keep,
+            compare_mask: 0xFF,
+            write_mask: 0xFF,
+        }
+    }
+
+    /// Passes where the stored value isn't the reference and writes nothing:
+    /// draws around what `write` marked, e.g. an outline.
+    pub const fn not_equal() -> Self {
+        Self {
+            compare: CompareOp::NotEqual,
+            fail: StencilOp::Keep,
+            pass: StencilOp::Keep,
+            depth_fail: StencilOp::Keep,
+            compare_mask: 0xFF,
+            write_mask: 0,
+        }
+    }
+}
+
+/// How finished frames reach the display.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PresentMode {
+    Fifo,        // Vsync, never tears; the one mode every surface supports
+    FifoRelaxed, // Vsync, but a late frame shows at once (a tear instead of a stutter)
+    Mailbox,     // Uncapped, the newest frame shows at the next vblank, no tearing
+    Immediate,   // Uncapped, tears
+}
+
+/// OS whose compositors the default present modes are tuned for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Platform {
+    Linux,
+    Windows,
+    MacOs,
+    Other,
+}
+
+impl Platform {
+    pub fn current() -> Self {
+        if cfg!(target_os = "linux") {
+            Self::Linux
+        } else if cfg!(windows) {
+            Self::Windows
+        } else if cfg!(target_os = "macos") {
+            Self::MacOs
+        } else {
+            Self::Other
+        }
+    }
+}
+
+/// Present modes to try in order, the first the surface supports wins; FIFO
+/// always comes last as the guaranteed fallback. An `explicit` mode is tried
+/// alone. Otherwise the default depends on `vsync` and the platform:
+/// - vsync on Linux: FIFO_RELAXED, so an occasional missed frame tears once
+///   instead of stalling a whole refresh (X11 compositors often tear anyway)
+/// - vsync elsewhere: FIFO
+/// - uncapped on Windows: MAILBOX, else IMMEDIATE, which exclusive fullscreen
+///   often offers instead of MAILBOX
+/// - uncapped elsewhere: MAILBOX
+pub fn present_preferences(
+    explicit: Option<PresentMode>,
+    vsync: bool,
+    platform: Platform,
+) -> &'static [PresentMode] {
+    use PresentMode::{Fifo, FifoRelaxed, Immediate, Mailbox};
+    match (explicit, vsync, platform) {
+        (Some(Fifo), _, _) => &[Fifo],
+        (Some(FifoRelaxed), _, _) => &[FifoRelaxed, Fifo],
+        (Some(Mailbox), _, _) => &[Mailbox, Fifo],
+        (Some(Immediate), _, _) => &[Immediate, Fifo],
+        (None, true, Platform::Linux) => &[FifoRelaxed, Fifo],
+        (None, true, _) => &[Fifo],
+        (None, false, Platform::Windows) => &[Mailbox, Immediate, Fifo],
+        (None, false, _) => &[Mailbox, Fifo],
+    }
+}
+
+/// `vkQueueSubmit` calls used for a frame with queued command buffers.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SubmitPolicy {
+    #[default]
+    Separate, // One call per queued buffer, then one for the frame
+    Batched, // Queued buffers and the frame's share a single call
+}
+
+/// How frame command buffers are recorded. Cached frames skip the overdraw
+/// query, and frames with compute dispatches, a blit or a screenshot are
+/// always recorded.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CommandRecording {
+    #[default]
+    Dynamic, // From scratch every frame
+    Cached, // Once per swapchain image, resubmitted until the frame changes
+}
+
+/// How swapchain images are fitted into a window of a different size.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScalingMode {
+    OneToOne,           // No scaling, centered (cropped or with a border)
+    AspectRatioStretch, // Largest fit that keeps the aspect ratio, centered
+    Stretch,            // Fill the window, distorting if needed
+}
+
+/// Alpha compositing of the presented images with the desktop. Surfaces support
+/// a subset; an unsupported choice falls back to the first supported one.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CompositeAlpha {
+    #[default]
+    Opaque, // Alpha ignored, the window covers what's behind it
+    PreMultiplied,  // Color already multiplied by alpha (what shaders usually output)
+    PostMultiplied, // Compositor multiplies color by alpha
+    Inherit,        // Set through platform APIs outside Vulkan
+}
+
+/// Clockwise rotation of the presented images relative to the display's
+/// natural orientation (`RendererConfig::force_pre_transform`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SurfaceTransform {
+    Identity,
+    Rotate90,
+    Rotate180,
+    Rotate270,
+}
+
+/// Diagnostics settings, only honored when the debug messenger is compiled in.
+#[derive(Debug, Clone, Default)]
+pub struct DebugConfig {
+    /// Tee validation messages into this file (truncated on startup).
+    pub log_file: Option<PathBuf>,
+    /// Also push warnings and errors into this shared buffer.
+    pub capture: Option<ValidationLog>,
+    /// Turn on the validation layer's synchronization validation (hazards like
+    /// a write racing a layout transition). Slow; off by default.
+    pub sync_validation: bool,
+}
+
+/// Descriptors of each type reserved per set when sizing a descriptor pool.
+/// A pool holds `max_descriptor_sets` times these counts; zero skips the type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DescriptorPoolSizes {
+    pub uniform_buffers: u32,
+    pub combined_image_samplers: u32,
+    pub storage_buffers: u32,
+    pub storage_images: u32,
+}
+
+impl Default for DescriptorPoolSizes {
+    fn default() -> Self {
+        Self {
+            uniform_buffers: 1,
+            combined_image_samplers: 2,
+            storage_buffers: 0,
+            storage_images: 0,
+        }
+    }
+}
+
+/// `major.minor.patch` packed like `vk::make_version`, for
+/// `RendererConfig::app_version` / `engine_version`.
+pub const fn pack_version(major: u32, minor: u32, patch: u32) -> u32 {
+    (major << 22) | (minor << 12) | patch
+}
+
+/// Version of this crate, the default `engine_version`.
+pub const ENGINE_VERSION: u32 = pack_version(
+    parse_version_part(env!("CARGO_PKG_VERSION_MAJOR")),
+    parse_version_part(env!("CARGO_PKG_VERSION_MINOR")),
+    parse_version_part(env!("CARGO_PKG_VERSION_PATCH")),
+);
+
+const fn parse_version_part(digits: &str) -> u32 {
+    let bytes = digits.as_bytes();
+    let mut value = 0;
+    let mut i = 0;
+    while i < bytes.len() {
+        value = value * 10 + (bytes[i] - b'0') as u32;
+        i += 1;
+    }
+    value
+}
+
+impl Default for RendererConfig {
+    fn default() -> Self {
+        Self {
+            app_name: "Wolf Engine".to_owned(),
+            app_version: pack_version(0, 1, 0),
+            engine_name: "Wolf Engine".to_owned(),
+            engine_version: ENGINE_VERSION,
+            msaa: 1,
+            vsync: false,
+            present_mode: None,
+            max_fps: None,
+            power_saving: false,
+            prefer_integrated: false,
+            fence_timeout: Duration::from_secs(5),
+            clock: Arc::new(RealClock),
+            submit_policy: SubmitPolicy::Separate,
+            command_recording: CommandRecording::Dynamic,
+            wide_lines: false,
+            flip_viewport_y: false,
+            tonemap: TonemapMode::None,
+            shader_dir: PathBuf::from(concat!(env!("CARGO_MANIFEST_DIR"), "/shaders")),
+            sort_draws: false,
+            target_aspect: None,
+            bar_color: [0.0, 0.0, 0.0, 1.0],
+            topology: Topology::TriangleList,
+            primitive_restart: false,
+            polygon_mode: PolygonMode::Fill,
+            cull_mode: CullMode::None,
+            stencil: false,
+            present_scaling: None,
+            composite_alpha: CompositeAlpha::Opaque,
+            force_pre_transform: None,
+            full_screen_exclusive: false,
+            vulkan_library: None,
+            max_descriptor_sets: 64,
+            descriptor_pool_sizes: DescriptorPoolSizes::default(),
+            debug: DebugConfig::default(),
+        }
+    }
+}
+
+impl DescriptorPoolSizes {
+    pub fn is_empty(&self) -> bool {
+        self.uniform_buffers == 0
+            && self.combined_image_samplers == 0
+            && self.storage_buffers == 0
+            && self.storage_images == 0
+    }
+}
+
+impl RendererConfig {
+    /// Checks for values the backend cannot honor.
+    pub fn validate(&self) -> Result<()> {
+        if self.app_name.is_empty() {
+            return Err(AppError::Config("app_name must not be empty".into()));
+        }
+        if self.app_name.contains('\0') {
+            return Err(AppError::Config(
+                "app_name must not contain NUL bytes".into(),
+            ));
+        }
+        if self.engine_name.contains('\0') {
+            return Err(AppError::Config(
+                "engine_name must not contain NUL bytes".into(),
+            ));
+        }
+
+        // Vulkan sample counts are powers of two up to 64
+        if !self.msaa.is_power_of_two() || self.msaa > 64 {
+            return Err(AppError::Config(format!(
+                "msaa must be a power of two in 1..=64, got {}",
+                self.msaa
+            )));
+        }
+
+        if self.max_fps == Some(0) {
+            return Err(AppError::Config(
+                "max_fps must be > 0 (use None for no cap)".into(),
+            ));
+        }
+
+        if let Some(aspect) = self.target_aspect
+            && !(aspect.is_finite() && aspect > 0.0)
+        {
+            return Err(AppError::Config(format!(
+                "target_aspect must be a positive ratio, got {aspect}"
+            )));
+        }
+
+        if self.fence_timeout.is_zero() {
+            return Err(AppError::Config(
+                "fence_timeout must be > 0 (use Duration::MAX to wait forever)".into(),
+            ));
+        }
+
+        if self.primitive_restart && !self.topology.is_strip() {
+            return Err(AppError::Config(format!(
+                "primitive_restart needs a strip topology, got {:?}",
+                self.topology
+            )));
+        }
+
+        if self.max_descriptor_sets == 0 {
+            return Err(AppError::Config("max_descriptor_sets must be > 0".into()));
+        }
+        if self.descriptor_pool_sizes.is_empty() {
+            return Err(AppError::Config(
+                "descriptor_pool_sizes must reserve at least one descriptor type".into(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Fluent front-end for `RendererConfig`.
+///
+/// ```ignore
+/// let config = RendererBuilder::new().app_name("x").msaa(4).vsync(true).build()?;
+/// ```
+#[derive(Debug, Default)]
+pub struct RendererBuilder {
+    config: RendererConfig,
+}
+
+impl RendererBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn app_name(mut self, name: impl Into<String>) -> Self {
+        self.config.app_name = name.into();
+        self
+    }
+
+    pub fn app_version(mut self, major: u32, minor: u32, patch: u32) -> Self {
+        self.config.app_version = pack_version(major, minor, patch);
+        self
+    }
+
+    /// Engine reported to the driver; for engines built on top of this one.
+    pub fn engine(mut self, name: impl Into<String>, major: u32, minor: u32, patch: u32) -> Self {
+        self.config.engine_name = name.into();
+        self.config.engine_version = pack_version(major, minor, patch);
+        self
+    }
+
+    pub fn msaa(mut self, samples: u32) -> Self {
+        self.config.msaa = samples;
+        self
+    }
+
+    pub fn vsync(mut self, enabled: bool) -> Self {
+        self.config.vsync = enabled;
+        self
+    }
+
+    /// Present with `mode` (falling back to FIFO where unsupported) instead of
+    /// the platform default for `vsync`.
+    pub fn present_mode(mut self, mode: PresentMode) -> Self {
+        self.config.present_mode = Some(mode);
+        self
+    }
+
+    pub fn max_fps(mut self, fps: u32) -> Self {
+        self.config.max_fps = Some(fps);
+        self
+    }
+
+    /// Present with vsync instead of uncapped (MAILBOX) while the window is
+    /// unfocused or, with the `battery` feature, the machine runs on battery;
+    /// the configured mode comes back on focus / AC. No effect with `vsync`.
+    pub fn power_saving(mut self, enabled: bool) -> Self {
+        self.config.power_saving = enabled;
+        self
+    }
+
+    /// Rank integrated GPUs above discrete ones when picking the device, for
+    /// battery life on laptops doing light work.
+    pub fn prefer_integrated(mut self, enabled: bool) -> Self {
+        self.config.prefer_integrated = enabled;
+        self
+    }
+
+    /// Give up on a frame or upload the GPU hasn't finished after `timeout`
+    /// (a hung GPU) with `AppError::GpuTimeout` instead of blocking forever.
+    pub fn fence_timeout(mut self, timeout: Duration) -> Self {
+        self.config.fence_timeout = timeout;
+        self
+    }
+
+    /// Read the time from `clock` instead of the OS (e.g. a `FakeClock`).
+    pub fn clock(mut self, clock: impl TimeSource + 'static) -> Self {
+        self.config.clock = Arc::new(clock);
+        self
+    }
+
+    pub fn submit_policy(mut self, policy: SubmitPolicy) -> Self {
+        self.config.submit_policy = policy;
+        self
+    }
+
+    /// Record frame command buffers once per swapchain image and resubmit them
+    /// while nothing changes (`CommandRecording::Cached`), for static scenes.
+    pub fn command_recording(mut self, recording: CommandRecording) -> Self {
+        self.config.command_recording = recording;
+        self
+    }
+
+    /// Have the driver scale frames during resizes instead of recreating the
+    /// swapchain for every intermediate size. Ignored where unsupported.
+    pub fn present_scaling(mut self, mode: ScalingMode) -> Self {
+        self.config.present_scaling = Some(mode);
+        self
+    }
+
+    /// See-through windows: blend the presented images with the desktop.
+    pub fn composite_alpha(mut self, mode: CompositeAlpha) -> Self {
+        self.config.composite_alpha = mode;
+        self
+    }
+
+    /// Present with `transform` whatever the surface reports (rotated panels).
+    pub fn force_pre_transform(mut self, transform: SurfaceTransform) -> Self {
+        self.config.force_pre_transform = Some(transform);
+        self
+    }
+
+    /// Allow `VulkanRenderer::acquire_full_screen_exclusive` (Windows only).
+    pub fn full_screen_exclusive(mut self, enabled: bool) -> Self {
+        self.config.full_screen_exclusive = enabled;
+        self
+    }
+
+    pub fn wide_lines(mut self, enabled: bool) -> Self {
+        self.config.wide_lines = enabled;
+        self
+    }
+
+    pub fn flip_viewport_y(mut self, enabled: bool) -> Self {
+        self.config.flip_viewport_y = enabled;
+        self
+    }
+
+    pub fn tonemap(mut self, mode: TonemapMode) -> Self {
+        self.config.tonemap = mode;
+        self
+    }
+
+    pub fn shader_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.config.shader_dir = dir.into();
+        self
+    }
+
+    /// Record each frame's draws grouped by pipeline, then material, instead
+    /// of in submission order, binding each once per run. Only worth it when
+    /// draw order doesn't matter (opaque geometry with depth testing); clears
+    /// between draws still split the frame.
+    pub fn sort_draws(mut self, enabled: bool) -> Self {
+        self.config.sort_draws = enabled;
+        self
+    }
+
+    /// Letterbox/pillarbox the scene to `aspect` (width / height), e.g. 16.0 / 9.0,
+    /// filling the rest of the window with `color`.
+    pub fn target_aspect(mut self, aspect: f32, color: [f32; 4]) -> Self {
+        self.config.target_aspect = Some(aspect);
+        self.config.bar_color = color;
+        self
+    }
+
+    pub fn topology(mut self, topology: Topology) -> Self {
+        self.config.topology = topology;
+        self
+    }
+
+    /// Let index 0xFFFFFFFF end the current strip (strip topologies only).
+    pub fn primitive_restart(mut self, enabled: bool) -> Self {
+        self.config.primitive_restart = enabled;
+        self
+    }
+
+    /// Draw meshes filled, as wireframe or as points.
+    pub fn polygon_mode(mut self, mode: PolygonMode) -> Self {
+        self.config.polygon_mode = mode;
+        self
+    }
+
+    pub fn cull_mode(mut self, mode: CullMode) -> Self {
+        self.config.cull_mode = mode;
+        self
+    }
+
+    /// Pick a depth format with stencil (`D32_SFLOAT_S8_UINT` or
+    /// `D24_UNORM_S8_UINT`); initialization fails if the GPU has neither.
+    pub fn stencil(mut self, stencil: bool) -> Self {
+        self.config.stencil = stencil;
+        self
+    }
+
+    /// Load the Vulkan loader from `path` instead of the platform default
+    /// (`libvulkan.so.1`, `vulkan-1.dll`, `libvulkan.1.dylib`).
+    pub fn vulkan_library(mut self, path: impl Into<PathBuf>) -> Self {
+        self.config.vulkan_library = Some(path.into());
+        self
+    }
+
+    pub fn max_descriptor_sets(mut self, sets: u32) -> Self {
+        self.config.max_descriptor_sets = sets;
+        self
+    }
+
+    pub fn descriptor_pool_sizes(mut self, sizes: DescriptorPoolSizes) -> Self {
+        self.config.descriptor_pool_sizes = sizes;
+        self
+    }
+
+    pub fn debug_log_file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.config.debug.log_file = Some(path.into());
+        self
+    }
+
+    /// Capture validation messages into `log` (keep a clone to read them).
+    pub fn capture_validation(mut self, log: ValidationLog) -> Self {
+        self.config.debug.capture = Some(log);
+        self
+    }
+
+    /// Enable synchronization validation (needs the validation layer).
+    pub fn sync_validation(mut self, enabled: bool) -> Self {
+        self.config.debug.sync_validation = enabled;
+        self
+    }
+
+    /// Validates and returns the finished config.
+    pub fn build(self) -> Result<RendererConfig> {
+        self.config.validate()?;
+        Ok(self.config)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::renderer::time::FakeClock;
+    use crate::core::renderer::validation::ValidationSeverity;
+
+    fn invalid(builder: RendererBuilder) -> String {
+        match builder.build() {
+            Err(AppError::Config(message)) => message,
+            other => panic!("expected a config error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn defaults_are_valid() {
+        let config = RendererBuilder::new().build().unwrap();
+        assert_eq!(config.msaa, 1);
+        assert!(!config.vsync);
+        assert_eq!(config.engine_version, ENGINE_VERSION);
+    }
+
+    #[test]
+    fn identity_setters() {
+        let config = RendererBuilder::new()
+            .app_name("demo")
+            .app_version(1, 2, 3)
+            .engine("layer", 4, 5, 6)
+            .build()
+            .unwrap();
+        assert_eq!(config.app_name, "demo");
+        assert_eq!(config.app_version, pack_version(1, 2, 3));
+        assert_eq!(config.engine_name, "layer");
+        assert_eq!(config.engine_version, pack_version(4, 5, 6));
+    }
+
+    #[test]
+    fn presentation_setters() {
+        let config = RendererBuilder::new()
+            .msaa(4)
+            .vsync(true)
+            .present_mode(PresentMode::Immediate)
+            .max_fps(30)
+            .power_saving(true)
+            .prefer_integrated(true)
+            .fence_timeout(Duration::from_secs(2))
+            .submit_policy(SubmitPolicy::Batched)
+            .command_recording(CommandRecording::Cached)
+            .present_scaling(ScalingMode::Stretch)
+            .composite_alpha(CompositeAlpha::PreMultiplied)
+            .force_pre_transform(SurfaceTransform::Rotate90)
+            .full_screen_exclusive(true)
+            .build()
+            .unwrap();
+        assert_eq!(config.msaa, 4);
+        assert!(config.vsync);
+        assert_eq!(config.present_mode, Some(PresentMode::Immediate));
+        assert_eq!(config.max_fps, Some(30));
+        assert!(config.power_saving);
+        assert!(config.prefer_integrated);
+        assert_eq!(config.fence_timeout, Duration::from_secs(2));
+        assert_eq!(config.submit_policy, SubmitPolicy::Batched);
+        assert_eq!(config.command_recording, CommandRecording::Cached);
+        assert_eq!(config.present_scaling, Some(ScalingMode::Stretch));
+        assert_eq!(config.composite_alpha, CompositeAlpha::PreMultiplied);
+        assert_eq!(config.force_pre_transform, Some(SurfaceTransform::Rotate90));
+        assert!(config.full_screen_exclusive);
+    }
+
+    #[test]
+    fn pipeline_setters() {
+        let config = RendererBuilder::new()
+            .wide_lines(true)
+            .flip_viewport_y(true)
+            .tonemap(TonemapMode::Aces)
+            .shader_dir("/tmp/spv")
+            .sort_draws(true)
+            .target_aspect(16.0 / 9.0, [0.1, 0.2, 0.3, 1.0])
+            .topology(Topology::LineStrip)
+            .primitive_restart(true)
+            .polygon_mode(PolygonMode::Line)
+            .cull_mode(CullMode::Back)
+            .stencil(true)
+            .build()
+            .unwrap();
+        assert!(config.wide_lines);
+        assert!(config.flip_viewport_y);
+        assert_eq!(config.tonemap, TonemapMode::Aces);
+        assert_eq!(config.shader_dir, PathBuf::from("/tmp/spv"));
+        assert!(config.sort_draws);
+        assert_eq!(config.target_aspect, Some(16.0 / 9.0));
+        assert_eq!(config.bar_color, [0.1, 0.2, 0.3, 1.0]);
+        assert_eq!(config.topology, Topology::LineStrip);
+        assert!(config.primitive_restart);
+        assert_eq!(config.polygon_mode, PolygonMode::Line);
+        assert_eq!(config.cull_mode, CullMode::Back);
+        assert!(config.stencil);
+    }
+
+    #[test]
+    fn resource_and_debug_setters() {
+        let sizes = DescriptorPoolSizes {
+            storage_images: 4,
+            ..DescriptorPoolSizes::default()
+        };
+        let clock = FakeClock::new();
+        let log = ValidationLog::new();
+        let config = RendererBuilder::new()
+            .vulkan_library("/opt/vulkan/libvulkan.so.1")
+            .max_descriptor_sets(8)
+            .descriptor_pool_sizes(sizes)
+            .clock(clock.clone())
+            .debug_log_file("validation.log")
+            .capture_validation(log.clone())
+            .sync_validation(true)
+            .build()
+            .unwrap();
+        assert_eq!(
+            config.vulkan_library,
+            Some(PathBuf::from("/opt/vulkan/libvulkan.so.1"))
+        );
+        assert_eq!(config.max_descriptor_sets, 8);
+        assert_eq!(config.descriptor_pool_sizes, sizes);
+        assert_eq!(config.debug.log_file, Some(PathBuf::from("validation.log")));
+        assert!(config.debug.sync_validation);
+
+        // Both are shared, not copied
+        clock.advance(Duration::from_secs(1));
+        assert_eq!(config.clock.now(), clock.now());
+        log.push(ValidationSeverity::Warning, "seen by both");
+        assert_eq!(config.debug.capture.map(|capture| capture.len()), Some(1));
+    }
+
+    #[test]
+    fn build_surfaces_validation_errors() {
+        assert!(invalid(RendererBuilder::new().msaa(3)).contains("msaa"));
+        assert!(invalid(RendererBuilder::new().msaa(128)).contains("msaa"));
+        assert!(invalid(RendererBuilder::new().app_name("")).contains("app_name"));
+        assert!(invalid(RendererBuilder::new().app_name("a\0b")).contains("NUL"));
+        assert!(invalid(RendererBuilder::new().engine("a\0b", 1, 0, 0)).contains("NUL"));
+        assert!(invalid(RendererBuilder::new().max_fps(0)).contains("max_fps"));
+        let flat = RendererBuilder::new().target_aspect(0.0, [0.0; 4]);
+        assert!(invalid(flat).contains("target_aspect"));
+        let timeout = RendererBuilder::new().fence_timeout(Duration::ZERO);
+        assert!(invalid(timeout).contains("fence_timeout"));
+        let restart = RendererBuilder::new().primitive_restart(true);
+        assert!(invalid(restart).contains("primitive_restart"));
+        let no_sets = RendererBuilder::new().max_descriptor_sets(0);
+        assert!(invalid(no_sets).contains("max_descriptor_sets"));
+        let empty = RendererBuilder::new().descriptor_pool_sizes(DescriptorPoolSizes {
+            uniform_buffers: 0,
+            combined_image_samplers: 0,
+            storage_buffers: 0,
+            storage_images: 0,
+        });
+        assert!(invalid(empty).contains("descriptor_pool_sizes"));
+    }
+
+    #[test]
+    fn vsync_maps_to_the_documented_present_modes() {
+        use PresentMode::{Fifo, FifoRelaxed, Immediate, Mailbox};
+        // FIFO is always the last resort
+        assert_eq!(
+            present_preferences(None, true, Platform::Linux),
+            [FifoRelaxed, Fifo]
+        );
+        for platform in [Platform::Windows, Platform::MacOs, Platform::Other] {
+            assert_eq!(present_preferences(None, true, platform), [Fifo]);
+        }
+        assert_eq!(
+            present_preferences(None, false, Platform::Windows),
+            [Mailbox, Immediate, Fifo]
+        );
+        for platform in [Platform::Linux, Platform::MacOs, Platform::Other] {
+            assert_eq!(present_preferences(None, false, platform), [Mailbox, Fifo]);
+        }
+    }
+
+    #[test]
+    fn explicit_present_mode_overrides_vsync() {
+        use PresentMode::{Fifo, Immediate, Mailbox};
+        for vsync in [true, false] {
+            let linux = Platform::Linux;
+            assert_eq!(present_preferences(Some(Fifo), vsync, linux), [Fifo]);
+            assert_eq!(
+                present_preferences(Some(Mailbox), vsync, linux),
+                [Mailbox, Fifo]
+            );
+            assert_eq!(
+                present_preferences(Some(Immediate), vsync, linux),
+                [Immediate, Fifo]
+            );
+        }
+    }
+}
@@ -0,0 +1,17 @@
+// src/core/renderer/log_targets.rs
+//
+// `log` targets of engine messages, so they can be filtered apart from the
+// application's own logs and from each other, e.g.
+// `RUST_LOG=info,wolf_engine=warn` or `RUST_LOG=wolf_engine::swapchain=debug`.
+
+/// Window creation and the event loop `App` drives.
+pub const APP: &str = "wolf_engine::app";
+
+/// Setup, frames, resources and everything else without a narrower target.
+pub const RENDERER: &str = "wolf_engine::renderer";
+
+/// Swapchain creation, recreation, presentation and fullscreen modes.
+pub const SWAPCHAIN: &str = "wolf_engine::swapchain";
+
+/// Messages from the Vulkan validation layers (debug builds with `debug-utils`).
+pub const VALIDATION: &str = "wolf_engine::validation";
@@ -1,2 +1,9 @@
 pub mod api;
 pub mod backend;
+pub mod config;
+pub mod log_targets;
+#[cfg(feature = "vulkan")]
+pub mod pacing;
+pub mod stats;
+pub mod time;
+pub mod validation;
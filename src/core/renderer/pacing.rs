@@ -0,0 +1,216 @@
+// src/core/renderer/pacing.rs
+use std::time::{Duration, Instant};
+
+use winit::event::WindowEvent;
+use winit::event_loop::ControlFlow;
+
+use crate::core::renderer::time::TimeSource;
+
+/// Holds the frame loop to a fixed rate, independent of the present mode.
+/// Waits through a `TimeSource`, whose `RealClock` sleeps then spins so the
+/// cap isn't undershot by OS sleep granularity.
+#[derive(Debug, Clone, Copy)]
+pub struct FrameLimiter {
+    frame_time: Duration,
+    deadline: Option<Instant>, // When the next frame may start
+}
+
+impl FrameLimiter {
+    /// `None` for a zero rate.
+    pub fn new(max_fps: u32) -> Option<Self> {
+        (max_fps > 0).then(|| Self {
+            frame_time: Duration::from_secs(1) / max_fps,
+            deadline: None,
+        })
+    }
+
+    pub fn frame_time(&self) -> Duration {
+        self.frame_time
+    }
+
+    /// Block until the next frame may start, then schedule the one after it.
+    pub fn wait(&mut self, clock: &dyn TimeSource) {
+        let now = clock.now();
+        let start = match self.deadline {
+            Some(deadline) if deadline > now => {
+                clock.wait_until(deadline);
+                deadline
+            }
+            // First frame, or we're already late: don't try to catch up with a burst
+            _ => now,
+        };
+        self.deadline = Some(start + self.frame_time);
+    }
+}
+
+/// Whether the window is worth drawing into, tracked from winit's `Occluded`
+/// events and the zero-sized `Resized` that minimizing sends.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Visibility {
+    occluded: bool,
+    minimized: bool,
+}
+
+impl Visibility {
+    /// Nothing on screen: rendering would only burn power.
+    pub fn hidden(&self) -> bool {
+        self.occluded || self.minimized
+    }
+
+    /// Apply `event`; `Some(hidden)` when it changed `hidden()`.
+    pub fn update(&mut self, event: &WindowEvent) -> Option<bool> {
+        let was_hidden = self.hidden();
+        match event {
+            WindowEvent::Occluded(occluded) => self.occluded = *occluded,
+            WindowEvent::Resized(size) => self.minimized = size.width == 0 || size.height == 0,
+            _ => return None,
+        }
+        (self.hidden() != was_hidden).then_some(self.hidden())
+    }
+}
+
+/// Whether uncapped presentation (MAILBOX) should give way to vsync to save
+/// power: while the window is unfocused, or running on battery (checked with
+/// the `battery` feature only). Inactive unless `RendererConfig::power_saving`.
+#[derive(Debug, Clone, Copy)]
+pub struct PowerSaver {
+    enabled: bool,
+    focused: bool,
+    on_battery: bool,
+    last_poll: Option<Instant>, // Last power source check
+}
+
+impl PowerSaver {
+    /// Power source checks cost a sysfs / OS query, so they are rate-limited.
+    const POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+    pub fn new(enabled: bool) -> Self {
+        Self {
+            enabled,
+            focused: true,
+            on_battery: false,
+            last_poll: None,
+        }
+    }
+
+    /// Present with vsync instead of the configured mode.
+    pub fn saving(&self) -> bool {
+        self.enabled && (!self.focused || self.on_battery)
+    }
+
+    /// Apply a `WindowEvent::Focused`; true when it changed `saving()`.
+    pub fn set_focused(&mut self, focused: bool) -> bool {
+        let was_saving = self.saving();
+        self.focused = focused;
+        self.saving() != was_saving
+    }
+
+    /// Apply a power source change; true when it changed `saving()`.
+    pub fn set_on_battery(&mut self, on_battery: bool) -> bool {
+        let was_saving = self.saving();
+        self.on_battery = on_battery;
+        self.saving() != was_saving
+    }
+
+    /// Re-check the power source if the last check is `POLL_INTERVAL` old;
+    /// true when it changed `saving()`. Without the `battery` feature the
+    /// machine always counts as plugged in.
+    pub fn poll(&mut self, now: Instant) -> bool {
+        let due = self
+            .last_poll
+            .is_none_or(|last| now.duration_since(last) >= Self::POLL_INTERVAL);
+        if !self.enabled || !cfg!(feature = "battery") || !due {
+            return false;
+        }
+        self.last_poll = Some(now);
+        self.set_on_battery(on_battery())
+    }
+}
+
+impl Default for PowerSaver {
+    fn default() -> Self {
+        Self::new(false)
+    }
+}
+
+/// Whether any battery is discharging, i.e. the machine runs off battery, read
+/// from `/sys/class/power_supply`. Machines without batteries, or where sysfs
+/// can't be read, count as plugged in.
+#[cfg(all(feature = "battery", target_os = "linux"))]
+pub fn on_battery() -> bool {
+    let Ok(supplies) = std::fs::read_dir("/sys/class/power_supply") else {
+        return false;
+    };
+    let read = |dir: &std::path::Path, name: &str| std::fs::read_to_string(dir.join(name));
+    supplies.flatten().any(|supply| {
+        let dir = supply.path();
+        read(&dir, "type").is_ok_and(|kind| kind.trim() == "Battery")
+            && read(&dir, "status").is_ok_and(|status| status.trim() == "Discharging")
+    })
+}
+
+/// Only read on Linux; elsewhere the machine always counts as plugged in.
+#[cfg(not(all(feature = "battery", target_os = "linux")))]
+pub fn on_battery() -> bool {
+    false
+}
+
+/// When `App` draws frames.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RedrawMode {
+    #[default]
+    Continuous, // Every time the event loop is idle (games, benchmarks)
+    OnDemand, // On OS redraw requests and `request_redraw_at` deadlines only
+}
+
+/// Control flow for the event loop once events are drained: sleep while hidden,
+/// spin when drawing continuously, otherwise sleep until the next requested
+/// redraw (or the next event if none is scheduled).
+pub fn control_flow(hidden: bool, mode: RedrawMode, redraw_at: Option<Instant>) -> ControlFlow {
+    match (hidden, mode, redraw_at) {
+        (true, _, _) => ControlFlow::Wait,
+        (false, RedrawMode::Continuous, _) => ControlFlow::Poll,
+        (false, RedrawMode::OnDemand, Some(deadline)) => ControlFlow::WaitUntil(deadline),
+        (false, RedrawMode::OnDemand, None) => ControlFlow::Wait,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hidden_windows_sleep() {
+        let soon = Some(Instant::now());
+        for mode in [RedrawMode::Continuous, RedrawMode::OnDemand] {
+            assert_eq!(control_flow(true, mode, soon), ControlFlow::Wait);
+            assert_eq!(control_flow(true, mode, None), ControlFlow::Wait);
+        }
+    }
+
+    #[test]
+    fn continuous_spins() {
+        let soon = Some(Instant::now());
+        assert_eq!(
+            control_flow(false, RedrawMode::Continuous, soon),
+            ControlFlow::Poll
+        );
+        assert_eq!(
+            control_flow(false, RedrawMode::Continuous, None),
+            ControlFlow::Poll
+        );
+    }
+
+    #[test]
+    fn on_demand_waits_for_the_next_redraw() {
+        let deadline = Instant::now() + Duration::from_millis(16);
+        assert_eq!(
+            control_flow(false, RedrawMode::OnDemand, Some(deadline)),
+            ControlFlow::WaitUntil(deadline)
+        );
+        assert_eq!(
+            control_flow(false, RedrawMode::OnDemand, None),
+            ControlFlow::Wait
+        );
+    }
+}
@@ -0,0 +1,107 @@
+// src/core/renderer/stats.rs
+use std::time::Duration;
+
+/// Which side is limiting the frame rate.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Bottleneck {
+    Cpu, // GPU finished before the CPU asked for the next frame
+    Gpu, // CPU spends most of the frame blocked on the frame fence
+    #[default]
+    Balanced,
+}
+
+impl Bottleneck {
+    /// Classify from how long the CPU blocked on the frames-in-flight fence
+    /// relative to the whole frame time.
+    pub fn classify(fence_wait: Duration, frame_time: Duration) -> Self {
+        if frame_time.is_zero() {
+            return Self::Balanced;
+        }
+        let stalled = fence_wait.as_secs_f64() / frame_time.as_secs_f64();
+        if stalled > 0.5 {
+            Self::Gpu
+        } else if stalled < 0.05 {
+            Self::Cpu
+        } else {
+            Self::Balanced
+        }
+    }
+}
+
+/// Draws recorded into one frame, counted while its command buffer is recorded.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DrawCounts {
+    pub calls: u32,   // Indexed draw calls
+    pub indices: u64, // Indices over all of them
+    pub binds: BindCounts,
+    pub binds_saved: u32, // Binds `RendererConfig::sort_draws` avoided vs. submission order
+}
+
+/// State changes between draws: `cmd_bind_pipeline` and material
+/// `cmd_bind_descriptor_sets` calls.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BindCounts {
+    pub pipelines: u32,
+    pub materials: u32,
+}
+
+impl BindCounts {
+    pub fn total(&self) -> u32 {
+        self.pipelines + self.materials
+    }
+}
+
+impl DrawCounts {
+    /// Count one indexed draw of `index_count` indices.
+    pub fn add_indexed(&mut self, index_count: u32) {
+        self.calls += 1;
+        self.indices += u64::from(index_count);
+    }
+}
+
+/// Rough overdraw: samples that passed the depth test per sample of the scene
+/// area (1.0 = every sample written once). Fragments hidden by nearer ones
+/// drawn earlier aren't counted. None without a scene to divide by.
+pub fn overdraw_estimate(samples_passed: u64, scene_samples: u64) -> Option<f32> {
+    (scene_samples > 0).then(|| (samples_passed as f64 / scene_samples as f64) as f32)
+}
+
+/// Per-frame timings, smoothed so the bottleneck doesn't flicker.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RenderStats {
+    pub frame_count: u64,
+    pub recordings: u64, // Frame command buffers recorded (see CommandRecording)
+    pub frame_time: Duration, // Smoothed time between frames
+    pub fence_wait: Duration, // Smoothed time the CPU blocked on the frame fence
+    pub bottleneck: Bottleneck,
+    pub queue_submits: u32, // vkQueueSubmit calls of the last frame (see SubmitPolicy)
+    pub draws: DrawCounts,  // Mesh draws of the last frame
+    // `overdraw_estimate` of the scene draws, from an occlusion query read back
+    // MAX_FRAMES_IN_FLIGHT frames later (None until the first result, or always
+    // without the occlusionQueryPrecise feature)
+    pub overdraw: Option<f32>,
+}
+
+impl RenderStats {
+    const SMOOTHING: f64 = 0.1; // Weight of the newest sample
+
+    /// Fold in one frame's raw timings.
+    pub fn record_frame(&mut self, frame_time: Duration, fence_wait: Duration) {
+        if self.frame_count == 0 {
+            self.frame_time = frame_time;
+            self.fence_wait = fence_wait;
+        } else {
+            self.frame_time = ema(self.frame_time, frame_time);
+            self.fence_wait = ema(self.fence_wait, fence_wait);
+        }
+        self.frame_count += 1;
+        self.bottleneck = Bottleneck::classify(self.fence_wait, self.frame_time);
+    }
+}
+
+fn ema(old: Duration, new: Duration) -> Duration {
+    Duration::from_secs_f64(
+        old.as_secs_f64() * (1.0 - RenderStats::SMOOTHING)
+            + new.as_secs_f64() * RenderStats::SMOOTHING,
+    )
+}
@@ -0,0 +1,92 @@
+// src/core/renderer/time.rs
+//
+// Where the renderer reads the time from (`RendererConfig::clock`). Frame
+// pacing, frame stats and resize settling all go through a `TimeSource`, so a
+// `FakeClock` can drive them deterministically: checks of the frame cap or the
+// averaged frame time advance it by hand instead of sleeping (see the
+// `frame_pacing` example).
+use std::fmt::Debug;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+pub trait TimeSource: Debug + Send + Sync {
+    fn now(&self) -> Instant;
+
+    /// Block until `now()` reaches `deadline`.
+    fn wait_until(&self, deadline: Instant);
+}
+
+/// Shared clock handle, cheap to clone into the config.
+pub type Clock = Arc<dyn TimeSource>;
+
+/// The OS monotonic clock.
+///
+/// `wait_until` sleeps most of the way, then spins the last stretch: OS sleeps
+/// overshoot by up to a scheduler tick, which alone would make a frame cap run
+/// well below its target.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RealClock;
+
+impl RealClock {
+    /// Spin instead of sleeping once this close to the deadline.
+    const SPIN_MARGIN: Duration = Duration::from_millis(2);
+}
+
+impl TimeSource for RealClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+
+    fn wait_until(&self, deadline: Instant) {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if let Some(coarse) = remaining.checked_sub(Self::SPIN_MARGIN) {
+            std::thread::sleep(coarse);
+        }
+        while Instant::now() < deadline {
+            std::hint::spin_loop();
+        }
+    }
+}
+
+/// Clock that only moves when told to. Clones share the time, so a test keeps
+/// one to `advance` while the renderer reads another; waiting jumps straight to
+/// the deadline.
+#[derive(Debug, Clone)]
+pub struct FakeClock {
+    now: Arc<Mutex<Instant>>,
+}
+
+impl FakeClock {
+    /// Starts at the real current time (an `Instant` can't be made otherwise).
+    pub fn new() -> Self {
+        Self {
+            now: Arc::new(Mutex::new(Instant::now())),
+        }
+    }
+
+    pub fn advance(&self, by: Duration) {
+        *self.lock() += by;
+    }
+
+    // A panic while holding the lock leaves the time intact
+    fn lock(&self) -> std::sync::MutexGuard<'_, Instant> {
+        self.now.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl Default for FakeClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TimeSource for FakeClock {
+    fn now(&self) -> Instant {
+        *self.lock()
+    }
+
+    fn wait_until(&self, deadline: Instant) {
+        let mut now = self.lock();
+        *now = (*now).max(deadline);
+    }
+}
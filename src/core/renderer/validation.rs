@@ -0,0 +1,63 @@
+// src/core/renderer/validation.rs
+//
+// In-memory copy of the validation messages the debug messenger reports, for
+// checks that fail on any warning (e.g. teardown ordering, see the
+// `cleanup_validation` example). Only filled in debug builds with `debug-utils`,
+// where the messenger exists.
+use std::sync::{Arc, Mutex, PoisonError};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValidationSeverity {
+    Warning,
+    Error,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationMessage {
+    pub severity: ValidationSeverity,
+    pub text: String,
+}
+
+/// Shared buffer of validation messages (`RendererBuilder::capture_validation`).
+/// Clones share the buffer, so the app keeps one to read what the renderer's
+/// messenger pushed, including during teardown.
+#[derive(Debug, Clone, Default)]
+pub struct ValidationLog {
+    messages: Arc<Mutex<Vec<ValidationMessage>>>,
+}
+
+impl ValidationLog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&self, severity: ValidationSeverity, text: impl Into<String>) {
+        let text = text.into();
+        self.lock().push(ValidationMessage { severity, text });
+    }
+
+    /// Messages captured so far; mark a position to check a phase with `since`.
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lock().is_empty()
+    }
+
+    /// Messages captured after the first `start`.
+    pub fn since(&self, start: usize) -> Vec<ValidationMessage> {
+        self.lock()
+            .get(start..)
+            .map_or_else(Vec::new, <[_]>::to_vec)
+    }
+
+    pub fn clear(&self) {
+        self.lock().clear();
+    }
+
+    // A panic while holding the lock leaves the messages intact
+    fn lock(&self) -> std::sync::MutexGuard<'_, Vec<ValidationMessage>> {
+        self.messages.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
@@ -0,0 +1,188 @@
+// src/core/window.rs
+//
+// Window settings, kept apart from `RendererConfig` (which is only about the
+// GPU). `App` turns them into winit `WindowAttributes` when the window is created.
+use winit::dpi::LogicalSize;
+use winit::monitor::MonitorHandle;
+use winit::window::{Fullscreen, Icon, WindowAttributes};
+
+use crate::core::renderer::pacing::RedrawMode;
+use crate::error::{AppError, Result};
+
+/// Settings for the window `App` creates.
+///
+/// ```ignore
+/// let window = WindowConfig::new().title("demo").size(1280, 720).resizable(false);
+/// App::<MyRenderer>::run_with(window, RendererConfig::default())?;
+/// ```
+#[derive(Debug, Clone)]
+pub struct WindowConfig {
+    pub title: String,
+    pub size: Option<(u32, u32)>, // Inner size in logical pixels (None = platform default)
+    pub resizable: bool,
+    pub decorations: bool, // Title bar and borders
+    pub fullscreen: bool,  // Borderless fullscreen on the current monitor
+    pub transparent: bool, // See-through background, with `RendererConfig::composite_alpha`
+    pub icon: Option<Icon>,
+    // Monitor `fullscreen` uses, as an index into the event loop's monitor list
+    // (see `app::monitors`); None or an index out of range = current monitor
+    pub fullscreen_monitor: Option<usize>,
+    pub redraw: RedrawMode, // Continuous frames, or only when asked for (low power)
+    // Text input through the platform IME (`Renderer::on_ime`). Off by default:
+    // while composing, the IME swallows key presses games expect
+    pub ime: bool,
+}
+
+impl Default for WindowConfig {
+    fn default() -> Self {
+        Self {
+            title: "Wolf Engine".to_owned(),
+            size: None,
+            resizable: true,
+            decorations: true,
+            fullscreen: false,
+            transparent: false,
+            icon: None,
+            fullscreen_monitor: None,
+            redraw: RedrawMode::Continuous,
+            ime: false,
+        }
+    }
+}
+
+impl WindowConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.title = title.into();
+        self
+    }
+
+    pub fn size(mut self, width: u32, height: u32) -> Self {
+        self.size = Some((width, height));
+        self
+    }
+
+    pub fn resizable(mut self, resizable: bool) -> Self {
+        self.resizable = resizable;
+        self
+    }
+
+    pub fn decorations(mut self, decorations: bool) -> Self {
+        self.decorations = decorations;
+        self
+    }
+
+    pub fn fullscreen(mut self, fullscreen: bool) -> Self {
+        self.fullscreen = fullscreen;
+        self
+    }
+
+    pub fn transparent(mut self, transparent: bool) -> Self {
+        self.transparent = transparent;
+        self
+    }
+
+    pub fn icon(mut self, icon: Icon) -> Self {
+        self.icon = Some(icon);
+        self
+    }
+
+    /// Start fullscreen on monitor `index` (implies `fullscreen(true)`).
+    pub fn fullscreen_monitor(mut self, index: usize) -> Self {
+        self.fullscreen = true;
+        self.fullscreen_monitor = Some(index);
+        self
+    }
+
+    pub fn redraw(mut self, redraw: RedrawMode) -> Self {
+        self.redraw = redraw;
+        self
+    }
+
+    pub fn ime(mut self, ime: bool) -> Self {
+        self.ime = ime;
+        self
+    }
+
+    /// Checks for values winit cannot honor.
+    pub fn validate(&self) -> Result<()> {
+        if let Some((width, height)) = self.size
+            && (width == 0 || height == 0)
+        {
+            return Err(AppError::Config(format!(
+                "window size must be non-zero, got {width}x{height}"
+            )));
+        }
+        Ok(())
+    }
+
+    /// Fullscreen setting on `fullscreen_monitor` of `monitors`. `attributes`
+    /// can't see the monitors, so `App` applies this on top of them.
+    pub fn fullscreen_on(
+        &self,
+        monitors: impl IntoIterator<Item = MonitorHandle>,
+    ) -> Option<Fullscreen> {
+        self.fullscreen
+            .then(|| Fullscreen::Borderless(nth_monitor(monitors, self.fullscreen_monitor)))
+    }
+
+    /// Attributes for `ActiveEventLoop::create_window`.
+    pub fn attributes(&self) -> WindowAttributes {
+        let mut attributes = WindowAttributes::default()
+            .with_title(self.title.as_str())
+            .with_resizable(self.resizable)
+            .with_decorations(self.decorations)
+            .with_fullscreen(self.fullscreen.then_some(Fullscreen::Borderless(None)))
+            .with_transparent(self.transparent)
+            .with_window_icon(self.icon.clone());
+        if let Some((width, height)) = self.size {
+            attributes = attributes.with_inner_size(LogicalSize::new(width, height));
+        }
+        attributes
+    }
+}
+
+/// Entry `index` of `monitors`; None without an index or when it's out of range.
+pub fn nth_monitor<M>(monitors: impl IntoIterator<Item = M>, index: Option<usize>) -> Option<M> {
+    monitors.into_iter().nth(index?)
+}
+
+#[cfg(test)]
+mod tests {
+    use winit::dpi::Size;
+
+    use super::*;
+
+    #[test]
+    fn attributes_carry_the_window_settings() {
+        let config = WindowConfig::new()
+            .title("demo")
+            .size(640, 480)
+            .resizable(false);
+        let attributes = config.attributes();
+        assert_eq!(attributes.title, "demo");
+        assert!(!attributes.resizable);
+        assert!(attributes.decorations);
+        assert_eq!(
+            attributes.inner_size,
+            Some(Size::Logical(LogicalSize::new(640.0, 480.0)))
+        );
+        assert!(attributes.fullscreen.is_none());
+    }
+
+    #[test]
+    fn zero_size_is_rejected() {
+        assert!(WindowConfig::new().size(0, 480).validate().is_err());
+        assert!(WindowConfig::new().size(640, 480).validate().is_ok());
+    }
+
+    #[test]
+    fn nth_monitor_ignores_missing_indices() {
+        assert_eq!(nth_monitor([10, 11], Some(1)), Some(11));
+        assert_eq!(nth_monitor([10, 11], Some(2)), None);
+        assert_eq!(nth_monitor([10, 11], None), None);
+    }
+}
@@ -1,17 +1,27 @@
-use std::{error::Error as StdError, fmt};
+use std::{error::Error as StdError, fmt, io, time::Duration};
 
 use libloading::Error as LibloadingError;
 use vulkanalia::loader::LoaderError;
 use vulkanalia::vk;
-use winit::error::EventLoopError;
+#[cfg(feature = "vulkan")]
+use winit::error::{EventLoopError, OsError};
 
 /// Application-wide error type.
 #[derive(Debug)]
 pub enum AppError {
     Lib(LibloadingError),         // dynamic library loading errors
     Vk(vk::Result, &'static str), // Vulkan error + context string
-    Winit(EventLoopError),        // winit event loop errors
+    #[cfg(feature = "vulkan")]
+    Winit(EventLoopError), // winit event loop errors
+    #[cfg(feature = "vulkan")]
+    Window(OsError), // window creation errors
     Loader(Box<dyn LoaderError>), // Vulkanalia loader errors (trait object)
+    Config(String),               // invalid renderer configuration
+    NotInitialized,               // renderer used before initialize()
+    Surface(String),              // window surface unusable (no formats/present modes)
+    Io(io::Error),                // filesystem errors (logs, captures)
+    // fence not signaled within `RendererConfig::fence_timeout` (hung GPU) + context
+    GpuTimeout(&'static str, Duration),
 }
 
 impl fmt::Display for AppError {
@@ -21,14 +31,29 @@ impl fmt::Display for AppError {
             Self::Vk(result, ctx) => {
                 write!(f, "Vulkan error: {:?} (context: {})", result, ctx)
             }
+            #[cfg(feature = "vulkan")]
             Self::Winit(e) => write!(f, "winit: {e}"),
+            #[cfg(feature = "vulkan")]
+            Self::Window(e) => write!(f, "window: {e}"),
             Self::Loader(e) => write!(f, "loader error: {}", e),
+            Self::Config(msg) => write!(f, "invalid config: {msg}"),
+            Self::NotInitialized => write!(f, "renderer used before initialize()"),
+            Self::Surface(msg) => write!(f, "unusable surface: {msg}"),
+            Self::Io(e) => write!(f, "io: {e}"),
+            Self::GpuTimeout(ctx, t) => write!(f, "GPU timeout after {t:?} (context: {ctx})"),
         }
     }
 }
 
 impl StdError for AppError {}
 
+impl AppError {
+    /// Wrap a Vulkan error code with a context string.
+    pub fn vk(code: vk::ErrorCode, ctx: &'static str) -> Self {
+        Self::Vk(vk::Result::from_raw(code.as_raw()), ctx)
+    }
+}
+
 /// Alias used in other modules.
 pub type Result<T> = std::result::Result<T, AppError>;
 
@@ -44,12 +69,32 @@ impl From<vk::Result> for AppError {
     }
 }
 
+impl From<vk::ErrorCode> for AppError {
+    fn from(e: vk::ErrorCode) -> Self {
+        Self::vk(e, "unspecified")
+    }
+}
+
+impl From<io::Error> for AppError {
+    fn from(e: io::Error) -> Self {
+        Self::Io(e)
+    }
+}
+
+#[cfg(feature = "vulkan")]
 impl From<EventLoopError> for AppError {
     fn from(e: EventLoopError) -> Self {
         Self::Winit(e)
     }
 }
 
+#[cfg(feature = "vulkan")]
+impl From<OsError> for AppError {
+    fn from(e: OsError) -> Self {
+        Self::Window(e)
+    }
+}
+
 impl From<Box<dyn LoaderError>> for AppError {
     fn from(e: Box<dyn LoaderError>) -> Self {
         Self::Loader(e)
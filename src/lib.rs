@@ -1 +1,4 @@
+#[cfg(feature = "vulkan")]
+pub mod app;
+pub mod core;
 pub mod error;
@@ -1,13 +1,11 @@
 // src/main.rs
-mod app;
-mod core;
-mod error;
-
-use crate::core::renderer::backend::SelectedRenderer;
-use app::App;
+use wolf_engine::app::App;
+use wolf_engine::core::renderer::api::Renderer;
+use wolf_engine::error;
 
 fn main() -> error::Result<()> {
     env_logger::init();
 
-    App::<SelectedRenderer>::run()
+    // Backend picked at runtime: WOLF_BACKEND=vulkan (default: first compiled in)
+    App::<Box<dyn Renderer>>::run()
 }